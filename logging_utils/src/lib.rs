@@ -1001,6 +1001,83 @@ pub trait LoggableActor {
     fn actor_id(&self) -> u32;
 }
 
+/// Fluent builder for a self-directed log event.
+///
+/// A function-based alternative to [`log_internal_op!`], usable where the macros
+/// are impractical (e.g. inside other macros or closures).
+///
+/// # Usage
+/// ```
+/// StructuredLogger::new(ActorType::Orchestrator, 0)
+///     .channel(Channel::Warning)
+///     .field("planet_id", planet_id)
+///     .result(&outcome)
+///     .emit();
+/// ```
+///
+/// # Defaults
+/// * channel: LOG_FN_INT_OPERATIONS (Trace level)
+/// * event type: the internal action of the actor type, as in [`log_internal_op!`]
+pub struct StructuredLogger {
+    actor_type: ActorType,
+    actor_id: u32,
+    channel: Channel,
+    event_type: EventType,
+    payload: std::collections::BTreeMap<String, String>,
+}
+
+impl StructuredLogger {
+    pub fn new(actor_type: ActorType, actor_id: u32) -> Self {
+        let event_type = match actor_type {
+            ActorType::Explorer => EventType::InternalExplorerAction,
+            ActorType::Planet => EventType::InternalPlanetAction,
+            _ => EventType::InternalOrchestratorAction,
+        };
+        StructuredLogger {
+            actor_type,
+            actor_id,
+            channel: LOG_FN_INT_OPERATIONS,
+            event_type,
+            payload: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Sets the channel (level) of the event.
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Sets the event type.
+    pub fn event_type(mut self, event_type: EventType) -> Self {
+        self.event_type = event_type;
+        self
+    }
+
+    /// Adds a key-value pair to the payload, replacing a previous value of the same key.
+    pub fn field(mut self, key: impl ToString, value: impl ToString) -> Self {
+        self.payload.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Adds the Debug-formatted value under the `"Result"` key, like [`log_fn_call!`].
+    pub fn result(mut self, value: impl std::fmt::Debug) -> Self {
+        self.payload.insert("Result".to_string(), format!("{:?}", value));
+        self
+    }
+
+    /// Emits the event.
+    pub fn emit(self) {
+        LogEvent::self_directed(
+            Participant::new(self.actor_type, self.actor_id),
+            self.event_type,
+            self.channel,
+            self.payload,
+        )
+        .emit();
+    }
+}
+
 pub fn get_sender_id<T>(chan: &Sender<T>) -> usize {
     // getting memory address of the channel
     chan as *const _ as *const () as usize
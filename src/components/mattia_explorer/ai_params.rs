@@ -26,6 +26,11 @@ pub struct AiParams {
     // --- INFORMATION STALENESS ---
     /// Max age (in ticks) before energy info is considered stale
     pub max_energy_info_age: u64,
+    /// Age (in ticks) of the current planet's energy info that forces a new survey (0 disables it)
+    pub energy_resurvey_interval: u64,
+    /// Age (in ticks) of the cached supported resources/combinations that forces a new survey
+    /// before answering the orchestrator (0 keeps them forever)
+    pub supported_cache_ttl: u64,
 
     // --- HYSTERESIS ---
     /// Minimum advantage required to switch from the current action
@@ -73,6 +78,8 @@ impl Default for AiParams {
             safety_warning: 0.6,
             energy_cells_defense_threshold: 2,
             max_energy_info_age: 150,
+            energy_resurvey_interval: 100,
+            supported_cache_ttl: 0,
             action_hysteresis_margin: 0.07,
             min_active_charge_rate: 0.05,
             max_prediction_horizon: 100,
@@ -1,3 +1,4 @@
+use crate::utils::recipes;
 use common_game::components::resource::{
     AIPartner, BasicResource, BasicResourceType, Carbon, ComplexResource, ComplexResourceRequest,
     ComplexResourceType, Diamond, Dolphin, GenericResource, Hydrogen, Life, Oxygen, ResourceType,
@@ -6,7 +7,7 @@ use common_game::components::resource::{
 use common_game::logging::ActorType;
 use logging_utils::log_internal_op;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 /// explorer bag
 pub(super) struct Bag {
     oxygen: Vec<Oxygen>,
@@ -24,18 +25,7 @@ pub(super) struct Bag {
 impl Bag {
     /// creates an empty bag
     pub(super) fn new() -> Self {
-        Self {
-            oxygen: vec![],
-            hydrogen: vec![],
-            carbon: vec![],
-            silicon: vec![],
-            diamond: vec![],
-            water: vec![],
-            life: vec![],
-            robot: vec![],
-            dolphin: vec![],
-            ai_partner: vec![],
-        }
+        Self::default()
     }
 
     /// inserts a resource in the bag
@@ -131,6 +121,46 @@ impl Bag {
         }
     }
 
+    /// removes every resource from the bag
+    pub(super) fn clear(&mut self) {
+        self.oxygen.clear();
+        self.hydrogen.clear();
+        self.carbon.clear();
+        self.silicon.clear();
+        self.diamond.clear();
+        self.water.clear();
+        self.life.clear();
+        self.robot.clear();
+        self.dolphin.clear();
+        self.ai_partner.clear();
+    }
+
+    /// moves every resource of `other` into this bag, consuming it
+    pub(super) fn merge(&mut self, mut other: Bag) {
+        self.oxygen.append(&mut other.oxygen);
+        self.hydrogen.append(&mut other.hydrogen);
+        self.carbon.append(&mut other.carbon);
+        self.silicon.append(&mut other.silicon);
+        self.diamond.append(&mut other.diamond);
+        self.water.append(&mut other.water);
+        self.life.append(&mut other.life);
+        self.robot.append(&mut other.robot);
+        self.dolphin.append(&mut other.dolphin);
+        self.ai_partner.append(&mut other.ai_partner);
+    }
+
+    /// moves up to `n` resources of type `ty` into a new bag
+    pub(super) fn split_off(&mut self, ty: ResourceType, n: usize) -> Bag {
+        let mut split = Bag::new();
+        for _ in 0..n {
+            match self.take_resource(ty) {
+                Some(res) => split.insert(res),
+                None => break,
+            }
+        }
+        split
+    }
+
     ///tells the number of resource of a certain type
     pub(super) fn count(&self, ty: ResourceType) -> usize {
         match ty {
@@ -148,6 +178,19 @@ impl Bag {
             ResourceType::Complex(ComplexResourceType::AIPartner) => self.ai_partner.len(),
         }
     }
+    /// tells the total number of resources in the bag
+    pub(super) fn total_count(&self) -> usize {
+        self.oxygen.len()
+            + self.hydrogen.len()
+            + self.carbon.len()
+            + self.silicon.len()
+            + self.diamond.len()
+            + self.water.len()
+            + self.life.len()
+            + self.robot.len()
+            + self.dolphin.len()
+            + self.ai_partner.len()
+    }
     ///this function checks if the explorer has the necessary resource to craft a complex resource.
     ///
     /// Returns `(bool, ResourceType, bool, ResourceType, bool)`:
@@ -165,84 +208,31 @@ impl Bag {
         &self,
         complex_type: ComplexResourceType,
     ) -> (bool, ResourceType, bool, ResourceType, bool) {
-        match complex_type {
-            ComplexResourceType::Diamond => {
-                let res = ResourceType::Basic(BasicResourceType::Carbon);
-                let n = self.count(res);
-                (n >= 2, res, n >= 1, res, n >= 2)
-            }
-            ComplexResourceType::Water => {
-                let r1 = ResourceType::Basic(BasicResourceType::Hydrogen);
-                let r2 = ResourceType::Basic(BasicResourceType::Oxygen);
-                (
-                    self.contains(r1) && self.contains(r2),
-                    r1,
-                    self.contains(r1),
-                    r2,
-                    self.contains(r2),
-                )
-            }
-            ComplexResourceType::Life => {
-                let r1 = ResourceType::Complex(ComplexResourceType::Water);
-                let r2 = ResourceType::Basic(BasicResourceType::Carbon);
-                (
-                    self.contains(r1) && self.contains(r2),
-                    r1,
-                    self.contains(r1),
-                    r2,
-                    self.contains(r2),
-                )
-            }
-            ComplexResourceType::Robot => {
-                let r1 = ResourceType::Basic(BasicResourceType::Silicon);
-                let r2 = ResourceType::Complex(ComplexResourceType::Life);
-                (
-                    self.contains(r1) && self.contains(r2),
-                    r1,
-                    self.contains(r1),
-                    r2,
-                    self.contains(r2),
-                )
-            }
-            ComplexResourceType::Dolphin => {
-                let r1 = ResourceType::Complex(ComplexResourceType::Water);
-                let r2 = ResourceType::Complex(ComplexResourceType::Life);
-                (
-                    self.contains(r1) && self.contains(r2),
-                    r1,
-                    self.contains(r1),
-                    r2,
-                    self.contains(r2),
-                )
-            }
-            ComplexResourceType::AIPartner => {
-                let r1 = ResourceType::Complex(ComplexResourceType::Robot);
-                let r2 = ResourceType::Complex(ComplexResourceType::Diamond);
-                (
-                    self.contains(r1) && self.contains(r2),
-                    r1,
-                    self.contains(r1),
-                    r2,
-                    self.contains(r2),
-                )
-            }
+        let [r1, r2] = recipes::ingredients(complex_type);
+        if r1 == r2 {
+            //e.g. Diamond needs two carbons
+            let n = self.count(r1);
+            (n >= 2, r1, n >= 1, r2, n >= 2)
+        } else {
+            (
+                self.contains(r1) && self.contains(r2),
+                r1,
+                self.contains(r1),
+                r2,
+                self.contains(r2),
+            )
         }
     }
+    /// tells if the bag holds every ingredient of a complex resource, checked before
+    /// taking any of them so that a failed request leaves the bag unchanged
+    pub(super) fn has_ingredients_for(&self, complex_type: ComplexResourceType) -> bool {
+        self.can_craft(complex_type).0
+    }
     /// this is needed because the bag cannot give his ownership to the orchestrator and cannot be passed as a reference
     ///
     /// construct an array of resource types to give to the orchestrator when requested
     pub(super) fn to_resource_types(&self) -> Vec<ResourceType> {
-        let total_size = self.oxygen.len()
-            + self.hydrogen.len()
-            + self.carbon.len()
-            + self.silicon.len()
-            + self.diamond.len()
-            + self.water.len()
-            + self.life.len()
-            + self.robot.len()
-            + self.dolphin.len()
-            + self.ai_partner.len();
-        let mut types = Vec::with_capacity(total_size); //this way the vec is already of the right size
+        let mut types = Vec::with_capacity(self.total_count()); //this way the vec is already of the right size
         for _ in 0..self.oxygen.len() {
             types.push(ResourceType::Basic(BasicResourceType::Oxygen));
         }
@@ -282,12 +272,62 @@ impl Bag {
         types
     }
 
+    /// creates the combine request of a complex resource, taking its ingredients from the bag
+    ///
+    /// Returns an error, leaving the bag unchanged, if an ingredient is missing or if the
+    /// request would make another resource
+    pub(super) fn make_complex_request(
+        &mut self,
+        complex_type: ComplexResourceType,
+    ) -> Result<ComplexResourceRequest, String> {
+        let request = match complex_type {
+            ComplexResourceType::Diamond => self.make_diamond_request(),
+            ComplexResourceType::Water => self.make_water_request(),
+            ComplexResourceType::Life => self.make_life_request(),
+            ComplexResourceType::Robot => self.make_robot_request(),
+            ComplexResourceType::Dolphin => self.make_dolphin_request(),
+            ComplexResourceType::AIPartner => self.make_ai_partner_request(),
+        }?;
+        if let Err(err) = recipes::check_request(&request, complex_type) {
+            self.restore_request(request);
+            return Err(err);
+        }
+        Ok(request)
+    }
+
+    /// puts the ingredients of a request that was not sent back in the bag
+    pub(super) fn restore_request(&mut self, request: ComplexResourceRequest) {
+        match request {
+            ComplexResourceRequest::Diamond(c1, c2) => self.carbon.extend([c1, c2]),
+            ComplexResourceRequest::Water(h, o) => {
+                self.hydrogen.push(h);
+                self.oxygen.push(o);
+            }
+            ComplexResourceRequest::Life(w, c) => {
+                self.water.push(w);
+                self.carbon.push(c);
+            }
+            ComplexResourceRequest::Robot(s, l) => {
+                self.silicon.push(s);
+                self.life.push(l);
+            }
+            ComplexResourceRequest::Dolphin(w, l) => {
+                self.water.push(w);
+                self.life.push(l);
+            }
+            ComplexResourceRequest::AIPartner(r, d) => {
+                self.robot.push(r);
+                self.diamond.push(d);
+            }
+        }
+    }
+
     /// the following methods are the ones to combine resources.
     /// They are all used in order to avoid code duplication.
     /// Returns an error if basic resources are missing, otherwise it returns a
     /// `ComplexResourceRequest` containing the basic resource needed
     pub(super) fn make_diamond_request(&mut self) -> Result<ComplexResourceRequest, String> {
-        if !self.can_craft(ComplexResourceType::Diamond).0 {
+        if !self.has_ingredients_for(ComplexResourceType::Diamond) {
             return Err("Missing resources for Diamond".to_string());
         }
 
@@ -304,7 +344,7 @@ impl Bag {
     }
 
     pub(super) fn make_water_request(&mut self) -> Result<ComplexResourceRequest, String> {
-        if !self.can_craft(ComplexResourceType::Water).0 {
+        if !self.has_ingredients_for(ComplexResourceType::Water) {
             return Err("Missing resources for Water".to_string());
         }
 
@@ -321,7 +361,7 @@ impl Bag {
     }
 
     pub(super) fn make_life_request(&mut self) -> Result<ComplexResourceRequest, String> {
-        if !self.can_craft(ComplexResourceType::Life).0 {
+        if !self.has_ingredients_for(ComplexResourceType::Life) {
             return Err("Missing resources for Life".to_string());
         }
 
@@ -338,7 +378,7 @@ impl Bag {
     }
 
     pub(super) fn make_robot_request(&mut self) -> Result<ComplexResourceRequest, String> {
-        if !self.can_craft(ComplexResourceType::Robot).0 {
+        if !self.has_ingredients_for(ComplexResourceType::Robot) {
             return Err("Missing resources for Robot".to_string());
         }
 
@@ -355,7 +395,7 @@ impl Bag {
     }
 
     pub(super) fn make_dolphin_request(&mut self) -> Result<ComplexResourceRequest, String> {
-        if !self.can_craft(ComplexResourceType::Dolphin).0 {
+        if !self.has_ingredients_for(ComplexResourceType::Dolphin) {
             return Err("Missing resources for Dolphin".to_string());
         }
 
@@ -372,7 +412,7 @@ impl Bag {
     }
 
     pub(super) fn make_ai_partner_request(&mut self) -> Result<ComplexResourceRequest, String> {
-        if !self.can_craft(ComplexResourceType::AIPartner).0 {
+        if !self.has_ingredients_for(ComplexResourceType::AIPartner) {
             return Err("Missing resources for AIPartner".to_string());
         }
 
@@ -3,12 +3,10 @@ use crate::components::mattia_explorer::handlers::{
     combine_resource_request, current_planet_request, generate_resource_request, kill_explorer,
     manage_available_energy_cell_response, manage_combine_response, manage_generate_response,
     manage_supported_combination_response, manage_supported_resource_response, move_to_planet,
-    neighbours_response, reset_explorer_ai, start_explorer_ai, stop_explorer_ai,
+    neighbours_response, planet_stopped, reset_explorer_ai, start_explorer_ai, stop_explorer_ai,
     supported_combination_request, supported_resource_request,
 };
-use crate::components::mattia_explorer::states::{
-    ExplorerState, orch_msg_match_state, planet_msg_match_state,
-};
+use crate::components::mattia_explorer::states::{orch_msg_match_state, planet_msg_match_state};
 use common_game::protocols::orchestrator_explorer::{
     ExplorerToOrchestrator, OrchestratorToExplorer,
 };
@@ -103,7 +101,7 @@ pub(super) fn manage_buffer_msg(explorer: &mut Explorer) -> Result<(), String> {
                     manage_available_energy_cell_response(explorer, available_cells)?;
                 }
                 PlanetToExplorer::Stopped => {
-                    explorer.state = ExplorerState::Idle;
+                    planet_stopped(explorer);
                 }
             }
         }
@@ -3,7 +3,9 @@ use crate::components::mattia_explorer::Explorer;
 use crate::components::mattia_explorer::ai_params::AiParams;
 use crate::components::mattia_explorer::helpers::gather_info_from_planet;
 use crate::components::mattia_explorer::planet_info::PlanetInfo;
-use crate::components::mattia_explorer::states::ExplorerState;
+use crate::components::mattia_explorer::states::{ExplorerState, SurveyFlags};
+use crate::utils::recipes;
+use crate::utils::request_ledger::PlanetRequestKind;
 use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
 use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
 use common_game::protocols::planet_explorer::ExplorerToPlanet;
@@ -126,81 +128,38 @@ impl ResourceNeeds {
             dolphin,
         }
     }
+    /// returns the need of a resource alone, without the needs it inherits
+    fn own_need(&self, resource: ResourceType) -> f32 {
+        match resource {
+            ResourceType::Basic(BasicResourceType::Oxygen) => self.oxygen,
+            ResourceType::Basic(BasicResourceType::Carbon) => self.carbon,
+            ResourceType::Basic(BasicResourceType::Silicon) => self.silicon,
+            ResourceType::Basic(BasicResourceType::Hydrogen) => self.hydrogen,
+            ResourceType::Complex(ComplexResourceType::Water) => self.water,
+            ResourceType::Complex(ComplexResourceType::Life) => self.life,
+            ResourceType::Complex(ComplexResourceType::Robot) => self.robot,
+            ResourceType::Complex(ComplexResourceType::Diamond) => self.diamond,
+            ResourceType::Complex(ComplexResourceType::AIPartner) => self.ai_partner,
+            ResourceType::Complex(ComplexResourceType::Dolphin) => self.dolphin,
+        }
+    }
     /// Recursively computes the effective need of a resource by propagating
     /// needs through the dependency hierarchy using the given `propagation_factor`.
-    /// Needs cascade from higher-level resources down to lower-level ones.
+    /// Needs cascade from every recipe using the resource (see [`recipes::used_in`])
+    /// down to its ingredients.
     /// The result is clamped between 0.0 and 1.0.
     fn get_effective_need(&self, resource: ResourceType, params: &AiParams) -> f32 {
         let pf = params.propagation_factor;
-        match resource {
-            //level 4
-            ResourceType::Complex(ComplexResourceType::AIPartner) => self.ai_partner,
-
-            // level 3
-            ResourceType::Complex(ComplexResourceType::Robot) => {
-                let ai_partner_need = self.get_effective_need(
-                    ResourceType::Complex(ComplexResourceType::AIPartner),
-                    params,
-                );
-                (self.robot + ai_partner_need * pf).min(1.0)
-            }
-            ResourceType::Complex(ComplexResourceType::Dolphin) => self.dolphin,
-
-            // level 2
-            ResourceType::Complex(ComplexResourceType::Life) => {
-                let robot_need = self
-                    .get_effective_need(ResourceType::Complex(ComplexResourceType::Robot), params);
-                let dolphin_need = self.get_effective_need(
-                    ResourceType::Complex(ComplexResourceType::Dolphin),
-                    params,
-                );
-                (self.life + robot_need * pf + dolphin_need * pf).min(1.0)
-            }
-            ResourceType::Complex(ComplexResourceType::Diamond) => {
-                let ai_partner_need = self.get_effective_need(
-                    ResourceType::Complex(ComplexResourceType::AIPartner),
-                    params,
-                );
-                (self.diamond + ai_partner_need * pf).min(1.0)
-            }
-
-            // level 1
-            ResourceType::Complex(ComplexResourceType::Water) => {
-                let life_need = self
-                    .get_effective_need(ResourceType::Complex(ComplexResourceType::Life), params);
-                let dolphin_need = self.get_effective_need(
-                    ResourceType::Complex(ComplexResourceType::Dolphin),
-                    params,
-                );
-                (self.water + life_need * pf + dolphin_need * pf).min(1.0)
-            }
-
-            // level 0: basic resources
-            ResourceType::Basic(BasicResourceType::Carbon) => {
-                let diamond_need = self.get_effective_need(
-                    ResourceType::Complex(ComplexResourceType::Diamond),
-                    params,
-                );
-                let life_need = self
-                    .get_effective_need(ResourceType::Complex(ComplexResourceType::Life), params);
-                (self.carbon + diamond_need * pf + life_need * pf).min(1.0)
-            }
-            ResourceType::Basic(BasicResourceType::Oxygen) => {
-                let water_need = self
-                    .get_effective_need(ResourceType::Complex(ComplexResourceType::Water), params);
-                (self.oxygen + water_need * pf).min(1.0)
-            }
-            ResourceType::Basic(BasicResourceType::Hydrogen) => {
-                let water_need = self
-                    .get_effective_need(ResourceType::Complex(ComplexResourceType::Water), params);
-                (self.hydrogen + water_need * pf).min(1.0)
-            }
-            ResourceType::Basic(BasicResourceType::Silicon) => {
-                let robot_need = self
-                    .get_effective_need(ResourceType::Complex(ComplexResourceType::Robot), params);
-                (self.silicon + robot_need * pf).min(1.0)
-            }
+        let users = recipes::used_in(resource);
+        if users.is_empty() {
+            //top of the hierarchy: AIPartner and Dolphin
+            return self.own_need(resource);
         }
+        let inherited: f32 = users
+            .into_iter()
+            .map(|user| self.get_effective_need(ResourceType::Complex(user), params) * pf)
+            .sum();
+        (self.own_need(resource) + inherited).min(1.0)
     }
 }
 #[derive(Debug)]
@@ -335,7 +294,7 @@ fn estimate_current_energy(
 /// survey neighbors, wait, and run_away.
 fn calc_utility(explorer: &mut Explorer) -> Result<(), String> {
     // updating planet safety score for every known ids
-    let known_ids: Vec<ID> = explorer.topology_info.keys().copied().collect();
+    let known_ids: Vec<ID> = explorer.topology_info.known_planets();
     for id in known_ids {
         let _ = calculate_safety_score(explorer, Some(id));
     }
@@ -809,7 +768,7 @@ fn can_run_away(actions: &AIAction, explorer: &Explorer) -> bool {
         return false;
     };
     for neighbor_id in neighbors {
-        let Some(planet_info) = explorer.topology_info.get(neighbor_id) else {
+        let Some(planet_info) = explorer.topology_info.get(*neighbor_id) else {
             continue;
         };
         let planet_safety = planet_info.safety_score.unwrap_or(params.safety_warning);
@@ -893,7 +852,7 @@ fn find_best_action(
         max_val = actions.survey_energy_cells;
         best = Some(AIActionType::SurveyEnergy);
     }
-    let current_planet_info = explorer.topology_info.get(&explorer.planet_id);
+    let current_planet_info = explorer.topology_info.get(explorer.planet_id);
     //guard in order to check if the planet has energy cells
     if current_planet_info.is_some_and(|x| x.energy_cells.is_some_and(|y| y > 0)) {
         // Production
@@ -951,9 +910,17 @@ fn find_best_action(
     best
 }
 
+/// checks if the energy info of the current planet is older than `energy_resurvey_interval` ticks
+fn energy_resurvey_due(explorer: &Explorer) -> Result<bool, &'static str> {
+    let interval = explorer.ai_data.params.energy_resurvey_interval;
+    let planet_info = explorer.get_current_planet_info()?;
+    Ok(interval > 0 && explorer.time.saturating_sub(planet_info.timestamp_energy) >= interval)
+}
+
 /// The main AI decision loop called every cycle when the explorer is idle and not in manual mode.
 /// Executes in three phases:
-/// 1. **Survey phase** (first visit): discovers neighbors and resources if unknown
+/// 1. **Survey phase** (first visit): discovers neighbors and resources if unknown, then
+///    surveys the energy cells again once they are older than `energy_resurvey_interval` ticks
 /// 2. **Utility calculation**: computes scores for all possible actions
 /// 3. **Action execution**: picks the action with the highest utility and executes it
 /// Handles all action types: produce, combine, move to, survey neighbors/energy, wait, and run away.
@@ -985,6 +952,9 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                 current_planet_id: explorer.planet_id,
             }) {
             Ok(()) => {
+                explorer
+                    .planet_requests
+                    .sent(PlanetRequestKind::Neighbours, explorer.planet_id);
                 return Ok(());
             }
             Err(err) => {
@@ -994,14 +964,25 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
         }
     } else if base_resource || comp_resource {
         log_internal_op!(explorer, "surveying resources");
-        explorer.state = ExplorerState::Surveying {
+        explorer.state = ExplorerState::Surveying(SurveyFlags {
             resources: base_resource,
             combinations: comp_resource,
             energy_cells: false,
             orch_resource: false,
             orch_combination: false,
-        };
+        });
         gather_info_from_planet(explorer)?;
+    } else if energy_resurvey_due(explorer)? {
+        //the available energy cells change over time, keeping the cached data fresh
+        log_internal_op!(explorer, "re-surveying energy cells");
+        explorer.state = ExplorerState::Surveying(SurveyFlags {
+            energy_cells: true,
+            ..SurveyFlags::none()
+        });
+        if let Err(err) = gather_info_from_planet(explorer) {
+            explorer.state = ExplorerState::Idle;
+            return Err(err);
+        }
     } else {
         //calculating utility of every action
         calc_utility(explorer)?;
@@ -1036,6 +1017,7 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                     if *max.0 != 0 {
                         //making sure that there is a planet to move to
                         explorer.state = ExplorerState::Traveling;
+                        explorer.next_planet_id = *max.0;
                         log_internal_op!(explorer, "action"=>"sending TravelToPlanetRequest", "planet_id"=>*max.0);
                         match explorer.orchestrator_channels.1.send(
                             ExplorerToOrchestrator::TravelToPlanetRequest {
@@ -1047,6 +1029,7 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                             Ok(()) => return Ok(()),
                             Err(err) => {
                                 explorer.state = ExplorerState::Idle;
+                                explorer.next_planet_id = explorer.planet_id;
                                 return Err(err.to_string());
                             }
                         }
@@ -1054,6 +1037,7 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                 }
                 AIActionType::MoveTo(id) => {
                     explorer.state = ExplorerState::Traveling;
+                    explorer.next_planet_id = id;
                     log_internal_op!(explorer, "action"=>"sending TravelToPlanetRequest", "planet_id"=>id);
                     match explorer.orchestrator_channels.1.send(
                         ExplorerToOrchestrator::TravelToPlanetRequest {
@@ -1067,6 +1051,7 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                         }
                         Err(err) => {
                             explorer.state = ExplorerState::Idle;
+                            explorer.next_planet_id = explorer.planet_id;
                             return Err(err.to_string());
                         }
                     }
@@ -1081,6 +1066,9 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                         },
                     ) {
                         Ok(()) => {
+                            explorer
+                                .planet_requests
+                                .sent(PlanetRequestKind::Neighbours, explorer.planet_id);
                             return Ok(());
                         }
                         Err(err) => {
@@ -1090,13 +1078,10 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                     }
                 }
                 AIActionType::SurveyEnergy => {
-                    explorer.state = ExplorerState::Surveying {
-                        resources: false,
-                        combinations: false,
+                    explorer.state = ExplorerState::Surveying(SurveyFlags {
                         energy_cells: true,
-                        orch_resource: false,
-                        orch_combination: false,
-                    };
+                        ..SurveyFlags::none()
+                    });
                     match gather_info_from_planet(explorer) {
                         Ok(()) => {
                             return Ok(());
@@ -1111,7 +1096,7 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                     explorer.state = ExplorerState::GeneratingResource {
                         orchestrator_response: false,
                     };
-                    if let Some(planet_info) = explorer.topology_info.get_mut(&explorer.planet_id) {
+                    if let Some(planet_info) = explorer.topology_info.get_mut(explorer.planet_id) {
                         if planet_info.energy_cells.is_some() {
                             planet_info.energy_cells =
                                 Some(planet_info.energy_cells.unwrap() - 1u32);
@@ -1138,20 +1123,12 @@ pub(super) fn ai_core_function(explorer: &mut Explorer) -> Result<(), String> {
                     explorer.state = ExplorerState::CombiningResources {
                         orchestrator_response: false,
                     };
-                    let complex_resource_req = match res {
-                        //provide the requested resources from the bag for each combination
-                        ComplexResourceType::Diamond => explorer.bag.make_diamond_request(),
-                        ComplexResourceType::Water => explorer.bag.make_water_request(),
-                        ComplexResourceType::Life => explorer.bag.make_life_request(),
-                        ComplexResourceType::Robot => explorer.bag.make_robot_request(),
-                        ComplexResourceType::Dolphin => explorer.bag.make_dolphin_request(),
-                        ComplexResourceType::AIPartner => explorer.bag.make_ai_partner_request(),
-                    };
+                    let complex_resource_req = explorer.bag.make_complex_request(res);
                     match complex_resource_req {
                         Ok(complex_resource_req) => {
                             log_internal_op!(explorer, "sending CombineResourceRequest");
                             if let Some(planet_info) =
-                                explorer.topology_info.get_mut(&explorer.planet_id)
+                                explorer.topology_info.get_mut(explorer.planet_id)
                             {
                                 if planet_info.energy_cells.is_some() {
                                     planet_info.energy_cells =
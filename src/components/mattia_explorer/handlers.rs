@@ -1,9 +1,10 @@
 use crate::components::mattia_explorer::explorer_ai::AiData;
 use crate::components::mattia_explorer::helpers::gather_info_from_planet;
 use crate::components::mattia_explorer::resource_management::ToGeneric;
-use crate::components::mattia_explorer::states::ExplorerState;
 use crate::components::mattia_explorer::states::ExplorerState::Surveying;
+use crate::components::mattia_explorer::states::{ExplorerState, SurveyFlags};
 use crate::components::mattia_explorer::{Explorer, PlanetInfo};
+use crate::utils::request_ledger::PlanetRequestKind;
 use common_game::components::resource::{
     BasicResource, BasicResourceType, ComplexResource, ComplexResourceType, GenericResource,
 };
@@ -40,7 +41,7 @@ pub(super) fn start_explorer_ai(explorer: &mut Explorer) -> Result<(), String> {
     Ok(())
 }
 
-/// this function resets the topology known by the explorer and its AiData,
+/// this function resets the topology known by the explorer, its AiData and its bag,
 /// it is called when the explorer receives the ResetExplorerAI message
 pub(super) fn reset_explorer_ai(explorer: &mut Explorer) -> Result<(), String> {
     explorer.state = ExplorerState::Idle;
@@ -52,6 +53,7 @@ pub(super) fn reset_explorer_ai(explorer: &mut Explorer) -> Result<(), String> {
     explorer.current_planet_neighbors_update = false;
     explorer.manual_mode = false;
     explorer.ai_data = AiData::new(explorer.ai_data.params.clone());
+    explorer.bag.clear();
     log_message!(
         ActorType::Orchestrator,
         0u32,
@@ -143,21 +145,32 @@ pub(super) fn move_to_planet(
         // the orchestrator refuses the move operation
         // the orchestrator kills also the explorer if it has already accepted the move
         Some(sender) => {
+            //the destination is the one of the last TravelToPlanetRequest, unless the
+            //orchestrator moved the explorer on its own
+            if explorer.next_planet_id != planet_id {
+                log_internal_op!(
+                    explorer,
+                    "action"=>"MoveToPlanet destination differs from next_planet_id",
+                    "next_planet_id"=>explorer.next_planet_id,
+                    "planet_id"=>planet_id
+                );
+                explorer.next_planet_id = planet_id;
+            }
             //updating planet channel and planet_id
             explorer.planet_channels.1 = sender;
-            explorer.planet_id = planet_id;
-            match explorer.topology_info.get(&planet_id) {
+            explorer.planet_id = explorer.next_planet_id;
+            match explorer.topology_info.get(planet_id) {
                 Some(planet_info) => {
                     if !explorer.manual_mode {
                         //in the case the explorer it is not in manual mode it
                         //automatically surveys vital information
-                        explorer.state = Surveying {
+                        explorer.state = Surveying(SurveyFlags {
                             resources: planet_info.basic_resources.is_none(),
                             combinations: planet_info.complex_resources.is_none(),
                             energy_cells: true,
                             orch_resource: false,
                             orch_combination: false,
-                        };
+                        });
                     }
 
                     log_internal_op!(explorer, "sending MovedToPlanetResult");
@@ -179,13 +192,13 @@ pub(super) fn move_to_planet(
                         //in the case the explorer it is not in manual mode it
                         //automatically surveys vital information
                         explorer.current_planet_neighbors_update = true;
-                        explorer.state = Surveying {
+                        explorer.state = Surveying(SurveyFlags {
                             resources: true,
                             combinations: true,
                             energy_cells: true,
                             orch_resource: false,
                             orch_combination: false,
-                        };
+                        });
                     }
                     log_internal_op!(explorer, "sending MovedToPlanetResult");
                     //sending the response to the orchestrator
@@ -208,6 +221,8 @@ pub(super) fn move_to_planet(
         }
         None => {
             //the explorer cannot move, but it is not a problem
+            //the travel is aborted, so the explorer stays where it is
+            explorer.next_planet_id = explorer.planet_id;
             //absolute priority
             explorer.current_planet_neighbors_update = true;
             log_message!(
@@ -261,9 +276,13 @@ pub(super) fn supported_resource_request(explorer: &mut Explorer) -> Result<(),
         "supported resource request";
         "planet_id"=>explorer.planet_id.to_string()
     );
-    match explorer.topology_info.get(&explorer.planet_id) {
+    match explorer.topology_info.get(explorer.planet_id) {
         Some(planet_info) => {
-            match &planet_info.basic_resources {
+            let cached = planet_info
+                .basic_resources
+                .as_ref()
+                .filter(|_| !supported_cache_expired(explorer, planet_info.timestamp_resources));
+            match cached {
                 Some(basic_resources) => {
                     //the explorer already has the supported resources in his topology
                     log_internal_op!(explorer, "sending SupportedResourceResult");
@@ -279,13 +298,13 @@ pub(super) fn supported_resource_request(explorer: &mut Explorer) -> Result<(),
                 None => match explorer.state {
                     // it is impossible that in this branch the explorer isn't in the Idle state
                     ExplorerState::Idle => {
-                        explorer.state = Surveying {
+                        explorer.state = Surveying(SurveyFlags {
                             resources: true,
                             combinations: false,
                             energy_cells: false,
                             orch_resource: true,
                             orch_combination: false,
-                        };
+                        });
                         gather_info_from_planet(explorer).map_err(|e| e.to_string())?;
                     }
                     _ => {
@@ -305,13 +324,10 @@ pub(super) fn supported_resource_request(explorer: &mut Explorer) -> Result<(),
             // it is impossible that in this branch the explorer isn't in the Idle state
             match explorer.state {
                 ExplorerState::Idle => {
-                    explorer.state = Surveying {
-                        resources: true,
-                        combinations: true,
-                        energy_cells: true,
-                        orch_resource: true,
+                    explorer.state = Surveying(SurveyFlags {
                         orch_combination: false,
-                    };
+                        ..SurveyFlags::all()
+                    });
                     gather_info_from_planet(explorer).map_err(|e| e.to_string())?;
                 }
                 _ => {
@@ -339,9 +355,13 @@ pub(super) fn supported_combination_request(explorer: &mut Explorer) -> Result<(
         "supported combination request";
         "planet_id"=>explorer.planet_id.to_string()
     );
-    match explorer.topology_info.get(&explorer.planet_id) {
+    match explorer.topology_info.get(explorer.planet_id) {
         Some(planet_info) => {
-            match &planet_info.complex_resources {
+            let cached = planet_info
+                .complex_resources
+                .as_ref()
+                .filter(|_| !supported_cache_expired(explorer, planet_info.timestamp_combinations));
+            match cached {
                 Some(complex_resource) => {
                     //the explorer already has the combination list in his topology
                     explorer
@@ -354,17 +374,17 @@ pub(super) fn supported_combination_request(explorer: &mut Explorer) -> Result<(
                         .map_err(|err| err.to_string())?;
                 }
                 None => {
-                    //this should not happen
+                    //the combinations are unknown or the cached ones expired
                     // it is impossible that in this branch the explorer isn't in the Idle state
                     match explorer.state {
                         ExplorerState::Idle => {
-                            explorer.state = Surveying {
+                            explorer.state = Surveying(SurveyFlags {
                                 resources: false,
                                 combinations: true,
                                 energy_cells: false,
                                 orch_resource: false,
                                 orch_combination: true,
-                            };
+                            });
                             gather_info_from_planet(explorer).map_err(|e| e.to_string())?;
                         }
                         _ => {
@@ -385,13 +405,10 @@ pub(super) fn supported_combination_request(explorer: &mut Explorer) -> Result<(
             );
             match explorer.state {
                 ExplorerState::Idle => {
-                    explorer.state = Surveying {
-                        resources: true,
-                        combinations: true,
-                        energy_cells: true,
+                    explorer.state = Surveying(SurveyFlags {
                         orch_resource: false,
-                        orch_combination: true,
-                    };
+                        ..SurveyFlags::all()
+                    });
                     gather_info_from_planet(explorer).map_err(|e| e.to_string())?;
                 }
                 _ => {
@@ -460,15 +477,7 @@ pub(super) fn combine_resource_request(
         "to_orchestrator" => to_orchestrator,
         "planet_id"=>explorer.planet_id.to_string()
     );
-    let complex_resource_req = match to_generate {
-        //provide the requested resources from the bag for each combination
-        ComplexResourceType::Diamond => explorer.bag.make_diamond_request(),
-        ComplexResourceType::Water => explorer.bag.make_water_request(),
-        ComplexResourceType::Life => explorer.bag.make_life_request(),
-        ComplexResourceType::Robot => explorer.bag.make_robot_request(),
-        ComplexResourceType::Dolphin => explorer.bag.make_dolphin_request(),
-        ComplexResourceType::AIPartner => explorer.bag.make_ai_partner_request(),
-    };
+    let complex_resource_req = explorer.bag.make_complex_request(to_generate);
     let ris = match complex_resource_req {
         Ok(request) => {
             //can create a request
@@ -517,16 +526,11 @@ pub(super) fn combine_resource_request(
     ris
 }
 
-/// this function processes the response of current planet neighbors updating the current planet data
+/// this function processes the response of current planet neighbors updating the current planet data,
+/// a response to a request sent before moving updates the planet the request was about
 pub(super) fn neighbours_response(explorer: &mut Explorer, neighbors: Vec<ID>) {
-    explorer.state = ExplorerState::Idle;
     //insert new planets in the topology if they are missing
-    for &neighbour in &neighbors {
-        explorer
-            .topology_info
-            .entry(neighbour)
-            .or_insert(PlanetInfo::new(0));
-    }
+    explorer.topology_info.add_planets(&neighbors);
     log_message!(
         ActorType::Planet,
         explorer.planet_id,
@@ -536,12 +540,23 @@ pub(super) fn neighbours_response(explorer: &mut Explorer, neighbors: Vec<ID>) {
         "neighbors received";
         "neighbors"=>format!("{:?}", neighbors)
     );
+    if let Some(origin) = stale_response_origin(explorer, PlanetRequestKind::Neighbours) {
+        //late response about a planet the explorer already left
+        let planet_info = explorer
+            .topology_info
+            .get_or_insert_with(origin, || PlanetInfo::new(explorer.time));
+        planet_info.set_neighbours(neighbors.into_iter().collect());
+        planet_info.timestamp_neighbors = explorer.time;
+        log_internal_op!(explorer, "action"=>"late neighbors response applied to its planet", "planet_id"=>origin);
+        return;
+    }
+    explorer.state = ExplorerState::Idle;
 
-    match explorer.topology_info.get_mut(&explorer.planet_id) {
+    match explorer.topology_info.get_mut(explorer.planet_id) {
         Some(planet_info) => {
             explorer.current_planet_neighbors_update = false;
             //already overriding the neighbors
-            planet_info.neighbors = Some(neighbors.clone().into_iter().collect());
+            planet_info.set_neighbours(neighbors.clone().into_iter().collect());
             planet_info.timestamp_neighbors = explorer.time;
             //updating ai move_utility data clearing the values (because we updated the neighbors)
             explorer.ai_data.ai_action.move_to.clear();
@@ -555,12 +570,75 @@ pub(super) fn neighbours_response(explorer: &mut Explorer, neighbors: Vec<ID>) {
             //this should never panic
             explorer
                 .topology_info
-                .get_mut(&explorer.planet_id)
+                .get_mut(explorer.planet_id)
                 .unwrap()
                 .neighbors = Some(neighbors.clone().into_iter().collect());
         }
     }
 }
+
+/// checks if supported resources/combinations observed at tick `observed` are older than
+/// `supported_cache_ttl` ticks
+fn supported_cache_expired(explorer: &Explorer, observed: u64) -> bool {
+    let ttl = explorer.ai_data.params.supported_cache_ttl;
+    ttl > 0 && explorer.time.saturating_sub(observed) >= ttl
+}
+
+/// this function returns the planet a response of the given kind comes from, if it is
+/// not the current one (the response was sent before the explorer moved)
+fn stale_response_origin(explorer: &mut Explorer, kind: PlanetRequestKind) -> Option<ID> {
+    match explorer.planet_requests.answered(kind) {
+        Some(origin) if origin != explorer.planet_id => Some(origin),
+        _ => None,
+    }
+}
+
+/// this function is called after a late response: if the survey of the current planet still
+/// waits for the given kind but no such request to the current planet is pending anymore
+/// (e.g. it was forgotten), the answer will never come, so the survey stops waiting for it
+fn settle_survey_flag(explorer: &mut Explorer, kind: PlanetRequestKind) {
+    let Surveying(flags) = explorer.state else {
+        return;
+    };
+    if explorer
+        .planet_requests
+        .is_pending(kind, explorer.planet_id)
+    {
+        return;
+    }
+    let remaining = match kind {
+        PlanetRequestKind::SupportedResources if flags.resources => SurveyFlags {
+            resources: false,
+            orch_resource: false,
+            ..flags
+        },
+        PlanetRequestKind::SupportedCombinations if flags.combinations => SurveyFlags {
+            combinations: false,
+            orch_combination: false,
+            ..flags
+        },
+        PlanetRequestKind::AvailableEnergyCells if flags.energy_cells => SurveyFlags {
+            energy_cells: false,
+            ..flags
+        },
+        _ => return,
+    };
+    log_internal_op!(explorer, "action"=>"survey stopped waiting for an unanswerable request", "kind"=>format!("{:?}", kind));
+    if remaining.is_complete() {
+        explorer.state = ExplorerState::Idle;
+    } else {
+        explorer.state = Surveying(remaining);
+    }
+}
+
+/// this function is called when the planet answers Stopped: a stopped planet rejects
+/// every request, so the ones still pending are forgotten
+pub(super) fn planet_stopped(explorer: &mut Explorer) {
+    explorer.state = ExplorerState::Idle;
+    explorer.planet_requests.forget_planet(explorer.planet_id);
+    log_internal_op!(explorer, "action"=>"pending requests of a stopped planet forgotten", "planet_id"=>explorer.planet_id);
+}
+
 /// this function takes a basic resource list and updates the explorer topology data,
 /// also if the orchestrator requested the supported resource this function will send it
 /// to the orchestrator
@@ -577,17 +655,30 @@ pub(super) fn manage_supported_resource_response(
         "supported resource received";
         "supported resource"=>format!("{:?}", resource_list)
     );
+    if let Some(origin) = stale_response_origin(explorer, PlanetRequestKind::SupportedResources) {
+        //late response from a planet the explorer already left
+        match explorer.topology_info.get_mut(origin) {
+            Some(planet_info) => {
+                planet_info.set_basic_resources(resource_list);
+                planet_info.timestamp_resources = explorer.time;
+                if planet_info.complex_resources.is_some() {
+                    planet_info.calculate_planet_type()?;
+                }
+                log_internal_op!(explorer, "action"=>"late supported resource response applied to its planet", "planet_id"=>origin);
+            }
+            None => {
+                log_internal_op!(explorer, "action"=>"late supported resource response discarded", "planet_id"=>origin);
+            }
+        }
+        settle_survey_flag(explorer, PlanetRequestKind::SupportedResources);
+        return Ok(());
+    }
     match explorer.state {
-        Surveying {
-            resources: true,
-            combinations,
-            energy_cells,
-            orch_resource,
-            orch_combination,
-        } => {
-            match explorer.topology_info.get_mut(&explorer.planet_id) {
+        Surveying(flags) if flags.resources => {
+            match explorer.topology_info.get_mut(explorer.planet_id) {
                 Some(planet_info) => {
-                    planet_info.basic_resources = Some(resource_list.clone());
+                    planet_info.set_basic_resources(resource_list.clone());
+                    planet_info.timestamp_resources = explorer.time;
                     if planet_info.complex_resources.is_some() {
                         //estimating the current planet type
                         planet_info.calculate_planet_type()?;
@@ -601,12 +692,12 @@ pub(super) fn manage_supported_resource_response(
                     //this should never panic
                     explorer
                         .topology_info
-                        .get_mut(&explorer.planet_id)
+                        .get_mut(explorer.planet_id)
                         .unwrap()
                         .basic_resources = Some(resource_list.clone());
                 }
             }
-            if orch_resource {
+            if flags.orch_resource {
                 //sending supported resource to the orchestrator if it was requested
                 log_internal_op!(explorer, "sending SupportedResourceResult");
                 explorer
@@ -620,17 +711,16 @@ pub(super) fn manage_supported_resource_response(
             }
 
             //updating explorer state
-            if !combinations && !energy_cells {
+            let remaining = SurveyFlags {
+                resources: false,
+                orch_resource: false,
+                ..flags
+            };
+            if remaining.is_complete() {
                 //if the explorer is not waiting for energy cells and combinations response
                 explorer.state = ExplorerState::Idle;
             } else {
-                explorer.state = Surveying {
-                    resources: false,
-                    combinations,
-                    energy_cells,
-                    orch_resource: false,
-                    orch_combination,
-                };
+                explorer.state = Surveying(remaining);
             }
         }
         _ => {
@@ -657,17 +747,31 @@ pub(super) fn manage_supported_combination_response(
         "supported combinations received";
         "supported combinations"=>format!("{:?}", combination_list)
     );
+    if let Some(origin) = stale_response_origin(explorer, PlanetRequestKind::SupportedCombinations)
+    {
+        //late response from a planet the explorer already left
+        match explorer.topology_info.get_mut(origin) {
+            Some(planet_info) => {
+                planet_info.set_complex_resources(combination_list);
+                planet_info.timestamp_combinations = explorer.time;
+                if planet_info.basic_resources.is_some() {
+                    planet_info.calculate_planet_type()?;
+                }
+                log_internal_op!(explorer, "action"=>"late supported combinations response applied to its planet", "planet_id"=>origin);
+            }
+            None => {
+                log_internal_op!(explorer, "action"=>"late supported combinations response discarded", "planet_id"=>origin);
+            }
+        }
+        settle_survey_flag(explorer, PlanetRequestKind::SupportedCombinations);
+        return Ok(());
+    }
     match explorer.state {
-        Surveying {
-            resources,
-            combinations: true,
-            energy_cells,
-            orch_resource,
-            orch_combination,
-        } => {
-            match explorer.topology_info.get_mut(&explorer.planet_id) {
+        Surveying(flags) if flags.combinations => {
+            match explorer.topology_info.get_mut(explorer.planet_id) {
                 Some(planet_info) => {
-                    planet_info.complex_resources = Some(combination_list.clone());
+                    planet_info.set_complex_resources(combination_list.clone());
+                    planet_info.timestamp_combinations = explorer.time;
                     if planet_info.basic_resources.is_some() {
                         //estimating the current planet type
                         planet_info.calculate_planet_type()?;
@@ -681,12 +785,12 @@ pub(super) fn manage_supported_combination_response(
                     //this should never panic
                     explorer
                         .topology_info
-                        .get_mut(&explorer.planet_id)
+                        .get_mut(explorer.planet_id)
                         .unwrap()
                         .complex_resources = Some(combination_list.clone());
                 }
             }
-            if orch_combination {
+            if flags.orch_combination {
                 // sending the combinations to orchestrator if it was requested
                 log_internal_op!(explorer, "sending SupportedCombinationResult");
                 explorer
@@ -698,17 +802,16 @@ pub(super) fn manage_supported_combination_response(
                     })
                     .map_err(|err| err.to_string())?;
             }
-            if !resources && !energy_cells {
+            let remaining = SurveyFlags {
+                combinations: false,
+                orch_combination: false,
+                ..flags
+            };
+            if remaining.is_complete() {
                 //if the explorer is not waiting for energy cells and resources response
                 explorer.state = ExplorerState::Idle;
             } else {
-                explorer.state = Surveying {
-                    resources,
-                    combinations: false,
-                    energy_cells,
-                    orch_resource,
-                    orch_combination: false,
-                };
+                explorer.state = Surveying(remaining);
             }
         }
         _ => {
@@ -770,13 +873,10 @@ pub(super) fn manage_generate_response(
                     .map_err(|err| err.to_string())?;
             }
             if survey_energy_cells {
-                explorer.state = Surveying {
-                    resources: false,
-                    combinations: false,
+                explorer.state = Surveying(SurveyFlags {
                     energy_cells: true,
-                    orch_resource: false,
-                    orch_combination: false,
-                };
+                    ..SurveyFlags::none()
+                });
                 gather_info_from_planet(explorer).map_err(|e| e.to_string())?;
             } else {
                 explorer.state = ExplorerState::Idle;
@@ -865,16 +965,16 @@ pub(super) fn manage_available_energy_cell_response(
         "available energy cells received";
         "available_cells" => format!("{:?}", available_cells)
     );
+    if let Some(origin) = stale_response_origin(explorer, PlanetRequestKind::AvailableEnergyCells) {
+        //the energy cells of a planet the explorer already left are useless
+        log_internal_op!(explorer, "action"=>"late available energy cells response discarded", "planet_id"=>origin);
+        settle_survey_flag(explorer, PlanetRequestKind::AvailableEnergyCells);
+        return Ok(());
+    }
 
     match explorer.state {
-        Surveying {
-            resources,
-            combinations,
-            energy_cells: true,
-            orch_resource,
-            orch_combination,
-        } => {
-            if let Some(planet_info) = explorer.topology_info.get_mut(&explorer.planet_id) {
+        Surveying(flags) if flags.energy_cells => {
+            if let Some(planet_info) = explorer.topology_info.get_mut(explorer.planet_id) {
                 planet_info.update_charge_rate(
                     available_cells,
                     explorer.time,
@@ -882,16 +982,14 @@ pub(super) fn manage_available_energy_cell_response(
                     explorer.explorer_id,
                 );
             }
-            if !resources && !combinations {
+            let remaining = SurveyFlags {
+                energy_cells: false,
+                ..flags
+            };
+            if remaining.is_complete() {
                 explorer.state = ExplorerState::Idle;
             } else {
-                explorer.state = Surveying {
-                    resources,
-                    combinations,
-                    energy_cells: false,
-                    orch_resource,
-                    orch_combination,
-                };
+                explorer.state = Surveying(remaining);
             }
         }
         _ => {
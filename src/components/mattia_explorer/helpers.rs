@@ -1,5 +1,6 @@
 use crate::components::mattia_explorer::Explorer;
 use crate::components::mattia_explorer::states::ExplorerState;
+use crate::utils::request_ledger::PlanetRequestKind;
 use common_game::logging::{ActorType};
 use common_game::protocols::planet_explorer::ExplorerToPlanet;
 use logging_utils::LoggableActor;
@@ -10,14 +11,8 @@ use logging_utils::log_internal_op;
 /// the supported resource and the supported combination
 pub(super) fn gather_info_from_planet(explorer: &mut Explorer) -> Result<(), String> {
     match explorer.state {
-        ExplorerState::Surveying {
-            resources,
-            combinations,
-            energy_cells,
-            orch_resource: _orch_resource,
-            orch_combination: _orch_combination,
-        } => {
-            if resources {
+        ExplorerState::Surveying(flags) => {
+            if flags.resources {
                 log_internal_op!(explorer, "sending SupportedResourceRequest");
                 explorer
                     .planet_channels
@@ -26,8 +21,11 @@ pub(super) fn gather_info_from_planet(explorer: &mut Explorer) -> Result<(), Str
                         explorer_id: explorer.explorer_id,
                     })
                     .map_err(|e| format!("Error sending SupportedResourceRequest: {}", e))?;
+                explorer
+                    .planet_requests
+                    .sent(PlanetRequestKind::SupportedResources, explorer.planet_id);
             }
-            if combinations {
+            if flags.combinations {
                 log_internal_op!(explorer, "sending SupportedCombinationRequest");
                 explorer
                     .planet_channels
@@ -36,8 +34,11 @@ pub(super) fn gather_info_from_planet(explorer: &mut Explorer) -> Result<(), Str
                         explorer_id: explorer.explorer_id,
                     })
                     .map_err(|e| format!("Error sending SupportedCombinationRequest: {}", e))?;
+                explorer
+                    .planet_requests
+                    .sent(PlanetRequestKind::SupportedCombinations, explorer.planet_id);
             }
-            if energy_cells {
+            if flags.energy_cells {
                 log_internal_op!(explorer, "sending AvailableEnergyCellRequest");
                 explorer
                     .planet_channels
@@ -46,6 +47,9 @@ pub(super) fn gather_info_from_planet(explorer: &mut Explorer) -> Result<(), Str
                         explorer_id: explorer.explorer_id,
                     })
                     .map_err(|e| format!("Error sending AvailableEnergyCellRequest: {}", e))?;
+                explorer
+                    .planet_requests
+                    .sent(PlanetRequestKind::AvailableEnergyCells, explorer.planet_id);
             }
         }
         _ => {
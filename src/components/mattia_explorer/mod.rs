@@ -17,33 +17,38 @@ use crate::components::mattia_explorer::handlers::{
     combine_resource_request, current_planet_request, generate_resource_request, kill_explorer,
     manage_available_energy_cell_response, manage_combine_response, manage_generate_response,
     manage_supported_combination_response, manage_supported_resource_response, move_to_planet,
-    neighbours_response, reset_explorer_ai, start_explorer_ai, stop_explorer_ai,
+    neighbours_response, planet_stopped, reset_explorer_ai, start_explorer_ai, stop_explorer_ai,
     supported_combination_request, supported_resource_request,
 };
 use crate::components::mattia_explorer::planet_info::PlanetInfo;
 use crate::components::mattia_explorer::states::{
     ExplorerState, orch_msg_match_state, planet_msg_match_state,
 };
-use common_game::components::resource::ResourceType;
+use crate::components::tommy_explorer::topology::TopologyManager;
+use crate::utils::TopologyDelta;
+use crate::utils::explorer_buffer::buffer_orchestrator_msg;
+use crate::utils::request_ledger::PlanetRequestLedger;
+use common_game::components::resource::{GenericResource, ResourceType};
 use common_game::protocols::orchestrator_explorer::{
     ExplorerToOrchestrator, OrchestratorToExplorer,
 };
 use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
 use common_game::utils::ID;
-use crossbeam_channel::{Receiver, Sender};
-use std::collections::{HashMap, VecDeque};
+use crossbeam_channel::{Receiver, Sender, never};
+use std::collections::VecDeque;
 
 /// struct of the explorer data
 pub(super) struct Explorer {
     explorer_id: ID, //explorer id
     planet_id: ID,   //current planet id
+    next_planet_id: ID, //destination of the last TravelToPlanetRequest (equal to planet_id when not traveling)
     orchestrator_channels: (
         // orchestrator channels
         Receiver<OrchestratorToExplorer>,
         Sender<ExplorerToOrchestrator<Vec<ResourceType>>>,
     ),
     planet_channels: (Receiver<PlanetToExplorer>, Sender<ExplorerToPlanet>), //planet channels
-    topology_info: HashMap<ID, PlanetInfo>, //hashmap containing the information of every planet
+    topology_info: TopologyManager<PlanetInfo>, //information of every known planet
     state: ExplorerState,
     bag: Bag,
     buffer_orchestrator_msg: VecDeque<OrchestratorToExplorer>, // orchestrator messages that the explorer cannot respond to immediately
@@ -52,6 +57,9 @@ pub(super) struct Explorer {
     ai_data: AiData,                               // data needed by the explorer ai
     current_planet_neighbors_update: bool,         //flag that states if the neighbors need update
     manual_mode: bool, //flag that states if the explorer is in manual mode
+    planet_requests: PlanetRequestLedger, //planet requests waiting for a response, with the planet they were sent to
+    topology_receiver: Receiver<TopologyDelta>, //links of the galaxy created or destroyed, sent by the orchestrator
+    planet_channel_active: bool, //false after the planet channel disconnects, until the next MoveToPlanet
 }
 
 impl Explorer {
@@ -95,14 +103,13 @@ impl Explorer {
             "explorer_to_orchestrator_channels" => format!("({}, {})", get_receiver_id(&explorer_to_orchestrator_channels.0), get_sender_id(&explorer_to_orchestrator_channels.1)),
             "explorer_to_planet_channels"=>format!("({}, {})", get_receiver_id(&explorer_to_planet_channels.0), get_sender_id(&explorer_to_planet_channels.1)),
         );
-        let mut starting_topology_info = HashMap::new();
-        starting_topology_info.insert(planet_id, PlanetInfo::new(0));
         Self {
             explorer_id,
             planet_id,
+            next_planet_id: planet_id,
             orchestrator_channels: explorer_to_orchestrator_channels,
             planet_channels: explorer_to_planet_channels,
-            topology_info: starting_topology_info,
+            topology_info: TopologyManager::with_starting_planet(planet_id),
             state: ExplorerState::Idle,
             bag: Bag::new(),
             buffer_orchestrator_msg: VecDeque::new(),
@@ -111,20 +118,33 @@ impl Explorer {
             ai_data: AiData::new(ai_params),
             current_planet_neighbors_update: false,
             manual_mode: true,
+            planet_requests: PlanetRequestLedger::new(),
+            topology_receiver: never(),
+            planet_channel_active: true,
         }
     }
 
     /// getter function for the id
-    pub fn id(&self) -> u32 {
+    pub fn explorer_id(&self) -> u32 {
         self.explorer_id
     }
 
+    /// getter function for the id of the planet the explorer is on
+    pub fn planet_id(&self) -> u32 {
+        self.planet_id
+    }
+
+    /// getter function for the state of the explorer state machine
+    pub fn state(&self) -> &ExplorerState {
+        &self.state
+    }
+
     ///generic getters for planet_info
     fn get_planet_info(&self, planet_id: ID) -> Option<&PlanetInfo> {
-        self.topology_info.get(&planet_id)
+        self.topology_info.get(planet_id)
     }
     fn get_planet_info_mut(&mut self, planet_id: ID) -> Option<&mut PlanetInfo> {
-        self.topology_info.get_mut(&planet_id)
+        self.topology_info.get_mut(planet_id)
     }
     /// current planet getters
     fn get_current_planet_info(&self) -> Result<&PlanetInfo, &'static str> {
@@ -140,6 +160,47 @@ impl Explorer {
         }
     }
 
+    /// sets the receiver of the orchestrator topology notifications
+    pub(super) fn set_topology_receiver(&mut self, receiver: Receiver<TopologyDelta>) {
+        self.topology_receiver = receiver;
+    }
+
+    /// puts a resource in the bag, used to give the explorer its starting resources
+    pub(super) fn insert_in_bag(&mut self, resource: GenericResource) {
+        self.bag.insert(resource);
+    }
+
+    /// applies the topology notifications received from the orchestrator to the
+    /// known neighbors of the planets
+    fn apply_topology_deltas(&mut self) {
+        while let Ok(delta) = self.topology_receiver.try_recv() {
+            for &(a, b) in &delta.removed {
+                self.topology_info.remove_edge(a, b);
+            }
+            for &planet_id in &delta.dead_planets {
+                self.planet_requests.forget_planet(planet_id);
+            }
+            for &(a, b) in &delta.added {
+                for (planet_id, other) in [(a, b), (b, a)] {
+                    let linked = self
+                        .get_planet_info_mut(planet_id)
+                        .and_then(|info| info.neighbors.as_mut())
+                        .is_some_and(|neighbors| neighbors.insert(other));
+                    if linked {
+                        let time = self.time;
+                        self.topology_info
+                            .get_or_insert_with(other, || PlanetInfo::new(time));
+                    }
+                }
+            }
+            log_internal_op!(
+                self,
+                "action" => "topology delta applied",
+                "delta" => format!("{:?}", delta)
+            );
+        }
+    }
+
     /// the explorer main loop
     ///
     /// every iteration the explorer receives messages from both planet and orchestrator channels,
@@ -147,274 +208,322 @@ impl Explorer {
     /// do not match together the message is pushed into the corresponding buffer, and it will be read
     /// when the explorer will be in an "Idle" state
     pub fn run(&mut self) -> Result<(), String> {
-        // Flag to track whether the planet channel is still alive
-        let mut planet_channel_active = true;
-
+        self.planet_channel_active = true;
         loop {
-            debug_println!("{:?}", planet_channel_active);
-            self.time = self.time.wrapping_add(1);
-
-            // Represents which channel fired and carries the received message (or disconnect error)
-            enum Selected {
-                Orchestrator(Result<OrchestratorToExplorer, crossbeam_channel::RecvError>),
-                Planet(Result<PlanetToExplorer, crossbeam_channel::RecvError>),
-                None,
+            if let Some(exit) = self.run_iteration() {
+                return exit;
             }
+            sleep(Duration::from_millis(20));
+        }
+    }
+
+    /// runs the main loop until `deadline`, then returns the state of the explorer
+    ///
+    /// it returns earlier if the explorer is killed, or with an error if the orchestrator
+    /// channel disconnects; used to drive the real loop without a thread
+    pub fn run_until(&mut self, deadline: Instant) -> Result<&ExplorerState, String> {
+        while Instant::now() < deadline {
+            if let Some(exit) = self.run_iteration() {
+                return exit.map(|_| &self.state);
+            }
+            sleep(Duration::from_millis(20));
+        }
+        Ok(&self.state)
+    }
+
+    /// runs `ticks` iterations of the main loop, then returns the state of the explorer
+    ///
+    /// see [`run_until`](Self::run_until)
+    pub fn run_ticks(&mut self, ticks: u64) -> Result<&ExplorerState, String> {
+        for _ in 0..ticks {
+            if let Some(exit) = self.run_iteration() {
+                return exit.map(|_| &self.state);
+            }
+            sleep(Duration::from_millis(20));
+        }
+        Ok(&self.state)
+    }
 
-            let selected = {
-                let mut sel = crossbeam_channel::Select::new();
-                let orch_idx = sel.recv(&self.orchestrator_channels.0);
-                let planet_idx;
-                if planet_channel_active {
-                    planet_idx = Some(sel.recv(&self.planet_channels.0))
-                } else {
-                    planet_idx = None
+    /// a single iteration of the main loop
+    ///
+    /// returns Some when the loop has to exit, with the result of the loop
+    fn run_iteration(&mut self) -> Option<Result<(), String>> {
+        debug_println!("{:?}", self.planet_channel_active);
+        self.time = self.time.wrapping_add(1);
+        self.apply_topology_deltas();
+
+        // Represents which channel fired and carries the received message (or disconnect error)
+        enum Selected {
+            Orchestrator(Result<OrchestratorToExplorer, crossbeam_channel::RecvError>),
+            Planet(Result<PlanetToExplorer, crossbeam_channel::RecvError>),
+            None,
+        }
+
+        let selected = {
+            let mut sel = crossbeam_channel::Select::new();
+            let orch_idx = sel.recv(&self.orchestrator_channels.0);
+            let planet_idx;
+            if self.planet_channel_active {
+                planet_idx = Some(sel.recv(&self.planet_channels.0))
+            } else {
+                planet_idx = None
+            }
+
+            match sel.try_select() {
+                // No message ready on any channel
+                Err(_) => Selected::None,
+                Ok(oper) if oper.index() == orch_idx => {
+                    // Consume the operation and capture the message before dropping sel
+                    Selected::Orchestrator(oper.recv(&self.orchestrator_channels.0))
+                }
+                Ok(oper) if planet_idx.is_some_and(|pi| oper.index() == pi) => {
+                    //consume the message only if the planet is alive
+                    Selected::Planet(oper.recv(&self.planet_channels.0))
                 }
+                // Unreachable: sel only contains the two indices above
+                Ok(_) => Selected::None,
+            }
+        };
+
+        // processing the new message
+        match selected {
+            Selected::None => {
+                // processing buffed messages
+                log_internal_op!(
+                    self,
+                    "action"   => "no message in the channels",
+                    "explorer_state" => format!("{:?}", self.state)
+                );
 
-                match sel.try_select() {
-                    // No message ready on any channel
-                    Err(_) => Selected::None,
-                    Ok(oper) if oper.index() == orch_idx => {
-                        // Consume the operation and capture the message before dropping sel
-                        Selected::Orchestrator(oper.recv(&self.orchestrator_channels.0))
+                if !self.buffer_planet_msg.is_empty() || !self.buffer_orchestrator_msg.is_empty() {
+                    if let Err(err) = manage_buffer_msg(self) {
+                        LogEvent::self_directed(
+                            Participant::new(ActorType::Explorer, self.explorer_id),
+                            EventType::InternalExplorerAction,
+                            Channel::Warning,
+                            warning_payload!(
+                                "message_buffer_handler returned an error",
+                                err,
+                                "mattia_explorer::run()"
+                            ),
+                        )
+                        .emit();
                     }
-                    Ok(oper) if planet_idx.is_some_and(|pi| oper.index() == pi) => {
-                        //consume the message only if the planet is alive
-                        Selected::Planet(oper.recv(&self.planet_channels.0))
+                    if self.state == ExplorerState::Killed {
+                        return Some(Ok(()));
+                    }
+                } else if !self.manual_mode && self.state == ExplorerState::Idle {
+                    //buffers empty and not in manual mode => running ai
+                    if let Err(err) = ai_core_function(self) {
+                        LogEvent::self_directed(
+                            Participant::new(ActorType::Explorer, self.explorer_id),
+                            EventType::InternalExplorerAction,
+                            Channel::Warning,
+                            warning_payload!(
+                                "ai_core_function returned an error",
+                                err,
+                                "mattia_explorer::run()"
+                            ),
+                        )
+                        .emit();
                     }
-                    // Unreachable: sel only contains the two indices above
-                    Ok(_) => Selected::None,
                 }
-            };
-
-            // processing the new message
-            match selected {
-                Selected::None => {
-                    // processing buffed messages
-                    log_internal_op!(
-                        self,
-                        "action"   => "no message in the channels",
-                        "explorer_state" => format!("{:?}", self.state)
-                    );
-
-                    if !self.buffer_planet_msg.is_empty()
-                        || !self.buffer_orchestrator_msg.is_empty()
-                    {
-                        if let Err(err) = manage_buffer_msg(self) {
-                            LogEvent::self_directed(
-                                Participant::new(ActorType::Explorer, self.explorer_id),
-                                EventType::InternalExplorerAction,
-                                Channel::Warning,
-                                warning_payload!(
-                                    "message_buffer_handler returned an error",
-                                    err,
-                                    "mattia_explorer::run()"
-                                ),
-                            )
-                            .emit();
-                        }
-                        if self.state == ExplorerState::Killed {
-                            return Ok(());
-                        }
-                    } else if !self.manual_mode && self.state == ExplorerState::Idle {
-                        //buffers empty and not in manual mode => running ai
-                        if let Err(err) = ai_core_function(self) {
-                            LogEvent::self_directed(
-                                Participant::new(ActorType::Explorer, self.explorer_id),
-                                EventType::InternalExplorerAction,
-                                Channel::Warning,
-                                warning_payload!(
-                                    "ai_core_function returned an error",
+            }
+
+            Selected::Orchestrator(msg_result) => {
+                match msg_result {
+                    //processing orchestrator message
+                    Ok(msg) => {
+                        log_message!(
+                            ActorType::Orchestrator, 0u32,
+                            ActorType::Explorer,     self.explorer_id,
+                            EventType::MessageOrchestratorToExplorer,
+                            "message received";
+                            "msg"          => format!("{:?}", msg),
+                            "explorer data" => format!("{:?}", self)
+                        );
+
+                        if orch_msg_match_state(&self.state, &msg) {
+                            let ris = match msg {
+                                OrchestratorToExplorer::StartExplorerAI => start_explorer_ai(self),
+                                OrchestratorToExplorer::ResetExplorerAI => reset_explorer_ai(self),
+                                OrchestratorToExplorer::StopExplorerAI => stop_explorer_ai(self),
+                                OrchestratorToExplorer::KillExplorer => {
+                                    if let Err(err) = kill_explorer(self) {
+                                        LogEvent::new(
+                                            Some(Participant::new(
+                                                ActorType::Explorer,
+                                                self.explorer_id,
+                                            )),
+                                            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+                                            EventType::MessageExplorerToOrchestrator,
+                                            Channel::Warning,
+                                            warning_payload!(
+                                                "kill_explorer() generated an error",
+                                                err,
+                                                "mattia_explorer::run()"
+                                            ),
+                                        )
+                                        .emit();
+                                    }
+                                    // exiting the loop
+                                    return Some(Ok(()));
+                                }
+                                OrchestratorToExplorer::MoveToPlanet {
+                                    sender_to_new_planet,
+                                    planet_id,
+                                } => {
+                                    // A new planet is being assigned: re-enable the planet channel
+                                    self.planet_channel_active = true;
+                                    move_to_planet(self, sender_to_new_planet, planet_id)
+                                }
+                                OrchestratorToExplorer::CurrentPlanetRequest => {
+                                    current_planet_request(self)
+                                }
+                                OrchestratorToExplorer::SupportedResourceRequest => {
+                                    supported_resource_request(self)
+                                }
+                                OrchestratorToExplorer::SupportedCombinationRequest => {
+                                    supported_combination_request(self)
+                                }
+                                OrchestratorToExplorer::GenerateResourceRequest { to_generate } => {
+                                    generate_resource_request(self, to_generate, true)
+                                }
+                                OrchestratorToExplorer::CombineResourceRequest { to_generate } => {
+                                    combine_resource_request(self, to_generate, true)
+                                }
+                                OrchestratorToExplorer::BagContentRequest => self
+                                    .orchestrator_channels
+                                    .1
+                                    .send(ExplorerToOrchestrator::BagContentResponse {
+                                        explorer_id: self.explorer_id,
+                                        bag_content: self.bag.to_resource_types(),
+                                    })
+                                    .map_err(|e| e.to_string()),
+                                OrchestratorToExplorer::NeighborsResponse { neighbors } => {
+                                    neighbours_response(self, neighbors);
+                                    Ok(())
+                                }
+                            };
+
+                            if let Err(err) = ris {
+                                LogEvent::self_directed(
+                                    Participant::new(ActorType::Explorer, self.explorer_id),
+                                    EventType::InternalExplorerAction,
+                                    Channel::Warning,
+                                    warning_payload!(
+                                    "a handler of a OrchestratorToExplorer message returned an error",
                                     err,
                                     "mattia_explorer::run()"
                                 ),
-                            )
-                            .emit();
+                                )
+                                    .emit();
+                            }
+                        } else {
+                            // Explorer is not in a state that can process this message: buffer it
+                            buffer_orchestrator_msg(&mut self.buffer_orchestrator_msg, msg);
                         }
                     }
+                    Err(err) => {
+                        LogEvent::self_directed(
+                            Participant::new(ActorType::Explorer, self.explorer_id),
+                            EventType::InternalExplorerAction,
+                            Channel::Error,
+                            warning_payload!(
+                                "Fatal Error: receiving channel from orchestrator disconnected",
+                                err,
+                                "mattia_explorer::run()"
+                            ),
+                        )
+                        .emit();
+                        return Some(Err(err.to_string()));
+                    }
                 }
+            }
 
-                Selected::Orchestrator(msg_result) => {
-                    match msg_result {
-                        //processing orchestrator message
-                        Ok(msg) => {
-                            log_message!(
-                                ActorType::Orchestrator, 0u32,
-                                ActorType::Explorer,     self.explorer_id,
-                                EventType::MessageOrchestratorToExplorer,
-                                "message received";
-                                "msg"          => format!("{:?}", msg),
-                                "explorer data" => format!("{:?}", self)
-                            );
-
-                            if orch_msg_match_state(&self.state, &msg) {
-                                let ris = match msg {
-                                    OrchestratorToExplorer::StartExplorerAI => {
-                                        start_explorer_ai(self)
-                                    }
-                                    OrchestratorToExplorer::ResetExplorerAI => {
-                                        reset_explorer_ai(self)
-                                    }
-                                    OrchestratorToExplorer::StopExplorerAI => {
-                                        stop_explorer_ai(self)
-                                    }
-                                    OrchestratorToExplorer::KillExplorer => {
-                                        if let Err(err) = kill_explorer(self) {
-                                            LogEvent::new(
-                                                Some(Participant::new(
-                                                    ActorType::Explorer,
-                                                    self.explorer_id,
-                                                )),
-                                                Some(Participant::new(
-                                                    ActorType::Orchestrator,
-                                                    0u32,
-                                                )),
-                                                EventType::MessageExplorerToOrchestrator,
-                                                Channel::Warning,
-                                                warning_payload!(
-                                                    "kill_explorer() generated an error",
-                                                    err,
-                                                    "mattia_explorer::run()"
-                                                ),
-                                            )
-                                            .emit();
-                                        }
-                                        // exiting the loop
-                                        return Ok(());
-                                    }
-                                    OrchestratorToExplorer::MoveToPlanet {
-                                        sender_to_new_planet,
-                                        planet_id,
-                                    } => {
-                                        // A new planet is being assigned: re-enable the planet channel
-                                        planet_channel_active = true;
-                                        move_to_planet(self, sender_to_new_planet, planet_id)
-                                    }
-                                    OrchestratorToExplorer::CurrentPlanetRequest => {
-                                        current_planet_request(self)
-                                    }
-                                    OrchestratorToExplorer::SupportedResourceRequest => {
-                                        supported_resource_request(self)
-                                    }
-                                    OrchestratorToExplorer::SupportedCombinationRequest => {
-                                        supported_combination_request(self)
-                                    }
-                                    OrchestratorToExplorer::GenerateResourceRequest {
-                                        to_generate,
-                                    } => generate_resource_request(self, to_generate, true),
-                                    OrchestratorToExplorer::CombineResourceRequest {
-                                        to_generate,
-                                    } => combine_resource_request(self, to_generate, true),
-                                    OrchestratorToExplorer::BagContentRequest => self
-                                        .orchestrator_channels
-                                        .1
-                                        .send(ExplorerToOrchestrator::BagContentResponse {
-                                            explorer_id: self.explorer_id,
-                                            bag_content: self.bag.to_resource_types(),
-                                        })
-                                        .map_err(|e| e.to_string()),
-                                    OrchestratorToExplorer::NeighborsResponse { neighbors } => {
-                                        neighbours_response(self, neighbors);
-                                        Ok(())
-                                    }
-                                };
-
-                                if let Err(err) = ris {
-                                    LogEvent::self_directed(
-                                        Participant::new(ActorType::Explorer, self.explorer_id),
-                                        EventType::InternalExplorerAction,
-                                        Channel::Warning,
-                                        warning_payload!(
-                                        "a handler of a OrchestratorToExplorer message returned an error",
+            Selected::Planet(msg_result) => {
+                match msg_result {
+                    Ok(msg) => {
+                        log_message!(
+                            ActorType::Planet,   self.planet_id,
+                            ActorType::Explorer, self.explorer_id,
+                            EventType::MessagePlanetToExplorer,
+                            "message received";
+                            "msg"           => format!("{:?}", msg),
+                            "explorer data" => format!("{:?}", self)
+                        );
+                        // a stopped planet answers Stopped to every request, any other
+                        // answer means it runs
+                        if matches!(msg, PlanetToExplorer::Stopped) {
+                            self.topology_info.mark_as_stopped(self.planet_id);
+                        } else {
+                            self.topology_info.mark_as_running(self.planet_id);
+                        }
+
+                        if planet_msg_match_state(&self.state, &msg) {
+                            let ris = match msg {
+                                PlanetToExplorer::SupportedResourceResponse { resource_list } => {
+                                    manage_supported_resource_response(self, resource_list)
+                                }
+                                PlanetToExplorer::SupportedCombinationResponse {
+                                    combination_list,
+                                } => manage_supported_combination_response(self, combination_list),
+                                PlanetToExplorer::GenerateResourceResponse { resource } => {
+                                    manage_generate_response(self, resource)
+                                }
+                                PlanetToExplorer::CombineResourceResponse { complex_response } => {
+                                    manage_combine_response(self, complex_response)
+                                }
+                                PlanetToExplorer::AvailableEnergyCellResponse {
+                                    available_cells,
+                                } => manage_available_energy_cell_response(self, available_cells),
+                                PlanetToExplorer::Stopped => {
+                                    planet_stopped(self);
+                                    Ok(())
+                                }
+                            };
+
+                            if let Err(err) = ris {
+                                LogEvent::self_directed(
+                                    Participant::new(ActorType::Explorer, self.explorer_id),
+                                    EventType::InternalExplorerAction,
+                                    Channel::Warning,
+                                    warning_payload!(
+                                        "a handler of a PlanetToExplorer message returned an error",
                                         err,
                                         "mattia_explorer::run()"
                                     ),
-                                    )
-                                        .emit();
-                                }
-                            } else {
-                                // Explorer is not in a state that can process this message: buffer it
-                                self.buffer_orchestrator_msg.push_back(msg);
+                                )
+                                .emit();
                             }
+                        } else {
+                            // Explorer is not in a state that can process this message: buffer it
+                            self.buffer_planet_msg.push_back(msg);
                         }
-                        Err(err) => {
-                            LogEvent::self_directed(
-                                Participant::new(ActorType::Explorer, self.explorer_id),
-                                EventType::InternalExplorerAction,
-                                Channel::Error,
+                    }
+                    Err(err) => {
+                        // Channel will not be added to Select on the next iteration avoiding
+                        // spin loop, MoveToPlanet re-enables it
+                        self.planet_channel_active = false;
+
+                        if self.state == ExplorerState::Traveling {
+                            // expected during a move: the MoveToPlanet brings the new channel
+                            LogEvent::new(
+                                Some(Participant::new(ActorType::Planet, self.planet_id)),
+                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                                EventType::MessagePlanetToExplorer,
+                                Channel::Warning,
                                 warning_payload!(
-                                    "Fatal Error: receiving channel from orchestrator disconnected",
+                                    "receiving channel from planet disconnected while traveling",
                                     err,
                                     "mattia_explorer::run()"
                                 ),
                             )
                             .emit();
-                            return Err(err.to_string());
-                        }
-                    }
-                }
-
-                Selected::Planet(msg_result) => {
-                    match msg_result {
-                        Ok(msg) => {
-                            log_message!(
-                                ActorType::Planet,   self.planet_id,
-                                ActorType::Explorer, self.explorer_id,
-                                EventType::MessagePlanetToExplorer,
-                                "message received";
-                                "msg"           => format!("{:?}", msg),
-                                "explorer data" => format!("{:?}", self)
-                            );
-
-                            if planet_msg_match_state(&self.state, &msg) {
-                                let ris = match msg {
-                                    PlanetToExplorer::SupportedResourceResponse {
-                                        resource_list,
-                                    } => manage_supported_resource_response(self, resource_list),
-                                    PlanetToExplorer::SupportedCombinationResponse {
-                                        combination_list,
-                                    } => manage_supported_combination_response(
-                                        self,
-                                        combination_list,
-                                    ),
-                                    PlanetToExplorer::GenerateResourceResponse { resource } => {
-                                        manage_generate_response(self, resource)
-                                    }
-                                    PlanetToExplorer::CombineResourceResponse {
-                                        complex_response,
-                                    } => manage_combine_response(self, complex_response),
-                                    PlanetToExplorer::AvailableEnergyCellResponse {
-                                        available_cells,
-                                    } => {
-                                        manage_available_energy_cell_response(self, available_cells)
-                                    }
-                                    PlanetToExplorer::Stopped => {
-                                        self.state = ExplorerState::Idle;
-                                        Ok(())
-                                    }
-                                };
-
-                                if let Err(err) = ris {
-                                    LogEvent::self_directed(
-                                        Participant::new(ActorType::Explorer, self.explorer_id),
-                                        EventType::InternalExplorerAction,
-                                        Channel::Warning,
-                                        warning_payload!(
-                                        "a handler of a PlanetToExplorer message returned an error",
-                                        err,
-                                        "mattia_explorer::run()"
-                                    ),
-                                    )
-                                        .emit();
-                                }
-                            } else {
-                                // Explorer is not in a state that can process this message: buffer it
-                                self.buffer_planet_msg.push_back(msg);
-                            }
-                        }
-                        Err(err) => {
-                            // Planet has died: disable its channel to prevent a spin loop
-                            // while waiting for the Kill message from the orchestrator
+                        } else {
+                            // Planet has died: the explorer cannot go on by itself, so it stops
+                            // its AI and tells the orchestrator (the protocol has no error
+                            // message, the StopExplorerAIResult marks the explorer as paused)
                             LogEvent::new(
                                 Some(Participant::new(ActorType::Planet, self.planet_id)),
                                 Some(Participant::new(ActorType::Explorer, self.explorer_id)),
@@ -423,20 +532,31 @@ impl Explorer {
                                 warning_payload!(
                                     "receiving channel from planet disconnected",
                                     err,
-                                    "mattia_explorer::run()"
+                                    "mattia_explorer::run()";
+                                    "explorer_state" => format!("{:?}", self.state)
                                 ),
                             )
                             .emit();
-                            // Channel will not be added to Select on the next iteration avoiding
-                            // spin loop
-                            planet_channel_active = false;
+                            if let Err(err) = stop_explorer_ai(self) {
+                                LogEvent::self_directed(
+                                    Participant::new(ActorType::Explorer, self.explorer_id),
+                                    EventType::InternalExplorerAction,
+                                    Channel::Warning,
+                                    warning_payload!(
+                                        "stop_explorer_ai() generated an error",
+                                        err,
+                                        "mattia_explorer::run()"
+                                    ),
+                                )
+                                .emit();
+                            }
                         }
                     }
                 }
             }
-
-            sleep(Duration::from_millis(20));
         }
+
+        None
     }
 }
 
@@ -448,6 +568,7 @@ use logging_utils::{
 use std::fmt;
 use std::thread::sleep;
 use std::time::Duration;
+use std::time::Instant;
 
 impl fmt::Debug for Explorer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -488,12 +609,34 @@ impl fmt::Debug for Explorer {
     }
 }
 
+/// concise one-line status, used where the `Debug` output is too verbose
+impl fmt::Display for Explorer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let energy = match self
+            .get_planet_info(self.planet_id)
+            .and_then(|info| info.energy_cells)
+        {
+            Some(cells) => cells.to_string(),
+            None => "?".to_string(),
+        };
+        write!(
+            f,
+            "Explorer#{} on planet {} state={} bag={} energy={}",
+            self.explorer_id,
+            self.planet_id,
+            self.state,
+            self.bag.total_count(),
+            energy
+        )
+    }
+}
+
 impl LoggableActor for Explorer {
     fn actor_type(&self) -> ActorType {
         ActorType::Explorer
     }
 
     fn actor_id(&self) -> u32 {
-        self.explorer_id
+        self.explorer_id()
     }
 }
@@ -1,3 +1,4 @@
+use crate::components::tommy_explorer::topology::PlanetKnowledge;
 use common_game::components::resource::{BasicResourceType, ComplexResourceType};
 use common_game::utils::ID;
 use logging_utils::log_fn_call;
@@ -38,10 +39,12 @@ pub(super) struct PlanetInfo {
     pub complex_resources: Option<HashSet<ComplexResourceType>>,
     pub neighbors: Option<HashSet<ID>>,
     pub energy_cells: Option<u32>,
-    pub charge_rate: Option<f32>,  //inferred charge rate
-    pub timestamp_neighbors: u64,  //last time tick that the neighbors were updated
-    pub timestamp_energy: u64,     //last time tick that energy cells were updated
-    pub safety_score: Option<f32>, //calculated safety score of the planet
+    pub charge_rate: Option<f32>,    //inferred charge rate
+    pub timestamp_neighbors: u64,    //last time tick that the neighbors were updated
+    pub timestamp_energy: u64,       //last time tick that energy cells were updated
+    pub timestamp_resources: u64,    //last time tick that the basic resources were updated
+    pub timestamp_combinations: u64, //last time tick that the complex resources were updated
+    pub safety_score: Option<f32>,   //calculated safety score of the planet
     pub inferred_planet_type: Option<PlanetClassType>,
 }
 impl PlanetInfo {
@@ -60,10 +63,53 @@ impl PlanetInfo {
             charge_rate: None,
             timestamp_neighbors: time,
             timestamp_energy: time,
+            timestamp_resources: time,
+            timestamp_combinations: time,
             safety_score: None,
             inferred_planet_type: None,
         }
     }
+    /// creates a PlanetInfo with resources and neighbors already known
+    #[cfg(test)]
+    pub(super) fn with_data(
+        basic_resources: HashSet<BasicResourceType>,
+        complex_resources: HashSet<ComplexResourceType>,
+        neighbors: HashSet<ID>,
+        time: u64,
+    ) -> Self {
+        let mut planet_info = Self::new(time);
+        planet_info.basic_resources = Some(basic_resources);
+        planet_info.complex_resources = Some(complex_resources);
+        planet_info.neighbors = Some(neighbors);
+        planet_info
+    }
+    /// checks if resources, combinations and neighbors of the planet are all known
+    pub(super) fn is_complete(&self) -> bool {
+        self.basic_resources.is_some()
+            && self.complex_resources.is_some()
+            && self.neighbors.is_some()
+    }
+    pub(super) fn get_basic_resources(&self) -> Option<&HashSet<BasicResourceType>> {
+        self.basic_resources.as_ref()
+    }
+    pub(super) fn get_complex_resources(&self) -> Option<&HashSet<ComplexResourceType>> {
+        self.complex_resources.as_ref()
+    }
+    pub(super) fn get_neighbours(&self) -> Option<&HashSet<ID>> {
+        self.neighbors.as_ref()
+    }
+    /// sets the basic resources the planet can generate
+    pub(super) fn set_basic_resources(&mut self, resources: HashSet<BasicResourceType>) {
+        self.basic_resources = Some(resources);
+    }
+    /// sets the complex resources the planet can combine
+    pub(super) fn set_complex_resources(&mut self, resources: HashSet<ComplexResourceType>) {
+        self.complex_resources = Some(resources);
+    }
+    /// sets the neighbors of the planet, timestamp_neighbors is left to the caller
+    pub(super) fn set_neighbours(&mut self, neighbors: HashSet<ID>) {
+        self.neighbors = Some(neighbors);
+    }
     /// this method update the charge rate of the planet, based on the available information
     pub(super) fn update_charge_rate(
         &mut self,
@@ -144,3 +190,28 @@ impl PlanetInfo {
         }
     }
 }
+
+/// planets met for the first time, e.g. as neighbors, are created at time 0
+impl Default for PlanetInfo {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl PlanetKnowledge for PlanetInfo {
+    fn get_basic_resources(&self) -> Option<&HashSet<BasicResourceType>> {
+        self.basic_resources.as_ref()
+    }
+    fn get_complex_resources(&self) -> Option<&HashSet<ComplexResourceType>> {
+        self.complex_resources.as_ref()
+    }
+    fn get_neighbours(&self) -> Option<&HashSet<ID>> {
+        self.neighbors.as_ref()
+    }
+    fn neighbours_mut(&mut self) -> Option<&mut HashSet<ID>> {
+        self.neighbors.as_mut()
+    }
+    fn set_neighbours(&mut self, neighbors: HashSet<ID>) {
+        self.neighbors = Some(neighbors);
+    }
+}
@@ -1,28 +1,74 @@
 use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
 use common_game::protocols::planet_explorer::PlanetToExplorer;
+use std::fmt;
 
 /// these are the states of the explorer state machine
 #[derive(PartialEq, Debug)]
-pub(super) enum ExplorerState {
+pub(crate) enum ExplorerState {
     Idle,
     WaitingForNeighbours,
     Traveling,
-    GeneratingResource {
-        orchestrator_response: bool,
-    },
-    CombiningResources {
-        orchestrator_response: bool,
-    },
-    Surveying {
-        resources: bool,
-        combinations: bool,
-        energy_cells: bool,
-        orch_resource: bool,
-        orch_combination: bool,
-    },
+    GeneratingResource { orchestrator_response: bool },
+    CombiningResources { orchestrator_response: bool },
+    Surveying(SurveyFlags),
     Killed,
 }
 
+/// flags of the `Surveying` state, every `true` flag is a pending request
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) struct SurveyFlags {
+    pub(super) resources: bool,        // waiting for basic resource list
+    pub(super) combinations: bool,     // waiting for combination list
+    pub(super) energy_cells: bool,     // waiting for energy cell data
+    pub(super) orch_resource: bool,    // orchestrator requested resource info
+    pub(super) orch_combination: bool, // orchestrator requested combination info
+}
+
+impl SurveyFlags {
+    /// every flag set
+    pub(super) fn all() -> Self {
+        Self {
+            resources: true,
+            combinations: true,
+            energy_cells: true,
+            orch_resource: true,
+            orch_combination: true,
+        }
+    }
+
+    /// no flag set
+    pub(super) fn none() -> Self {
+        Self {
+            resources: false,
+            combinations: false,
+            energy_cells: false,
+            orch_resource: false,
+            orch_combination: false,
+        }
+    }
+
+    /// tells if there is nothing left to survey (all flags false)
+    pub(super) fn is_complete(&self) -> bool {
+        *self == Self::none()
+    }
+}
+
+impl fmt::Display for ExplorerState {
+    /// short state name, without the internal flags
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExplorerState::Idle => "Idle",
+            ExplorerState::WaitingForNeighbours => "WaitingForNeighbours",
+            ExplorerState::Traveling => "Traveling",
+            ExplorerState::GeneratingResource { .. } => "GeneratingResource",
+            ExplorerState::CombiningResources { .. } => "CombiningResources",
+            ExplorerState::Surveying(_) => "Surveying",
+            ExplorerState::Killed => "Killed",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// this function checks if the orchestrator message received is the one expected (based on the explorer state)
 pub(super) fn orch_msg_match_state(
     explorer_state: &ExplorerState,
@@ -60,21 +106,21 @@ pub(super) fn planet_msg_match_state(
             PlanetToExplorer::CombineResourceResponse { .. },
         ) => true,
         (
-            ExplorerState::Surveying {
+            ExplorerState::Surveying(SurveyFlags {
                 resources: true, ..
-            },
+            }),
             PlanetToExplorer::SupportedResourceResponse { .. },
         ) => true,
         (
-            ExplorerState::Surveying {
+            ExplorerState::Surveying(SurveyFlags {
                 combinations: true, ..
-            },
+            }),
             PlanetToExplorer::SupportedCombinationResponse { .. },
         ) => true,
         (
-            ExplorerState::Surveying {
+            ExplorerState::Surveying(SurveyFlags {
                 energy_cells: true, ..
-            },
+            }),
             PlanetToExplorer::AvailableEnergyCellResponse { .. },
         ) => true,
         _ => false,
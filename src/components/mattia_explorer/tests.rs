@@ -19,6 +19,8 @@ mod test_one_million_crabs_planet {
     #[cfg(test)]
     use super::*;
     #[cfg(test)]
+    use crate::components::orchestrator::channels::ExplorerHandle;
+    #[cfg(test)]
     use crate::utils::ExplorerInfo;
     #[cfg(test)]
     use crate::utils::registry::PlanetType;
@@ -57,9 +59,9 @@ mod test_one_million_crabs_planet {
 
         // get the sender from explorer to planet
         let (orch_to_planet, expl_to_planet) = match orchestrator.planet_channels.get(&planet_id) {
-            Some((orchestrator_sender, explorer_sender)) => (
-                Some(orchestrator_sender.clone()),
-                Some(explorer_sender.clone()),
+            Some(handle) => (
+                Some(handle.to_planet().clone()),
+                Some(handle.explorer_sender_for_arrival()),
             ),
             None => (None, None), // sender does not exist
         };
@@ -78,9 +80,10 @@ mod test_one_million_crabs_planet {
             ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), planet_id),
         );
 
-        orchestrator
-            .explorer_channels
-            .insert(new_explorer.id(), (sender_orch, sender_planet.clone()));
+        orchestrator.explorer_channels.insert(
+            new_explorer.explorer_id(),
+            ExplorerHandle::new(sender_orch, sender_planet.clone()),
+        );
 
         match orch_to_planet {
             Some(orchestrator_sender) => {
@@ -99,7 +102,7 @@ mod test_one_million_crabs_planet {
             .planet_channels
             .get(&planet_id)
             .unwrap()
-            .0
+            .to_planet()
             .clone();
         orchestrator
             .send_sunray(planet_id, &planet_channel)
@@ -239,7 +242,7 @@ mod test_one_million_crabs_planet {
             .expect("testing expect");
         orchestrator
             .send_internal_state_request(
-                &orchestrator.planet_channels.get(&planet_id).unwrap().0,
+                orchestrator.planet_channels[&planet_id].to_planet(),
                 planet_id,
             )
             .expect("testing expect");
@@ -313,9 +316,9 @@ mod test_one_million_crabs_planet {
 
         // get the sender from explorer to planet
         let (orch_to_planet, expl_to_planet) = match orchestrator.planet_channels.get(&planet_id) {
-            Some((orchestrator_sender, explorer_sender)) => (
-                Some(orchestrator_sender.clone()),
-                Some(explorer_sender.clone()),
+            Some(handle) => (
+                Some(handle.to_planet().clone()),
+                Some(handle.explorer_sender_for_arrival()),
             ),
             None => (None, None), // sender does not exist
         };
@@ -334,9 +337,10 @@ mod test_one_million_crabs_planet {
             ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), planet_id),
         );
 
-        orchestrator
-            .explorer_channels
-            .insert(new_explorer.id(), (sender_orch, sender_planet.clone()));
+        orchestrator.explorer_channels.insert(
+            new_explorer.explorer_id(),
+            ExplorerHandle::new(sender_orch, sender_planet.clone()),
+        );
 
         match orch_to_planet {
             Some(orchestrator_sender) => {
@@ -355,7 +359,7 @@ mod test_one_million_crabs_planet {
             .planet_channels
             .get(&planet_id)
             .unwrap()
-            .0
+            .to_planet()
             .clone();
 
         // max charge
@@ -388,7 +392,7 @@ mod test_one_million_crabs_planet {
             .expect("testing expect");
         orchestrator
             .send_internal_state_request(
-                &orchestrator.planet_channels.get(&planet_id).unwrap().0,
+                orchestrator.planet_channels[&planet_id].to_planet(),
                 planet_id,
             )
             .expect("testing expect");
@@ -466,9 +470,9 @@ mod test_one_million_crabs_planet {
             // get the sender from explorer to planet
             let (orch_to_planet, expl_to_planet) =
                 match orchestrator.planet_channels.get(&planet_id) {
-                    Some((orchestrator_sender, explorer_sender)) => (
-                        Some(orchestrator_sender.clone()),
-                        Some(explorer_sender.clone()),
+                    Some(handle) => (
+                        Some(handle.to_planet().clone()),
+                        Some(handle.explorer_sender_for_arrival()),
                     ),
                     None => (None, None), // sender does not exist
                 };
@@ -487,9 +491,10 @@ mod test_one_million_crabs_planet {
                 ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), planet_id),
             );
 
-            orchestrator
-                .explorer_channels
-                .insert(new_explorer.id(), (sender_orch, sender_planet.clone()));
+            orchestrator.explorer_channels.insert(
+                new_explorer.explorer_id(),
+                ExplorerHandle::new(sender_orch, sender_planet.clone()),
+            );
 
             match orch_to_planet {
                 Some(orchestrator_sender) => {
@@ -508,7 +513,7 @@ mod test_one_million_crabs_planet {
                 .planet_channels
                 .get(&planet_id)
                 .unwrap()
-                .0
+                .to_planet()
                 .clone();
 
             // max charge
@@ -576,7 +581,7 @@ mod test_one_million_crabs_planet {
                 .expect("testing expect");
             orchestrator
                 .send_internal_state_request(
-                    &orchestrator.planet_channels.get(&planet_id).unwrap().0,
+                    orchestrator.planet_channels[&planet_id].to_planet(),
                     planet_id,
                 )
                 .expect("testing expect");
@@ -656,10 +661,10 @@ mod game_simulation {
     fn simulation_25s() {
         let mut orchestrator = Orchestrator::new().unwrap();
         let explorer_id = 10;
+        let galaxy = crate::parse_galaxy_description(include_str!("test_topology_files/t0.txt"))
+            .expect("testing expect");
         orchestrator
-            .initialize_galaxy_by_file(
-                "./src/components/mattia_explorer/test_topology_files/t0.txt",
-            )
+            .initialize_galaxy(galaxy)
             .expect("testing expect");
         orchestrator.start_all_planet_ais().expect("testing expect");
         orchestrator
@@ -1453,7 +1458,7 @@ mod generate_resource_tests {
         let mut orch = setup_orch_with_explorer(PlanetType::OneMillionCrabs, 0, 0);
 
         // charge the planet with sunrays first
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -1484,7 +1489,7 @@ mod generate_resource_tests {
     fn generate_multiple_resources_sequentially() {
         let mut orch = setup_orch_with_explorer(PlanetType::BlackAdidasShoe, 0, 0);
 
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -1627,7 +1632,7 @@ mod combine_resource_tests {
     #[test]
     fn combine_resource_with_only_an_ingredient() {
         let mut orch = setup_orch_with_explorer(PlanetType::RustyCrab, 0, 0);
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -1680,12 +1685,12 @@ mod combine_resource_tests {
     fn generate_then_combine_diamond() {
         let mut orch = setup_multi_planet_orch(0);
 
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..2 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
         }
-        let planet_channel = orch.planet_channels.get(&1).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&1].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(1, &planet_channel)
                 .expect("testing expect");
@@ -1747,7 +1752,7 @@ mod bag_content_tests {
         let mut orch = setup_orch_with_explorer(PlanetType::OneMillionCrabs, 0, 0);
 
         // charge planet
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -1773,6 +1778,13 @@ mod bag_content_tests {
         let _ = orch.send_kill_explorer_ai(0);
         drain_messages(&mut orch, 200);
     }
+
+    #[test]
+    fn default_bag_is_empty() {
+        let bag = crate::components::mattia_explorer::bag::Bag::default();
+        assert_eq!(bag.total_count(), 0);
+        assert!(bag.to_resource_types().is_empty());
+    }
 }
 
 // ============================================================================
@@ -1936,7 +1948,7 @@ mod resource_after_movement_tests {
 
         // charge both planets
         if let Some(ch) = orch.planet_channels.get(&1) {
-            let sender = ch.0.clone();
+            let sender = ch.to_planet().clone();
             orch.send_sunray(1, &sender).unwrap();
         }
         drain_messages(&mut orch, 50);
@@ -2012,7 +2024,7 @@ mod end_to_end_tests {
     fn rapid_generate_requests() {
         let mut orch = setup_orch_with_explorer(PlanetType::BlackAdidasShoe, 0, 0);
 
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -2047,19 +2059,48 @@ mod end_to_end_tests {
 // ============================================================================
 // 12. Explorer-Planet direct communication tests
 // ============================================================================
+#[cfg(test)]
+mod survey_flags_tests {
+    use crate::components::mattia_explorer::states::SurveyFlags;
+
+    #[test]
+    fn none_is_complete_and_all_is_not() {
+        assert!(SurveyFlags::none().is_complete());
+        assert!(!SurveyFlags::all().is_complete());
+    }
+
+    #[test]
+    fn single_pending_flag_is_not_complete() {
+        let flags = SurveyFlags {
+            energy_cells: true,
+            ..SurveyFlags::none()
+        };
+        assert!(!flags.is_complete());
+        assert!(
+            SurveyFlags {
+                energy_cells: false,
+                ..flags
+            }
+            .is_complete()
+        );
+    }
+}
+
 #[cfg(test)]
 mod explorer_planet_comms {
     use super::*;
     use crate::Status;
+    use crate::components::orchestrator::channels::ExplorerHandle;
     use crate::utils::ExplorerInfo;
     use crate::utils::registry::PlanetType;
-    use common_game::components::resource::BasicResourceType;
+    use common_game::components::resource::{BasicResourceType, ResourceType};
     use common_game::protocols::orchestrator_explorer::{
         ExplorerToOrchestrator, OrchestratorToExplorer,
     };
     use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
     use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
     use crossbeam_channel::{select, tick};
+    use std::collections::HashSet;
     use std::thread::sleep;
     use std::time::Duration;
 
@@ -2079,9 +2120,9 @@ mod explorer_planet_comms {
             Orchestrator::init_comms_explorers();
 
         let (orch_to_planet, expl_to_planet) = match orch.planet_channels.get(&planet_id) {
-            Some((orchestrator_sender, explorer_sender)) => (
-                Some(orchestrator_sender.clone()),
-                Some(explorer_sender.clone()),
+            Some(handle) => (
+                Some(handle.to_planet().clone()),
+                Some(handle.explorer_sender_for_arrival()),
             ),
             None => (None, None),
         };
@@ -2097,8 +2138,10 @@ mod explorer_planet_comms {
             explorer_id,
             ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), planet_id),
         );
-        orch.explorer_channels
-            .insert(explorer_id, (sender_orch, sender_planet.clone()));
+        orch.explorer_channels.insert(
+            explorer_id,
+            ExplorerHandle::new(sender_orch, sender_planet.clone()),
+        );
 
         if let Some(orchestrator_sender) = orch_to_planet {
             orchestrator_sender
@@ -2112,6 +2155,22 @@ mod explorer_planet_comms {
         (orch, new_explorer)
     }
 
+    // ---- Getters ----
+
+    #[test]
+    fn explorer_getters() {
+        let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 3, 5);
+        assert_eq!(explorer.explorer_id(), 5);
+        assert_eq!(explorer.planet_id(), 3);
+        assert_eq!(
+            *explorer.state(),
+            crate::components::mattia_explorer::states::ExplorerState::Idle
+        );
+
+        let _ = orch.send_planet_kill_to_all();
+        drain_messages(&mut orch, 200);
+    }
+
     // ---- SupportedResourceRequest to Planet ----
 
     #[test]
@@ -2201,7 +2260,7 @@ mod explorer_planet_comms {
         let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
 
         // charge the planet
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..5 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -2253,7 +2312,7 @@ mod explorer_planet_comms {
         let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
 
         // charge the planet
-        let planet_channel = orch.planet_channels.get(&0).unwrap().0.clone();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
         for _ in 0..3 {
             orch.send_sunray(0, &planet_channel)
                 .expect("testing expect");
@@ -2346,6 +2405,244 @@ mod explorer_planet_comms {
         drain_messages(&mut orch, 200);
     }
 
+    // ---- next_planet_id tracks the in-transit destination ----
+
+    #[test]
+    fn next_planet_id_is_used_on_move_to_planet() {
+        let (mut orch, mut explorer) = setup_manual_explorer_two_planets(0);
+        drain_messages(&mut orch, 200);
+        assert_eq!(explorer.next_planet_id, explorer.planet_id);
+
+        // the explorer asked to travel to planet 1, it is still on planet 0
+        explorer.next_planet_id = 1;
+        assert_eq!(explorer.planet_id, 0);
+
+        let sender = orch.planet_channels[&1].explorer_sender_for_arrival();
+        crate::components::mattia_explorer::handlers::move_to_planet(
+            &mut explorer,
+            Some(sender),
+            1,
+        )
+        .expect("testing expect");
+        assert_eq!(explorer.planet_id, 1);
+        assert_eq!(explorer.next_planet_id, 1);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn late_supported_resource_response_after_move_is_not_misattributed() {
+        use crate::components::mattia_explorer::states::{ExplorerState, SurveyFlags};
+        use crate::utils::request_ledger::PlanetRequestKind;
+
+        let (mut orch, mut explorer) = setup_manual_explorer_two_planets(0);
+        drain_messages(&mut orch, 200);
+
+        // a supported resource request was sent to planet 0 before moving
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::SupportedResources, 0);
+        explorer.next_planet_id = 1;
+        let sender = orch.planet_channels[&1].explorer_sender_for_arrival();
+        crate::components::mattia_explorer::handlers::move_to_planet(
+            &mut explorer,
+            Some(sender),
+            1,
+        )
+        .expect("testing expect");
+
+        // the explorer is now surveying planet 1
+        let surveying = ExplorerState::Surveying(SurveyFlags {
+            resources: true,
+            ..SurveyFlags::none()
+        });
+        explorer.state = surveying;
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::SupportedResources, 1);
+
+        // the late response of planet 0 arrives
+        crate::components::mattia_explorer::handlers::manage_supported_resource_response(
+            &mut explorer,
+            HashSet::from([BasicResourceType::Carbon]),
+        )
+        .expect("testing expect");
+
+        assert!(
+            explorer
+                .topology_info
+                .get(1)
+                .unwrap()
+                .basic_resources
+                .is_none()
+        );
+        assert_eq!(
+            explorer.topology_info.get(0).unwrap().basic_resources,
+            Some(HashSet::from([BasicResourceType::Carbon]))
+        );
+        // still waiting for the response of planet 1
+        assert_eq!(explorer.state, surveying);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn requests_to_a_dead_planet_do_not_shift_later_responses() {
+        use crate::components::mattia_explorer::states::{ExplorerState, SurveyFlags};
+        use crate::utils::TopologyDelta;
+        use crate::utils::request_ledger::PlanetRequestKind;
+
+        let (mut orch, mut explorer) = setup_manual_explorer_two_planets(0);
+        drain_messages(&mut orch, 200);
+        let (delta_sender, delta_receiver) = crossbeam_channel::unbounded();
+        explorer.set_topology_receiver(delta_receiver);
+
+        // the request sent to planet 0 before moving will never be answered
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::SupportedResources, 0);
+        explorer.next_planet_id = 1;
+        let sender = orch.planet_channels[&1].explorer_sender_for_arrival();
+        crate::components::mattia_explorer::handlers::move_to_planet(
+            &mut explorer,
+            Some(sender),
+            1,
+        )
+        .expect("testing expect");
+        explorer.state = ExplorerState::Surveying(SurveyFlags {
+            resources: true,
+            ..SurveyFlags::none()
+        });
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::SupportedResources, 1);
+
+        // planet 0 dies
+        delta_sender
+            .send(TopologyDelta {
+                removed: vec![(0, 1)],
+                added: Vec::new(),
+                dead_planets: vec![0],
+            })
+            .expect("testing expect");
+        explorer.apply_topology_deltas();
+
+        // the response of planet 1 is not taken for the one of planet 0
+        crate::components::mattia_explorer::handlers::manage_supported_resource_response(
+            &mut explorer,
+            HashSet::from([BasicResourceType::Carbon]),
+        )
+        .expect("testing expect");
+        assert_eq!(
+            explorer.topology_info.get(1).unwrap().basic_resources,
+            Some(HashSet::from([BasicResourceType::Carbon]))
+        );
+        assert_eq!(explorer.state, ExplorerState::Idle);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn stopped_planet_requests_are_forgotten() {
+        use crate::components::mattia_explorer::states::{ExplorerState, SurveyFlags};
+        use crate::utils::request_ledger::PlanetRequestKind;
+
+        let (mut orch, mut explorer) = setup_manual_explorer_two_planets(0);
+        drain_messages(&mut orch, 200);
+
+        explorer.state = ExplorerState::Surveying(SurveyFlags {
+            energy_cells: true,
+            ..SurveyFlags::none()
+        });
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::AvailableEnergyCells, 0);
+
+        crate::components::mattia_explorer::handlers::planet_stopped(&mut explorer);
+
+        assert_eq!(explorer.state, ExplorerState::Idle);
+        assert!(
+            !explorer
+                .planet_requests
+                .is_pending(PlanetRequestKind::AvailableEnergyCells, 0)
+        );
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn late_neighbours_response_updates_the_requested_planet() {
+        use crate::components::mattia_explorer::states::ExplorerState;
+        use crate::utils::request_ledger::PlanetRequestKind;
+
+        let (mut orch, mut explorer) = setup_manual_explorer_two_planets(0);
+        drain_messages(&mut orch, 200);
+
+        // the neighbours of planet 0 were requested before moving
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::Neighbours, 0);
+        explorer.next_planet_id = 1;
+        let sender = orch.planet_channels[&1].explorer_sender_for_arrival();
+        crate::components::mattia_explorer::handlers::move_to_planet(
+            &mut explorer,
+            Some(sender),
+            1,
+        )
+        .expect("testing expect");
+
+        // the explorer now asks for the neighbours of planet 1
+        explorer.state = ExplorerState::WaitingForNeighbours;
+        explorer
+            .planet_requests
+            .sent(PlanetRequestKind::Neighbours, 1);
+
+        // the late response about planet 0 arrives first
+        crate::components::mattia_explorer::handlers::neighbours_response(&mut explorer, vec![1]);
+        assert_eq!(
+            explorer.topology_info.get(0).unwrap().neighbors,
+            Some(HashSet::from([1]))
+        );
+        assert!(explorer.topology_info.get(1).unwrap().neighbors.is_none());
+        assert_eq!(explorer.state, ExplorerState::WaitingForNeighbours);
+
+        crate::components::mattia_explorer::handlers::neighbours_response(&mut explorer, vec![0]);
+        assert_eq!(
+            explorer.topology_info.get(1).unwrap().neighbors,
+            Some(HashSet::from([0]))
+        );
+        assert_eq!(explorer.state, ExplorerState::Idle);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn next_planet_id_is_reset_when_move_is_rejected() {
+        let (mut orch, mut explorer) = setup_manual_explorer_two_planets(0);
+        drain_messages(&mut orch, 200);
+
+        // the destination dies during the travel
+        explorer.next_planet_id = 1;
+        crate::components::mattia_explorer::handlers::move_to_planet(&mut explorer, None, 1)
+            .expect("testing expect");
+        assert_eq!(explorer.planet_id, 0);
+        assert_eq!(explorer.next_planet_id, 0);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
     // ========================================================================
     // Edge Case Tests — Race Condition Guards
     // ========================================================================
@@ -2368,14 +2665,13 @@ mod explorer_planet_comms {
             .planet_channels
             .get(&0)
             .expect("planet 0 should exist")
-            .1
-            .clone();
+            .explorer_sender_for_arrival();
 
         let orch_to_planet = orch
             .planet_channels
             .get(&0)
             .expect("planet 0 should exist")
-            .0
+            .to_planet()
             .clone();
 
         let new_explorer = crate::components::mattia_explorer::Explorer::new(
@@ -2389,8 +2685,10 @@ mod explorer_planet_comms {
             explorer_id,
             ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), 0),
         );
-        orch.explorer_channels
-            .insert(explorer_id, (sender_orch, sender_planet.clone()));
+        orch.explorer_channels.insert(
+            explorer_id,
+            ExplorerHandle::new(sender_orch, sender_planet.clone()),
+        );
 
         orch_to_planet
             .send(OrchestratorToPlanet::IncomingExplorerRequest {
@@ -2687,4 +2985,701 @@ mod explorer_planet_comms {
             .expect("failed to send planet kill to all");
         drain_messages(&mut orch, 200);
     }
+
+    // ---- Bag merge / split_off ----
+
+    /// Helper: charges the planet and generates `n` resources of type `ty`
+    /// directly through the explorer planet channel, returning them in a new bag.
+    fn generate_into_bag(
+        orch: &mut Orchestrator,
+        explorer: &crate::components::mattia_explorer::Explorer,
+        ty: BasicResourceType,
+        n: usize,
+    ) -> crate::components::mattia_explorer::bag::Bag {
+        use crate::components::mattia_explorer::resource_management::ToGeneric;
+
+        let mut bag = crate::components::mattia_explorer::bag::Bag::new();
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
+        for _ in 0..n {
+            orch.send_sunray(0, &planet_channel)
+                .expect("testing expect");
+            sleep(Duration::from_millis(20));
+            explorer
+                .planet_channels
+                .1
+                .send(ExplorerToPlanet::GenerateResourceRequest {
+                    explorer_id: 0,
+                    resource: ty,
+                })
+                .unwrap();
+            let timeout = tick(Duration::from_millis(300));
+            loop {
+                select! {
+                    recv(explorer.planet_channels.0) -> msg => {
+                        if let Ok(PlanetToExplorer::GenerateResourceResponse { resource }) = msg {
+                            bag.insert(resource.expect("planet should be charged").res_to_generic());
+                            break;
+                        }
+                    }
+                    recv(orch.receiver_orch_planet) -> msg => {
+                        if let Ok(m) = msg {
+                            orch.handle_planet_message(m).expect("testing expect");
+                        }
+                    }
+                    recv(timeout) -> _ => { panic!("GenerateResourceResponse not received"); }
+                }
+            }
+        }
+        bag
+    }
+
+    #[test]
+    fn bag_merge_moves_all_resources() {
+        use common_game::components::resource::ResourceType;
+
+        let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
+        drain_messages(&mut orch, 100);
+
+        let hydrogen = ResourceType::Basic(BasicResourceType::Hydrogen);
+        let silicon = ResourceType::Basic(BasicResourceType::Silicon);
+
+        let mut bag = generate_into_bag(&mut orch, &explorer, BasicResourceType::Hydrogen, 2);
+        let mut other = generate_into_bag(&mut orch, &explorer, BasicResourceType::Hydrogen, 1);
+        other.merge(generate_into_bag(
+            &mut orch,
+            &explorer,
+            BasicResourceType::Silicon,
+            1,
+        ));
+
+        assert_eq!(bag.count(hydrogen), 2);
+        assert_eq!(bag.count(silicon), 0);
+        assert_eq!(other.count(hydrogen), 1);
+        assert_eq!(other.count(silicon), 1);
+
+        bag.merge(other);
+
+        assert_eq!(bag.count(hydrogen), 3);
+        assert_eq!(bag.count(silicon), 1);
+        assert_eq!(bag.to_resource_types().len(), 4);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn bag_split_off_moves_subset() {
+        use common_game::components::resource::ResourceType;
+
+        let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
+        drain_messages(&mut orch, 100);
+
+        let hydrogen = ResourceType::Basic(BasicResourceType::Hydrogen);
+        let silicon = ResourceType::Basic(BasicResourceType::Silicon);
+
+        let mut bag = generate_into_bag(&mut orch, &explorer, BasicResourceType::Hydrogen, 3);
+        bag.merge(generate_into_bag(
+            &mut orch,
+            &explorer,
+            BasicResourceType::Silicon,
+            1,
+        ));
+
+        assert_eq!(bag.count(hydrogen), 3);
+        assert_eq!(bag.count(silicon), 1);
+
+        let split = bag.split_off(hydrogen, 2);
+        assert_eq!(split.count(hydrogen), 2);
+        assert_eq!(split.count(silicon), 0);
+        assert_eq!(bag.count(hydrogen), 1);
+        assert_eq!(bag.count(silicon), 1);
+
+        // asking for more than available only moves what is in the bag
+        let split = bag.split_off(silicon, 5);
+        assert_eq!(split.count(silicon), 1);
+        assert!(!bag.contains(silicon));
+        assert_eq!(bag.to_resource_types().len(), 1);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn reset_explorer_ai_empties_the_bag() {
+        use crate::components::mattia_explorer::handlers::reset_explorer_ai;
+
+        let (mut orch, mut explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
+        drain_messages(&mut orch, 100);
+
+        explorer.bag = generate_into_bag(&mut orch, &explorer, BasicResourceType::Hydrogen, 2);
+        assert_eq!(explorer.bag.total_count(), 2);
+
+        reset_explorer_ai(&mut explorer).expect("testing expect");
+
+        assert_eq!(explorer.bag.total_count(), 0);
+        assert!(explorer.bag.to_resource_types().is_empty());
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn cell_telemetry_follows_sunray_and_generate() {
+        use crate::utils::cell_telemetry::{CellCause, CellRecord};
+
+        let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
+        drain_messages(&mut orch, 100);
+        let telemetry = orch.enable_cell_telemetry();
+        let state_timeout = Duration::from_millis(500);
+
+        // baseline: whatever the planet starts with
+        orch.query_planet_state(0, state_timeout)
+            .expect("testing expect");
+        while telemetry.try_recv().is_ok() {}
+
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
+        orch.send_sunray(0, &planet_channel)
+            .expect("testing expect");
+        sleep(Duration::from_millis(20));
+        orch.query_planet_state(0, state_timeout)
+            .expect("testing expect");
+        let charged: Vec<CellRecord> = telemetry.try_iter().collect();
+        assert_eq!(charged.len(), 1);
+        assert!(charged[0].charged);
+        assert_eq!(charged[0].cause, CellCause::Sunray);
+
+        explorer
+            .planet_channels
+            .1
+            .send(ExplorerToPlanet::GenerateResourceRequest {
+                explorer_id: 0,
+                resource: BasicResourceType::Carbon,
+            })
+            .unwrap();
+        match explorer
+            .planet_channels
+            .0
+            .recv_timeout(Duration::from_millis(300))
+        {
+            Ok(PlanetToExplorer::GenerateResourceResponse { resource }) => {
+                assert!(resource.is_some())
+            }
+            other => panic!("expected GenerateResourceResponse, got {:?}", other),
+        }
+        orch.query_planet_state(0, state_timeout)
+            .expect("testing expect");
+        let spent: Vec<CellRecord> = telemetry.try_iter().collect();
+        assert_eq!(
+            spent,
+            vec![CellRecord {
+                planet_id: 0,
+                cell_index: charged[0].cell_index,
+                charged: false,
+                cause: CellCause::Resource,
+            }]
+        );
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn failed_combine_request_leaves_bag_unchanged() {
+        use common_game::components::resource::{ComplexResourceType, ResourceType};
+
+        let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 0);
+        drain_messages(&mut orch, 100);
+
+        let hydrogen = ResourceType::Basic(BasicResourceType::Hydrogen);
+        let silicon = ResourceType::Basic(BasicResourceType::Silicon);
+
+        let mut bag = generate_into_bag(&mut orch, &explorer, BasicResourceType::Hydrogen, 1);
+        bag.merge(generate_into_bag(
+            &mut orch,
+            &explorer,
+            BasicResourceType::Silicon,
+            1,
+        ));
+        let before = bag.to_resource_types();
+
+        // water misses the oxygen, robot misses the life: only the first ingredient is there
+        assert!(!bag.has_ingredients_for(ComplexResourceType::Water));
+        assert!(
+            bag.make_complex_request(ComplexResourceType::Water)
+                .is_err()
+        );
+        assert!(
+            bag.make_complex_request(ComplexResourceType::Robot)
+                .is_err()
+        );
+
+        assert_eq!(bag.count(hydrogen), 1);
+        assert_eq!(bag.count(silicon), 1);
+        assert_eq!(bag.to_resource_types(), before);
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    #[test]
+    fn explorer_display_is_single_line_status() {
+        let (mut orch, explorer) = setup_manual_explorer(PlanetType::OneMillionCrabs, 0, 3);
+        drain_messages(&mut orch, 100);
+
+        assert_eq!(
+            explorer.to_string(),
+            "Explorer#3 on planet 0 state=Idle bag=0 energy=?"
+        );
+
+        orch.send_planet_kill_to_all()
+            .expect("failed to send planet kill to all");
+        drain_messages(&mut orch, 200);
+    }
+
+    /// explorer with plain channels: (explorer, orchestrator sender, planet sender,
+    /// receiver of the explorer messages to the orchestrator)
+    fn explorer_with_raw_channels() -> (
+        crate::components::mattia_explorer::Explorer,
+        crossbeam_channel::Sender<OrchestratorToExplorer>,
+        crossbeam_channel::Sender<PlanetToExplorer>,
+        crossbeam_channel::Receiver<ExplorerToOrchestrator<Vec<ResourceType>>>,
+    ) {
+        let (orch_sender, orch_receiver) = crossbeam_channel::unbounded();
+        let (to_orch_sender, to_orch_receiver) = crossbeam_channel::unbounded();
+        let (planet_sender, planet_receiver) = crossbeam_channel::unbounded();
+        let (to_planet_sender, _) = crossbeam_channel::unbounded();
+        let explorer = crate::components::mattia_explorer::Explorer::new(
+            7,
+            0,
+            (orch_receiver, to_orch_sender),
+            (planet_receiver, to_planet_sender),
+        );
+        (explorer, orch_sender, planet_sender, to_orch_receiver)
+    }
+
+    #[test]
+    fn planet_channel_disconnect_stops_the_explorer_ai() {
+        let (mut explorer, orch_sender, planet_sender, to_orch) = explorer_with_raw_channels();
+        explorer.manual_mode = false;
+        drop(planet_sender);
+
+        let handle = std::thread::spawn(move || explorer.run());
+
+        match to_orch.recv_timeout(Duration::from_millis(500)) {
+            Ok(ExplorerToOrchestrator::StopExplorerAIResult { explorer_id }) => {
+                assert_eq!(explorer_id, 7)
+            }
+            other => panic!("expected StopExplorerAIResult, got {:?}", other),
+        }
+
+        orch_sender.send(OrchestratorToExplorer::KillExplorer).unwrap();
+        assert!(handle.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn planet_channel_disconnect_while_traveling_waits_for_move() {
+        use crate::components::mattia_explorer::states::ExplorerState;
+
+        let (mut explorer, orch_sender, planet_sender, to_orch) = explorer_with_raw_channels();
+        explorer.state = ExplorerState::Traveling;
+        drop(planet_sender);
+
+        let handle = std::thread::spawn(move || explorer.run());
+
+        // nothing is reported: the disconnection is expected during a move
+        assert!(to_orch.recv_timeout(Duration::from_millis(200)).is_err());
+
+        orch_sender.send(OrchestratorToExplorer::KillExplorer).unwrap();
+        assert!(handle.join().unwrap().is_ok());
+    }
+}
+
+#[cfg(test)]
+mod energy_resurvey_tests {
+    use crate::components::mattia_explorer::Explorer;
+    use crate::components::mattia_explorer::ai_params::AiParams;
+    use crate::components::mattia_explorer::explorer_ai::ai_core_function;
+    use crate::components::mattia_explorer::states::ExplorerState;
+    use common_game::protocols::planet_explorer::ExplorerToPlanet;
+    use crossbeam_channel::{Receiver, unbounded};
+    use std::collections::HashSet;
+
+    /// explorer on planet 0 with every planet information known, energy surveyed at tick 1
+    fn surveyed_explorer(interval: u64) -> (Explorer, Receiver<ExplorerToPlanet>) {
+        let (_orch_sender, orch_receiver) = unbounded();
+        let (to_orch_sender, _) = unbounded();
+        let (_planet_sender, planet_receiver) = unbounded();
+        let (to_planet_sender, to_planet_receiver) = unbounded();
+        let mut explorer = Explorer::with_params(
+            7,
+            0,
+            (orch_receiver, to_orch_sender),
+            (planet_receiver, to_planet_sender),
+            AiParams {
+                energy_resurvey_interval: interval,
+                ..AiParams::default()
+            },
+        );
+        let info = explorer.get_current_planet_info_mut().unwrap();
+        info.neighbors = Some(HashSet::new());
+        info.basic_resources = Some(HashSet::new());
+        info.complex_resources = Some(HashSet::new());
+        info.energy_cells = Some(1);
+        info.timestamp_energy = 1;
+        (explorer, to_planet_receiver)
+    }
+
+    #[test]
+    fn stale_energy_info_triggers_a_new_survey() {
+        let (mut explorer, to_planet) = surveyed_explorer(10);
+        explorer.time = 11;
+
+        ai_core_function(&mut explorer).unwrap();
+
+        assert!(matches!(
+            to_planet.try_recv(),
+            Ok(ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id: 7 })
+        ));
+        assert!(matches!(
+            explorer.state,
+            ExplorerState::Surveying(flags) if flags.energy_cells
+        ));
+    }
+}
+
+#[cfg(test)]
+mod planet_info_tests {
+    use crate::components::mattia_explorer::planet_info::PlanetInfo;
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType};
+    use std::collections::HashSet;
+
+    #[test]
+    fn planet_info_is_complete_once_every_field_is_set() {
+        let mut planet_info = PlanetInfo::new(0);
+        assert!(!planet_info.is_complete());
+        assert!(planet_info.get_basic_resources().is_none());
+
+        planet_info.set_basic_resources(HashSet::from([BasicResourceType::Carbon]));
+        planet_info.set_complex_resources(HashSet::new());
+        assert!(!planet_info.is_complete());
+
+        planet_info.set_neighbours(HashSet::from([1, 2]));
+        assert!(planet_info.is_complete());
+        assert_eq!(
+            planet_info.get_basic_resources(),
+            Some(&HashSet::from([BasicResourceType::Carbon]))
+        );
+        assert_eq!(planet_info.get_neighbours(), Some(&HashSet::from([1, 2])));
+    }
+
+    #[test]
+    fn planet_info_with_data_is_complete() {
+        let planet_info = PlanetInfo::with_data(
+            HashSet::new(),
+            HashSet::from([ComplexResourceType::Diamond]),
+            HashSet::from([3]),
+            5,
+        );
+        assert!(planet_info.is_complete());
+        assert_eq!(planet_info.timestamp_neighbors, 5);
+        assert_eq!(
+            planet_info.get_complex_resources(),
+            Some(&HashSet::from([ComplexResourceType::Diamond]))
+        );
+    }
+
+    #[test]
+    fn topology_manager_finds_paths_over_planet_info() {
+        use crate::components::tommy_explorer::topology::TopologyManager;
+        use common_game::components::resource::ResourceType;
+        use std::collections::VecDeque;
+
+        // 0 - 1 - 2, planet 2 known only as a neighbor
+        let mut topology: TopologyManager<PlanetInfo> = TopologyManager::with_starting_planet(0);
+        topology.insert(
+            0,
+            PlanetInfo::with_data(HashSet::new(), HashSet::new(), HashSet::from([1]), 1),
+        );
+        topology.insert(
+            1,
+            PlanetInfo::with_data(
+                HashSet::from([BasicResourceType::Carbon]),
+                HashSet::new(),
+                HashSet::from([0, 2]),
+                1,
+            ),
+        );
+        topology.add_planets(&[2]);
+        assert_eq!(topology.get(2).unwrap().timestamp_neighbors, 0);
+
+        assert_eq!(
+            topology.find_path_to_nearest_frontier(0),
+            Some(VecDeque::from([1, 2]))
+        );
+        assert_eq!(
+            topology.find_path_to_resource(0, ResourceType::Basic(BasicResourceType::Carbon)),
+            Some(VecDeque::from([1]))
+        );
+
+        topology.mark_as_dead(1);
+        assert_eq!(topology.find_path_to_nearest_frontier(0), None);
+    }
+
+    #[test]
+    fn topology_manager_lists_reachable_resources_over_planet_info() {
+        use crate::components::tommy_explorer::topology::TopologyManager;
+        use common_game::components::resource::ResourceType;
+
+        // 0 - 1 - 2, carbon on 1 and 2, hydrogen only on 2
+        let mut topology: TopologyManager<PlanetInfo> = TopologyManager::with_starting_planet(0);
+        topology.insert(
+            0,
+            PlanetInfo::with_data(HashSet::new(), HashSet::new(), HashSet::from([1]), 1),
+        );
+        topology.insert(
+            1,
+            PlanetInfo::with_data(
+                HashSet::from([BasicResourceType::Carbon]),
+                HashSet::new(),
+                HashSet::from([0, 2]),
+                1,
+            ),
+        );
+        topology.insert(
+            2,
+            PlanetInfo::with_data(
+                HashSet::from([BasicResourceType::Carbon, BasicResourceType::Hydrogen]),
+                HashSet::new(),
+                HashSet::from([1]),
+                1,
+            ),
+        );
+
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        let hydrogen = ResourceType::Basic(BasicResourceType::Hydrogen);
+        assert_eq!(
+            topology.reachable_resources(0, 1),
+            vec![(carbon, vec![(1, 1)])]
+        );
+        assert_eq!(
+            topology.reachable_resources(0, 2),
+            vec![(hydrogen, vec![(2, 2)]), (carbon, vec![(1, 1), (2, 2)])]
+        );
+    }
+}
+
+#[cfg(test)]
+mod supported_cache_tests {
+    use crate::components::mattia_explorer::Explorer;
+    use crate::components::mattia_explorer::ai_params::AiParams;
+    use crate::components::mattia_explorer::handlers::supported_resource_request;
+    use common_game::components::resource::BasicResourceType;
+    use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+    use common_game::protocols::planet_explorer::ExplorerToPlanet;
+    use crossbeam_channel::{Receiver, unbounded};
+    use std::collections::HashSet;
+
+    /// explorer on planet 0 whose supported resources were surveyed at tick 1
+    fn cached_explorer(
+        ttl: u64,
+    ) -> (
+        Explorer,
+        Receiver<ExplorerToOrchestrator>,
+        Receiver<ExplorerToPlanet>,
+    ) {
+        let (_orch_sender, orch_receiver) = unbounded();
+        let (to_orch_sender, to_orch_receiver) = unbounded();
+        let (_planet_sender, planet_receiver) = unbounded();
+        let (to_planet_sender, to_planet_receiver) = unbounded();
+        let mut explorer = Explorer::with_params(
+            7,
+            0,
+            (orch_receiver, to_orch_sender),
+            (planet_receiver, to_planet_sender),
+            AiParams {
+                supported_cache_ttl: ttl,
+                ..AiParams::default()
+            },
+        );
+        let info = explorer.get_current_planet_info_mut().unwrap();
+        info.set_basic_resources(HashSet::from([BasicResourceType::Carbon]));
+        info.timestamp_resources = 1;
+        (explorer, to_orch_receiver, to_planet_receiver)
+    }
+
+    #[test]
+    fn expired_cache_triggers_a_fresh_planet_request() {
+        let (mut explorer, to_orch, to_planet) = cached_explorer(5);
+        explorer.time = 2;
+
+        // fresh cache: answered without asking the planet
+        supported_resource_request(&mut explorer).unwrap();
+        assert!(matches!(
+            to_orch.try_recv(),
+            Ok(ExplorerToOrchestrator::SupportedResourceResult { explorer_id: 7, .. })
+        ));
+        assert!(to_planet.try_recv().is_err());
+
+        explorer.time = 6;
+        supported_resource_request(&mut explorer).unwrap();
+        assert!(matches!(
+            to_planet.try_recv(),
+            Ok(ExplorerToPlanet::SupportedResourceRequest { explorer_id: 7 })
+        ));
+        assert!(to_orch.try_recv().is_err());
+    }
+
+    #[test]
+    fn cache_without_ttl_never_expires() {
+        let (mut explorer, to_orch, to_planet) = cached_explorer(0);
+        explorer.time = 1_000_000;
+
+        supported_resource_request(&mut explorer).unwrap();
+        assert!(matches!(
+            to_orch.try_recv(),
+            Ok(ExplorerToOrchestrator::SupportedResourceResult { explorer_id: 7, .. })
+        ));
+        assert!(to_planet.try_recv().is_err());
+    }
+}
+
+#[cfg(test)]
+mod manual_command_tests {
+    use super::*;
+    use crate::components::orchestrator::{ManualCommand, OrchestratorEvent};
+    use crate::utils::registry::PlanetType;
+    use common_game::components::resource::BasicResourceType;
+
+    #[test]
+    fn command_generate_after_stopping_the_ai() {
+        let mut orch = setup_orch_with_explorer(PlanetType::OneMillionCrabs, 0, 0);
+        orch.send_start_explorer_ai(0).unwrap();
+        drain_messages(&mut orch, 200);
+
+        // the AI is running, so the explorer cannot be driven by hand
+        assert!(
+            orch.command_explorer_generate(0, BasicResourceType::Silicon)
+                .is_err()
+        );
+
+        orch.send_stop_explorer_ai(0).unwrap();
+        drain_messages(&mut orch, 200);
+
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
+        for _ in 0..5 {
+            orch.send_sunray(0, &planet_channel)
+                .expect("testing expect");
+        }
+        drain_messages(&mut orch, 200);
+        orch.send_bag_content_request(0).unwrap();
+        drain_messages(&mut orch, 200);
+        let before = orch.explorers_info.get(&0).unwrap().bag.len();
+        orch.gui_messages.clear();
+
+        orch.command_explorer_generate(0, BasicResourceType::Silicon)
+            .unwrap();
+        // a second command is refused until the first one is answered
+        assert!(
+            orch.command_explorer_generate(0, BasicResourceType::Silicon)
+                .is_err()
+        );
+        drain_messages(&mut orch, 300);
+
+        assert_eq!(orch.explorers_info.get(&0).unwrap().bag.len(), before + 1);
+        assert!(orch.manual_commands.is_empty());
+        assert!(orch.gui_messages.iter().any(|event| matches!(
+            event,
+            OrchestratorEvent::ManualCommandFinished {
+                explorer_id: 0,
+                command: ManualCommand::Generate(BasicResourceType::Silicon),
+                result: Ok(()),
+            }
+        )));
+
+        let _ = orch.send_planet_kill_to_all();
+        let _ = orch.send_kill_explorer_ai(0);
+        drain_messages(&mut orch, 200);
+    }
+}
+
+#[cfg(test)]
+mod run_loop_tests {
+    use crate::components::mattia_explorer::Explorer;
+    use crate::components::mattia_explorer::states::ExplorerState;
+    use common_game::components::resource::ResourceType;
+    use common_game::protocols::orchestrator_explorer::{
+        ExplorerToOrchestrator, OrchestratorToExplorer,
+    };
+    use common_game::protocols::planet_explorer::PlanetToExplorer;
+    use crossbeam_channel::{Receiver, Sender, unbounded};
+    use std::time::{Duration, Instant};
+
+    /// explorer 3 on planet 5, with the orchestrator side of its channels; the planet
+    /// sender is returned too so that the planet channel stays connected
+    fn looping_explorer() -> (
+        Explorer,
+        Sender<OrchestratorToExplorer>,
+        Receiver<ExplorerToOrchestrator<Vec<ResourceType>>>,
+        Sender<PlanetToExplorer>,
+    ) {
+        let (orch_sender, orch_receiver) = unbounded();
+        let (to_orch_sender, to_orch_receiver) = unbounded();
+        let (planet_sender, planet_receiver) = unbounded();
+        let (to_planet_sender, _) = unbounded();
+        let explorer = Explorer::new(
+            3,
+            5,
+            (orch_receiver, to_orch_sender),
+            (planet_receiver, to_planet_sender),
+        );
+        (explorer, orch_sender, to_orch_receiver, planet_sender)
+    }
+
+    #[test]
+    fn current_planet_request_is_answered_by_the_loop() {
+        let (mut explorer, to_explorer, from_explorer, _planet) = looping_explorer();
+
+        to_explorer
+            .send(OrchestratorToExplorer::CurrentPlanetRequest)
+            .unwrap();
+        assert_eq!(explorer.run_ticks(3), Ok(&ExplorerState::Idle));
+        assert!(matches!(
+            from_explorer.try_recv(),
+            Ok(ExplorerToOrchestrator::CurrentPlanetResult {
+                explorer_id: 3,
+                planet_id: 5
+            })
+        ));
+        assert!(from_explorer.try_recv().is_err());
+    }
+
+    #[test]
+    fn kill_ends_the_bounded_loop_early() {
+        let (mut explorer, to_explorer, from_explorer, _planet) = looping_explorer();
+
+        to_explorer
+            .send(OrchestratorToExplorer::KillExplorer)
+            .unwrap();
+        let start = Instant::now();
+        let state = explorer.run_until(start + Duration::from_secs(5));
+        assert_eq!(state, Ok(&ExplorerState::Killed));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(matches!(
+            from_explorer.try_recv(),
+            Ok(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 3 })
+        ));
+    }
+
+    #[test]
+    fn disconnected_orchestrator_stops_the_bounded_loop() {
+        let (mut explorer, to_explorer, _from_explorer, _planet) = looping_explorer();
+
+        drop(to_explorer);
+        assert!(explorer.run_ticks(3).is_err());
+    }
 }
@@ -0,0 +1,130 @@
+use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
+use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
+use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+use crossbeam_channel::{Sender, TrySendError};
+
+use crate::components::orchestrator::Orchestrator;
+use crate::utils::tracing::TracedSender;
+
+/// Channels of a planet kept by the orchestrator.
+#[derive(Debug, Clone)]
+pub struct PlanetHandle {
+    /// Messages from the orchestrator to the planet.
+    to_planet: TracedSender<OrchestratorToPlanet>,
+    /// Messages from the explorers to the planet, handed to every explorer that
+    /// arrives on it.
+    explorer_gateway: Sender<ExplorerToPlanet>,
+}
+
+impl PlanetHandle {
+    pub fn new(
+        to_planet: impl Into<TracedSender<OrchestratorToPlanet>>,
+        explorer_gateway: Sender<ExplorerToPlanet>,
+    ) -> Self {
+        PlanetHandle {
+            to_planet: to_planet.into(),
+            explorer_gateway,
+        }
+    }
+
+    /// Sends a message to the planet.
+    ///
+    /// Returns Err if the planet is gone.
+    pub fn send_to_planet(&self, msg: OrchestratorToPlanet) -> Result<(), String> {
+        self.to_planet
+            .send(msg)
+            .map_err(|e| format!("Cannot send {:?} to the planet", e.0))
+    }
+
+    /// Sends a message to the planet without blocking.
+    ///
+    /// Returns Err if the planet is gone or its channel is full.
+    pub fn try_send_to_planet(&self, msg: OrchestratorToPlanet) -> Result<(), String> {
+        self.to_planet.try_send(msg).map_err(|e| match e {
+            TrySendError::Full(msg) => format!("Cannot send {:?}, the planet is busy", msg),
+            TrySendError::Disconnected(msg) => format!("Cannot send {:?} to the planet", msg),
+        })
+    }
+
+    /// Returns the sender the orchestrator talks to the planet with, for the functions
+    /// that take it explicitly (e.g. [`Orchestrator::send_sunray`]).
+    pub fn to_planet(&self) -> &TracedSender<OrchestratorToPlanet> {
+        &self.to_planet
+    }
+
+    /// Returns the sender an explorer arriving on the planet talks to it with.
+    pub fn explorer_sender_for_arrival(&self) -> Sender<ExplorerToPlanet> {
+        self.explorer_gateway.clone()
+    }
+}
+
+/// Channels of an explorer kept by the orchestrator.
+#[derive(Debug, Clone)]
+pub struct ExplorerHandle {
+    /// Messages from the orchestrator to the explorer.
+    to_explorer: TracedSender<OrchestratorToExplorer>,
+    /// Messages from the planets to the explorer, handed to the planet it arrives on.
+    planet_gateway: Sender<PlanetToExplorer>,
+}
+
+impl ExplorerHandle {
+    pub fn new(
+        to_explorer: impl Into<TracedSender<OrchestratorToExplorer>>,
+        planet_gateway: Sender<PlanetToExplorer>,
+    ) -> Self {
+        ExplorerHandle {
+            to_explorer: to_explorer.into(),
+            planet_gateway,
+        }
+    }
+
+    /// Sends a message to the explorer.
+    ///
+    /// Returns Err if the explorer is gone.
+    pub fn send_to_explorer(&self, msg: OrchestratorToExplorer) -> Result<(), String> {
+        self.to_explorer
+            .send(msg)
+            .map_err(|e| format!("Cannot send {:?} to the explorer", e.0))
+    }
+
+    /// Sends a message to the explorer without blocking.
+    ///
+    /// Returns Err if the explorer is gone or its channel is full.
+    pub fn try_send_to_explorer(&self, msg: OrchestratorToExplorer) -> Result<(), String> {
+        self.to_explorer.try_send(msg).map_err(|e| match e {
+            TrySendError::Full(msg) => format!("Cannot send {:?}, the explorer is busy", msg),
+            TrySendError::Disconnected(msg) => format!("Cannot send {:?} to the explorer", msg),
+        })
+    }
+
+    /// Returns the sender the orchestrator talks to the explorer with, for the functions
+    /// that take it explicitly.
+    pub fn to_explorer(&self) -> &TracedSender<OrchestratorToExplorer> {
+        &self.to_explorer
+    }
+
+    /// Returns the sender the planet the explorer arrives on talks to it with.
+    pub fn planet_sender_for_arrival(&self) -> Sender<PlanetToExplorer> {
+        self.planet_gateway.clone()
+    }
+}
+
+impl Orchestrator {
+    /// Iterates over the channels of the planets not known to be dead.
+    pub fn living_planets(&self) -> impl Iterator<Item = (u32, &PlanetHandle)> {
+        self.planet_channels
+            .iter()
+            .filter(|(id, _)| !self.planets_info.is_dead(id))
+            .map(|(id, handle)| (*id, handle))
+    }
+
+    /// Iterates over the channels of the explorers not known to be dead.
+    ///
+    /// An explorer without info counts as dead.
+    pub fn living_explorers(&self) -> impl Iterator<Item = (u32, &ExplorerHandle)> {
+        self.explorer_channels
+            .iter()
+            .filter(|(id, _)| !self.explorers_info.is_dead(id))
+            .map(|(id, handle)| (*id, handle))
+    }
+}
@@ -0,0 +1,318 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use common_game::protocols::orchestrator_explorer::{
+    ExplorerToOrchestrator, OrchestratorToExplorer,
+};
+use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+use crossbeam_channel::Sender;
+use logging_utils::{LoggableActor, log_fn_call};
+
+use crate::Orchestrator;
+use crate::components::tommy_explorer::bag::BagType;
+use crate::utils::Status;
+use crate::utils::tracing::{
+    MessageRecorder, RecordingSender, TracedActor, TracedSender, explorer_to_planet_sender,
+};
+
+/// How long `kill planet <id>` waits for the planet's acknowledgement.
+const DEBUG_KILL_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// List of the commands accepted by [`exec_debug_command`].
+pub const DEBUG_HELP: &str = "available commands:
+  status            number of planets and explorers by status
+  planet <id>       details of a planet
+  explorer <id>     details of an explorer
+  sunray <id>       send a sunray to a planet
+  asteroid <id>     send an asteroid to a planet
+  kill planet <id>  kill a planet and wait for its acknowledgement
+  topology          text map of the galaxy
+  stats             game statistics
+  help              this message";
+
+/// Execute a debug console command on the orchestrator.
+///
+/// The command is parsed from `line` (surrounding whitespace and a leading `:`
+/// are ignored) and the result is returned as formatted text, ready to be
+/// printed by a GUI or on stdout.
+///
+/// Returns Err with the help text if the command is unknown or malformed,
+/// or with the orchestrator's error if the command fails.
+pub fn exec_debug_command(orch: &mut Orchestrator, line: &str) -> Result<String, String> {
+    //LOG
+    log_fn_call!(orch, "exec_debug_command()", line,);
+    //LOG
+    let line = line.trim();
+    let line = line.strip_prefix(':').unwrap_or(line);
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["status"] => Ok(format_status(orch)),
+        ["planet", id] => format_planet(orch, parse_id(id)?),
+        ["explorer", id] => format_explorer(orch, parse_id(id)?),
+        ["sunray", id] => {
+            let planet_id = parse_id(id)?;
+            let sender = alive_planet_sender(orch, planet_id)?;
+            orch.send_sunray(planet_id, &sender)?;
+            Ok(format!("sunray sent to planet {}", planet_id))
+        }
+        ["asteroid", id] => {
+            let planet_id = parse_id(id)?;
+            let sender = alive_planet_sender(orch, planet_id)?;
+            orch.send_asteroid(planet_id, &sender)?;
+            Ok(format!("asteroid sent to planet {}", planet_id))
+        }
+        ["kill", "planet", id] => {
+            let planet_id = parse_id(id)?;
+            orch.kill_planet(planet_id, DEBUG_KILL_TIMEOUT)?;
+            Ok(format!("planet {} killed", planet_id))
+        }
+        ["topology"] => Ok(orch.render_ascii_map()),
+        ["stats"] => Ok(format_stats(orch)),
+        ["help"] => Ok(DEBUG_HELP.to_string()),
+        [] => Err(format!("empty command\n{}", DEBUG_HELP)),
+        _ => Err(format!("unknown command: '{}'\n{}", line, DEBUG_HELP)),
+    }
+}
+
+fn parse_id(s: &str) -> Result<u32, String> {
+    s.parse::<u32>()
+        .map_err(|_| format!("invalid id: '{}'\n{}", s, DEBUG_HELP))
+}
+
+fn alive_planet_sender(
+    orch: &Orchestrator,
+    planet_id: u32,
+) -> Result<TracedSender<OrchestratorToPlanet>, String> {
+    if orch.planets_info.is_dead(&planet_id) {
+        return Err(format!("planet {} is dead", planet_id));
+    }
+    orch.planet_channels
+        .get(&planet_id)
+        .map(|handle| handle.to_planet().clone())
+        .ok_or_else(|| format!("planet {} does not exist", planet_id))
+}
+
+fn count_by_status(statuses: impl Iterator<Item = Status>) -> (usize, usize, usize) {
+    statuses.fold((0, 0, 0), |(r, p, d), status| match status {
+        Status::Running => (r + 1, p, d),
+        Status::Paused => (r, p + 1, d),
+        Status::Dead => (r, p, d + 1),
+    })
+}
+
+fn format_status(orch: &Orchestrator) -> String {
+    let (pr, pp, pd) = count_by_status(orch.planets_info.iter().map(|(_, i)| i.status));
+    let (er, ep, ed) = count_by_status(orch.explorers_info.iter().map(|(_, i)| i.status));
+    format!(
+        "planets: {} (running {}, paused {}, dead {})\nexplorers: {} (running {}, paused {}, dead {})",
+        orch.planets_info.len(),
+        pr,
+        pp,
+        pd,
+        orch.explorers_info.len(),
+        er,
+        ep,
+        ed
+    )
+}
+
+fn format_planet(orch: &Orchestrator, planet_id: u32) -> Result<String, String> {
+    let info = orch
+        .planets_info
+        .get_info(planet_id)
+        .ok_or_else(|| format!("planet {} does not exist", planet_id))?;
+    let explorers: Vec<String> = orch
+        .explorers_info
+        .iter()
+        .filter(|(_, e)| e.current_planet_id == planet_id && e.status != Status::Dead)
+        .map(|(id, _)| id.to_string())
+        .collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "planet {} ({:?})", planet_id, info.name);
+    let _ = writeln!(out, "  status: {:?}", info.status);
+    let _ = writeln!(
+        out,
+        "  energy cells: {}/{} charged",
+        info.charged_cells_count,
+        info.energy_cells.len()
+    );
+    let _ = writeln!(out, "  rocket: {}", info.rocket);
+    let _ = writeln!(out, "  explorers: [{}]", explorers.join(", "));
+    let _ = write!(
+        out,
+        "  supported resources: {}, supported combinations: {}",
+        info.supported_resources
+            .as_ref()
+            .map_or("?".to_string(), |r| r.len().to_string()),
+        info.supported_combination
+            .as_ref()
+            .map_or("?".to_string(), |c| c.len().to_string())
+    );
+    Ok(out)
+}
+
+fn format_explorer(orch: &Orchestrator, explorer_id: u32) -> Result<String, String> {
+    let info = orch
+        .explorers_info
+        .get(&explorer_id)
+        .ok_or_else(|| format!("explorer {} does not exist", explorer_id))?;
+    let mut out = String::new();
+    let _ = writeln!(out, "explorer {}", explorer_id);
+    let _ = writeln!(out, "  status: {:?}", info.status);
+    let _ = writeln!(out, "  planet: {}", info.current_planet_id);
+    if info.move_to_planet_id >= 0 {
+        let _ = writeln!(out, "  moving to: {}", info.move_to_planet_id);
+    }
+    let _ = write!(out, "  bag: {} resources", info.bag.len());
+    Ok(out)
+}
+
+fn format_stats(orch: &Orchestrator) -> String {
+    let (edges, _) = orch.get_topology();
+    let resources: usize = orch.explorers_info.iter().map(|(_, e)| e.bag.len()).sum();
+    format!(
+        "alive planets: {}/{}\nalive explorers: {}/{}\nedges: {}\nresources in bags: {}\npending gui events: {}",
+        orch.planets_info.count_survivors(),
+        orch.planets_info.len(),
+        orch.explorers_info.count_survivors(),
+        orch.explorers_info.len(),
+        edges.len(),
+        resources,
+        orch.gui_messages.len()
+    )
+}
+
+impl Orchestrator {
+    /// Start recording a transcript of every protocol message.
+    ///
+    /// From now on the senders handed out to planets and explorers are wrapped in
+    /// recording proxies (see [`RecordingSender::into_proxy`]), so their
+    /// constructors receive them transparently, while the orchestrator records its
+    /// own messages inline (see [`TracedSender`]) and still sees the send errors of
+    /// the dead planets and explorers; planets and explorers added before this call
+    /// are not traced. Calling it again does nothing.
+    ///
+    /// Messages from a planet to an explorer carry no planet id: they are
+    /// attributed to the planet the explorer last sent a message to.
+    pub fn enable_message_tracing(&mut self) {
+        //LOG
+        log_fn_call!(self, "enable_message_tracing()");
+        //LOG
+        if self.message_recorder.is_some() {
+            return;
+        }
+        let recorder = MessageRecorder::new();
+        self.sender_planet_orch = RecordingSender::with_route(
+            self.sender_planet_orch.clone(),
+            recorder.clone(),
+            |msg: &PlanetToOrchestrator| {
+                (
+                    TracedActor::Planet(msg.planet_id()),
+                    TracedActor::Orchestrator,
+                )
+            },
+        )
+        .into_proxy();
+        self.sender_explorer_orch = RecordingSender::with_route(
+            self.sender_explorer_orch.clone(),
+            recorder.clone(),
+            |msg: &ExplorerToOrchestrator<BagType>| {
+                (
+                    TracedActor::Explorer(msg.explorer_id()),
+                    TracedActor::Orchestrator,
+                )
+            },
+        )
+        .into_proxy();
+        self.message_recorder = Some(recorder);
+    }
+
+    /// Wrap the senders of a new planet in recording ones, if tracing is enabled.
+    ///
+    /// The sender kept by the orchestrator records inline, the one handed to the
+    /// explorers is a recording proxy.
+    pub(crate) fn traced_planet_senders(
+        &self,
+        planet_id: u32,
+        sender_orchestrator: Sender<OrchestratorToPlanet>,
+        sender_explorer: Sender<ExplorerToPlanet>,
+    ) -> (TracedSender<OrchestratorToPlanet>, Sender<ExplorerToPlanet>) {
+        let Some(recorder) = &self.message_recorder else {
+            return (sender_orchestrator.into(), sender_explorer);
+        };
+        (
+            RecordingSender::new(
+                sender_orchestrator,
+                recorder.clone(),
+                TracedActor::Orchestrator,
+                TracedActor::Planet(planet_id),
+            )
+            .into(),
+            RecordingSender::with_route(sender_explorer, recorder.clone(), move |msg| {
+                (
+                    TracedActor::Explorer(explorer_to_planet_sender(msg)),
+                    TracedActor::Planet(planet_id),
+                )
+            })
+            .into_proxy(),
+        )
+    }
+
+    /// Wrap the senders of a new explorer in recording ones, if tracing is enabled.
+    ///
+    /// The sender kept by the orchestrator records inline, the one handed to the
+    /// planets is a recording proxy.
+    pub(crate) fn traced_explorer_senders(
+        &self,
+        explorer_id: u32,
+        planet_id: u32,
+        sender_orch: Sender<OrchestratorToExplorer>,
+        sender_planet: Sender<PlanetToExplorer>,
+    ) -> (
+        TracedSender<OrchestratorToExplorer>,
+        Sender<PlanetToExplorer>,
+    ) {
+        let Some(recorder) = &self.message_recorder else {
+            return (sender_orch.into(), sender_planet);
+        };
+        let locations = recorder.clone();
+        (
+            RecordingSender::new(
+                sender_orch,
+                recorder.clone(),
+                TracedActor::Orchestrator,
+                TracedActor::Explorer(explorer_id),
+            )
+            .into(),
+            RecordingSender::with_route(sender_planet, recorder.clone(), move |_| {
+                // until the explorer talks to a planet, it is on the one it spawned on
+                let planet = locations.planet_of(explorer_id).unwrap_or(planet_id);
+                (
+                    TracedActor::Planet(planet),
+                    TracedActor::Explorer(explorer_id),
+                )
+            })
+            .into_proxy(),
+        )
+    }
+
+    /// Write the recorded transcripts into `dir`, one file per pair of actors.
+    ///
+    /// See [`MessageRecorder::dump`] for the file format.
+    ///
+    /// Returns the number of files written, or Err if message tracing is not
+    /// enabled or a file cannot be written.
+    pub fn dump_transcripts(&self, dir: &str) -> Result<usize, String> {
+        //LOG
+        log_fn_call!(self, "dump_transcripts()", dir,);
+        //LOG
+        self.message_recorder
+            .as_ref()
+            .ok_or_else(|| "message tracing is not enabled".to_string())?
+            .dump(Path::new(dir))
+    }
+}
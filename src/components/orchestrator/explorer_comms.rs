@@ -1,23 +1,30 @@
+use crate::components::orchestrator::channels::PlanetHandle;
+use crate::utils::tracing::TracedSender;
 use crate::{Orchestrator, utils::Status};
 use common_game::components::resource::{BasicResourceType, ComplexResourceType};
-use common_game::logging::{ActorType, EventType};
-use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
-use crossbeam_channel::Sender;
-use logging_utils::{LoggableActor, log_fn_call, log_message};
+use common_game::logging::{ActorType, Channel, EventType, LogEvent, Participant};
+use common_game::protocols::orchestrator_explorer::{
+    ExplorerToOrchestrator, OrchestratorToExplorer,
+};
+use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
+use crossbeam_channel::{after, select};
+use logging_utils::{LoggableActor, log_fn_call, log_internal_op, log_message, warning_payload};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
 impl Orchestrator {
     /// this method gets the sender used by all the "send methods" below
     pub fn get_sender_from_orchestrator_to_explorer(
         &self,
         explorer_id: u32,
-    ) -> Result<&Sender<OrchestratorToExplorer>, String> {
+    ) -> Result<&TracedSender<OrchestratorToExplorer>, String> {
         log_fn_call!(
             self,
             "get_sender_from_orchestrator_to_explorer()",
             explorer_id,
         );
         match self.explorer_channels.get(&explorer_id) {
-            Some((sender, _)) => Ok(sender),
+            Some(handle) => Ok(handle.to_explorer()),
             None => Err(format!("No sender found for explorer {}", explorer_id)),
         }
     }
@@ -114,6 +121,7 @@ impl Orchestrator {
         sender
             .send(OrchestratorToExplorer::KillExplorer)
             .map_err(|_| format!("Failed to send kill explorer to explorer {}", explorer_id))?;
+        self.signal_explorer_kill(explorer_id);
 
         //LOG
         log_message!(
@@ -128,6 +136,336 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// signals the kill channel of the explorer, if it has one
+    ///
+    /// must be called after sending KillExplorer: the explorer answers the kill signal
+    /// with KillExplorerResult and exits, the KillExplorer message is left unread
+    pub(crate) fn signal_explorer_kill(&self, explorer_id: u32) {
+        if let Some(kill_sender) = self.explorer_kill_senders.get(&explorer_id) {
+            // the channel holds a single signal, a second kill has nothing to add
+            let _ = kill_sender.try_send(());
+        }
+    }
+
+    /// sends the topology changes collected since the last call to every explorer
+    ///
+    /// the changes are sent as a single delta, so an explorer gets at most one
+    /// notification per tick; the explorers whose channel is disconnected are forgotten
+    pub fn flush_topology_deltas(&mut self) {
+        if self.pending_topology_delta.is_empty() {
+            return;
+        }
+        let delta = std::mem::take(&mut self.pending_topology_delta);
+        //LOG
+        log_internal_op!(
+            self,
+            "action"=>"topology delta sent to the explorers",
+            "delta"=>format!("{:?}", delta),
+        );
+        //LOG
+        self.explorer_topology_senders
+            .retain(|_, sender| sender.send(delta.clone()).is_ok());
+    }
+
+    /// sends the KillExplorer message and waits until the explorer is marked as dead
+    ///
+    /// the explorer messages received in the meantime are handled normally,
+    /// returns Err if the explorer does not acknowledge the kill within `timeout`;
+    /// in that case, or if the explorer cannot be reached at all, the explorer is
+    /// forcibly removed (see [`force_explorer_cleanup`](Self::force_explorer_cleanup))
+    pub fn kill_explorer(&mut self, explorer_id: u32, timeout: Duration) -> Result<(), String> {
+        log_fn_call!(self, "kill_explorer()", explorer_id, timeout,);
+        if self.explorers_info.get_status(&explorer_id) == Some(Status::Dead) {
+            return Err(format!("explorer {} is already dead", explorer_id));
+        }
+        if let Err(err) = self.send_kill_explorer_ai(explorer_id) {
+            self.force_explorer_cleanup(explorer_id, &err);
+            return Err(err);
+        }
+
+        let deadline = after(timeout);
+        loop {
+            select! {
+                recv(self.receiver_orch_explorer) -> msg => {
+                    let msg = msg.map_err(|e| format!("Cannot receive message from explorers: {}", e))?;
+                    let msg_string = format!("{:?}", msg);
+                    // the status update is done by handle_explorer_message
+                    if let Err(err) = self.handle_explorer_message(msg) {
+                        self.report_handler_error(&msg_string, err, "kill_explorer()");
+                    }
+                    if self.explorers_info.get_status(&explorer_id) == Some(Status::Dead) {
+                        return Ok(());
+                    }
+                }
+                recv(deadline) -> _ => {
+                    let err = format!(
+                        "Timeout: explorer {} did not acknowledge KillExplorer within {:?}",
+                        explorer_id, timeout
+                    );
+                    self.force_explorer_cleanup(explorer_id, &err);
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    /// stops the AI of every running explorer and waits for their acknowledgements
+    ///
+    /// the StopExplorerAI messages are all sent before waiting, so the explorers stop
+    /// in parallel; the explorer messages received in the meantime are handled
+    /// normally. Returns Err listing the explorers that could not be reached or did
+    /// not answer within `timeout`
+    pub fn pause_all_explorers(&mut self, timeout: Duration) -> Result<(), String> {
+        log_fn_call!(self, "pause_all_explorers()", timeout,);
+        let ids: Vec<u32> = self
+            .explorer_channels
+            .keys()
+            .copied()
+            .filter(|id| self.explorers_info.is_running(id))
+            .collect();
+        let mut pending = HashSet::new();
+        let mut errors = Vec::new();
+        for id in ids {
+            match self.send_stop_explorer_ai(id) {
+                Ok(()) => {
+                    pending.insert(id);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        self.await_explorer_acks(pending, timeout, errors, |msg| match msg {
+            ExplorerToOrchestrator::StopExplorerAIResult { explorer_id } => Some(*explorer_id),
+            _ => None,
+        })
+    }
+
+    /// starts the AI of every paused explorer and waits for their acknowledgements
+    ///
+    /// see [`pause_all_explorers`](Self::pause_all_explorers)
+    pub fn resume_all_explorers(&mut self, timeout: Duration) -> Result<(), String> {
+        log_fn_call!(self, "resume_all_explorers()", timeout,);
+        let ids: Vec<u32> = self
+            .explorer_channels
+            .keys()
+            .copied()
+            .filter(|id| self.explorers_info.is_paused(id))
+            .collect();
+        let mut pending = HashSet::new();
+        let mut errors = Vec::new();
+        for id in ids {
+            match self.send_start_explorer_ai(id) {
+                Ok(()) => {
+                    pending.insert(id);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        self.await_explorer_acks(pending, timeout, errors, |msg| match msg {
+            ExplorerToOrchestrator::StartExplorerAIResult { explorer_id } => Some(*explorer_id),
+            _ => None,
+        })
+    }
+
+    /// handles the explorer messages until every pending explorer has sent the
+    /// acknowledgement recognized by `ack`, or until `timeout`
+    fn await_explorer_acks(
+        &mut self,
+        mut pending: HashSet<u32>,
+        timeout: Duration,
+        mut errors: Vec<String>,
+        ack: impl Fn(&ExplorerToOrchestrator) -> Option<u32>,
+    ) -> Result<(), String> {
+        let deadline = after(timeout);
+        while !pending.is_empty() {
+            select! {
+                recv(self.receiver_orch_explorer) -> msg => {
+                    let msg = msg.map_err(|e| format!("Cannot receive message from explorers: {}", e))?;
+                    if let Some(explorer_id) = ack(&msg) {
+                        pending.remove(&explorer_id);
+                    }
+                    let msg_string = format!("{:?}", msg);
+                    // the status update is done by handle_explorer_message
+                    if let Err(err) = self.handle_explorer_message(msg) {
+                        self.report_handler_error(&msg_string, err, "await_explorer_acks()");
+                    }
+                }
+                recv(deadline) -> _ => {
+                    let mut late: Vec<u32> = pending.into_iter().collect();
+                    late.sort();
+                    errors.push(format!(
+                        "Timeout: explorers {:?} did not answer within {:?}",
+                        late, timeout
+                    ));
+                    break;
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// waits for the thread of an explorer to exit and returns its result
+    ///
+    /// the handle is consumed, so an explorer can be joined only once; returns Err
+    /// with the panic payload if the thread panicked, or with a String payload if
+    /// there is no handle for the explorer
+    #[cfg(feature = "expose-handles")]
+    pub fn join_explorer(
+        &mut self,
+        explorer_id: u32,
+    ) -> Result<Result<(), String>, Box<dyn std::any::Any + Send>> {
+        log_fn_call!(self, "join_explorer()", explorer_id,);
+        match self.explorer_handles.remove(&explorer_id) {
+            Some(handle) => handle.join(),
+            None => Err(Box::new(format!(
+                "no thread handle for explorer {}",
+                explorer_id
+            ))),
+        }
+    }
+
+    /// removes every trace of a dead explorer from the orchestrator
+    ///
+    /// its info, channels, thread handle and pending manual command are dropped, so
+    /// its thread (if still alive) sees the channels from the orchestrator
+    /// disconnected; returns Err if the explorer is unknown or not dead
+    pub fn remove_explorer(&mut self, explorer_id: u32) -> Result<(), String> {
+        log_fn_call!(self, "remove_explorer()", explorer_id,);
+        match self.explorers_info.get_status(&explorer_id) {
+            Some(Status::Dead) => {}
+            Some(status) => {
+                return Err(format!(
+                    "Explorer {} is {:?}, only dead explorers can be removed",
+                    explorer_id, status
+                ));
+            }
+            None => return Err(format!("Explorer {} not found", explorer_id)),
+        }
+
+        self.explorers_info.remove(&explorer_id);
+        self.explorer_channels.remove(&explorer_id);
+        self.explorer_kill_senders.remove(&explorer_id);
+        self.explorer_topology_senders.remove(&explorer_id);
+        self.manual_commands.remove(&explorer_id);
+        self.pending_explorer_removals
+            .retain(|(id, _)| *id != explorer_id);
+        // the thread is detached, a dead explorer has nothing left to report
+        #[cfg(feature = "expose-handles")]
+        self.explorer_handles.remove(&explorer_id);
+
+        //LOG
+        log_internal_op!(
+            self,
+            "action"=>"explorer removed",
+            "explorer_id"=>explorer_id
+        );
+        //LOG
+        Ok(())
+    }
+
+    /// removes the killed explorers whose removal time has passed
+    pub(crate) fn remove_expired_explorers(&mut self) {
+        let now = Instant::now();
+        let expired: Vec<u32> = self
+            .pending_explorer_removals
+            .iter()
+            .filter(|(_, at)| *at <= now)
+            .map(|(id, _)| *id)
+            .collect();
+        for explorer_id in expired {
+            if let Err(err) = self.remove_explorer(explorer_id) {
+                // e.g. the explorer has been added again with the same id
+                self.pending_explorer_removals
+                    .retain(|(id, _)| *id != explorer_id);
+                log_internal_op!(
+                    self,
+                    "action"=>"explorer not removed",
+                    "explorer_id"=>explorer_id,
+                    "reason"=>err
+                );
+            }
+        }
+    }
+
+    /// tells the planet hosting a dead explorer that the explorer left it
+    ///
+    /// the planet drops the explorer's sender, so it stops answering the explorer;
+    /// its OutgoingExplorerResponse is only acknowledged, no MoveToPlanet follows
+    pub(crate) fn notify_explorer_departure(&self, explorer_id: u32) {
+        let Some(planet_id) = self.explorers_info.get_current_planet(&explorer_id) else {
+            return;
+        };
+        // a dead planet has no explorers to forget
+        if self.planets_info.is_dead(&planet_id) {
+            return;
+        }
+        let Some(handle) = self.planet_channels.get(&planet_id) else {
+            return;
+        };
+        match handle.send_to_planet(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id }) {
+            Ok(()) => {
+                //LOG
+                log_message!(
+                    ActorType::Orchestrator,
+                    0u32,
+                    ActorType::Planet,
+                    planet_id,
+                    EventType::MessageOrchestratorToPlanet,
+                    "OutgoingExplorerRequest sent for a dead explorer";
+                    "explorer_id"=>explorer_id
+                );
+                //LOG
+            }
+            Err(err) => {
+                //LOG
+                LogEvent::self_directed(
+                    Participant::new(ActorType::Orchestrator, 0u32),
+                    EventType::InternalOrchestratorAction,
+                    Channel::Warning,
+                    warning_payload!(
+                        "departure of the dead explorer not sent",
+                        err,
+                        "notify_explorer_departure()";
+                        "explorer_id"=>explorer_id,
+                        "planet_id"=>planet_id
+                    ),
+                )
+                .emit();
+                //LOG
+            }
+        }
+    }
+
+    /// marks an unresponsive explorer as dead and drops its channels
+    ///
+    /// used when the explorer does not answer a kill: its thread, if still alive,
+    /// is left alone, but it cannot receive anything from the orchestrator anymore
+    pub(crate) fn force_explorer_cleanup(&mut self, explorer_id: u32, reason: &str) {
+        self.notify_explorer_departure(explorer_id);
+        self.explorer_channels.remove(&explorer_id);
+        self.explorer_kill_senders.remove(&explorer_id);
+        self.explorer_topology_senders.remove(&explorer_id);
+        self.manual_commands.remove(&explorer_id);
+        self.explorers_info.insert_status(explorer_id, Status::Dead);
+        //LOG
+        LogEvent::self_directed(
+            Participant::new(ActorType::Orchestrator, 0u32),
+            EventType::InternalOrchestratorAction,
+            Channel::Error,
+            warning_payload!(
+                "explorer forcibly removed",
+                reason,
+                "force_explorer_cleanup()";
+                "explorer_id"=>explorer_id
+            ),
+        )
+        .emit();
+        //LOG
+    }
+
     /// gets the sender to the planet (from the explorer) and sends it with the MoveToPlanet message
     pub fn send_move_to_planet(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), String> {
         log_fn_call!(self, "send_move_to_planet()", explorer_id, planet_id,);
@@ -135,10 +473,10 @@ impl Orchestrator {
         let sender = self.get_sender_from_orchestrator_to_explorer(explorer_id)?;
 
         // get the sender from explorer to planet
-        let sender_to_new_planet = match self.planet_channels.get(&planet_id) {
-            Some((_, explorer_sender)) => Some(explorer_sender.clone()),
-            None => None, // sender does not exist
-        };
+        let sender_to_new_planet = self
+            .planet_channels
+            .get(&planet_id)
+            .map(PlanetHandle::explorer_sender_for_arrival); // None if the sender does not exist
 
         // send the MoveToPlanet
         sender
@@ -348,14 +686,10 @@ impl Orchestrator {
         Ok(())
     }
 
-    /// gets the neighbors and sends them with the NeighborsResponse message
-    pub fn send_neighbours_response(
-        &mut self,
-        explorer_id: u32,
-        planet_id: u32,
-    ) -> Result<(), String> {
-        log_fn_call!(self, "send_neighbors_response()", explorer_id, planet_id,);
-        let sender = self.get_sender_from_orchestrator_to_explorer(explorer_id)?;
+    /// returns the real ids of the planets linked to `planet_id` in the galaxy topology
+    ///
+    /// returns Err if the planet is not part of the galaxy
+    pub fn get_neighbors_of(&self, planet_id: u32) -> Result<Vec<u32>, String> {
         // Translate the real planet_id to its matrix index via the lookup table
         let matrix_idx = self
             .galaxy_lookup
@@ -364,24 +698,63 @@ impl Orchestrator {
             .ok_or_else(|| format!("planet_id {} not found in galaxy_lookup", planet_id))?;
         // the neighbors are obtained from the galaxy_topology adjacent matrix
         // and translated back to real planet_ids via the reverse lookup
-        let neighbors: Vec<u32> = {
-            let guard = &self.galaxy_topology;
-
-            guard
-                .get(matrix_idx)
-                .into_iter() // Handles the Option if the index is out of bounds
-                .flat_map(|row| {
-                    row.iter().enumerate().filter_map(|(i, &is_connected)| {
-                        // only return the real planet_id if the connection exists (true)
-                        if is_connected {
-                            self.galaxy_reverse_lookup.get(&(i as u32)).copied()
-                        } else {
-                            None
-                        }
-                    })
+        let neighbors: Vec<u32> = self
+            .galaxy_topology
+            .get(matrix_idx)
+            .into_iter() // Handles the Option if the index is out of bounds
+            .flat_map(|row| {
+                row.iter().enumerate().filter_map(|(i, &is_connected)| {
+                    // only return the real planet_id if the connection exists (true)
+                    if is_connected {
+                        self.galaxy_reverse_lookup.get(&(i as u32)).copied()
+                    } else {
+                        None
+                    }
                 })
-                .collect()
+            })
+            .collect();
+        Ok(neighbors)
+    }
+
+    /// answers a NeighborsRequest, reading the neighbors from `neighbor_cache`
+    ///
+    /// on a cache miss the neighbors are read from the topology and cached
+    pub fn handle_neighbors_request(
+        &mut self,
+        explorer_id: u32,
+        planet_id: u32,
+    ) -> Result<(), String> {
+        log_fn_call!(self, "handle_neighbors_request()", explorer_id, planet_id,);
+        let neighbors = match self.neighbor_cache.get(&planet_id) {
+            Some(neighbors) => neighbors.clone(),
+            None => {
+                let neighbors = self.get_neighbors_of(planet_id)?;
+                self.neighbor_cache.insert(planet_id, neighbors.clone());
+                neighbors
+            }
         };
+        self.send_neighbors(explorer_id, planet_id, neighbors)
+    }
+
+    /// gets the neighbors and sends them with the NeighborsResponse message
+    pub fn send_neighbours_response(
+        &mut self,
+        explorer_id: u32,
+        planet_id: u32,
+    ) -> Result<(), String> {
+        log_fn_call!(self, "send_neighbors_response()", explorer_id, planet_id,);
+        let neighbors = self.get_neighbors_of(planet_id)?;
+        self.send_neighbors(explorer_id, planet_id, neighbors)
+    }
+
+    /// sends the NeighborsResponse message
+    fn send_neighbors(
+        &self,
+        explorer_id: u32,
+        planet_id: u32,
+        neighbors: Vec<u32>,
+    ) -> Result<(), String> {
+        let sender = self.get_sender_from_orchestrator_to_explorer(explorer_id)?;
 
         sender
             .send(OrchestratorToExplorer::NeighborsResponse { neighbors })
@@ -399,7 +772,8 @@ impl Orchestrator {
             ActorType::Explorer,
             explorer_id,
             EventType::MessageOrchestratorToExplorer,
-            "NeighborsResponse",
+            "NeighborsResponse";
+            "planet_id"=>planet_id
         );
         //LOG
         Ok(())
@@ -1,12 +1,21 @@
 use crate::PlanetInfoMap;
+use common_game::components::resource::{BasicResourceType, ComplexResourceType};
 use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
 use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
-use crossbeam_channel::Sender;
+use crossbeam_channel::{Receiver, bounded};
 use log::info;
 
-use crate::utils::{ExplorerInfoMap, Status};
+use crate::utils::actor_threads::ActorCrash;
+use crate::utils::cell_telemetry::{CellRecord, DEFAULT_TELEMETRY_CAPACITY};
+use crate::utils::recipes;
+use crate::utils::registry::PlanetType;
+use crate::utils::tracing::TracedSender;
+use crate::utils::{
+    CrossbeamChannelExt, ExplorerInfoMap, GalaxyState, GalaxyUpdate, GameReport, LaunchInfo,
+    Status, StatusTable, render_ascii_map,
+};
 use crate::{
-    components::orchestrator::{Orchestrator, OrchestratorEvent},
+    components::orchestrator::{ManualCommand, Orchestrator, OrchestratorEvent},
     utils::GalaxySnapshot,
 };
 use logging_utils::LoggableActor;
@@ -30,10 +39,10 @@ impl Orchestrator {
                 continue;
             }
 
-            let parameters: Option<(u32, Sender<OrchestratorToPlanet>)> =
-                self.planet_channels.iter().find_map(|(&id, (sender, _))| {
+            let parameters: Option<(u32, TracedSender<OrchestratorToPlanet>)> =
+                self.planet_channels.iter().find_map(|(&id, handle)| {
                     if id == planet_id {
-                        Some((id, sender.clone()))
+                        Some((id, handle.to_planet().clone()))
                     } else {
                         None
                     }
@@ -53,6 +62,15 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Load a new galaxy from a topology file when requested from the GUI
+    ///
+    /// The current galaxy (if any) is reset before the new one is created,
+    /// so the old planets and explorers are killed first
+    pub fn load_galaxy_from_gui(&mut self, path: &str) -> Result<(), String> {
+        log_fn_call!(self, "load_galaxy_from_gui()", path,);
+        self.initialize_galaxy_by_file(path)
+    }
+
     /// Get a snapshot of the current galaxy topology
     ///
     /// Returns an atomic reference of the current
@@ -63,33 +81,173 @@ impl Orchestrator {
         //LOG
         log_fn_call!(self, "get_topology()");
         //LOG
-        let topology = &self.galaxy_topology;
-
-        let mut edges = Vec::new();
-        let planet_num = topology.len();
-
-        for i in 0..topology.len() {
-            for j in (i + 1)..topology[i].len() {
-                if topology[i][j] {
-                    // Translate matrix indices back to real planet_ids
-                    let planet_a = self
-                        .galaxy_reverse_lookup
-                        .get(&(i as u32))
-                        .copied()
-                        .unwrap_or(i as u32);
-                    let planet_b = self
-                        .galaxy_reverse_lookup
-                        .get(&(j as u32))
-                        .copied()
-                        .unwrap_or(j as u32);
-                    edges.push((planet_a, planet_b));
-                }
-            }
-        }
+        let edges = self.edges().collect();
+        let planet_num = self.galaxy_topology.len();
 
         (edges, planet_num)
     }
 
+    /// Iterate over the links of the galaxy topology
+    ///
+    /// Every undirected link is yielded once, as a pair of planet ids
+    /// with the planet of the lower matrix index first. The iterator
+    /// borrows the orchestrator, so the topology cannot change while
+    /// it is in use.
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let planet_id = |idx: usize| {
+            self.galaxy_reverse_lookup
+                .get(&(idx as u32))
+                .copied()
+                .unwrap_or(idx as u32)
+        };
+        self.galaxy_topology
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .skip(i + 1)
+                    .filter(|(_, linked)| **linked)
+                    .map(move |(j, _)| (planet_id(i), planet_id(j)))
+            })
+    }
+
+    /// Get the type of a planet of the galaxy
+    ///
+    /// Returns None if the planet is not part of the galaxy; dead planets
+    /// keep their type.
+    pub fn planet_type_of(&self, id: u32) -> Option<PlanetType> {
+        self.galaxy_lookup
+            .get(&id)
+            .map(|(_, planet_type)| *planet_type)
+    }
+
+    /// Render the current galaxy as plain text
+    ///
+    /// Useful when running headless or in test failure messages.
+    /// See [`render_ascii_map`](`crate::utils::render_ascii_map`) for the format
+    pub fn render_ascii_map(&self) -> String {
+        //LOG
+        log_fn_call!(self, "render_ascii_map()");
+        //LOG
+        let (edges, _) = self.get_topology();
+        render_ascii_map(&edges, &self.planets_info, &self.explorers_info)
+    }
+
+    /// Build a report of the current state of planets and explorers
+    ///
+    /// Meant to be called at the end of a headless run, see
+    /// [`GameReport::to_csv`](`crate::utils::report::GameReport::to_csv`) for a tabular output
+    /// and [`GameReport::to_markdown`](`crate::utils::report::GameReport::to_markdown`) or
+    /// [`GameReport::to_json`](`crate::utils::report::GameReport::to_json`) for a summary
+    pub fn game_report(&self) -> GameReport {
+        //LOG
+        log_fn_call!(self, "game_report()");
+        //LOG
+        GameReport::from_state(&self.planets_info, &self.explorers_info).with_topology(self.edges())
+    }
+
+    /// Start sending the changes of the energy cells of the planets
+    ///
+    /// The cells are compared every time a planet sends its internal state,
+    /// e.g. after [`query_planet_state`](Self::query_planet_state), and one
+    /// [`CellRecord`] is sent for each cell whose charge changed. The channel
+    /// holds up to [`DEFAULT_TELEMETRY_CAPACITY`] records, see
+    /// [`enable_cell_telemetry_with_capacity`](Self::enable_cell_telemetry_with_capacity)
+    pub fn enable_cell_telemetry(&mut self) -> Receiver<CellRecord> {
+        self.enable_cell_telemetry_with_capacity(DEFAULT_TELEMETRY_CAPACITY)
+    }
+
+    /// Start sending the changes of the energy cells of the planets, see
+    /// [`enable_cell_telemetry`](Self::enable_cell_telemetry)
+    ///
+    /// The channel holds up to `capacity` records: when the GUI does not keep up
+    /// the new records are dropped. Calling it again replaces the previous channel
+    pub fn enable_cell_telemetry_with_capacity(&mut self, capacity: usize) -> Receiver<CellRecord> {
+        //LOG
+        log_fn_call!(self, "enable_cell_telemetry_with_capacity()", capacity,);
+        //LOG
+        let (sender, receiver) = bounded(capacity);
+        self.cell_telemetry = Some(sender);
+        receiver
+    }
+
+    /// Get the status of every planet and explorer in one call
+    ///
+    /// Meant for the ratatui GUI, which renders the whole table at once
+    pub fn status_table(&self) -> StatusTable {
+        //LOG
+        log_fn_call!(self, "status_table()");
+        //LOG
+        StatusTable::from_state(&self.planets_info, &self.explorers_info)
+    }
+
+    /// Get a versioned snapshot of the planets, explorers and links
+    ///
+    /// The version is bumped only when the galaxy differs from the last
+    /// published snapshot, so two calls without changes in between return
+    /// the same version
+    pub fn galaxy_state(&mut self) -> GalaxyState {
+        //LOG
+        log_fn_call!(self, "galaxy_state()");
+        //LOG
+        let current = GalaxyState::from_state(
+            &self.planets_info,
+            &self.explorers_info,
+            self.snapshot_version,
+        )
+        .with_edges(self.edges());
+        if let Some(last) = &self.published_snapshots.0
+            && last.same_content(&current)
+        {
+            return last.clone();
+        }
+        self.snapshot_version += 1;
+        let current = GalaxyState {
+            version: self.snapshot_version,
+            ..current
+        };
+        self.published_snapshots = (Some(current.clone()), self.published_snapshots.0.take());
+        current
+    }
+
+    /// Get the changes of the galaxy since the version held by the GUI
+    ///
+    /// Returns a delta if `known_version` is the current or the previous
+    /// version of [`galaxy_state`](Self::galaxy_state), the full snapshot
+    /// otherwise (e.g. on the first call, with None)
+    pub fn galaxy_update(&mut self, known_version: Option<u64>) -> GalaxyUpdate {
+        //LOG
+        log_fn_call!(self, "galaxy_update()"; "known_version"=>format!("{:?}", known_version));
+        //LOG
+        let current = self.galaxy_state();
+        let known = match (known_version, &self.published_snapshots.1) {
+            (Some(v), _) if v == current.version => &current,
+            (Some(v), Some(previous)) if v == previous.version => previous,
+            _ => return GalaxyUpdate::Full(current),
+        };
+        GalaxyUpdate::Delta(GalaxyState::diff(known, &current))
+    }
+
+    /// Rank the explorers by the value of their bag
+    ///
+    /// Returns the pairs of explorer id and bag score, best first; explorers
+    /// with the same score are sorted by id. The scores come from the last
+    /// `BagContentResponse` of each explorer, see
+    /// [`bag_score`](`crate::utils::recipes::bag_score`)
+    pub fn explorer_ranking(&self) -> Vec<(u32, u64)> {
+        //LOG
+        log_fn_call!(self, "explorer_ranking()");
+        //LOG
+        let mut ranking: Vec<(u32, u64)> = self
+            .explorers_info
+            .iter()
+            .map(|(id, info)| (*id, recipes::bag_score(&info.bag)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranking
+    }
+
     // Getter functions necessary for Ratatui-gui
 
     pub fn get_planets_info(&mut self) -> PlanetInfoMap {
@@ -103,10 +261,8 @@ impl Orchestrator {
             .explorer_channels
             .get(&explorer_id)
             .ok_or_else(|| format!("Explorer {explorer_id} not found"))?;
-        let from_orch = &explorer_channel.0;
-
-        from_orch
-            .try_send(OrchestratorToExplorer::StopExplorerAI)
+        explorer_channel
+            .try_send_to_explorer(OrchestratorToExplorer::StopExplorerAI)
             .map_err(|_| format!("Cannot send message to {explorer_id}"))?;
 
         self.explorers_info
@@ -140,13 +296,13 @@ impl Orchestrator {
         //     .explorer_channels
         //     .get(&explorer_id)
         //     .ok_or_else(|| format!("Explorer {explorer_id} not found"))?;
-        // let from_orch = &explorer_channel.0;
+        // let from_orch = &explorer_channel.to_explorer;
 
         // let planet_channel = self
         //     .planet_channels
         //     .get(&destination_planet_id)
         //     .ok_or_else(|| format!("Planet {destination_planet_id} not found"))?;
-        // let sender_to_new_planet = planet_channel.1.clone();
+        // let sender_to_new_planet = planet_channel.explorer_sender_for_arrival();
 
         if !self.planets_info.is_dead(&destination_planet_id) {
             let moved_explorer_info = self.explorers_info.get_mut(&explorer_id);
@@ -160,17 +316,121 @@ impl Orchestrator {
         }
         Ok(())
     }
-    pub fn send_bag_content_request_from_ui(&self) -> Result<(), String> {
-        for explorer_id in self.explorer_channels.keys() {
-            if !self.explorers_info.is_dead(explorer_id) {
-                self.send_bag_content_request(*explorer_id)?;
+    /// Checks that the explorer can be given a manual command.
+    ///
+    /// Returns Err if the explorer is unknown or dead, if its AI is still running
+    /// (see [`send_stop_explorer_from_gui`](Self::send_stop_explorer_from_gui)) or if
+    /// it has not answered the previous command yet.
+    fn check_manual_command(&self, explorer_id: u32) -> Result<(), String> {
+        let info = self
+            .explorers_info
+            .get(&explorer_id)
+            .ok_or_else(|| format!("Explorer {explorer_id} not found"))?;
+        match info.status {
+            Status::Dead => return Err(format!("Explorer {explorer_id} is dead")),
+            Status::Running => {
+                return Err(format!(
+                    "Explorer {explorer_id} is not in manual mode, stop its AI first"
+                ));
             }
+            Status::Paused => {}
+        }
+        if let Some(pending) = self.manual_commands.get(&explorer_id) {
+            return Err(format!(
+                "Explorer {explorer_id} is still executing {:?}",
+                pending
+            ));
+        }
+        Ok(())
+    }
+
+    /// Moves an explorer in manual mode to a neighbouring planet.
+    ///
+    /// The outcome is reported with a `ManualCommandFinished` event once the
+    /// explorer arrives.
+    ///
+    /// Returns Err if the explorer cannot be commanded, or if `destination` is not a
+    /// running neighbour of the explorer's planet.
+    pub fn command_explorer_move(
+        &mut self,
+        explorer_id: u32,
+        destination: u32,
+    ) -> Result<(), String> {
+        log_fn_call!(self, "command_explorer_move()", explorer_id, destination,);
+        self.check_manual_command(explorer_id)?;
+
+        let current_planet_id = self
+            .explorers_info
+            .get_current_planet(&explorer_id)
+            .ok_or_else(|| format!("Explorer {explorer_id} has no planet"))?;
+        if !self
+            .get_neighbors_of(current_planet_id)?
+            .contains(&destination)
+            || !self.planets_info.is_running(&destination)
+        {
+            return Err(format!(
+                "Planet {destination} is not a running neighbour of planet {current_planet_id}"
+            ));
+        }
+
+        if let Some(info) = self.explorers_info.get_mut(&explorer_id) {
+            info.move_to_planet_id = destination as i32;
+        }
+        self.send_incoming_explorer_request(destination, explorer_id)?;
+        self.manual_commands
+            .insert(explorer_id, ManualCommand::Move { destination });
+        self.emit_explorer_move_started(explorer_id, destination);
+        Ok(())
+    }
+
+    /// Makes an explorer in manual mode generate a basic resource on its planet.
+    ///
+    /// The outcome is reported with a `ManualCommandFinished` event.
+    ///
+    /// Returns Err if the explorer cannot be commanded or the request cannot be sent.
+    pub fn command_explorer_generate(
+        &mut self,
+        explorer_id: u32,
+        resource: BasicResourceType,
+    ) -> Result<(), String> {
+        log_fn_call!(self, "command_explorer_generate()", explorer_id, resource,);
+        self.check_manual_command(explorer_id)?;
+        self.send_generate_resource_request(explorer_id, resource)?;
+        self.manual_commands
+            .insert(explorer_id, ManualCommand::Generate(resource));
+        Ok(())
+    }
+
+    /// Makes an explorer in manual mode combine a complex resource on its planet.
+    ///
+    /// The outcome is reported with a `ManualCommandFinished` event.
+    ///
+    /// Returns Err if the explorer cannot be commanded or the request cannot be sent.
+    pub fn command_explorer_combine(
+        &mut self,
+        explorer_id: u32,
+        resource: ComplexResourceType,
+    ) -> Result<(), String> {
+        log_fn_call!(self, "command_explorer_combine()", explorer_id, resource,);
+        self.check_manual_command(explorer_id)?;
+        self.send_combine_resource_request(explorer_id, resource)?;
+        self.manual_commands
+            .insert(explorer_id, ManualCommand::Combine(resource));
+        Ok(())
+    }
+
+    pub fn send_bag_content_request_from_ui(&self) -> Result<(), String> {
+        for (explorer_id, _) in self.living_explorers() {
+            self.send_bag_content_request(explorer_id)?;
         }
         Ok(())
     }
     pub fn get_explorer_states(&self) -> ExplorerInfoMap {
         self.explorers_info.clone()
     }
+    /// Returns a copy of the adjacency matrix of the galaxy.
+    ///
+    /// Changing the copy does not change the topology of the orchestrator.
     pub fn get_galaxy_topology(&self) -> Vec<Vec<bool>> {
         self.galaxy_topology.clone()
     }
@@ -188,22 +448,28 @@ impl Orchestrator {
             .push(OrchestratorEvent::PlanetDestroyed { planet_id });
     }
 
+    pub(crate) fn emit_galaxy_partitioned(&mut self, components: Vec<Vec<u32>>) {
+        info!("GUI event galaxy_partitioned was triggered");
+        self.gui_messages
+            .push(OrchestratorEvent::GalaxyPartitioned { components });
+    }
+
     pub(crate) fn emit_sunray_ack(&mut self, planet_id: u32) {
         info!("GUI event sunray_ack was triggered");
         self.gui_messages
             .push(OrchestratorEvent::SunrayReceived { planet_id });
     }
 
-    pub(crate) fn emit_sunray_send(&mut self, planet_id: u32) {
+    pub(crate) fn emit_sunray_send(&mut self, planet_id: u32, info: LaunchInfo) {
         info!("GUI event sunray_send was triggered");
         self.gui_messages
-            .push(OrchestratorEvent::SunraySent { planet_id });
+            .push(OrchestratorEvent::SunraySent { planet_id, info });
     }
 
-    pub(crate) fn emit_asteroid_send(&mut self, planet_id: u32) {
+    pub(crate) fn emit_asteroid_send(&mut self, planet_id: u32, info: LaunchInfo) {
         info!("GUI event asteroid_send was triggered");
         self.gui_messages
-            .push(OrchestratorEvent::AsteroidSent { planet_id });
+            .push(OrchestratorEvent::AsteroidSent { planet_id, info });
     }
 
     ///inform the GUI that an explorer move started
@@ -221,6 +487,47 @@ impl Orchestrator {
             .push(OrchestratorEvent::ResourceGenerationFailed { message: msg });
     }
 
+    /// Collects the panics of the actor threads reported since the last call
+    ///
+    /// Every crash is also sent to the GUI as an `ActorCrashed` event.
+    pub fn collect_actor_crashes(&mut self) -> Vec<ActorCrash> {
+        let crashes = self.crash_receiver.try_recv_all();
+        for crash in &crashes {
+            info!("GUI event actor_crashed was triggered");
+            self.gui_messages.push(OrchestratorEvent::ActorCrashed {
+                thread_name: crash.thread_name.clone(),
+                message: crash.message.clone(),
+            });
+        }
+        crashes
+    }
+
+    /// Reports the outcome of the manual command of an explorer
+    ///
+    /// Nothing is emitted if the explorer is not executing a command accepted
+    /// by `answers`, so the answers to the AI requests are ignored.
+    pub(crate) fn finish_manual_command(
+        &mut self,
+        explorer_id: u32,
+        answers: impl Fn(&ManualCommand) -> bool,
+        result: Result<(), String>,
+    ) {
+        let Some(command) = self.manual_commands.get(&explorer_id).copied() else {
+            return;
+        };
+        if !answers(&command) {
+            return;
+        }
+        self.manual_commands.remove(&explorer_id);
+        info!("GUI event manual_command_finished was triggered");
+        self.gui_messages
+            .push(OrchestratorEvent::ManualCommandFinished {
+                explorer_id,
+                command,
+                result,
+            });
+    }
+
     pub(crate) fn emit_explorer_move(&mut self, explorer_id: u32, planet_id: u32) {
         let move_to_id = self
             .explorers_info
@@ -1,3 +1,6 @@
+use common_game::components::planet::DummyPlanetState;
+use common_game::components::resource::{BasicResourceType, ComplexResourceType};
+use common_game::components::rocket::Rocket;
 use common_game::protocols::orchestrator_explorer::{
     ExplorerToOrchestrator, OrchestratorToExplorer,
 };
@@ -11,20 +14,66 @@ use logging_utils::{
     LOG_ACTORS_ACTIVITY, LoggableActor, debug_println, log_explorer_to_orch, log_fn_call,
     log_internal_op, log_message, log_planet_to_orch, payload, warning_payload,
 };
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use crate::{components::orchestrator::Orchestrator, utils::Status};
+use crate::components::orchestrator::routing::{
+    ExplorerMessageKind, HandlerRegistry, PlanetMessageKind, RoutedMessage,
+};
 use crate::components::tommy_explorer::bag::BagType;
+use crate::utils::CrossbeamChannelExt;
+use crate::utils::recipes;
+use crate::{
+    components::orchestrator::{ManualCommand, Orchestrator},
+    utils::Status,
+};
+
+/// Registers the handlers of some message variants, each handler gets the fields
+/// of its message instead of the whole message.
+///
+/// The registry passes to a handler only the messages of its kind, and the kind and
+/// the variant share their name, so the fallback arm is never taken.
+macro_rules! route {
+    ($handlers:ident, $msg:ident, $kind:ident {
+        $($variant:ident { $($field:ident),* } => $handler:path),* $(,)?
+    }) => {
+        $(
+            $handlers.register($kind::$variant, |orch: &mut Orchestrator, msg| match msg {
+                $msg::$variant { $($field),* } => $handler(orch, $($field),*),
+                other => unreachable!(
+                    "{:?} routed to the {} handler",
+                    other.message_kind(),
+                    stringify!($variant)
+                ),
+            });
+        )*
+    };
+}
 
 pub const TIMEOUT_DURATION: Duration = Duration::from_millis(10);
 
+/// What the game loop should do after [`handle_game_messages`](Orchestrator::handle_game_messages).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLoopStatus {
+    /// The messages were handled, the game can go on.
+    Running,
+    /// A channel towards the orchestrator has no sender left, so no message will
+    /// ever arrive on it again: the game loop should shut the game down.
+    ChannelClosed,
+}
+/// Time a killed explorer is kept before [`remove_explorer`](Orchestrator::remove_explorer)
+/// is called on it, unless changed with `explorer_removal_delay`.
+pub const DEFAULT_EXPLORER_REMOVAL_DELAY: Duration = Duration::from_secs(5);
+
 impl Orchestrator {
     /// Handle the planet messages that are sent through the orchestrator's
     /// communication channels.
     ///
     /// This function serves as an entry point to all the messages that originate
     /// from the planets that need the orchestrator's intervention; no logic is
-    /// actually present.
+    /// actually present, the message is passed to the handler of its kind in
+    /// `planet_handlers`.
     ///
     /// * `msg` - the message to pass along to other functions
     pub(crate) fn handle_planet_message(
@@ -40,610 +89,832 @@ impl Orchestrator {
         log_planet_to_orch!(format!("{:?} received", msg), msg.planet_id());
         //LOG
 
-        match msg {
-            PlanetToOrchestrator::SunrayAck { planet_id } => {
-                debug_println!("SunrayAck from: {}", planet_id);
+        let handlers = Arc::clone(&self.planet_handlers);
+        handlers.dispatch(self, msg)
+    }
 
-                self.emit_sunray_ack(planet_id);
+    pub fn handle_explorer_message(
+        &mut self,
+        msg: ExplorerToOrchestrator<BagType>,
+    ) -> Result<(), String> {
+        log_internal_op!(self, "explorer message received");
+        log_explorer_to_orch!(format!("{:?} received", msg), msg.explorer_id());
+
+        // Guard: skip messages from dead explorers (stale messages that were
+        // queued before the explorer was killed). We still allow
+        // KillExplorerResult through so the status update is recorded.
+        let explorer_id_for_guard = msg.explorer_id();
+        if !matches!(msg, ExplorerToOrchestrator::KillExplorerResult { .. }) {
+            if self.explorers_info.is_dead(&explorer_id_for_guard) {
+                log_internal_op!(self, "action" => format!(
+                    "Ignoring message from dead explorer {}, skipping",
+                    explorer_id_for_guard
+                ));
+                return Ok(());
             }
-            PlanetToOrchestrator::AsteroidAck { planet_id, rocket } => {
-                debug_println!("AsteroidAck from: {}", planet_id);
-
-                if let None = rocket {
-                    // Skip if the planet is already dead (e.g. a previous AsteroidAck
-                    // already triggered its kill before this one was processed)
-                    if self.planets_info.get_status(&planet_id) == Status::Dead {
-                        log_internal_op!(self, "action" => format!(
-                            "AsteroidAck for already-dead planet {}, skipping",
-                            planet_id
-                        ));
-                        return Ok(());
-                    }
+        }
 
-                    //If you have the id then surely that planet exists so we can unwrap without worrying
-                    let sender = &self.planet_channels.get(&planet_id).ok_or_else(
-                        || format!{"No channels found in the orchestrator for planet:{}", planet_id}
-                    )?.0;
+        let handlers = Arc::clone(&self.explorer_handlers);
+        handlers.dispatch(self, msg)
+    }
 
-                    //Send KillPlanet message, if it returns Err then the planet it's already killed
-                    sender.send(OrchestratorToPlanet::KillPlanet).map_err(|_| {
-                        format!("Unable to send KillPlanet to planet: {}", planet_id)
-                    })?;
+    fn on_sunray_ack(&mut self, planet_id: ID) -> Result<(), String> {
+        debug_println!("SunrayAck from: {}", planet_id);
 
-                    //LOG
-                    log_message!(
-                        ActorType::Orchestrator, 0u32,
-                        ActorType::Planet, planet_id,
-                        EventType::MessageOrchestratorToPlanet,
-                        "KillPlanet sent",
-                        planet_id;
-                        "reason"=>"no rocket to deflect asteroid"
-                    );
-                    //LOG
+        self.emit_sunray_ack(planet_id);
+        Ok(())
+    }
 
-                    self.destroy_topology_link(planet_id as usize)?;
+    fn on_asteroid_ack(&mut self, planet_id: ID, rocket: Option<Rocket>) -> Result<(), String> {
+        debug_println!("AsteroidAck from: {}", planet_id);
 
-                    //Update planet State
-                    match self.planets_info.update_status(planet_id, Status::Dead) {
-                        Ok(_) => {}
-                        Err(err) => {
-                            log_internal_op!(self, "action" => format!("planet status not updated: {}", err));
-                            return Err(err.to_string());
-                        }
-                    }
-                    //LOG
-                    log_internal_op!(
-                        self,
-                        "action"=>"planet status updated to Dead",
-                        "planet_id"=>planet_id,
-                        "planet status"=> format!("{:?}",self.planets_info.get_status(&planet_id))
-                    );
-                    //LOG
-                    //sending explorer kill
-                    self.send_kill_to_explorers_on_dying_planet(&planet_id)?;
-                }
-            }
-            PlanetToOrchestrator::InternalStateResponse {
-                planet_id,
-                planet_state,
-            } => {
-                self.planets_info
-                    .update_from_planet_state(planet_id, planet_state);
-            }
-            PlanetToOrchestrator::KillPlanetResult { planet_id } => {
-                // Guard: if the planet is already dead (e.g. killed via AsteroidAck
-                // before this KillPlanetResult arrived), skip redundant processing
-                if self.planets_info.get_status(&planet_id) == Status::Dead {
-                    log_internal_op!(self, "action" => format!(
-                        "KillPlanetResult for already-dead planet {}, skipping",
-                        planet_id
-                    ));
-                    return Ok(());
-                }
-                self.destroy_topology_link(planet_id as usize)?;
-                self.planets_info.update_status(planet_id, Status::Dead)?;
-                self.emit_planet_death(planet_id);
+        // a planet that deflected the asteroid had a rocket; the InternalStateRequest
+        // sent along with the asteroid will tell whether it has built a new one
+        self.planets_info.update_rocket(planet_id, rocket.is_some());
 
-                //LOG
-                debug_println!("Planet killed: {}", planet_id);
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Planet, planet_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessagePlanetToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "Message"=>"Planet killed",
-                        "planet_id"=>planet_id,
-                    ),
-                )
-                .emit();
-                //killing explorer just in case the KillPlanet message is manually sended
-                self.send_kill_to_explorers_on_dying_planet(&planet_id)?;
-                //LOG
-            }
-            // PlanetToOrchestrator::OutgoingExplorerResponse { planet_id, res }=>{},
-            PlanetToOrchestrator::StartPlanetAIResult { planet_id } => {
-                if self.planets_info.is_dead(&planet_id) {
-                    log_internal_op!(self, "action" => format!(
-                        "planet: {} is already dead, StartPlanetAIResult is ineffective",
-                        planet_id
-                    ));
-                    return Ok(());
-                }
-                self.planets_info
-                    .update_status(planet_id, Status::Running)?;
-                //LOG
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Planet, planet_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessagePlanetToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "message"=>"Planet AI started",
-                        "planet_id"=>planet_id
-                    ),
-                )
-                .emit();
-                //LOG
-            }
-            PlanetToOrchestrator::StopPlanetAIResult { planet_id } => {
-                if self.planets_info.is_dead(&planet_id) {
-                    log_internal_op!(self, "action" => format!(
-                        "planet: {} is already dead, StopPlanetAIResult is ineffective",
-                        planet_id
-                    ));
-                    return Ok(());
-                }
-                //LOG
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Planet, planet_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessagePlanetToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "message"=>"Planet AI stopped",
-                        "planet_id"=>planet_id
-                    ),
-                )
-                .emit();
-                //LOG
-                self.planets_info.update_status(planet_id, Status::Paused)?;
+        if let None = rocket {
+            // Skip if the planet is already dead (e.g. a previous AsteroidAck
+            // already triggered its kill before this one was processed)
+            if self.planets_info.get_status(&planet_id) == Status::Dead {
+                log_internal_op!(self, "action" => format!(
+                    "AsteroidAck for already-dead planet {}, skipping",
+                    planet_id
+                ));
+                return Ok(());
             }
-            PlanetToOrchestrator::Stopped { planet_id: _ } => {}
-            PlanetToOrchestrator::IncomingExplorerResponse {
-                planet_id,
-                explorer_id,
-                res,
-            } => {
-                if let Ok(_) = res {
-                    // Guard: if the explorer is already dead, skip processing
-                    if self.explorers_info.is_dead(&explorer_id) {
-                        log_internal_op!(self, "action" => format!(
-                            "IncomingExplorerResponse for dead explorer {}, skipping",
-                            explorer_id
-                        ));
-                        return Ok(());
-                    }
 
-                    let current_planet_id = self
-                        .explorers_info
-                        .get_current_planet(&explorer_id)
-                        .ok_or("could not get explorer planet".to_string())?;
-
-                    //this is safe because we already checked it before
-                    let move_to_planet_id = self
-                        .explorers_info
-                        .get(&explorer_id)
-                        .unwrap()
-                        .move_to_planet_id;
-
-                    // If move_to_planet_id is -1, no travel was requested for this
-                    // explorer (e.g. this is just the initial arrival confirmation).
-                    // Skip the destination planet check entirely.
-                    if move_to_planet_id >= 0
-                        && !self.planets_info.is_running(&(move_to_planet_id as u32))
-                    {
-                        log_internal_op!(self, "action" => format!(
-                            "IncomingExplorerResponse: destination planet {} is dead, skipping",
-                            move_to_planet_id
-                        ));
-                        let sender = &self
-                            .explorer_channels
-                            .get(&explorer_id)
-                            .ok_or("could not get explorer sender".to_string())?
-                            .0;
-                        sender
-                            .send(OrchestratorToExplorer::MoveToPlanet {
-                                sender_to_new_planet: None,
-                                planet_id: move_to_planet_id as ID,
-                            })
-                            .map_err(|err| format!("could not send MoveToPlanet: {:?}", err))?;
-                        return Ok(());
-                    }
+            //If you have the id then surely that planet exists so we can unwrap without worrying
+            let handle = self.planet_channels.get(&planet_id).ok_or_else(
+                || format! {"No channels found in the orchestrator for planet:{}", planet_id},
+            )?;
 
-                    // Guard: if the current planet is dead, we cannot send OutgoingExplorerRequest
-                    if self.planets_info.is_dead(&current_planet_id) {
-                        //not need to send a response to the explorer because it will be killed in moments
-                        log_internal_op!(self, "action" => format!(
-                            "IncomingExplorerResponse: current planet {} is dead, skipping",
-                            current_planet_id
-                        ));
-                        return Ok(());
-                    }
+            //Send KillPlanet message, if it returns Err then the planet it's already killed
+            handle
+                .send_to_planet(OrchestratorToPlanet::KillPlanet)
+                .map_err(|_| format!("Unable to send KillPlanet to planet: {}", planet_id))?;
 
-                    let orch_current_planet_sender =
-                        match self.planet_channels.get(&current_planet_id) {
-                            Some(sender) => sender,
-                            None => {
-                                return Err(format!("Planet not found: {}", planet_id));
-                            }
-                        };
-
-                    if move_to_planet_id >= 0 && (move_to_planet_id as u32) != current_planet_id {
-                        match orch_current_planet_sender
-                            .0
-                            .send(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id })
-                        {
-                            Ok(_) => {
-                                log_message!(
-                                    ActorType::Orchestrator,
-                                    0u32,
-                                    ActorType::Planet,
-                                    current_planet_id,
-                                    EventType::MessageOrchestratorToPlanet,
-                                    "OutgoingExplorerRequest sended"
-                                );
-                            }
-                            Err(err) => {
-                                return Err(format!(
-                                    "Failed to send OutgoingExplorerRequest in handle_planet_msg(). Error: {}. Context: PlanetToOrchestrator::IncomingExplorerResponse {{ {}, {}, {:?} }}",
-                                    err, planet_id, explorer_id, res
-                                ));
-                            }
-                        }
-                    }
+            //LOG
+            log_message!(
+                ActorType::Orchestrator, 0u32,
+                ActorType::Planet, planet_id,
+                EventType::MessageOrchestratorToPlanet,
+                "KillPlanet sent",
+                planet_id;
+                "reason"=>"no rocket to deflect asteroid"
+            );
+            //LOG
 
-                    return Ok(());
-                }
-            }
-            PlanetToOrchestrator::OutgoingExplorerResponse {
-                planet_id,
-                explorer_id,
-                res,
-            } => {
-                if let Ok(_) = res {
-                    // Guard: if the explorer is already dead, skip sending
-                    let dst_planet_id = self
-                        .explorers_info
-                        .get(&explorer_id)
-                        .ok_or("could not get explorer info".to_string())?
-                        .move_to_planet_id;
-                    let explorer_alive = !self.explorers_info.is_dead(&explorer_id);
-                    let dst_planet_alive = !self.planets_info.is_dead(&(dst_planet_id as u32));
-                    let current_planet_alive = !self.planets_info.is_dead(&planet_id);
-                    //current planet dead so the explorer will be killed
-                    if !explorer_alive || !current_planet_alive {
-                        log_internal_op!(self, "action" => format!(
-                            "OutgoingExplorerResponse for dead planet {}/explorer {}, skipping",
-                            planet_id, explorer_id
-                        ));
-                        //this unwrap should not panic
-                        let sender_dst_planet =
-                            &self.planet_channels.get(&(dst_planet_id as u32)).unwrap().0;
-                        sender_dst_planet.send(OrchestratorToPlanet::OutgoingExplorerRequest {explorer_id}).map_err(
-                            |err| format!("could not send OutgoingExplroerRequest to planet: {}. Err: {:?}", dst_planet_id, err)
-                        )?;
-                        return Ok(());
-                    }
-                    //destination planet killed, trying to recover
-                    if !dst_planet_alive {
-                        self.explorers_info
-                            .get_mut(&explorer_id)
-                            .unwrap()
-                            .move_to_planet_id = planet_id as i32; //updating dst to current planet
-                        self.send_incoming_explorer_request(planet_id, explorer_id)?;
-                        log_internal_op!(self, "action" => format!(
-                            "OutgoingExplorerResponse with destination planet killed {}, trying to recover",
-                            dst_planet_id
-                        ));
-                        return Ok(());
-                    }
+            self.destroy_topology_link(planet_id as usize)?;
 
-                    let dst_planet_id = match self.explorers_info.get(&explorer_id) {
-                        Some(explorer_info) => explorer_info.move_to_planet_id,
-                        None => {
-                            return Err(format!("Explorer not found: {}", explorer_id));
-                        }
-                    };
-                    if let Err(err) = self.send_move_to_planet(explorer_id, dst_planet_id as u32) {
-                        return Err(format!("Failed to send explorer request: {}", err));
-                    }
+            //Update planet State
+            match self.planets_info.update_status(planet_id, Status::Dead) {
+                Ok(_) => {}
+                Err(err) => {
+                    log_internal_op!(self, "action" => format!("planet status not updated: {}", err));
+                    return Err(err.to_string());
                 }
             }
+            //LOG
+            log_internal_op!(
+                self,
+                "action"=>"planet status updated to Dead",
+                "planet_id"=>planet_id,
+                "planet status"=> format!("{:?}",self.planets_info.get_status(&planet_id))
+            );
+            //LOG
+            //sending explorer kill
+            self.send_kill_to_explorers_on_dying_planet(&planet_id)?;
         }
         Ok(())
     }
 
-    pub fn handle_explorer_message(
+    fn on_internal_state_response(
         &mut self,
-        msg: ExplorerToOrchestrator<BagType>,
+        planet_id: ID,
+        planet_state: DummyPlanetState,
     ) -> Result<(), String> {
-        log_internal_op!(self, "explorer message received");
-        log_explorer_to_orch!(format!("{:?} received", msg), msg.explorer_id());
+        self.update_planet_state(planet_id, &planet_state);
+        Ok(())
+    }
 
-        // Guard: skip messages from dead explorers (stale messages that were
-        // queued before the explorer was killed). We still allow
-        // KillExplorerResult through so the status update is recorded.
-        let explorer_id_for_guard = msg.explorer_id();
-        if !matches!(msg, ExplorerToOrchestrator::KillExplorerResult { .. }) {
-            if self.explorers_info.is_dead(&explorer_id_for_guard) {
+    fn on_kill_planet_result(&mut self, planet_id: ID) -> Result<(), String> {
+        // Guard: if the planet is already dead (e.g. killed via AsteroidAck
+        // before this KillPlanetResult arrived), skip redundant processing
+        if self.planets_info.get_status(&planet_id) == Status::Dead {
+            log_internal_op!(self, "action" => format!(
+                "KillPlanetResult for already-dead planet {}, skipping",
+                planet_id
+            ));
+            return Ok(());
+        }
+        self.destroy_topology_link(planet_id as usize)?;
+        self.planets_info.update_status(planet_id, Status::Dead)?;
+        self.emit_planet_death(planet_id);
+
+        //LOG
+        debug_println!("Planet killed: {}", planet_id);
+        LogEvent::new(
+            Some(Participant::new(ActorType::Planet, planet_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessagePlanetToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "Message"=>"Planet killed",
+                "planet_id"=>planet_id,
+            ),
+        )
+        .emit();
+        //killing explorer just in case the KillPlanet message is manually sended
+        self.send_kill_to_explorers_on_dying_planet(&planet_id)?;
+        //LOG
+        Ok(())
+    }
+
+    fn on_start_planet_ai_result(&mut self, planet_id: ID) -> Result<(), String> {
+        if self.planets_info.is_dead(&planet_id) {
+            log_internal_op!(self, "action" => format!(
+                "planet: {} is already dead, StartPlanetAIResult is ineffective",
+                planet_id
+            ));
+            return Ok(());
+        }
+        self.planets_info
+            .update_status(planet_id, Status::Running)?;
+        //LOG
+        LogEvent::new(
+            Some(Participant::new(ActorType::Planet, planet_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessagePlanetToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "message"=>"Planet AI started",
+                "planet_id"=>planet_id
+            ),
+        )
+        .emit();
+        //LOG
+        Ok(())
+    }
+
+    fn on_stop_planet_ai_result(&mut self, planet_id: ID) -> Result<(), String> {
+        if self.planets_info.is_dead(&planet_id) {
+            log_internal_op!(self, "action" => format!(
+                "planet: {} is already dead, StopPlanetAIResult is ineffective",
+                planet_id
+            ));
+            return Ok(());
+        }
+        //LOG
+        LogEvent::new(
+            Some(Participant::new(ActorType::Planet, planet_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessagePlanetToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "message"=>"Planet AI stopped",
+                "planet_id"=>planet_id
+            ),
+        )
+        .emit();
+        //LOG
+        self.planets_info.update_status(planet_id, Status::Paused)?;
+        Ok(())
+    }
+
+    fn on_incoming_explorer_response(
+        &mut self,
+        planet_id: ID,
+        explorer_id: ID,
+        res: Result<(), String>,
+    ) -> Result<(), String> {
+        if let Ok(_) = res {
+            // Guard: if the explorer is already dead, skip processing
+            if self.explorers_info.is_dead(&explorer_id) {
                 log_internal_op!(self, "action" => format!(
-                    "Ignoring message from dead explorer {}, skipping",
-                    explorer_id_for_guard
+                    "IncomingExplorerResponse for dead explorer {}, skipping",
+                    explorer_id
                 ));
                 return Ok(());
             }
-        }
 
-        match msg {
-            ExplorerToOrchestrator::StartExplorerAIResult { explorer_id } => {
-                //LOG
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Explorer, explorer_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessageExplorerToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "message"=> "Explorer AI started",
-                        "explorer_id"=>explorer_id,
-                    ),
-                )
-                .emit();
-                //LOG
+            let current_planet_id = self
+                .explorers_info
+                .get_current_planet(&explorer_id)
+                .ok_or("could not get explorer planet".to_string())?;
 
-                self.explorers_info
-                    .insert_status(explorer_id, Status::Running);
-                if self.explorers_info.get(&explorer_id).is_none() {
-                    self.send_current_planet_request(explorer_id)?;
-                }
+            //this is safe because we already checked it before
+            let move_to_planet_id = self
+                .explorers_info
+                .get(&explorer_id)
+                .unwrap()
+                .move_to_planet_id;
 
-                //LOG
-                log_internal_op!(
-                    self,
-                    "action" => "explorer status updated to Active",
-                    "explorer_id" => explorer_id
+            // If move_to_planet_id is -1, no travel was requested for this
+            // explorer (e.g. this is just the initial arrival confirmation).
+            // Skip the destination planet check entirely.
+            if move_to_planet_id >= 0 && !self.planets_info.is_running(&(move_to_planet_id as u32))
+            {
+                log_internal_op!(self, "action" => format!(
+                    "IncomingExplorerResponse: destination planet {} is dead, skipping",
+                    move_to_planet_id
+                ));
+                self.explorer_channels
+                    .get(&explorer_id)
+                    .ok_or("could not get explorer sender".to_string())?
+                    .send_to_explorer(OrchestratorToExplorer::MoveToPlanet {
+                        sender_to_new_planet: None,
+                        planet_id: move_to_planet_id as ID,
+                    })
+                    .map_err(|err| format!("could not send MoveToPlanet: {}", err))?;
+                self.finish_manual_command(
+                    explorer_id,
+                    |command| matches!(command, ManualCommand::Move { .. }),
+                    Err(format!("Planet {} is dead", move_to_planet_id)),
                 );
-                //LOG
+                return Ok(());
             }
-            ExplorerToOrchestrator::KillExplorerResult { explorer_id } => {
-                debug_println!("Explorer killed: {}", explorer_id);
-
-                self.explorers_info.insert_status(explorer_id, Status::Dead);
 
-                //LOG
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Explorer, explorer_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessageExplorerToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "message"=> "Explorer killed",
-                        "explorer_id"=>explorer_id,
-                    ),
-                )
-                .emit();
-                log_internal_op!(
-                    self,
-                    "action" => "explorer status updated to Dead",
-                    "explorer_id" => explorer_id
-                );
-                //LOG
+            // Guard: if the current planet is dead, we cannot send OutgoingExplorerRequest
+            if self.planets_info.is_dead(&current_planet_id) {
+                //not need to send a response to the explorer because it will be killed in moments
+                log_internal_op!(self, "action" => format!(
+                    "IncomingExplorerResponse: current planet {} is dead, skipping",
+                    current_planet_id
+                ));
+                return Ok(());
             }
-            ExplorerToOrchestrator::ResetExplorerAIResult { explorer_id } => {
-                //LOG
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Explorer, explorer_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessageExplorerToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "message"=> "Explorer AI reset",
-                        "explorer_id"=>explorer_id,
-                    ),
-                )
-                .emit();
-                //LOG
-                //the AI is started if it was in manual mode
-                self.explorers_info
-                    .insert_status(explorer_id, Status::Running);
-                if self.explorers_info.get(&explorer_id).is_none() {
-                    self.send_current_planet_request(explorer_id)?; 
-                }
 
-                //LOG
-                log_internal_op!(
-                    self,
-                    "action" => "explorer AI reset completed",
-                    "explorer_id" => explorer_id
-                );
-                //LOG
-            }
-            ExplorerToOrchestrator::StopExplorerAIResult { explorer_id } => {
-                //LOG
-                LogEvent::new(
-                    Some(Participant::new(ActorType::Explorer, explorer_id)),
-                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                    EventType::MessageExplorerToOrchestrator,
-                    LOG_ACTORS_ACTIVITY,
-                    payload!(
-                        "message"=> "Explorer AI stopped ",
-                        "explorer_id"=>explorer_id,
-                    ),
-                )
-                .emit();
-                //LOG
-                self.explorers_info
-                    .insert_status(explorer_id, Status::Paused);
-                if self.explorers_info.get(&explorer_id).is_none() {
-                    self.send_current_planet_request(explorer_id)?;
+            let orch_current_planet_sender = match self.planet_channels.get(&current_planet_id) {
+                Some(sender) => sender,
+                None => {
+                    return Err(format!("Planet not found: {}", planet_id));
                 }
+            };
 
-                //LOG
-                log_internal_op!(
-                    self,
-                    "action" => "explorer status updated to Paused",
-                    "explorer_id" => explorer_id
-                );
-                //LOG
+            if move_to_planet_id >= 0 && (move_to_planet_id as u32) != current_planet_id {
+                match orch_current_planet_sender
+                    .send_to_planet(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id })
+                {
+                    Ok(_) => {
+                        log_message!(
+                            ActorType::Orchestrator,
+                            0u32,
+                            ActorType::Planet,
+                            current_planet_id,
+                            EventType::MessageOrchestratorToPlanet,
+                            "OutgoingExplorerRequest sended"
+                        );
+                    }
+                    Err(err) => {
+                        return Err(format!(
+                            "Failed to send OutgoingExplorerRequest in handle_planet_msg(). Error: {}. Context: PlanetToOrchestrator::IncomingExplorerResponse {{ {}, {}, {:?} }}",
+                            err, planet_id, explorer_id, res
+                        ));
+                    }
+                }
             }
-            ExplorerToOrchestrator::MovedToPlanetResult {
-                explorer_id,
-                planet_id,
-            } => {
-                debug_println!("Explorer {} moved to planet {}", explorer_id, planet_id);
 
-                self.explorers_info
-                    .update_current_planet(explorer_id, planet_id);
+            return Ok(());
+        }
+        Ok(())
+    }
 
-                self.emit_explorer_move(explorer_id, planet_id);
-            }
-            ExplorerToOrchestrator::CurrentPlanetResult {
-                explorer_id,
-                planet_id,
-            } => {
-                self.explorers_info
-                    .update_current_planet(explorer_id, planet_id);
-            }
-            ExplorerToOrchestrator::SupportedResourceResult {
-                explorer_id,
-                supported_resources,
-            } => {
-                //dobbiamo aggiornare le info dei pianeti salvarcele una volta per poterle riusare a piacimento
-                let planet_id = self
-                    .explorers_info
-                    .get_current_planet(&explorer_id)
-                    .ok_or("could not get explorer planet".to_string())?;
-                self.planets_info
-                    .update_supported_resources(planet_id, supported_resources)?;
+    fn on_outgoing_explorer_response(
+        &mut self,
+        planet_id: ID,
+        explorer_id: ID,
+        res: Result<(), String>,
+    ) -> Result<(), String> {
+        if let Ok(_) = res {
+            let dst_planet_id = match self.explorers_info.get(&explorer_id) {
+                Some(explorer_info) => explorer_info.move_to_planet_id,
+                None => {
+                    // departure of an explorer already removed
+                    log_internal_op!(self, "action" => format!(
+                        "OutgoingExplorerResponse: explorer {} left planet {}",
+                        explorer_id, planet_id
+                    ));
+                    return Ok(());
+                }
+            };
+            let explorer_alive = !self.explorers_info.is_dead(&explorer_id);
+            // departure of a dead explorer that was not moving, see notify_explorer_departure
+            if !explorer_alive && (dst_planet_id < 0 || dst_planet_id as u32 == planet_id) {
+                log_internal_op!(self, "action" => format!(
+                    "OutgoingExplorerResponse: dead explorer {} left planet {}",
+                    explorer_id, planet_id
+                ));
+                return Ok(());
             }
-            ExplorerToOrchestrator::SupportedCombinationResult {
-                explorer_id,
-                combination_list,
-            } => {
-                //dobbiamo aggiornare le info dei pianeti salvarcele una volta per poterle riusare a piacimento
-                let planet_id = self
-                    .explorers_info
-                    .get_current_planet(&explorer_id)
-                    .ok_or("could not get explorer planet".to_string())?;
-                self.planets_info
-                    .update_supported_combination(planet_id, combination_list)?;
+            let dst_planet_alive = !self.planets_info.is_dead(&(dst_planet_id as u32));
+            let current_planet_alive = !self.planets_info.is_dead(&planet_id);
+            //current planet dead so the explorer will be killed
+            if !explorer_alive || !current_planet_alive {
+                log_internal_op!(self, "action" => format!(
+                    "OutgoingExplorerResponse for dead planet {}/explorer {}, skipping",
+                    planet_id, explorer_id
+                ));
+                //this unwrap should not panic
+                let dst_planet = self.planet_channels.get(&(dst_planet_id as u32)).unwrap();
+                dst_planet
+                    .send_to_planet(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id })
+                    .map_err(|err| {
+                        format!(
+                            "could not send OutgoingExplroerRequest to planet: {}. Err: {}",
+                            dst_planet_id, err
+                        )
+                    })?;
+                return Ok(());
             }
-            ExplorerToOrchestrator::GenerateResourceResponse {
-                explorer_id,
-                generated,
-            } => {
-                if generated.is_ok() {
-                    self.send_bag_content_request(explorer_id)?;
-                } else {
-                    // let the gui know of the error
-                    self.emit_failed_resource_generation(
-                        "Failed to generate basic resource".to_string(),
-                    );
-                }
+            //destination planet killed, trying to recover
+            if !dst_planet_alive {
+                self.explorers_info
+                    .get_mut(&explorer_id)
+                    .unwrap()
+                    .move_to_planet_id = planet_id as i32; //updating dst to current planet
+                self.send_incoming_explorer_request(planet_id, explorer_id)?;
+                log_internal_op!(self, "action" => format!(
+                    "OutgoingExplorerResponse with destination planet killed {}, trying to recover",
+                    dst_planet_id
+                ));
+                return Ok(());
             }
-            ExplorerToOrchestrator::CombineResourceResponse {
-                explorer_id,
-                generated,
-            } => {
-                if generated.is_ok() {
-                    self.send_bag_content_request(explorer_id)?;
-                } else {
-                    // let the GUI know of the error
-                    self.emit_failed_resource_generation(
-                        "Failed to combine into complex resource".to_string(),
-                    );
+
+            let dst_planet_id = match self.explorers_info.get(&explorer_id) {
+                Some(explorer_info) => explorer_info.move_to_planet_id,
+                None => {
+                    return Err(format!("Explorer not found: {}", explorer_id));
                 }
+            };
+            if let Err(err) = self.send_move_to_planet(explorer_id, dst_planet_id as u32) {
+                return Err(format!("Failed to send explorer request: {}", err));
             }
-            ExplorerToOrchestrator::BagContentResponse {
-                explorer_id,
-                bag_content,
-            } => {
-                self.explorers_info.update_bag(explorer_id, bag_content);
-            }
-            ExplorerToOrchestrator::NeighborsRequest {
-                explorer_id,
-                current_planet_id,
-            } => {
-                self.send_neighbours_response(explorer_id, current_planet_id)?;
+        }
+        Ok(())
+    }
+
+    fn on_start_explorer_ai_result(&mut self, explorer_id: ID) -> Result<(), String> {
+        //LOG
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, explorer_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessageExplorerToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "message"=> "Explorer AI started",
+                "explorer_id"=>explorer_id,
+            ),
+        )
+        .emit();
+        //LOG
+
+        self.explorers_info
+            .insert_status(explorer_id, Status::Running);
+        if self.explorers_info.get(&explorer_id).is_none() {
+            self.send_current_planet_request(explorer_id)?;
+        }
+
+        //LOG
+        log_internal_op!(
+            self,
+            "action" => "explorer status updated to Active",
+            "explorer_id" => explorer_id
+        );
+        //LOG
+        Ok(())
+    }
+
+    fn on_kill_explorer_result(&mut self, explorer_id: ID) -> Result<(), String> {
+        debug_println!("Explorer killed: {}", explorer_id);
+
+        self.explorers_info.insert_status(explorer_id, Status::Dead);
+
+        //LOG
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, explorer_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessageExplorerToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "message"=> "Explorer killed",
+                "explorer_id"=>explorer_id,
+            ),
+        )
+        .emit();
+        log_internal_op!(
+            self,
+            "action" => "explorer status updated to Dead",
+            "explorer_id" => explorer_id
+        );
+        //LOG
+        self.notify_explorer_departure(explorer_id);
+        if let Some(delay) = self.explorer_removal_delay {
+            self.pending_explorer_removals
+                .push((explorer_id, Instant::now() + delay));
+        }
+        Ok(())
+    }
+
+    fn on_reset_explorer_ai_result(&mut self, explorer_id: ID) -> Result<(), String> {
+        //LOG
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, explorer_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessageExplorerToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "message"=> "Explorer AI reset",
+                "explorer_id"=>explorer_id,
+            ),
+        )
+        .emit();
+        //LOG
+        //the AI is started if it was in manual mode
+        self.explorers_info
+            .insert_status(explorer_id, Status::Running);
+        self.manual_commands.remove(&explorer_id);
+        if self.explorers_info.get(&explorer_id).is_none() {
+            self.send_current_planet_request(explorer_id)?;
+        }
+
+        //LOG
+        log_internal_op!(
+            self,
+            "action" => "explorer AI reset completed",
+            "explorer_id" => explorer_id
+        );
+        //LOG
+        Ok(())
+    }
+
+    fn on_stop_explorer_ai_result(&mut self, explorer_id: ID) -> Result<(), String> {
+        //LOG
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, explorer_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessageExplorerToOrchestrator,
+            LOG_ACTORS_ACTIVITY,
+            payload!(
+                "message"=> "Explorer AI stopped ",
+                "explorer_id"=>explorer_id,
+            ),
+        )
+        .emit();
+        //LOG
+        self.explorers_info
+            .insert_status(explorer_id, Status::Paused);
+        if self.explorers_info.get(&explorer_id).is_none() {
+            self.send_current_planet_request(explorer_id)?;
+        }
+
+        //LOG
+        log_internal_op!(
+            self,
+            "action" => "explorer status updated to Paused",
+            "explorer_id" => explorer_id
+        );
+        //LOG
+        Ok(())
+    }
+
+    fn on_moved_to_planet_result(&mut self, explorer_id: ID, planet_id: ID) -> Result<(), String> {
+        debug_println!("Explorer {} moved to planet {}", explorer_id, planet_id);
+
+        self.explorers_info
+            .update_current_planet(explorer_id, planet_id);
+
+        self.emit_explorer_move(explorer_id, planet_id);
+        // the explorer is sent back to its planet if the destination dies
+        let result = match self.manual_commands.get(&explorer_id) {
+            Some(ManualCommand::Move { destination }) if *destination != planet_id => {
+                Err(format!("Explorer stayed on planet {}", planet_id))
             }
+            _ => Ok(()),
+        };
+        self.finish_manual_command(
+            explorer_id,
+            |command| matches!(command, ManualCommand::Move { .. }),
+            result,
+        );
+        Ok(())
+    }
+
+    fn on_current_planet_result(&mut self, explorer_id: ID, planet_id: ID) -> Result<(), String> {
+        self.explorers_info
+            .update_current_planet(explorer_id, planet_id);
+        Ok(())
+    }
+
+    fn on_supported_resource_result(
+        &mut self,
+        explorer_id: ID,
+        supported_resources: HashSet<BasicResourceType>,
+    ) -> Result<(), String> {
+        //dobbiamo aggiornare le info dei pianeti salvarcele una volta per poterle riusare a piacimento
+        let planet_id = self
+            .explorers_info
+            .get_current_planet(&explorer_id)
+            .ok_or("could not get explorer planet".to_string())?;
+        self.planets_info
+            .update_supported_resources(planet_id, supported_resources)?;
+        Ok(())
+    }
+
+    fn on_supported_combination_result(
+        &mut self,
+        explorer_id: ID,
+        combination_list: HashSet<ComplexResourceType>,
+    ) -> Result<(), String> {
+        //dobbiamo aggiornare le info dei pianeti salvarcele una volta per poterle riusare a piacimento
+        let planet_id = self
+            .explorers_info
+            .get_current_planet(&explorer_id)
+            .ok_or("could not get explorer planet".to_string())?;
+        self.planets_info
+            .update_supported_combination(planet_id, combination_list)?;
+        Ok(())
+    }
+
+    fn on_generate_resource_response(
+        &mut self,
+        explorer_id: ID,
+        generated: Result<(), String>,
+    ) -> Result<(), String> {
+        let generate = |command: &ManualCommand| matches!(command, ManualCommand::Generate(_));
+        if generated.is_ok() {
+            self.finish_manual_command(explorer_id, generate, Ok(()));
+            self.send_bag_content_request(explorer_id)?;
+        } else {
+            let message = "Failed to generate basic resource".to_string();
+            self.finish_manual_command(explorer_id, generate, Err(message.clone()));
+            // let the gui know of the error
+            self.emit_failed_resource_generation(message);
+        }
+        Ok(())
+    }
+
+    fn on_combine_resource_response(
+        &mut self,
+        explorer_id: ID,
+        generated: Result<(), String>,
+    ) -> Result<(), String> {
+        let combine = |command: &ManualCommand| matches!(command, ManualCommand::Combine(_));
+        if generated.is_ok() {
+            self.finish_manual_command(explorer_id, combine, Ok(()));
+            self.send_bag_content_request(explorer_id)?;
+        } else {
+            let message = "Failed to combine into complex resource".to_string();
+            self.finish_manual_command(explorer_id, combine, Err(message.clone()));
+            // let the GUI know of the error
+            self.emit_failed_resource_generation(message);
+        }
+        Ok(())
+    }
+
+    fn on_bag_content_response(
+        &mut self,
+        explorer_id: ID,
+        bag_content: BagType,
+    ) -> Result<(), String> {
+        //LOG
+        log_internal_op!(
+            self,
+            "action"=>"bag content received",
+            "explorer_id"=>explorer_id,
+            "score"=>recipes::bag_score(&bag_content)
+        );
+        //LOG
+        self.explorers_info.update_bag(explorer_id, bag_content);
+        self.idle_watch.probe_answered(explorer_id);
+        Ok(())
+    }
+
+    fn on_neighbors_request(
+        &mut self,
+        explorer_id: ID,
+        current_planet_id: ID,
+    ) -> Result<(), String> {
+        // NeighborsResponse does not name its planet: the explorer matches it with
+        // the planet it asked about, so the answer must describe that planet even
+        // if the explorer has moved in the meantime
+        if let Some(info) = self.explorers_info.get(&explorer_id)
+            && info.current_planet_id != current_planet_id
+        {
+            log_internal_op!(
+                self,
+                "action"=>"NeighborsRequest about a planet the explorer is not on",
+                "explorer_id"=>explorer_id,
+                "requested planet_id"=>current_planet_id,
+                "current planet_id"=>info.current_planet_id
+            );
+        }
+        self.handle_neighbors_request(explorer_id, current_planet_id)?;
+        Ok(())
+    }
+
+    /// Routing table of the planet messages, with a handler for every kind.
+    pub(crate) fn default_planet_handlers() -> HandlerRegistry<PlanetToOrchestrator> {
+        let mut handlers: HandlerRegistry<PlanetToOrchestrator> = HandlerRegistry::new();
+        route!(handlers, PlanetToOrchestrator, PlanetMessageKind {
+            SunrayAck { planet_id } => Self::on_sunray_ack,
+            AsteroidAck { planet_id, rocket } => Self::on_asteroid_ack,
+            InternalStateResponse { planet_id, planet_state } => Self::on_internal_state_response,
+            KillPlanetResult { planet_id } => Self::on_kill_planet_result,
+            StartPlanetAIResult { planet_id } => Self::on_start_planet_ai_result,
+            StopPlanetAIResult { planet_id } => Self::on_stop_planet_ai_result,
+            IncomingExplorerResponse { planet_id, explorer_id, res } =>
+                Self::on_incoming_explorer_response,
+            OutgoingExplorerResponse { planet_id, explorer_id, res } =>
+                Self::on_outgoing_explorer_response,
+        });
+        handlers.register(PlanetMessageKind::Stopped, |_, _| Ok(()));
+        handlers
+    }
+
+    /// Routing table of the explorer messages, with a handler for every kind.
+    pub(crate) fn default_explorer_handlers() -> HandlerRegistry<ExplorerToOrchestrator<BagType>> {
+        let mut handlers: HandlerRegistry<ExplorerToOrchestrator<BagType>> = HandlerRegistry::new();
+        route!(handlers, ExplorerToOrchestrator, ExplorerMessageKind {
+            StartExplorerAIResult { explorer_id } => Self::on_start_explorer_ai_result,
+            KillExplorerResult { explorer_id } => Self::on_kill_explorer_result,
+            ResetExplorerAIResult { explorer_id } => Self::on_reset_explorer_ai_result,
+            StopExplorerAIResult { explorer_id } => Self::on_stop_explorer_ai_result,
+            MovedToPlanetResult { explorer_id, planet_id } => Self::on_moved_to_planet_result,
+            CurrentPlanetResult { explorer_id, planet_id } => Self::on_current_planet_result,
+            SupportedResourceResult { explorer_id, supported_resources } =>
+                Self::on_supported_resource_result,
+            SupportedCombinationResult { explorer_id, combination_list } =>
+                Self::on_supported_combination_result,
+            GenerateResourceResponse { explorer_id, generated } =>
+                Self::on_generate_resource_response,
+            CombineResourceResponse { explorer_id, generated } =>
+                Self::on_combine_resource_response,
+            BagContentResponse { explorer_id, bag_content } => Self::on_bag_content_response,
+            NeighborsRequest { explorer_id, current_planet_id } => Self::on_neighbors_request,
+        });
+        // the travel requests keep their public handler, which takes the whole message
+        handlers.register(
+            ExplorerMessageKind::TravelToPlanetRequest,
+            Self::handle_explorer_travel_request,
+        );
+        handlers
+    }
+
+    /// Handle a `TravelToPlanetRequest` sent by an explorer.
+    ///
+    /// The request is accepted only if the destination planet exists, is a neighbour
+    /// of `current_planet_id` (the topology edge is still alive) and both planets are
+    /// running. On acceptance the explorer's `move_to_planet_id` is updated and an
+    /// `IncomingExplorerRequest` is sent to the destination planet; the
+    /// `MoveToPlanet` with the planet's sender is sent once the planet answers.
+    /// On rejection the explorer receives `MoveToPlanet` with `sender_to_new_planet: None`.
+    ///
+    /// Returns Err if `msg` is not a `TravelToPlanetRequest`, or if the explorer
+    /// is unknown to the orchestrator.
+    ///
+    /// * `msg` - the explorer's `TravelToPlanetRequest`
+    pub fn handle_explorer_travel_request(
+        &mut self,
+        msg: ExplorerToOrchestrator<BagType>,
+    ) -> Result<(), String> {
+        let (explorer_id, current_planet_id, dst_planet_id) = match msg {
             ExplorerToOrchestrator::TravelToPlanetRequest {
                 explorer_id,
                 current_planet_id,
                 dst_planet_id,
-            } => {
-                // Check if dst_planet_id exists in the galaxy at all
-                if self.galaxy_lookup.get(&dst_planet_id).is_none() {
-                    log_internal_op!(self, "action" => format!(
-                        "TravelToPlanetRequest: dst_planet_id {} does not exist, rejecting",
-                        dst_planet_id
-                    ));
-                    if let Some(ch) = self.explorer_channels.get(&explorer_id) {
-                        let _ = ch.0.send(OrchestratorToExplorer::MoveToPlanet {
-                            sender_to_new_planet: None,
-                            planet_id: dst_planet_id,
-                        });
-                    }
-                    return Ok(());
-                }
+            } => (explorer_id, current_planet_id, dst_planet_id),
+            other => {
+                return Err(format!("Expected TravelToPlanetRequest, got {:?}", other));
+            }
+        };
+        //LOG
+        log_fn_call!(
+            self,
+            "handle_explorer_travel_request()",
+            explorer_id,
+            current_planet_id,
+            dst_planet_id,
+        );
+        //LOG
 
-                // verify that the destination planet is a neighbour
-                let is_neighbour = {
-                    // Translate real planet_ids to matrix indices via the lookup table
-                    let current_idx = self
-                        .galaxy_lookup
-                        .get(&current_planet_id)
-                        .map(|(idx, _)| *idx as usize);
-                    let dst_idx = self
-                        .galaxy_lookup
-                        .get(&dst_planet_id)
-                        .map(|(idx, _)| *idx as usize);
-
-                    match (current_idx, dst_idx) {
-                        (Some(ci), Some(di)) => self
-                            .galaxy_topology
-                            .get(ci)
-                            .and_then(|row| row.get(di))
-                            .copied()
-                            .unwrap_or(false),
-                        _ => false,
-                    }
-                };
+        // Check if dst_planet_id exists in the galaxy at all
+        if self.galaxy_lookup.get(&dst_planet_id).is_none() {
+            log_internal_op!(self, "action" => format!(
+                "TravelToPlanetRequest: dst_planet_id {} does not exist, rejecting",
+                dst_planet_id
+            ));
+            if let Some(ch) = self.explorer_channels.get(&explorer_id) {
+                let _ = ch.send_to_explorer(OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: None,
+                    planet_id: dst_planet_id,
+                });
+            }
+            return Ok(());
+        }
 
-                // avoid answering if the explorer has been put in manual mode
-                if let Some(map) = self.explorers_info.get(&explorer_id) {
-                    if matches!(map.status, Status::Paused) {
-                        return Ok(());
-                    }
-                }
+        // verify that the destination planet is a neighbour
+        let is_neighbour = {
+            // Translate real planet_ids to matrix indices via the lookup table
+            let current_idx = self
+                .galaxy_lookup
+                .get(&current_planet_id)
+                .map(|(idx, _)| *idx as usize);
+            let dst_idx = self
+                .galaxy_lookup
+                .get(&dst_planet_id)
+                .map(|(idx, _)| *idx as usize);
 
-                // if not existing or not a neighbour of the current planet, reject
-                if !is_neighbour
-                    || self.planets_info.get_status(&dst_planet_id) != Status::Running
-                    || self.planets_info.get_status(&current_planet_id) != Status::Running
-                {
-                    // Try to notify the explorer that the move was rejected.
-                    // If the explorer is already dead its channel is disconnected,
-                    // so we just log and move on instead of propagating the error.
-                    if let Some(ch) = self.explorer_channels.get(&explorer_id) {
-                        let _ = ch.0.send(OrchestratorToExplorer::MoveToPlanet {
-                            sender_to_new_planet: None,
-                            planet_id: dst_planet_id,
-                        });
-                    } else {
-                        return Err(format!(
-                            "could not get explorer channel for {}",
-                            explorer_id
-                        ));
-                    }
-                    return Ok(());
-                }
+            match (current_idx, dst_idx) {
+                (Some(ci), Some(di)) => self
+                    .galaxy_topology
+                    .get(ci)
+                    .and_then(|row| row.get(di))
+                    .copied()
+                    .unwrap_or(false),
+                _ => false,
+            }
+        };
 
-                //updating move_to_planet_id
-                if self.explorers_info.get(&explorer_id).is_some() {
-                    log_internal_op!(self, "updated move_to_planet_id");
-                }
-                match self.explorers_info.get_mut(&explorer_id) {
-                    Some(explorer_info) => {
-                        explorer_info.move_to_planet_id = dst_planet_id as i32;
-                    }
-                    None => {
-                        return Err(format!("Explorer {} not found", explorer_id));
-                    }
-                }
-                self.send_incoming_explorer_request(dst_planet_id, explorer_id)?
+        // avoid answering if the explorer has been put in manual mode
+        if let Some(map) = self.explorers_info.get(&explorer_id) {
+            if matches!(map.status, Status::Paused) {
+                return Ok(());
             }
         }
-        Ok(())
+
+        // if not existing or not a neighbour of the current planet, reject
+        if !is_neighbour
+            || self.planets_info.get_status(&dst_planet_id) != Status::Running
+            || self.planets_info.get_status(&current_planet_id) != Status::Running
+        {
+            // Try to notify the explorer that the move was rejected.
+            // If the explorer is already dead its channel is disconnected,
+            // so we just log and move on instead of propagating the error.
+            if let Some(ch) = self.explorer_channels.get(&explorer_id) {
+                let _ = ch.send_to_explorer(OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: None,
+                    planet_id: dst_planet_id,
+                });
+            } else {
+                return Err(format!(
+                    "could not get explorer channel for {}",
+                    explorer_id
+                ));
+            }
+            return Ok(());
+        }
+
+        //updating move_to_planet_id
+        if self.explorers_info.get(&explorer_id).is_some() {
+            log_internal_op!(self, "updated move_to_planet_id");
+        }
+        match self.explorers_info.get_mut(&explorer_id) {
+            Some(explorer_info) => {
+                explorer_info.move_to_planet_id = dst_planet_id as i32;
+            }
+            None => {
+                return Err(format!("Explorer {} not found", explorer_id));
+            }
+        }
+        self.send_incoming_explorer_request(dst_planet_id, explorer_id)
+    }
+
+    /// Handles every planet message that is currently queued, without waiting.
+    ///
+    /// Unlike [`handle_game_messages`](`Self::handle_game_messages`) this does not
+    /// go through `select!`, so a burst of planet messages is processed in one call.
+    /// Handler errors are logged and do not stop the draining.
+    ///
+    /// Returns the number of messages handled. The planet channel cannot be
+    /// disconnected while the orchestrator holds `sender_planet_orch`.
+    pub fn drain_planet_messages(&mut self) -> Result<usize, String> {
+        //LOG
+        log_fn_call!(self, "drain_planet_messages()");
+        //LOG
+        let msgs = self.receiver_orch_planet.try_recv_all();
+        let handled = msgs.len();
+        for msg in msgs {
+            let msg_string = format!("{:?}", msg);
+            if let Err(err) = self.handle_planet_message(msg) {
+                //LOG
+                LogEvent::self_directed(
+                    Participant::new(ActorType::Orchestrator, 0u32),
+                    EventType::InternalOrchestratorAction,
+                    Channel::Warning,
+                    warning_payload!(
+                        format!(
+                            "A handler returned a error while handling the planet msg: {:?}",
+                            msg_string
+                        ),
+                        err,
+                        "drain_planet_messages()"
+                    ),
+                )
+                .emit();
+                //LOG
+            }
+        }
+        Ok(handled)
     }
 
     /// Handle the planet messages that are sent through the orchestrator's
@@ -651,9 +922,15 @@ impl Orchestrator {
     ///
     /// This function serves as an entry point to all the messages that need the
     /// orchestrator's intervention; no logic is actually present.
-    pub fn handle_game_messages(&mut self) -> Result<(), String> {
+    ///
+    /// Returns [`MessageLoopStatus::ChannelClosed`] as soon as a channel towards the
+    /// orchestrator disconnects, which only happens if the orchestrator's own sender
+    /// has been dropped; the caller should then stop calling this function.
+    pub fn handle_game_messages(&mut self) -> Result<MessageLoopStatus, String> {
         //LOG
         log_fn_call!(self, "handle_game_messages()");
+        // catch up with the planet messages queued since the last call
+        self.drain_planet_messages()?;
         let deadline = Instant::now() + TIMEOUT_DURATION;
         while Instant::now() < deadline {
             select! {
@@ -661,19 +938,8 @@ impl Orchestrator {
                     let msg_unwraped = match msg{
                         Ok(res)=>res,
                         Err(e)=>{
-                            //LOG
-                            LogEvent::self_directed(
-                                Participant::new(ActorType::Orchestrator, 0u32),
-                                EventType::InternalOrchestratorAction,
-                                Channel::Warning,
-                                warning_payload!(
-                                    "Cannot receive message from planets",
-                                    e,
-                                    "handle_game_messages()"
-                                )
-                            ).emit();
-                            //LOG
-                            return Err(format!{"Cannot receive message from planets: {}", e})
+                            self.report_channel_closed("planets", e);
+                            return Ok(MessageLoopStatus::ChannelClosed);
                         },
                     };
                     let msg_string=format!("{:?}", msg_unwraped);
@@ -696,7 +962,8 @@ impl Orchestrator {
                     let msg_unwraped = match msg{
                         Ok(res)=>res,
                         Err(e)=>{
-                            return Err(format!("Cannot receive message from explorers: {}", e));
+                            self.report_channel_closed("explorers", e);
+                            return Ok(MessageLoopStatus::ChannelClosed);
                         },
                     };
                     let msg_string=format!("{:?}", msg_unwraped);
@@ -720,8 +987,51 @@ impl Orchestrator {
                 }
             }
         }
+        // the topology changes of this tick are sent all together
+        self.flush_topology_deltas();
+        self.collect_actor_crashes();
+        self.remove_expired_explorers();
 
-        Ok(())
+        Ok(MessageLoopStatus::Running)
+    }
+
+    /// Logs the error returned by a handler while waiting for another message.
+    ///
+    /// The message has nothing to do with the awaited one, so the wait goes on as in
+    /// [`handle_game_messages`](Self::handle_game_messages).
+    ///
+    /// * `msg` - the debug string of the handled message
+    /// * `waiting_in` - the function that was waiting
+    pub(crate) fn report_handler_error(&self, msg: &str, err: String, waiting_in: &str) {
+        //LOG
+        LogEvent::self_directed(
+            Participant::new(ActorType::Orchestrator, 0u32),
+            EventType::InternalOrchestratorAction,
+            Channel::Warning,
+            warning_payload!(
+                format!("A handler returned a error while handling the msg: {}", msg),
+                err,
+                waiting_in
+            ),
+        )
+        .emit();
+        //LOG
+    }
+
+    fn report_channel_closed(&self, actors: &str, err: crossbeam_channel::RecvError) {
+        //LOG
+        LogEvent::self_directed(
+            Participant::new(ActorType::Orchestrator, 0u32),
+            EventType::InternalOrchestratorAction,
+            Channel::Error,
+            warning_payload!(
+                format!("Cannot receive message from {}, channel closed", actors),
+                err,
+                "handle_game_messages()"
+            ),
+        )
+        .emit();
+        //LOG
     }
     fn send_kill_to_explorers_on_dying_planet(&mut self, planet_id: &ID) -> Result<(), String> {
         log_fn_call!(self, "send_kill_to_explorers_on_dying_planet()", planet_id);
@@ -734,10 +1044,10 @@ impl Orchestrator {
                 .explorer_channels
                 .get(i.0)
                 .unwrap()
-                .0
-                .send(OrchestratorToExplorer::KillExplorer)
+                .send_to_explorer(OrchestratorToExplorer::KillExplorer)
             {
                 Ok(_) => {
+                    self.signal_explorer_kill(*i.0);
                     log_message!(
                         ActorType::Orchestrator,
                         0u32,
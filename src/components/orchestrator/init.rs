@@ -1,5 +1,6 @@
 use logging_utils::{LoggableActor, get_receiver_id, get_sender_id};
-use std::{fs, thread};
+use std::fs;
+use std::str::FromStr;
 
 use common_game::{
     logging::{ActorType, Channel, EventType, LogEvent, Participant},
@@ -9,28 +10,268 @@ use common_game::{
         planet_explorer::{ExplorerToPlanet, PlanetToExplorer},
     },
 };
-use crossbeam_channel::{Receiver, Sender, unbounded};
+use crossbeam_channel::{Receiver, Sender, bounded, unbounded};
 use rustc_hash::FxHashMap;
 
 use super::Orchestrator;
+use super::channels::{ExplorerHandle, PlanetHandle};
 use crate::components::mattia_explorer::Explorer as MattiaExplorer;
-use crate::utils::registry::PlanetType::{
-    BlackAdidasShoe, Ciuc, HoustonWeHaveABorrow, ImmutableCosmicBorrow, OneMillionCrabs, Rustrelli,
-    RustyCrab, TheCompilerStrikesBack,
-};
 use crate::{
     GalaxyTopology,
     components::tommy_explorer::Explorer as TommyExplorer,
     utils::{
         Status,
+        actor_threads::{explorer_thread_name, planet_thread_name, spawn_actor_thread},
         registry::{PLANET_REGISTRY, PlanetType},
     },
 };
 
 use crate::utils::ExplorerInfo;
+use crate::utils::resource_mint::mint_resources;
+use common_game::components::resource::{GenericResource, ResourceType};
 use logging_utils::{debug_println, log_fn_call, log_internal_op, warning_payload};
 
+/// Default maximum number of planets of a galaxy, see [`Orchestrator::max_galaxy_size`].
+pub const DEFAULT_MAX_GALAXY_SIZE: usize = 1024;
+
+/// A galaxy description, before any planet is created.
+///
+/// It is produced by [`parse_galaxy_description`] or built with
+/// [`ParsedGalaxy::new`] by any other galaxy source, and then handed to
+/// [`Orchestrator::initialize_galaxy`].
+#[derive(Debug, Clone)]
+pub struct ParsedGalaxy {
+    /// `(planet_id, (planet index in the adj matrix, PlanetType))`, see
+    /// [`Orchestrator::galaxy_lookup`]
+    pub lookup: FxHashMap<u32, (u32, PlanetType)>,
+    /// Neighbours of each planet, both indexed by the planet index in the adj matrix
+    pub adj_list: Vec<Vec<u32>>,
+}
+
+/// Parse a galaxy description, i.e. the content of a galaxy initialization file.
+///
+/// Every non-empty line is `<planet id>,<planet type>[,<neighbour id>...]`; at most
+/// [`DEFAULT_MAX_GALAXY_SIZE`] planets are accepted, see
+/// [`ParsedGalaxy::parse_with_limit`] for the errors.
+pub fn parse_galaxy_description(input: &str) -> Result<ParsedGalaxy, String> {
+    input.parse()
+}
+
+impl ParsedGalaxy {
+    /// Type assigned to each planet, by planet id.
+    pub fn planet_types(&self) -> impl Iterator<Item = (u32, PlanetType)> + '_ {
+        self.lookup
+            .iter()
+            .map(|(id, (_, planet_type))| (*id, *planet_type))
+    }
+
+    /// Build a galaxy from an adjacency list and a lookup table.
+    ///
+    /// Returns Err if the galaxy has no planets, if the lookup and the adjacency
+    /// list do not have the same number of planets, if two planets share an index
+    /// or if a neighbour index is out of range.
+    ///
+    /// * `adj_list` - neighbours of each planet, by planet index in the adj matrix
+    /// * `lookup` - `(planet_id, (planet index in the adj matrix, PlanetType))`
+    pub fn new(
+        adj_list: Vec<Vec<u32>>,
+        lookup: FxHashMap<u32, (u32, PlanetType)>,
+    ) -> Result<Self, String> {
+        let num_planets = adj_list.len();
+        if num_planets == 0 {
+            return Err("The galaxy has no planets".to_string());
+        }
+        if lookup.len() != num_planets {
+            return Err(format!(
+                "The lookup has {} planets but the adjacency list has {} rows",
+                lookup.len(),
+                num_planets
+            ));
+        }
+        let mut used = vec![false; num_planets];
+        for (planet_id, (idx, _)) in lookup.iter() {
+            match used.get_mut(*idx as usize) {
+                Some(false) => used[*idx as usize] = true,
+                Some(true) => {
+                    return Err(format!("Planet {}: index {} is used twice", planet_id, idx));
+                }
+                None => {
+                    return Err(format!(
+                        "Planet {}: index {} is out of range",
+                        planet_id, idx
+                    ));
+                }
+            }
+        }
+        if let Some((row, _)) = adj_list
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.iter().any(|n| *n as usize >= num_planets))
+        {
+            return Err(format!("Row {}: neighbour index out of range", row));
+        }
+        Ok(ParsedGalaxy { lookup, adj_list })
+    }
+
+    /// Parse the content of a galaxy initialization file.
+    ///
+    /// Returns Err if a line is formatted incorrectly, if a planet id is defined
+    /// twice (the error contains both line numbers), if there are no planets or if
+    /// there are more than `max_planets` of them.
+    ///
+    /// * `input` - string content of the galaxy initialization
+    /// * `max_planets` - maximum number of planets accepted
+    pub fn parse_with_limit(input: &str, max_planets: usize) -> Result<Self, String> {
+        let mut adj_list = Vec::new();
+        let mut lookup: FxHashMap<u32, (u32, PlanetType)> = FxHashMap::default();
+        // line number where each planet id was defined
+        let mut defined_at: FxHashMap<u32, usize> = FxHashMap::default();
+
+        let mut planet_idx = 0u32;
+        for (line_num, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            // Split at comma and u32 conversion
+            let values: Vec<u32> = line
+                .split(',')
+                .map(|s| {
+                    s.trim().parse::<u32>().map_err(|_| {
+                        format!("Error row {}: value '{}' is not a u32", line_num + 1, s)
+                    })
+                })
+                .collect::<Result<Vec<u32>, String>>()?;
+
+            if values.len() < 2 {
+                return Err(format!("Row {}: ID or Type missing", line_num + 1));
+            }
+
+            let node_id = values[0];
+            let node_type = values[1];
+            let neighbors = &values[2..];
+
+            if let Some(first_line) = defined_at.insert(node_id, line_num + 1) {
+                return Err(format!(
+                    "Row {}: planet {} is already defined at row {}",
+                    line_num + 1,
+                    node_id,
+                    first_line
+                ));
+            }
+            // checked while parsing, so that a huge file fails before the adj matrix is built
+            if defined_at.len() > max_planets {
+                return Err(format!(
+                    "Row {}: the galaxy has more than {} planets",
+                    line_num + 1,
+                    max_planets
+                ));
+            }
+
+            // the type id is the position in PlanetType::all_variants(), unknown ids
+            // get a random type
+            let planet_type = match PlanetType::all_variants().get(node_type as usize) {
+                Some(planet_type) => *planet_type,
+                None => {
+                    LogEvent::self_directed(
+                        Participant::new(ActorType::Orchestrator, 0u32),
+                        EventType::InternalOrchestratorAction,
+                        Channel::Warning,
+                        warning_payload!(
+                            "unknown planet type, a random one is used",
+                            format!(
+                                "Row {}: planet type {} is not one of: {}",
+                                line_num + 1,
+                                node_type,
+                                PlanetType::valid_types_list()
+                            ),
+                            "ParsedGalaxy::parse_with_limit()";
+                            "planet_id"=>node_id
+                        ),
+                    )
+                    .emit();
+                    PlanetType::random()
+                }
+            };
+
+            // saving id-index to lookup table using a counter that ignores empty lines
+            lookup.insert(node_id, (planet_idx, planet_type));
+
+            let mut adj_row = vec![];
+            adj_row.extend_from_slice(neighbors);
+            adj_list.push(adj_row);
+
+            planet_idx += 1;
+        }
+
+        if lookup.is_empty() {
+            return Err("The galaxy has no planets".to_string());
+        }
+
+        // Remap neighbors to their internal indices
+        for row in &mut adj_list {
+            for node in row {
+                if let Some(&(new_idx, _)) = lookup.get(node) {
+                    *node = new_idx;
+                }
+            }
+        }
+
+        Ok(ParsedGalaxy { lookup, adj_list })
+    }
+}
+
+impl FromStr for ParsedGalaxy {
+    type Err = String;
+
+    /// Parse the content of a galaxy initialization file, accepting at most
+    /// [`DEFAULT_MAX_GALAXY_SIZE`] planets (see [`ParsedGalaxy::parse_with_limit`]).
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_limit(input, DEFAULT_MAX_GALAXY_SIZE)
+    }
+}
+
 //Initialization game functions
+/// Implementation of an explorer, see [`ExplorerSpawnConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExplorerKind {
+    Mattia,
+    Tommy,
+}
+
+/// Description of an explorer to spawn with [`Orchestrator::spawn_explorer`].
+///
+/// Scenarios and tests use it to start explorers in a given situation instead of
+/// playing the game until they get there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplorerSpawnConfig {
+    pub kind: ExplorerKind,
+    pub explorer_id: u32,
+    /// Planet the explorer is spawned on.
+    pub planet_id: u32,
+    /// Charged cells of the starting planet the explorer believes there are, None to
+    /// use the last count known by the orchestrator.
+    ///
+    /// Only Tommy's explorer takes it; Mattia's explorer asks the planet instead.
+    pub initial_energy_hint: Option<u32>,
+    /// Resources in the bag of the explorer when it starts, only basic resources.
+    pub starting_bag: Vec<ResourceType>,
+}
+
+impl ExplorerSpawnConfig {
+    /// Config of an explorer with an empty bag and no energy hint.
+    pub fn new(kind: ExplorerKind, explorer_id: u32, planet_id: u32) -> Self {
+        ExplorerSpawnConfig {
+            kind,
+            explorer_id,
+            planet_id,
+            initial_energy_hint: None,
+            starting_bag: Vec::new(),
+        }
+    }
+}
+
 impl Orchestrator {
     /// Create a new Galaxy Topology.
     ///
@@ -145,11 +386,13 @@ impl Orchestrator {
         //Init comms OrchestratorToPlanet, ExplorerToPlanet
         let (sender_orchestrator, receiver_orchestrator, sender_explorer, receiver_explorer) =
             Orchestrator::init_comms_planet();
+        let (sender_orchestrator, sender_explorer) =
+            self.traced_planet_senders(id, sender_orchestrator, sender_explorer);
 
         log_internal_op!(
             self,
             "action"=>"Init comms OrchestratorToPlanet, ExplorerToPlanet",
-            "sender_orchestrator"=>format!("{}",get_sender_id(&sender_orchestrator)),
+            "sender_orchestrator"=>format!("{}",get_sender_id(sender_orchestrator.inner())),
             "receiver_orchestrator"=>format!("{}",get_receiver_id(&receiver_orchestrator)),
             "sender_explorer"=>format!("{}",get_sender_id(&sender_explorer)),
             "receiver_explorer"=>format!("{}",get_receiver_id(&receiver_explorer)),
@@ -171,7 +414,14 @@ impl Orchestrator {
 
         //creation of the planet
 
-        let mut new_planet = PLANET_REGISTRY.get(&type_id).unwrap().as_ref()(
+        let planet_factory = PLANET_REGISTRY.get(&type_id).ok_or_else(|| {
+            format!(
+                "No factory registered for planet type {:?}, valid types: {}",
+                type_id,
+                PlanetType::valid_types_list()
+            )
+        })?;
+        let mut new_planet = planet_factory.as_ref()(
             planet_to_orchestrator_channels.0,
             planet_to_orchestrator_channels.1,
             receiver_explorer,
@@ -197,11 +447,17 @@ impl Orchestrator {
             Some(basic),
             Some(complex),
         );
-        self.planet_channels
-            .insert(new_planet.id(), (sender_orchestrator, sender_explorer));
+        self.planet_channels.insert(
+            new_planet.id(),
+            PlanetHandle::new(sender_orchestrator, sender_explorer),
+        );
 
         debug_println!("Start planet{id} thread");
-        thread::spawn(move || -> Result<(), String> { new_planet.run() });
+        spawn_actor_thread(
+            planet_thread_name(id, type_id),
+            self.crash_sender.clone(),
+            move || -> Result<(), String> { new_planet.run() },
+        )?;
 
         //LOG
         log_internal_op!(
@@ -226,9 +482,44 @@ impl Orchestrator {
     /// REMEMBER in order to work this function needs to be called when the planet AI is ALREADY
     /// running, not before
     pub fn add_tommy_explorer(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), String> {
+        self.spawn_explorer(ExplorerSpawnConfig::new(
+            ExplorerKind::Tommy,
+            explorer_id,
+            planet_id,
+        ))
+    }
+
+    /// Spawn an explorer as described by `config`.
+    ///
+    /// The starting bag is minted by the orchestrator before anything else is created,
+    /// see [`mint_resources`]: it returns Err, and spawns nothing, if a resource cannot
+    /// be minted. Otherwise it works like [`Orchestrator::add_tommy_explorer`] and
+    /// [`Orchestrator::add_mattia_explorer`].
+    ///
+    /// REMEMBER in order to work this function needs to be called when the planet AI is ALREADY
+    /// running, not before
+    pub fn spawn_explorer(&mut self, config: ExplorerSpawnConfig) -> Result<(), String> {
+        log_fn_call!(
+            self,
+            "spawn_explorer()";
+            "config"=>format!("{:?}", config)
+        );
+        let starting_bag = mint_resources(&config.starting_bag, &self.forge)?;
+        match config.kind {
+            ExplorerKind::Tommy => self.spawn_tommy_explorer(&config, starting_bag),
+            ExplorerKind::Mattia => self.spawn_mattia_explorer(&config, starting_bag),
+        }
+    }
+
+    fn spawn_tommy_explorer(
+        &mut self,
+        config: &ExplorerSpawnConfig,
+        starting_bag: Vec<GenericResource>,
+    ) -> Result<(), String> {
+        let (explorer_id, planet_id) = (config.explorer_id, config.planet_id);
         log_fn_call!(
             self,
-            "add_tommy_explorer()",
+            "spawn_tommy_explorer()",
             explorer_id,
             planet_id;
             "sender_explorer"=>"Sender<ExplorerToPlanet>"
@@ -236,12 +527,14 @@ impl Orchestrator {
         //Create the comms for the new explorer
         let (sender_orch, receiver_orch, sender_planet, receiver_planet) =
             Orchestrator::init_comms_explorers();
+        let (sender_orch, sender_planet) =
+            self.traced_explorer_senders(explorer_id, planet_id, sender_orch, sender_planet);
 
         // get the sender from explorer to planet
         let (orch_to_planet, expl_to_planet) = match self.planet_channels.get(&planet_id) {
-            Some((orchestrator_sender, explorer_sender)) => (
-                Some(orchestrator_sender.clone()),
-                Some(explorer_sender.clone()),
+            Some(handle) => (
+                Some(handle.to_planet().clone()),
+                Some(handle.explorer_sender_for_arrival()),
             ),
             None => {
                 return Err(
@@ -257,6 +550,9 @@ impl Orchestrator {
                 free_cells = planet_info.charged_cells_count as u32;
             }
         }
+        if let Some(hint) = config.initial_energy_hint {
+            free_cells = hint;
+        }
 
         //Construct Explorer
         let mut new_explorer = TommyExplorer::new(
@@ -266,6 +562,16 @@ impl Orchestrator {
             (receiver_planet, expl_to_planet.unwrap()), //this unwrap is safe because is already checked
             free_cells,
         );
+        let (kill_sender, kill_receiver) = bounded(1);
+        for resource in starting_bag {
+            new_explorer.insert_in_bag(resource);
+        }
+        new_explorer.set_kill_receiver(kill_receiver);
+        self.explorer_kill_senders.insert(explorer_id, kill_sender);
+        let (topology_sender, topology_receiver) = unbounded();
+        new_explorer.set_topology_receiver(topology_receiver);
+        self.explorer_topology_senders
+            .insert(explorer_id, topology_sender);
 
         log_internal_op!(
             self,
@@ -276,15 +582,22 @@ impl Orchestrator {
         //Update HashMaps
         self.explorers_info.insert(
             explorer_id,
-            ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), planet_id),
+            ExplorerInfo::from(
+                explorer_id,
+                Status::Paused,
+                config.starting_bag.clone(),
+                planet_id,
+            ),
         );
 
         log_internal_op!(
             self,
             "action"=>"explorer_status hashmap updated",
         );
-        self.explorer_channels
-            .insert(new_explorer.id(), (sender_orch, sender_planet.clone()));
+        self.explorer_channels.insert(
+            new_explorer.id(),
+            ExplorerHandle::new(sender_orch, sender_planet.clone()),
+        );
         log_internal_op!(
             self,
             "action"=>"saved channels: sender_orch, sender_planet",
@@ -305,10 +618,13 @@ impl Orchestrator {
         }
         // self.explorers.push(explorer);
         //Spawn the corresponding thread for the explorer
-        thread::spawn(move || -> Result<(), String> {
-            let _ = new_explorer.run().map_err(|_| "Error run");
-            Ok(())
-        });
+        let _handle = spawn_actor_thread(
+            explorer_thread_name(explorer_id, "tommy"),
+            self.crash_sender.clone(),
+            move || -> Result<(), String> { new_explorer.run() },
+        )?;
+        #[cfg(feature = "expose-handles")]
+        self.explorer_handles.insert(explorer_id, _handle);
         log_internal_op!(
             self,
             "action"=>"explorer thread created",
@@ -329,9 +645,22 @@ impl Orchestrator {
     /// REMEMBER in order to work this function needs to be called when the planet AI is ALREADY
     /// running, not before
     pub fn add_mattia_explorer(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), String> {
+        self.spawn_explorer(ExplorerSpawnConfig::new(
+            ExplorerKind::Mattia,
+            explorer_id,
+            planet_id,
+        ))
+    }
+
+    fn spawn_mattia_explorer(
+        &mut self,
+        config: &ExplorerSpawnConfig,
+        starting_bag: Vec<GenericResource>,
+    ) -> Result<(), String> {
+        let (explorer_id, planet_id) = (config.explorer_id, config.planet_id);
         log_fn_call!(
             self,
-            "add_mattia_explorer()",
+            "spawn_mattia_explorer()",
             explorer_id,
             planet_id;
             "sender_explorer"=>"Sender<ExplorerToPlanet>"
@@ -339,12 +668,14 @@ impl Orchestrator {
         //Create the comms for the new explorer
         let (sender_orch, receiver_orch, sender_planet, receiver_planet) =
             Orchestrator::init_comms_explorers();
+        let (sender_orch, sender_planet) =
+            self.traced_explorer_senders(explorer_id, planet_id, sender_orch, sender_planet);
 
         // get the sender from explorer to planet
         let (orch_to_planet, expl_to_planet) = match self.planet_channels.get(&planet_id) {
-            Some((orchestrator_sender, explorer_sender)) => (
-                Some(orchestrator_sender.clone()),
-                Some(explorer_sender.clone()),
+            Some(handle) => (
+                Some(handle.to_planet().clone()),
+                Some(handle.explorer_sender_for_arrival()),
             ),
             None => {
                 return Err(
@@ -360,6 +691,13 @@ impl Orchestrator {
             (receiver_orch, self.sender_explorer_orch.clone()),
             (receiver_planet, expl_to_planet.unwrap()), // this unwrap is safe because it is checked before
         );
+        for resource in starting_bag {
+            new_explorer.insert_in_bag(resource);
+        }
+        let (topology_sender, topology_receiver) = unbounded();
+        new_explorer.set_topology_receiver(topology_receiver);
+        self.explorer_topology_senders
+            .insert(explorer_id, topology_sender);
 
         log_internal_op!(
             self,
@@ -370,15 +708,22 @@ impl Orchestrator {
         //Update HashMaps
         self.explorers_info.insert(
             explorer_id,
-            ExplorerInfo::from(explorer_id, Status::Paused, Vec::new(), planet_id),
+            ExplorerInfo::from(
+                explorer_id,
+                Status::Paused,
+                config.starting_bag.clone(),
+                planet_id,
+            ),
         );
 
         log_internal_op!(
             self,
             "action"=>"explorer_status hashmap updated",
         );
-        self.explorer_channels
-            .insert(new_explorer.id(), (sender_orch, sender_planet.clone()));
+        self.explorer_channels.insert(
+            new_explorer.explorer_id(),
+            ExplorerHandle::new(sender_orch, sender_planet.clone()),
+        );
         log_internal_op!(
             self,
             "action"=>"saved channels: sender_orch, sender_planet",
@@ -399,10 +744,13 @@ impl Orchestrator {
 
         // self.explorers.push(explorer);
         //Spawn the corresponding thread for the explorer
-        thread::spawn(move || -> Result<(), String> {
-            let _ = new_explorer.run().map_err(|_| "Error run");
-            Ok(())
-        });
+        let _handle = spawn_actor_thread(
+            explorer_thread_name(explorer_id, "mattia"),
+            self.crash_sender.clone(),
+            move || -> Result<(), String> { new_explorer.run() },
+        )?;
+        #[cfg(feature = "expose-handles")]
+        self.explorer_handles.insert(explorer_id, _handle);
         log_internal_op!(
             self,
             "action"=>"explorer thread created",
@@ -414,9 +762,9 @@ impl Orchestrator {
     /// Initialize the galaxy using a topology file.
     ///
     /// Uses the galaxy topology file (which should be based on the INPUT_FILE
-    /// environment variable, set in the .env file of the project) and performs
-    /// parsing operations to pass it on to
-    /// [`initialize_galaxy_by_adj_list`](Self::initialize_galaxy_by_adj_list).
+    /// environment variable, set in the .env file of the project) and passes its
+    /// content on to [`initialize_galaxy_by_content`](Self::initialize_galaxy_by_content);
+    /// embedders and tests can skip the file with [`parse_galaxy_description`].
     ///
     /// Returns Err if the file is formatted incorrectly or if any of the following
     /// initialization functions return Err as well.
@@ -458,77 +806,67 @@ impl Orchestrator {
     /// This function performs parsing operations on a string content and passes
     /// it on to [`initialize_galaxy_by_adj_list`](Self::initialize_galaxy_by_adj_list).
     ///
-    /// Returns Err if the content is formatted incorrectly.
+    /// If a galaxy was already initialized, it is torn down first through
+    /// [`reset`](Self::reset), so that the old planets and explorers are killed
+    /// instead of being left running next to the new ones.
+    ///
+    /// Returns Err if the content is formatted incorrectly or is not a valid galaxy
+    /// (see [`ParsedGalaxy::parse_with_limit`]); in that case the current galaxy is
+    /// left untouched.
     ///
     /// * `input` - string content of the galaxy initialization
     pub fn initialize_galaxy_by_content(&mut self, input: &str) -> Result<(), String> {
         log_fn_call!(self, "initialize_galaxy_by_content()", input);
         log_internal_op!(self, "action" => "parsing galaxy content", "content" => input);
+        // the content is validated before the current galaxy is torn down
+        let galaxy = ParsedGalaxy::parse_with_limit(input, self.max_galaxy_size)?;
+        self.initialize_galaxy(galaxy)
+    }
 
-        let mut adj_list_for_topology = Vec::new();
-        let mut new_lookup: FxHashMap<u32, (u32, PlanetType)> = FxHashMap::default();
-
-        let mut planet_idx = 0u32;
-        for (line_num, line) in input.lines().enumerate() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-
-            // Split at comma and u32 conversion
-            let values: Vec<u32> = line
-                .split(',')
-                .map(|s| {
-                    s.trim().parse::<u32>().map_err(|_| {
-                        format!("Error row {}: value '{}' is not a u32", line_num + 1, s)
-                    })
-                })
-                .collect::<Result<Vec<u32>, String>>()?;
-
-            if values.len() < 2 {
-                return Err(format!("Row {}: ID or Type missing", line_num + 1));
-            }
-
-            let node_id = values[0];
-            let node_type = values[1];
-            let neighbors = &values[2..];
-
-            // saving id-index to lookup table using a counter that ignores empty lines
-            new_lookup.insert(
-                node_id,
-                (
-                    planet_idx,
-                    match node_type {
-                        0 => BlackAdidasShoe,
-                        1 => Ciuc,
-                        2 => HoustonWeHaveABorrow,
-                        3 => ImmutableCosmicBorrow,
-                        4 => OneMillionCrabs,
-                        5 => Rustrelli,
-                        6 => RustyCrab,
-                        7 => TheCompilerStrikesBack,
-                        _ => PlanetType::random(),
-                    },
-                ),
-            );
-
-            let mut adj_row = vec![];
-            adj_row.extend_from_slice(neighbors);
-            adj_list_for_topology.push(adj_row);
-
-            planet_idx += 1;
-        }
+    /// Create an orchestrator with an in-memory galaxy.
+    ///
+    /// Runs the same initialization as
+    /// [`initialize_galaxy_by_file`](Self::initialize_galaxy_by_file), without
+    /// reading any file: the planets are created and paused.
+    ///
+    /// Returns Err if the galaxy is not valid (see [`ParsedGalaxy::new`]) or if a
+    /// planet cannot be created.
+    ///
+    /// * `adj_list` - neighbours of each planet, by planet index in the adj matrix
+    /// * `lookup` - `(planet_id, (planet index in the adj matrix, PlanetType))`
+    pub fn with_topology(
+        adj_list: Vec<Vec<u32>>,
+        lookup: FxHashMap<u32, (u32, PlanetType)>,
+    ) -> Result<Self, String> {
+        let mut orch = Self::new()?;
+        log_fn_call!(orch, "with_topology()", adj_list);
+        orch.initialize_galaxy(ParsedGalaxy::new(adj_list, lookup)?)?;
+        Ok(orch)
+    }
 
-        // Remap neighbors to their internal indices
-        for row in &mut adj_list_for_topology {
-            for node in row {
-                if let Some(&(new_idx, _)) = new_lookup.get(node) {
-                    *node = new_idx;
-                }
-            }
+    /// Initialize the galaxy from a parsed description.
+    ///
+    /// If a galaxy was already initialized, it is torn down first through
+    /// [`reset`](Self::reset). The planets are created and paused.
+    ///
+    /// Returns Err if the galaxy has more than
+    /// [`max_galaxy_size`](Self::max_galaxy_size) planets (the current galaxy is
+    /// then left untouched) or if a planet cannot be created.
+    ///
+    /// * `galaxy` - the galaxy, see [`parse_galaxy_description`]
+    pub fn initialize_galaxy(&mut self, galaxy: ParsedGalaxy) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "initialize_galaxy()", galaxy,);
+        //LOG
+        if galaxy.lookup.len() > self.max_galaxy_size {
+            return Err(format!(
+                "The galaxy has more than {} planets",
+                self.max_galaxy_size
+            ));
         }
+        self.reset_if_initialized()?;
 
-        self.galaxy_lookup = new_lookup;
+        self.galaxy_lookup = galaxy.lookup;
         // Build the reverse lookup: matrix_index -> planet_id
         self.galaxy_reverse_lookup = self
             .galaxy_lookup
@@ -536,7 +874,7 @@ impl Orchestrator {
             .map(|(&planet_id, &(matrix_idx, _))| (matrix_idx, planet_id))
             .collect();
         //Initialize the orchestrator galaxy topology
-        self.initialize_galaxy_by_adj_list(adj_list_for_topology)?;
+        self.initialize_galaxy_by_adj_list(galaxy.adj_list)?;
 
         Ok(())
     }
@@ -619,6 +957,7 @@ impl Orchestrator {
         num_planets: u32,
     ) -> Result<(), String> {
         log_fn_call!(self, "initialize_galaxy_by_random_selection()", num_planets);
+        self.reset_if_initialized()?;
 
         let mut new_topology: Vec<Vec<bool>> = Vec::new();
         for _ in 0..num_planets {
@@ -649,6 +988,23 @@ impl Orchestrator {
 
         Ok(())
     }
+    /// Tells whether a galaxy has already been initialized.
+    pub fn is_initialized(&self) -> bool {
+        !self.planet_channels.is_empty() || !self.galaxy_lookup.is_empty()
+    }
+
+    /// Runs [`reset`](Self::reset) if a galaxy is already present, so that a
+    /// second initialization does not layer over the live planets.
+    fn reset_if_initialized(&mut self) -> Result<(), String> {
+        if self.is_initialized() {
+            //LOG
+            log_internal_op!(self, "action" => "galaxy already initialized, resetting before re-initialization");
+            //LOG
+            self.reset()?;
+        }
+        Ok(())
+    }
+
     /// Initialize the galaxy using a list of planet IDs.
     ///
     /// This function is normally called by
@@ -691,6 +1047,10 @@ impl Orchestrator {
                 }
             };
         }
+        for planet_id in ids_list {
+            let neighbors = self.get_neighbors_of(planet_id)?;
+            self.neighbor_cache.insert(planet_id, neighbors);
+        }
         Ok(())
     }
 }
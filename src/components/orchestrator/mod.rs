@@ -1,37 +1,70 @@
+pub mod channels;
+pub mod debug;
 mod explorer_comms;
 pub mod gui_comms;
 pub mod handlers;
 pub mod init;
 pub mod planets_comms;
+pub mod routing;
+pub mod sequence;
 pub mod update;
 
-use crate::utils::registry::PlanetType;
+use crate::components::orchestrator::channels::{ExplorerHandle, PlanetHandle};
+use crate::components::orchestrator::routing::HandlerRegistry;
+use crate::utils::actor_threads::{ActorCrash, install_panic_hook};
+use crate::utils::cell_telemetry::CellRecord;
+use crate::utils::registry::{PlanetType, validate_planet_registry};
+use crate::utils::tracing::MessageRecorder;
 use crate::utils::types::GalaxyTopology;
-use crate::utils::{ExplorerInfoMap, PlanetInfoMap};
+use crate::utils::{
+    ExplorerInfoMap, GalaxyState, GameClock, IdlePolicy, IdleWatch, LaunchInfo, PlanetInfoMap,
+    TopologyDelta,
+};
 use common_game::components::forge::Forge;
+use common_game::components::resource::{BasicResourceType, ComplexResourceType};
 use common_game::logging::ActorType;
 use common_game::protocols::orchestrator_explorer::{
     ExplorerToOrchestrator, OrchestratorToExplorer,
 };
 use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
-use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
 use crossbeam_channel::{Receiver, Sender, unbounded};
 use logging_utils::LoggableActor;
 use logging_utils::{log_fn_call, log_internal_op};
 use rand::Rng;
 use rustc_hash::FxHashMap;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use crate::components::tommy_explorer::bag::BagType;
 
 #[derive(Debug)]
 pub enum OrchestratorEvent {
     PlanetDestroyed { planet_id: u32 },
-    SunraySent { planet_id: u32 },
+    /// The death of a planet split the living planets into groups not linked to each
+    /// other, see [`connected_components`](Orchestrator::connected_components).
+    GalaxyPartitioned { components: Vec<Vec<u32>> },
+    SunraySent { planet_id: u32, info: LaunchInfo },
     SunrayReceived { planet_id: u32 },
-    AsteroidSent { planet_id: u32 },
+    AsteroidSent { planet_id: u32, info: LaunchInfo },
     ExplorerMoved { explorer_id: u32, destination: u32 },
     ExplorerMoveStarted { explorer_id: u32, destination: u32 },
     ResourceGenerationFailed { message: String },
+    /// The thread of an actor panicked.
+    ActorCrashed { thread_name: String, message: String },
+    /// An explorer answered a command sent with one of the `command_explorer_*` methods.
+    ManualCommandFinished {
+        explorer_id: u32,
+        command: ManualCommand,
+        result: Result<(), String>,
+    },
+}
+
+/// A command given by hand to an explorer in manual mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManualCommand {
+    Move { destination: u32 },
+    Generate(BasicResourceType),
+    Combine(ComplexResourceType),
 }
 
 ///The core of the game.
@@ -64,30 +97,114 @@ pub struct Orchestrator {
     ///
     /// Indexed by the matrix index, returns the real planet_id.
     pub galaxy_reverse_lookup: FxHashMap<u32, u32>,
+    /// Neighbours of each planet, as returned by
+    /// [`get_neighbors_of`](Self::get_neighbors_of).
+    ///
+    /// Filled when the planets are created and invalidated when their links are
+    /// destroyed, see [`handle_neighbors_request`](Self::handle_neighbors_request).
+    pub neighbor_cache: FxHashMap<u32, Vec<u32>>,
 
     //Status for each planet and explorers, BTreeMaps are useful for printing
     pub planets_info: PlanetInfoMap,
     pub explorers_info: ExplorerInfoMap,
 
     //Communication channels for sending messages to planets and explorers
-    pub planet_channels: HashMap<u32, (Sender<OrchestratorToPlanet>, Sender<ExplorerToPlanet>)>,
-    pub explorer_channels: HashMap<u32, (Sender<OrchestratorToExplorer>, Sender<PlanetToExplorer>)>,
+    pub planet_channels: HashMap<u32, PlanetHandle>,
+    pub explorer_channels: HashMap<u32, ExplorerHandle>,
+    /// Kill signal channels of the explorers that support them, signalled along with
+    /// every `KillExplorer` so that the explorer can exit without finishing its wait.
+    pub explorer_kill_senders: HashMap<u32, Sender<()>>,
+    /// Topology notification channels of the explorers, see
+    /// [`flush_topology_deltas`](Self::flush_topology_deltas).
+    pub explorer_topology_senders: HashMap<u32, Sender<TopologyDelta>>,
+    /// Topology changes not yet sent to the explorers.
+    pub pending_topology_delta: TopologyDelta,
+    /// Handles of the explorer threads, indexed by explorer id, dropped when the
+    /// explorer is removed.
+    #[cfg(feature = "expose-handles")]
+    pub explorer_handles: HashMap<u32, std::thread::JoinHandle<Result<(), String>>>,
 
     //Channel to clone for the planets and for receiving Planet Messages
+    /// Kept for the whole life of the orchestrator, even with no planet alive, so that
+    /// `receiver_orch_planet` never disconnects: replacing it is a bug, reported by
+    /// [`handle_game_messages`](Self::handle_game_messages) as
+    /// [`MessageLoopStatus::ChannelClosed`](handlers::MessageLoopStatus::ChannelClosed).
     pub sender_planet_orch: Sender<PlanetToOrchestrator>,
     pub receiver_orch_planet: Receiver<PlanetToOrchestrator>,
 
     //Channel to clone for the explorer and for receiving Explorer Messages
+    /// Kept for the whole life of the orchestrator, like `sender_planet_orch`.
     pub sender_explorer_orch: Sender<ExplorerToOrchestrator<BagType>>,
     pub receiver_orch_explorer: Receiver<ExplorerToOrchestrator<BagType>>,
 
     pub gui_messages: Vec<OrchestratorEvent>,
+    /// Version of the last [`GalaxyState`] published, bumped every time the published
+    /// state differs from the previous one, see [`galaxy_update`](Self::galaxy_update).
+    pub snapshot_version: u64,
+    /// Last published state, and the one before it.
+    pub published_snapshots: (Option<GalaxyState>, Option<GalaxyState>),
+    /// Handlers of the planet messages, see
+    /// [`planet_handlers_mut`](Self::planet_handlers_mut).
+    pub planet_handlers: Arc<HandlerRegistry<PlanetToOrchestrator>>,
+    /// Handlers of the explorer messages, see
+    /// [`explorer_handlers_mut`](Self::explorer_handlers_mut).
+    pub explorer_handlers: Arc<HandlerRegistry<ExplorerToOrchestrator<BagType>>>,
+    /// Time after which a killed explorer is removed, None to keep the killed
+    /// explorers as tombstones until [`remove_explorer`](Self::remove_explorer) is
+    /// called, [`DEFAULT_EXPLORER_REMOVAL_DELAY`](handlers::DEFAULT_EXPLORER_REMOVAL_DELAY)
+    /// by default.
+    pub explorer_removal_delay: Option<Duration>,
+    /// Killed explorers waiting to be removed, with the time of their removal.
+    pub pending_explorer_removals: Vec<(u32, Instant)>,
+    /// Command each explorer in manual mode is executing, see
+    /// [`command_explorer_move`](Self::command_explorer_move).
+    pub manual_commands: HashMap<u32, ManualCommand>,
+    /// Number of steps run with [`tick_once`](Self::tick_once).
+    pub tick: u64,
+    /// Number of sunrays sent since the galaxy was initialized.
+    pub sunrays_sent: u64,
+    /// Number of asteroids sent since the galaxy was initialized.
+    pub asteroids_sent: u64,
+    /// Simulated time of the game and schedule of the sunrays and asteroids, see
+    /// [`advance_game`](Self::advance_game).
+    pub game_clock: GameClock,
+    /// How the running explorers that made no progress are nudged, None to leave them
+    /// alone (the default), see [`nudge_idle_explorers`](Self::nudge_idle_explorers).
+    pub idle_policy: Option<IdlePolicy>,
+    /// Progress of the explorers and nudges they got.
+    pub idle_watch: IdleWatch,
+    /// Whether the groups of planets left by a split of the galaxy are linked again
+    /// with random links, false by default.
+    pub rejoin_partitions: bool,
+
+    /// Maximum number of planets accepted when a galaxy is initialized,
+    /// [`DEFAULT_MAX_GALAXY_SIZE`](init::DEFAULT_MAX_GALAXY_SIZE) by default.
+    pub max_galaxy_size: usize,
+
+    /// Transcripts of the protocol messages, recorded only when message tracing
+    /// is enabled (see [`enable_message_tracing`](Self::enable_message_tracing)).
+    pub message_recorder: Option<MessageRecorder>,
+
+    /// Changes of the energy cells of the planets, sent only when the telemetry
+    /// is enabled (see [`enable_cell_telemetry`](Self::enable_cell_telemetry)).
+    pub cell_telemetry: Option<Sender<CellRecord>>,
+
+    /// Panics of the actor threads started by this orchestrator, reported by the
+    /// panic hook, see [`collect_actor_crashes`](Self::collect_actor_crashes).
+    pub crash_receiver: Receiver<ActorCrash>,
+    /// Handed to every actor thread so that its panic reaches `crash_receiver`.
+    pub(crate) crash_sender: Sender<ActorCrash>,
 }
 impl Orchestrator {
     /// Create a new orchestrator instance.
+    ///
+    /// Returns Err if a planet type has no factory in the planet registry, see
+    /// [`validate_planet_registry`].
     pub fn new() -> Result<Self, String> {
         //env_logger initialization
         let _res = env_logger::try_init();
+        install_panic_hook();
+        let (crash_sender, crash_receiver) = unbounded();
         //Log
         log_fn_call!(dir ActorType::Orchestrator, 0u32, "new()",);
         //LOG
@@ -106,24 +223,72 @@ impl Orchestrator {
         );
         //LOG
 
+        // checked before the forge is made, since only one can exist
+        validate_planet_registry()?;
+
         let new_orch = Self {
             forge: Forge::new()?,
             galaxy_topology: Self::new_gtop(),
             galaxy_lookup: FxHashMap::default(),
             galaxy_reverse_lookup: FxHashMap::default(),
+            neighbor_cache: FxHashMap::default(),
             planets_info: PlanetInfoMap::new(),
             explorers_info: ExplorerInfoMap::new(),
             planet_channels: HashMap::new(),
             explorer_channels: HashMap::new(),
+            explorer_kill_senders: HashMap::new(),
+            explorer_topology_senders: HashMap::new(),
+            pending_topology_delta: TopologyDelta::new(),
+            #[cfg(feature = "expose-handles")]
+            explorer_handles: HashMap::new(),
             sender_planet_orch,
             receiver_orch_planet,
             sender_explorer_orch,
             receiver_orch_explorer,
             gui_messages: Vec::new(),
+            snapshot_version: 0,
+            published_snapshots: (None, None),
+            planet_handlers: Arc::new(Self::default_planet_handlers()),
+            explorer_handlers: Arc::new(Self::default_explorer_handlers()),
+            explorer_removal_delay: Some(handlers::DEFAULT_EXPLORER_REMOVAL_DELAY),
+            pending_explorer_removals: Vec::new(),
+            manual_commands: HashMap::new(),
+            tick: 0,
+            sunrays_sent: 0,
+            asteroids_sent: 0,
+            game_clock: GameClock::default(),
+            idle_policy: None,
+            idle_watch: IdleWatch::new(),
+            rejoin_partitions: false,
+            max_galaxy_size: init::DEFAULT_MAX_GALAXY_SIZE,
+            message_recorder: None,
+            cell_telemetry: None,
+            crash_receiver,
+            crash_sender,
         };
         Ok(new_orch)
     }
 
+    /// Returns the routing table of the planet messages, to add or replace handlers.
+    ///
+    /// Returns Err if called from a handler, while a message is being dispatched.
+    pub fn planet_handlers_mut(
+        &mut self,
+    ) -> Result<&mut HandlerRegistry<PlanetToOrchestrator>, String> {
+        Arc::get_mut(&mut self.planet_handlers)
+            .ok_or_else(|| "The planet handlers are in use".to_string())
+    }
+
+    /// Returns the routing table of the explorer messages, to add or replace handlers.
+    ///
+    /// Returns Err if called from a handler, while a message is being dispatched.
+    pub fn explorer_handlers_mut(
+        &mut self,
+    ) -> Result<&mut HandlerRegistry<ExplorerToOrchestrator<BagType>>, String> {
+        Arc::get_mut(&mut self.explorer_handlers)
+            .ok_or_else(|| "The explorer handlers are in use".to_string())
+    }
+
     pub fn get_random_planet_id(&self) -> Result<u32, String> {
         //LOG
         log_fn_call!(self, "get_random_planet_id()");
@@ -147,3 +312,25 @@ impl LoggableActor for Orchestrator {
         0
     }
 }
+
+impl Drop for Orchestrator {
+    /// Kills the planets and the explorers still alive, so that their threads do not
+    /// outlive the orchestrator.
+    ///
+    /// The kill messages are sent without waiting for the answers, which could not be
+    /// received anyway; only the explorer threads whose handle is kept are joined.
+    fn drop(&mut self) {
+        for (id, handle) in self.living_explorers() {
+            // the explorer thread might already be gone
+            let _ = handle.send_to_explorer(OrchestratorToExplorer::KillExplorer);
+            self.signal_explorer_kill(id);
+        }
+        for (_, handle) in self.living_planets() {
+            let _ = handle.send_to_planet(OrchestratorToPlanet::KillPlanet);
+        }
+        #[cfg(feature = "expose-handles")]
+        for (_, handle) in self.explorer_handles.drain() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -1,16 +1,20 @@
-#[cfg(test)]
 use crate::Status;
 use crate::{components::orchestrator::Orchestrator};
+use crate::utils::cell_telemetry::cell_changes;
+use crate::utils::tracing::TracedSender;
+use crate::utils::{Launch, LaunchInfo};
+use common_game::components::planet::DummyPlanetState;
 use common_game::logging::{Channel, LogEvent, Participant};
 use common_game::utils::ID;
 use common_game::{
     logging::{ActorType, EventType},
-    protocols::orchestrator_planet::OrchestratorToPlanet,
+    protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator},
 };
-use crossbeam_channel::Sender;
+use crossbeam_channel::{TrySendError, after, select};
 use logging_utils::{
     LoggableActor, log_fn_call, log_internal_op, log_orch_to_planet, warning_payload,
 };
+use std::time::Duration;
 
 impl Orchestrator {
     /// Send a sun ray to a planet.
@@ -21,7 +25,7 @@ impl Orchestrator {
     pub fn send_sunray(
         &mut self,
         planet_id: u32,
-        sender: &Sender<OrchestratorToPlanet>,
+        sender: &TracedSender<OrchestratorToPlanet>,
     ) -> Result<(), String> {
         //LOG
         log_fn_call!(
@@ -35,13 +39,19 @@ impl Orchestrator {
         let _handle_by_log = sender
             .send(OrchestratorToPlanet::Sunray(self.forge.generate_sunray()))
             .map_err(|_| "Unable to send a sunray to planet: {id}".to_string());
-        self.emit_sunray_send(planet_id);
+        self.sunrays_sent += 1;
+        let info = LaunchInfo::new(
+            Launch::Sunray,
+            self.sunrays_sent,
+            self.planets_info.get_info(planet_id),
+        );
+        self.emit_sunray_send(planet_id, info);
 
         //send update request
         self.send_internal_state_request(sender, planet_id)?;
 
         //LOG
-        log_orch_to_planet!(self, "sunray sent", planet_id);
+        self.log_launch(planet_id, &info);
         //LOG
         Ok(())
     }
@@ -55,18 +65,7 @@ impl Orchestrator {
         log_fn_call!(self, "send_sunray_to_all()");
         //LOG
         //collect all the senders in a vector
-        let senders_sunray: Vec<(u32, Sender<OrchestratorToPlanet>)> = self
-            .planet_channels
-            .iter()
-            .filter_map(|(id, (sender, _))| {
-                let status = &self.planets_info;
-                if status.get_status(id) != Status::Dead {
-                    Some((*id, sender.clone()))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let senders_sunray = self.living_planet_senders("send_sunray_to_all()");
 
         // actually send the messages
         for (id, sender) in senders_sunray {
@@ -75,6 +74,43 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Collects the senders of the planets that are not dead.
+    ///
+    /// A planet with a channel but no entry in `planets_info`, as happens while it is
+    /// being added, is skipped with a warning.
+    #[cfg(test)]
+    fn living_planet_senders(
+        &self,
+        caller: &str,
+    ) -> Vec<(u32, TracedSender<OrchestratorToPlanet>)> {
+        self.planet_channels
+            .iter()
+            .filter_map(|(id, handle)| match self.planets_info.get_info(*id) {
+                Some(info) if info.status != Status::Dead => {
+                    Some((*id, handle.to_planet().clone()))
+                }
+                Some(_) => None,
+                None => {
+                    //LOG
+                    LogEvent::self_directed(
+                        Participant::new(ActorType::Orchestrator, 0u32),
+                        EventType::InternalOrchestratorAction,
+                        Channel::Warning,
+                        warning_payload!(
+                            "planet has a channel but no status, skipped",
+                            "_",
+                            caller;
+                            "planet_id"=>id
+                        ),
+                    )
+                    .emit();
+                    //LOG
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Send an asteroid to a planet.
     ///
     /// Requests an asteroid through the `forge` and sends it to the planet.
@@ -83,7 +119,7 @@ impl Orchestrator {
     pub fn send_asteroid(
         &mut self,
         planet_id: u32,
-        sender: &Sender<OrchestratorToPlanet>,
+        sender: &TracedSender<OrchestratorToPlanet>,
     ) -> Result<(), String> {
         //LOG
         log_fn_call!(
@@ -99,16 +135,34 @@ impl Orchestrator {
                 self.forge.generate_asteroid(),
             ))
             .map_err(|_| "Unable to send asteroid to planet: {id}".to_string());
-        self.emit_asteroid_send(planet_id);
+        self.asteroids_sent += 1;
+        let info = LaunchInfo::new(
+            Launch::Asteroid,
+            self.asteroids_sent,
+            self.planets_info.get_info(planet_id),
+        );
+        self.emit_asteroid_send(planet_id, info);
         //send update request
         self.send_internal_state_request(sender, planet_id)?;
 
         //LOG
-        log_orch_to_planet!(self, "asteroid sent", planet_id);
+        self.log_launch(planet_id, &info);
         //LOG
         Ok(())
     }
 
+    /// Logs a sunray or an asteroid sent to a planet, with its details.
+    fn log_launch(&self, planet_id: u32, info: &LaunchInfo) {
+        LogEvent::new(
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            Some(Participant::new(ActorType::Planet, planet_id)),
+            EventType::MessageOrchestratorToPlanet,
+            Channel::Debug,
+            info.payload(planet_id),
+        )
+        .emit();
+    }
+
 
     /// Kill a specific planet.
     ///
@@ -120,7 +174,7 @@ impl Orchestrator {
     pub fn send_planet_kill(
         &mut self,
         planet_id: u32,
-        sender: &Sender<OrchestratorToPlanet>,
+        sender: &TracedSender<OrchestratorToPlanet>,
     ) -> Result<(), String> {
         //LOG
         log_fn_call!(
@@ -147,18 +201,7 @@ impl Orchestrator {
         //LOG
 
         //collect all the senders in a vector
-        let senders_to_kill: Vec<(u32, Sender<OrchestratorToPlanet>)> = self
-            .planet_channels
-            .iter()
-            .filter_map(|(id, (sender, _))| {
-                let status = &self.planets_info;
-                if status.get_status(id) != Status::Dead {
-                    Some((*id, sender.clone()))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let senders_to_kill = self.living_planet_senders("send_planet_kill_to_all()");
 
         // actually send the messages
         for (id, sender) in senders_to_kill {
@@ -169,7 +212,7 @@ impl Orchestrator {
 
     pub fn send_internal_state_request(
         &self,
-        sender: &Sender<OrchestratorToPlanet>,
+        sender: &TracedSender<OrchestratorToPlanet>,
         planet_id: ID,
     ) -> Result<(), String> {
         //LOG
@@ -186,6 +229,147 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Tells whether a planet can deflect an asteroid.
+    ///
+    /// The answer is the last known rocket state of the planet, updated on every
+    /// `AsteroidAck` and `InternalStateResponse`; dead planets cannot deflect.
+    ///
+    /// Returns None if the planet does not exist.
+    pub fn planet_can_deflect(&self, planet_id: ID) -> Option<bool> {
+        self.planets_info
+            .get_info(planet_id)
+            .map(|info| info.rocket && info.status != Status::Dead)
+    }
+
+    /// Asks a planet for its internal state and waits for the answer.
+    ///
+    /// Sends `InternalStateRequest` and polls the planet receiver until the matching
+    /// `InternalStateResponse` arrives; the planet info is updated with it as well.
+    /// Messages from other planets received in the meantime are handled normally, a
+    /// handler error is logged and the wait goes on.
+    ///
+    /// Returns Err if the planet does not exist, its channel is inaccessible or
+    /// no answer arrives within `timeout`.
+    pub fn query_planet_state(
+        &mut self,
+        planet_id: ID,
+        timeout: Duration,
+    ) -> Result<DummyPlanetState, String> {
+        //LOG
+        log_fn_call!(self, "query_planet_state()", planet_id, timeout,);
+        //LOG
+        self.planet_channels
+            .get(&planet_id)
+            .ok_or_else(|| format!("planet {} does not exist", planet_id))?
+            .send_to_planet(OrchestratorToPlanet::InternalStateRequest)
+            .map_err(|_| format!("Unable to send planet state request to {}", planet_id))?;
+        log_orch_to_planet!(self, "RequestPlanetState sent", planet_id);
+
+        let deadline = after(timeout);
+        loop {
+            select! {
+                recv(self.receiver_orch_planet) -> msg => {
+                    let msg = msg.map_err(|e| format!("Cannot receive message from planets: {}", e))?;
+                    match msg {
+                        PlanetToOrchestrator::InternalStateResponse {
+                            planet_id: id,
+                            planet_state,
+                        } if id == planet_id => {
+                            self.update_planet_state(planet_id, &planet_state);
+                            return Ok(planet_state);
+                        }
+                        other => {
+                            let msg_string = format!("{:?}", other);
+                            if let Err(err) = self.handle_planet_message(other) {
+                                self.report_handler_error(&msg_string, err, "query_planet_state()");
+                            }
+                        }
+                    }
+                }
+                recv(deadline) -> _ => {
+                    return Err(format!(
+                        "planet {} did not send its internal state within {:?}",
+                        planet_id, timeout
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Update the planet info with the internal state sent by the planet.
+    ///
+    /// If the cell telemetry is enabled, the changes of the energy cells are sent
+    /// first; it is disabled if the GUI dropped the receiving end.
+    pub(crate) fn update_planet_state(&mut self, planet_id: ID, planet_state: &DummyPlanetState) {
+        if let (Some(telemetry), Some(info)) =
+            (&self.cell_telemetry, self.planets_info.get_info(planet_id))
+        {
+            let changes = cell_changes(
+                planet_id,
+                (&info.energy_cells, info.rocket),
+                (&planet_state.energy_cells, planet_state.has_rocket),
+            );
+            // a full channel drops the record, the GUI only misses a frame
+            let disconnected = changes.into_iter().any(|record| {
+                matches!(
+                    telemetry.try_send(record),
+                    Err(TrySendError::Disconnected(_))
+                )
+            });
+            if disconnected {
+                self.cell_telemetry = None;
+            }
+        }
+        self.planets_info
+            .update_from_planet_state(planet_id, planet_state);
+    }
+
+    /// Kill a planet and wait for its acknowledgement.
+    ///
+    /// Sends a KillPlanet message (see [`send_planet_kill`](`Self::send_planet_kill`))
+    /// and handles the incoming planet messages until the planet is marked as dead,
+    /// which happens when its `KillPlanetResult` is handled.
+    ///
+    /// Returns Err if the planet does not exist or is already dead, or if it does
+    /// not acknowledge the kill within `timeout`.
+    pub fn kill_planet(&mut self, planet_id: ID, timeout: Duration) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "kill_planet()", planet_id, timeout,);
+        //LOG
+        if self.planets_info.is_dead(&planet_id) {
+            return Err(format!("planet {} is already dead", planet_id));
+        }
+        let sender = self
+            .planet_channels
+            .get(&planet_id)
+            .map(|handle| handle.to_planet().clone())
+            .ok_or_else(|| format!("planet {} does not exist", planet_id))?;
+        self.send_planet_kill(planet_id, &sender)?;
+
+        let deadline = after(timeout);
+        loop {
+            select! {
+                recv(self.receiver_orch_planet) -> msg => {
+                    let msg = msg.map_err(|e| format!("Cannot receive message from planets: {}", e))?;
+                    let msg_string = format!("{:?}", msg);
+                    // the status update is done by handle_planet_message
+                    if let Err(err) = self.handle_planet_message(msg) {
+                        self.report_handler_error(&msg_string, err, "kill_planet()");
+                    }
+                    if self.planets_info.is_dead(&planet_id) {
+                        return Ok(());
+                    }
+                }
+                recv(deadline) -> _ => {
+                    return Err(format!(
+                        "Timeout: planet {} did not acknowledge KillPlanet within {:?}",
+                        planet_id, timeout
+                    ));
+                }
+            }
+        }
+    }
+
     pub fn send_incoming_explorer_request(
         &self,
         planet_id: ID,
@@ -224,12 +408,10 @@ impl Orchestrator {
             }
         };
 
-        match sender
-            .0
-            .send(OrchestratorToPlanet::IncomingExplorerRequest {
-                explorer_id,
-                new_sender: new_planet_to_explorer_sender.1.clone(),
-            }) {
+        match sender.send_to_planet(OrchestratorToPlanet::IncomingExplorerRequest {
+            explorer_id,
+            new_sender: new_planet_to_explorer_sender.planet_sender_for_arrival(),
+        }) {
             Ok(_) => {
                 log_orch_to_planet!(self, "IncomingExplorerRequest sent", planet_id);
             }
@@ -248,7 +430,7 @@ impl Orchestrator {
                     ),
                 )
                 .emit();
-                return Err(err.to_string());
+                return Err(err);
             }
         }
 
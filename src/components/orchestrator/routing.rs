@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use common_game::logging::{ActorType, Channel, EventType, LogEvent, Participant};
+use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+use logging_utils::warning_payload;
+
+use crate::components::orchestrator::Orchestrator;
+use crate::components::tommy_explorer::bag::BagType;
+
+/// A handler of the messages of one kind.
+pub type MessageHandler<M> = Box<dyn Fn(&mut Orchestrator, M) -> Result<(), String> + Send + Sync>;
+
+/// A message that can be routed by a [`HandlerRegistry`].
+pub trait RoutedMessage {
+    /// The kinds of the message, one for each variant.
+    type Kind: Copy + Eq + Hash + Debug;
+
+    /// Returns the kind of the message.
+    fn message_kind(&self) -> Self::Kind;
+}
+
+/// Routing table from the kind of a message to its handler.
+///
+/// Messages of a kind without a handler go to the `unhandled` handler, which by
+/// default logs a warning and ignores them.
+pub struct HandlerRegistry<M: RoutedMessage> {
+    handlers: HashMap<M::Kind, MessageHandler<M>>,
+    unhandled: MessageHandler<M>,
+}
+
+impl<M: RoutedMessage + Debug + 'static> HandlerRegistry<M> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        HandlerRegistry {
+            handlers: HashMap::new(),
+            unhandled: Box::new(log_unhandled),
+        }
+    }
+
+    /// Sets the handler of the messages of the given kind.
+    ///
+    /// Returns the handler it replaces, if any.
+    pub fn register(
+        &mut self,
+        kind: M::Kind,
+        handler: impl Fn(&mut Orchestrator, M) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Option<MessageHandler<M>> {
+        self.handlers.insert(kind, Box::new(handler))
+    }
+
+    /// Removes the handler of the messages of the given kind.
+    pub fn unregister(&mut self, kind: M::Kind) -> Option<MessageHandler<M>> {
+        self.handlers.remove(&kind)
+    }
+
+    /// Sets the handler of the messages whose kind has no handler.
+    pub fn set_unhandled(
+        &mut self,
+        handler: impl Fn(&mut Orchestrator, M) -> Result<(), String> + Send + Sync + 'static,
+    ) {
+        self.unhandled = Box::new(handler);
+    }
+
+    /// Checks if the messages of the given kind have a handler.
+    pub fn is_registered(&self, kind: M::Kind) -> bool {
+        self.handlers.contains_key(&kind)
+    }
+
+    /// Passes the message to the handler of its kind.
+    pub fn dispatch(&self, orch: &mut Orchestrator, msg: M) -> Result<(), String> {
+        match self.handlers.get(&msg.message_kind()) {
+            Some(handler) => handler(orch, msg),
+            None => (self.unhandled)(orch, msg),
+        }
+    }
+}
+
+impl<M: RoutedMessage + Debug + 'static> Default for HandlerRegistry<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn log_unhandled<M: Debug>(_orch: &mut Orchestrator, msg: M) -> Result<(), String> {
+    //LOG
+    LogEvent::self_directed(
+        Participant::new(ActorType::Orchestrator, 0u32),
+        EventType::InternalOrchestratorAction,
+        Channel::Warning,
+        warning_payload!(
+            "unhandled message",
+            format!("{:?}", msg),
+            "HandlerRegistry::dispatch()"
+        ),
+    )
+    .emit();
+    //LOG
+    Ok(())
+}
+
+/// The kind of a planet message, i.e. its variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlanetMessageKind {
+    SunrayAck,
+    AsteroidAck,
+    InternalStateResponse,
+    KillPlanetResult,
+    StartPlanetAIResult,
+    StopPlanetAIResult,
+    Stopped,
+    IncomingExplorerResponse,
+    OutgoingExplorerResponse,
+}
+
+/// The kind of an explorer message, i.e. its variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExplorerMessageKind {
+    StartExplorerAIResult,
+    KillExplorerResult,
+    ResetExplorerAIResult,
+    StopExplorerAIResult,
+    MovedToPlanetResult,
+    CurrentPlanetResult,
+    SupportedResourceResult,
+    SupportedCombinationResult,
+    GenerateResourceResponse,
+    CombineResourceResponse,
+    BagContentResponse,
+    NeighborsRequest,
+    TravelToPlanetRequest,
+}
+
+/// Every kind of planet message.
+pub const PLANET_MESSAGE_KINDS: [PlanetMessageKind; 9] = [
+    PlanetMessageKind::SunrayAck,
+    PlanetMessageKind::AsteroidAck,
+    PlanetMessageKind::InternalStateResponse,
+    PlanetMessageKind::KillPlanetResult,
+    PlanetMessageKind::StartPlanetAIResult,
+    PlanetMessageKind::StopPlanetAIResult,
+    PlanetMessageKind::Stopped,
+    PlanetMessageKind::IncomingExplorerResponse,
+    PlanetMessageKind::OutgoingExplorerResponse,
+];
+
+/// Every kind of explorer message.
+pub const EXPLORER_MESSAGE_KINDS: [ExplorerMessageKind; 13] = [
+    ExplorerMessageKind::StartExplorerAIResult,
+    ExplorerMessageKind::KillExplorerResult,
+    ExplorerMessageKind::ResetExplorerAIResult,
+    ExplorerMessageKind::StopExplorerAIResult,
+    ExplorerMessageKind::MovedToPlanetResult,
+    ExplorerMessageKind::CurrentPlanetResult,
+    ExplorerMessageKind::SupportedResourceResult,
+    ExplorerMessageKind::SupportedCombinationResult,
+    ExplorerMessageKind::GenerateResourceResponse,
+    ExplorerMessageKind::CombineResourceResponse,
+    ExplorerMessageKind::BagContentResponse,
+    ExplorerMessageKind::NeighborsRequest,
+    ExplorerMessageKind::TravelToPlanetRequest,
+];
+
+impl RoutedMessage for PlanetToOrchestrator {
+    type Kind = PlanetMessageKind;
+
+    fn message_kind(&self) -> PlanetMessageKind {
+        match self {
+            PlanetToOrchestrator::SunrayAck { .. } => PlanetMessageKind::SunrayAck,
+            PlanetToOrchestrator::AsteroidAck { .. } => PlanetMessageKind::AsteroidAck,
+            PlanetToOrchestrator::InternalStateResponse { .. } => {
+                PlanetMessageKind::InternalStateResponse
+            }
+            PlanetToOrchestrator::KillPlanetResult { .. } => PlanetMessageKind::KillPlanetResult,
+            PlanetToOrchestrator::StartPlanetAIResult { .. } => {
+                PlanetMessageKind::StartPlanetAIResult
+            }
+            PlanetToOrchestrator::StopPlanetAIResult { .. } => {
+                PlanetMessageKind::StopPlanetAIResult
+            }
+            PlanetToOrchestrator::Stopped { .. } => PlanetMessageKind::Stopped,
+            PlanetToOrchestrator::IncomingExplorerResponse { .. } => {
+                PlanetMessageKind::IncomingExplorerResponse
+            }
+            PlanetToOrchestrator::OutgoingExplorerResponse { .. } => {
+                PlanetMessageKind::OutgoingExplorerResponse
+            }
+        }
+    }
+}
+
+impl RoutedMessage for ExplorerToOrchestrator<BagType> {
+    type Kind = ExplorerMessageKind;
+
+    fn message_kind(&self) -> ExplorerMessageKind {
+        match self {
+            ExplorerToOrchestrator::StartExplorerAIResult { .. } => {
+                ExplorerMessageKind::StartExplorerAIResult
+            }
+            ExplorerToOrchestrator::KillExplorerResult { .. } => {
+                ExplorerMessageKind::KillExplorerResult
+            }
+            ExplorerToOrchestrator::ResetExplorerAIResult { .. } => {
+                ExplorerMessageKind::ResetExplorerAIResult
+            }
+            ExplorerToOrchestrator::StopExplorerAIResult { .. } => {
+                ExplorerMessageKind::StopExplorerAIResult
+            }
+            ExplorerToOrchestrator::MovedToPlanetResult { .. } => {
+                ExplorerMessageKind::MovedToPlanetResult
+            }
+            ExplorerToOrchestrator::CurrentPlanetResult { .. } => {
+                ExplorerMessageKind::CurrentPlanetResult
+            }
+            ExplorerToOrchestrator::SupportedResourceResult { .. } => {
+                ExplorerMessageKind::SupportedResourceResult
+            }
+            ExplorerToOrchestrator::SupportedCombinationResult { .. } => {
+                ExplorerMessageKind::SupportedCombinationResult
+            }
+            ExplorerToOrchestrator::GenerateResourceResponse { .. } => {
+                ExplorerMessageKind::GenerateResourceResponse
+            }
+            ExplorerToOrchestrator::CombineResourceResponse { .. } => {
+                ExplorerMessageKind::CombineResourceResponse
+            }
+            ExplorerToOrchestrator::BagContentResponse { .. } => {
+                ExplorerMessageKind::BagContentResponse
+            }
+            ExplorerToOrchestrator::NeighborsRequest { .. } => {
+                ExplorerMessageKind::NeighborsRequest
+            }
+            ExplorerToOrchestrator::TravelToPlanetRequest { .. } => {
+                ExplorerMessageKind::TravelToPlanetRequest
+            }
+        }
+    }
+}
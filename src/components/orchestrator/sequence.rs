@@ -0,0 +1,210 @@
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common_game::logging::ActorType;
+use logging_utils::{LoggableActor, log_fn_call};
+
+use crate::Orchestrator;
+use crate::utils::registry::PlanetType;
+use crate::utils::{Cadence, GameClock, GameReport};
+
+/// Real time between two iterations of the loop of
+/// [`run_headless`](Orchestrator::run_headless).
+pub const HEADLESS_STEP: Duration = Duration::from_millis(10);
+
+/// A single step of a scripted game scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrchestratorAction {
+    /// Send a sunray to a random living planet.
+    Sunray,
+    /// Send an asteroid to a random living planet.
+    Asteroid,
+    /// Wait before executing the next action.
+    Pause(Duration),
+}
+
+/// Planets targeted by an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanetFilter {
+    /// Every living planet.
+    AllPlanets,
+    /// The living planets among the given ids.
+    PlanetIds(Vec<u32>),
+    /// The living planets of the given type.
+    PlanetType(PlanetType),
+}
+
+/// Parses a duration written as `<number>ms` or `<number>s`.
+fn parse_pause_duration(s: &str) -> Option<Duration> {
+    if let Some(ms) = s.strip_suffix("ms") {
+        return ms.trim().parse::<u64>().ok().map(Duration::from_millis);
+    }
+    s.strip_suffix('s')
+        .and_then(|secs| secs.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Parses a single action, ignoring case and surrounding whitespace.
+///
+/// Accepted actions: `sunray`, `asteroid`, `pause <n>ms`, `pause <n>s`.
+pub fn parse_orchestrator_action(s: &str) -> Result<OrchestratorAction, String> {
+    let action = s.trim().to_ascii_lowercase();
+    match action.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["sunray"] => Ok(OrchestratorAction::Sunray),
+        ["asteroid"] => Ok(OrchestratorAction::Asteroid),
+        ["pause", duration] => parse_pause_duration(duration)
+            .map(OrchestratorAction::Pause)
+            .ok_or_else(|| format!("Invalid pause duration: '{}'", duration)),
+        _ => Err(format!("Unknown action: '{}'", s.trim())),
+    }
+}
+
+/// Parses a sequence of actions, one per line.
+///
+/// Empty lines and lines starting with `#` are skipped.
+///
+/// Returns Err with the line number (starting from 1) and the content of the
+/// first line that cannot be parsed.
+pub fn parse_sequence(content: &str) -> Result<Vec<OrchestratorAction>, String> {
+    let mut actions = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let action = parse_orchestrator_action(line)
+            .map_err(|err| format!("line {}: '{}': {}", line_num + 1, line, err))?;
+        actions.push(action);
+    }
+    Ok(actions)
+}
+
+impl Orchestrator {
+    /// Expand a planet filter against the current planet set.
+    ///
+    /// The filter is evaluated every time this is called, so dead planets are
+    /// always skipped; the planet types are taken from `galaxy_lookup`.
+    ///
+    /// Returns the matching planet ids sorted in ascending order.
+    pub fn planets_matching(&self, filter: &PlanetFilter) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .planets_info
+            .get_list_id_alive()
+            .into_iter()
+            .filter(|id| match filter {
+                PlanetFilter::AllPlanets => true,
+                PlanetFilter::PlanetIds(ids) => ids.contains(id),
+                PlanetFilter::PlanetType(planet_type) => self
+                    .galaxy_lookup
+                    .get(id)
+                    .is_some_and(|(_, t)| t == planet_type),
+            })
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Load a game scenario from a sequence file.
+    ///
+    /// The file contains one action per line (see [`parse_sequence`] for the
+    /// format), e.g.:
+    /// ```text
+    /// sunray
+    /// pause 500ms
+    /// asteroid
+    /// ```
+    ///
+    /// Returns Err if the file cannot be read or if a line cannot be parsed;
+    /// in the latter case the error contains the line number and its content.
+    ///
+    /// * `path` - path to the sequence file
+    pub fn sequence_from_file(path: &str) -> Result<Vec<OrchestratorAction>, String> {
+        //LOG
+        log_fn_call!(dir ActorType::Orchestrator, 0u32, "sequence_from_file()", path,);
+        //LOG
+        let content = fs::read_to_string(path)
+            .map_err(|_| format!("Unable to read the sequence from {path}"))?;
+        parse_sequence(&content)
+    }
+
+    /// Run a game scenario on an orchestrator prepared by the caller.
+    ///
+    /// The galaxy must already be initialized and the AIs started; each action of
+    /// the sequence (see [`parse_sequence`] for the format) runs in its own
+    /// [`tick_once`](Self::tick_once).
+    ///
+    /// Returns Err if the sequence cannot be parsed, in which case nothing runs,
+    /// or if a tick fails.
+    ///
+    /// * `sequence` - content of a sequence file
+    pub fn run_sequence(&mut self, sequence: &str) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "run_sequence()"; "sequence"=>sequence);
+        //LOG
+        for action in parse_sequence(sequence)? {
+            self.tick_once(Some(action))?;
+        }
+        Ok(())
+    }
+
+    /// Run a game scenario on a new orchestrator.
+    ///
+    /// Builds the orchestrator, loads the galaxy from `file_path`, starts the
+    /// planet AIs and delegates to [`run_sequence`](Self::run_sequence); use
+    /// the latter directly to customize the orchestrator before the game starts.
+    ///
+    /// Returns the orchestrator at the end of the sequence, or Err if any step fails.
+    ///
+    /// * `file_path` - path to the galaxy file
+    /// * `sequence` - content of a sequence file
+    pub fn run(file_path: &str, sequence: &str) -> Result<Orchestrator, String> {
+        let mut orch = Orchestrator::new()?;
+        orch.initialize_galaxy_by_file(file_path)?;
+        orch.start_all(&[], &[])?;
+        orch.run_sequence(sequence)?;
+        Ok(orch)
+    }
+
+    /// Run a game without GUI on a new orchestrator for `duration` of real time.
+    ///
+    /// Builds the orchestrator, loads the galaxy from `file_path` and starts the
+    /// planets and the explorers as [`start_all`](Self::start_all) does. Then the
+    /// game loop calls [`advance_game`](Self::advance_game) every [`HEADLESS_STEP`],
+    /// launching the celestial bodies at the given `cadence`, until `duration` has
+    /// passed or every planet is dead. Finally every actor is killed.
+    ///
+    /// Returns the report of the galaxy at the end of the game, or Err if any step fails.
+    ///
+    /// * `file_path` - path to the galaxy file
+    /// * `mattia_explorers`, `tommy_explorers` - pairs `(explorer_id, planet_id)`
+    /// * `cadence` - simulated time between the sunrays and between the asteroids
+    /// * `duration` - real time the game lasts
+    pub fn run_headless(
+        file_path: &str,
+        mattia_explorers: &[(u32, u32)],
+        tommy_explorers: &[(u32, u32)],
+        cadence: Cadence,
+        duration: Duration,
+    ) -> Result<GameReport, String> {
+        let mut orch = Orchestrator::new()?;
+        orch.initialize_galaxy_by_file(file_path)?;
+        orch.game_clock = GameClock::new(cadence);
+        orch.start_all(mattia_explorers, tommy_explorers)?;
+
+        let start = Instant::now();
+        let mut last_step = start;
+        while start.elapsed() < duration && !orch.planets_info.get_list_id_alive().is_empty() {
+            thread::sleep(HEADLESS_STEP);
+            let now = Instant::now();
+            orch.advance_game(now - last_step)?;
+            last_step = now;
+        }
+        // the messages sent during the last step are part of the game
+        orch.handle_game_messages()?;
+
+        let report = orch.game_report();
+        orch.reset()?;
+        Ok(report)
+    }
+}
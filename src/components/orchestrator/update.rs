@@ -1,3 +1,10 @@
+use crate::components::orchestrator::handlers::MessageLoopStatus;
+use crate::components::orchestrator::sequence::OrchestratorAction;
+use crate::utils::tracing::TracedSender;
+use crate::utils::{
+    CrossbeamChannelExt, ExplorerInfoMap, GameClock, IdleWatch, Launch, Nudge, Observation,
+    PlanetInfoMap, TopologyDelta, WeightedPlanetPicker,
+};
 use crate::{components::orchestrator::Orchestrator, utils::Status};
 use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
 use common_game::{
@@ -5,23 +12,36 @@ use common_game::{
     protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator},
 };
 use logging_utils::{
-    LOG_ACTORS_ACTIVITY, LoggableActor, Sender, debug_println, log_fn_call, log_internal_op,
-    log_message, payload, warning_payload,
+    LOG_ACTORS_ACTIVITY, LoggableActor, debug_println, log_fn_call, log_internal_op, log_message,
+    payload, warning_payload,
 };
 use rand::{Rng, random, seq::IndexedRandom};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::thread;
 use std::time::Duration;
 
+/// Time given to the explorers to pause before a reset kills them.
+const RESET_PAUSE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Time given to each explorer to acknowledge the kill during a reset.
+const RESET_KILL_TIMEOUT: Duration = Duration::from_millis(500);
+
 impl Orchestrator {
     /// Removes the link between two planets if one of them explodes.
     ///
-    /// Returns Err if the given indexes are out of bounds, Ok otherwise;
-    /// it does NOT currently check wether the link was already set to false beforehand.
+    /// Returns Err if the given indexes are out of bounds, otherwise the links that were
+    /// set, as `(neighbour_id, was_set_ab, was_set_ba)` where `ab` goes from the dead
+    /// planet to the neighbour; a one-directional link is also logged as a warning.
     /// The function uses CONTIGUOUS indexes; you can go from real to contiguous indexes
     /// using the galaxy lookup hashmap.
+    /// The dead planet and its removed links are added to the pending topology delta,
+    /// sent to the explorers by [`flush_topology_deltas`](Self::flush_topology_deltas).
     ///
     /// * `dead_planet_pos` - Position of the dead planet in the matrix. Must be a valid index
-    pub fn destroy_topology_link(&mut self, dead_planet_id: usize) -> Result<(), String> {
+    pub fn destroy_topology_link(
+        &mut self,
+        dead_planet_id: usize,
+    ) -> Result<Vec<(u32, bool, bool)>, String> {
         //LOG
         log_fn_call!(self, "destroy_topology_link()", dead_planet_id,);
         //LOG
@@ -32,7 +52,25 @@ impl Orchestrator {
             .ok_or_else(|| format!("planet {} not in lookup", dead_planet_id))?;
         let gtop_len = self.galaxy_topology.len();
         if dead_planet_pos < gtop_len {
+            let mut cleared = Vec::new();
+            let dead_id = dead_planet_id as u32;
+            // the planet is still marked alive, it is counted along with its links
+            let components_before = self
+                .components_of(|id| id == dead_id || !self.planets_info.is_dead(&id))
+                .len();
+            self.neighbor_cache.remove(&(dead_planet_id as u32));
+            self.pending_topology_delta
+                .planet_died(dead_planet_id as u32);
             for i in 0..gtop_len {
+                let was_set_ab = self.galaxy_topology[dead_planet_pos][i];
+                let was_set_ba = self.galaxy_topology[i][dead_planet_pos];
+                let linked = was_set_ab || was_set_ba;
+                if linked && let Some(neighbour_id) = self.galaxy_reverse_lookup.get(&(i as u32)) {
+                    self.pending_topology_delta
+                        .remove_link(dead_planet_id as u32, *neighbour_id);
+                    cleared.push((*neighbour_id, was_set_ab, was_set_ba));
+                    self.neighbor_cache.remove(neighbour_id);
+                }
                 self.galaxy_topology[dead_planet_pos][i] = false;
                 self.galaxy_topology[i][dead_planet_pos] = false;
             }
@@ -42,7 +80,30 @@ impl Orchestrator {
                 "action"=>"adj link destroyed",
                 "updated topology"=>format!("{:?}",self.galaxy_topology),
             );
-            Ok(())
+            for (neighbour_id, was_set_ab, was_set_ba) in &cleared {
+                if was_set_ab != was_set_ba {
+                    LogEvent::self_directed(
+                        Participant::new(ActorType::Orchestrator, 0u32),
+                        EventType::InternalOrchestratorAction,
+                        Channel::Warning,
+                        warning_payload!(
+                            "one-directional link in the adj matrix",
+                            format!("ab: {}, ba: {}", was_set_ab, was_set_ba),
+                            "destroy_topology_link()",
+                            dead_planet_id;
+                            "neighbour_id"=>neighbour_id
+                        ),
+                    )
+                    .emit();
+                }
+            }
+            //LOG
+            let components =
+                self.components_of(|id| id != dead_id && !self.planets_info.is_dead(&id));
+            if components.len() > components_before {
+                self.on_galaxy_partitioned(components);
+            }
+            Ok(cleared)
         } else {
             //LOG
             let event = LogEvent::self_directed(
@@ -65,6 +126,98 @@ impl Orchestrator {
         }
     }
 
+    /// Returns the groups of living planets linked to each other, directly or through
+    /// other living planets.
+    ///
+    /// Each group is sorted by planet id, and the groups by their first planet.
+    pub fn connected_components(&self) -> Vec<Vec<u32>> {
+        self.components_of(|id| !self.planets_info.is_dead(&id))
+    }
+
+    /// Connected components of the planets for which `is_live` returns true, see
+    /// [`connected_components`](Self::connected_components).
+    fn components_of(&self, is_live: impl Fn(u32) -> bool) -> Vec<Vec<u32>> {
+        let mut planets: Vec<u32> = self
+            .galaxy_lookup
+            .keys()
+            .copied()
+            .filter(|id| is_live(*id))
+            .collect();
+        planets.sort_unstable();
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for start in planets {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut frontier = vec![start];
+            while let Some(planet_id) = frontier.pop() {
+                for neighbor in self.get_neighbors_of(planet_id).unwrap_or_default() {
+                    if is_live(neighbor) && visited.insert(neighbor) {
+                        component.push(neighbor);
+                        frontier.push(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Reports that the galaxy split into `components`, and links them again if
+    /// `rejoin_partitions` is set.
+    fn on_galaxy_partitioned(&mut self, components: Vec<Vec<u32>>) {
+        //LOG
+        LogEvent::self_directed(
+            Participant::new(ActorType::Orchestrator, 0u32),
+            EventType::InternalOrchestratorAction,
+            Channel::Info,
+            payload!(
+                "message" => "galaxy partitioned",
+                "components" => format!("{:?}", components),
+            ),
+        )
+        .emit();
+        //LOG
+        if self.rejoin_partitions {
+            self.rejoin_components(&components);
+        }
+        self.emit_galaxy_partitioned(components);
+    }
+
+    /// Links a random planet of each component to a random planet of the components
+    /// before it, so that the galaxy is connected again.
+    ///
+    /// The new links are added to the pending topology delta.
+    fn rejoin_components(&mut self, components: &[Vec<u32>]) {
+        let mut rng = rand::rng();
+        for (i, component) in components.iter().enumerate().skip(1) {
+            let joined = components[..i].concat();
+            let (Some(&a), Some(&b)) = (component.choose(&mut rng), joined.choose(&mut rng)) else {
+                continue;
+            };
+            let (Some(&(idx_a, _)), Some(&(idx_b, _))) =
+                (self.galaxy_lookup.get(&a), self.galaxy_lookup.get(&b))
+            else {
+                continue;
+            };
+            self.galaxy_topology[idx_a as usize][idx_b as usize] = true;
+            self.galaxy_topology[idx_b as usize][idx_a as usize] = true;
+            self.neighbor_cache.remove(&a);
+            self.neighbor_cache.remove(&b);
+            self.pending_topology_delta.add_link(a, b);
+            //LOG
+            log_internal_op!(
+                self,
+                "action"=>"link created to rejoin the galaxy",
+                "planets"=>format!("({}, {})", a, b),
+            );
+            //LOG
+        }
+    }
+
     /// Starts the AI of every planet.
     ///
     /// Goes through every PlanetToOrchestrator channel and sends the `StartPlanetAI`
@@ -82,10 +235,10 @@ impl Orchestrator {
         // Collect all planet ids that we need to hear back from
         let mut pending_planets: HashSet<u32> = HashSet::new();
 
-        for (id, (from_orch, _)) in &self.planet_channels {
+        for (id, handle) in &self.planet_channels {
             if !self.planets_info.is_dead(id) {
-                from_orch
-                    .try_send(OrchestratorToPlanet::StartPlanetAI)
+                handle
+                    .try_send_to_planet(OrchestratorToPlanet::StartPlanetAI)
                     .map_err(|_| format!("Cannot send message to {id}"))?;
 
                 pending_planets.insert(*id);
@@ -149,8 +302,8 @@ impl Orchestrator {
         // Retry: re-send StartPlanetAI to planets that haven't responded
         let retry_planets: Vec<u32> = pending_planets.iter().copied().collect();
         for planet_id in &retry_planets {
-            if let Some((from_orch, _)) = self.planet_channels.get(planet_id) {
-                let _ = from_orch.try_send(OrchestratorToPlanet::StartPlanetAI);
+            if let Some(handle) = self.planet_channels.get(planet_id) {
+                let _ = handle.try_send_to_planet(OrchestratorToPlanet::StartPlanetAI);
 
                 //LOG
                 log_message!(
@@ -222,26 +375,118 @@ impl Orchestrator {
         log_fn_call!(self, "stop_all_planet_ais()");
         //LOG
 
-        for (id, (from_orch, _)) in &self.planet_channels {
-            if !self.planets_info.is_dead(id) {
-                from_orch
-                    .try_send(OrchestratorToPlanet::StopPlanetAI)
-                    .map_err(|_| format!("Cannot send message to {id}"))?;
+        for (id, handle) in self.living_planets() {
+            handle
+                .try_send_to_planet(OrchestratorToPlanet::StopPlanetAI)
+                .map_err(|_| format!("Cannot send message to {id}"))?;
 
-                //LOG
-                log_message!(
-                    ActorType::Orchestrator, 0u32,
-                    ActorType::Planet, *id,
-                    EventType::MessageOrchestratorToPlanet,
-                    "StopPlanetAI";
-                    "planet_id"=>id
-                );
-                //LOG
-            }
+            //LOG
+            log_message!(
+                ActorType::Orchestrator, 0u32,
+                ActorType::Planet, id,
+                EventType::MessageOrchestratorToPlanet,
+                "StopPlanetAI";
+                "planet_id"=>id
+            );
+            //LOG
         }
         Ok(())
     }
 
+    /// Starts the AI of a single planet and waits for its answer.
+    ///
+    /// Sends `StartPlanetAI` to the given planet, then polls the planet receiver
+    /// until the matching `StartPlanetAIResult` arrives or `timeout` expires.
+    /// Messages from other planets received in the meantime are handled normally.
+    ///
+    /// Returns Err if the planet does not exist, is dead, its channel is
+    /// inaccessible or it does not answer in time.
+    pub fn start_planet_ai(&mut self, planet_id: u32, timeout: Duration) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "start_planet_ai()", planet_id, timeout,);
+        //LOG
+        self.send_planet_ai_and_await(planet_id, timeout, true)
+    }
+
+    /// Stops the AI of a single planet and waits for its answer.
+    ///
+    /// Same as [`start_planet_ai`](`Self::start_planet_ai`), but sends
+    /// `StopPlanetAI` and waits for `StopPlanetAIResult`.
+    pub fn stop_planet_ai(&mut self, planet_id: u32, timeout: Duration) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "stop_planet_ai()", planet_id, timeout,);
+        //LOG
+        self.send_planet_ai_and_await(planet_id, timeout, false)
+    }
+
+    /// Shared body of [`start_planet_ai`](`Self::start_planet_ai`) and
+    /// [`stop_planet_ai`](`Self::stop_planet_ai`).
+    fn send_planet_ai_and_await(
+        &mut self,
+        planet_id: u32,
+        timeout: Duration,
+        start: bool,
+    ) -> Result<(), String> {
+        if self.planets_info.is_dead(&planet_id) {
+            return Err(format!("planet {} is dead", planet_id));
+        }
+        let handle = self
+            .planet_channels
+            .get(&planet_id)
+            .ok_or_else(|| format!("planet {} does not exist", planet_id))?;
+
+        let (msg, msg_name) = if start {
+            (OrchestratorToPlanet::StartPlanetAI, "StartPlanetAI")
+        } else {
+            (OrchestratorToPlanet::StopPlanetAI, "StopPlanetAI")
+        };
+        handle
+            .send_to_planet(msg)
+            .map_err(|_| format!("Cannot send message to {planet_id}"))?;
+
+        //LOG
+        log_message!(
+            ActorType::Orchestrator, 0u32,
+            ActorType::Planet, planet_id,
+            EventType::MessageOrchestratorToPlanet,
+            msg_name;
+            "planet_id"=>planet_id
+        );
+        //LOG
+
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            match self.receiver_orch_planet.recv_timeout(remaining) {
+                Ok(msg) => {
+                    let is_answer = match &msg {
+                        PlanetToOrchestrator::StartPlanetAIResult { planet_id: id } => {
+                            start && *id == planet_id
+                        }
+                        PlanetToOrchestrator::StopPlanetAIResult { planet_id: id } => {
+                            !start && *id == planet_id
+                        }
+                        _ => false,
+                    };
+                    let msg_string = format!("{:?}", msg);
+                    // the status update is done by handle_planet_message
+                    if let Err(err) = self.handle_planet_message(msg) {
+                        self.report_handler_error(&msg_string, err, "send_planet_ai_and_await()");
+                    }
+                    if is_answer {
+                        return Ok(());
+                    }
+                }
+                Err(_) => {
+                    return Err(format!(
+                        "planet {} did not answer to {} within {:?}",
+                        planet_id, msg_name, timeout
+                    ));
+                }
+            }
+        }
+    }
+
     /// Starts the AI of every explorer.
     ///
     /// Goes through every OrchestratorToExplorer channel and sends the `StartExplorerAI`
@@ -252,23 +497,20 @@ impl Orchestrator {
         log_fn_call!(self, "start_all_explorer_ais()");
         //LOG
 
-        for (id, (from_orch, _)) in &self.explorer_channels {
-            if !self.explorers_info.is_dead(id) {
-                from_orch
-                    .try_send(OrchestratorToExplorer::StartExplorerAI)
-                    .map_err(|_| format!("Cannot send message to explorer {}", id))?;
-
-                //LOG
-                log_message!(
-                    ActorType::Orchestrator, 0u32,
-                    ActorType::Explorer, *id,
-                    EventType::MessageOrchestratorToExplorer,
-                    "StartExplorerAI";
-                    "explorer_id"=>id
-                );
-            }
+        for (id, handle) in self.living_explorers() {
+            handle
+                .try_send_to_explorer(OrchestratorToExplorer::StartExplorerAI)
+                .map_err(|_| format!("Cannot send message to explorer {}", id))?;
 
             //LOG
+            log_message!(
+                ActorType::Orchestrator, 0u32,
+                ActorType::Explorer, id,
+                EventType::MessageOrchestratorToExplorer,
+                "StartExplorerAI";
+                "explorer_id"=>id
+            );
+            //LOG
         }
         //
         // let mut count = 0;
@@ -324,35 +566,65 @@ impl Orchestrator {
         Ok(())
     }
 
-    /// Stop the AI of every explorer.
+    /// Send a message to every explorer that is not dead.
     ///
-    /// Goes through every OrchestratorToExplorer channel and sends the `StopExplorerAI`
-    ///
-    /// Returns Err if any of the communication channels are inaccessible.
-    pub(crate) fn stop_all_explorer_ais(&mut self) -> Result<(), String> {
+    /// `make_msg` builds the message once per explorer. A failed send does not
+    /// stop the broadcast: the result of every send is returned, by explorer id
+    /// in ascending order.
+    pub fn broadcast_to_explorers(
+        &self,
+        make_msg: impl Fn() -> OrchestratorToExplorer,
+    ) -> Vec<(u32, Result<(), String>)> {
         //LOG
-        log_fn_call!(self, "stop_all_explorer_ais()");
+        log_fn_call!(self, "broadcast_to_explorers()");
         //LOG
 
-        for (id, (from_orch, _)) in &self.explorer_channels {
-            if !self.explorers_info.is_dead(id) {
-                from_orch
-                    .try_send(OrchestratorToExplorer::StopExplorerAI)
-                    .map_err(|_| format!("Cannot send message to explorer {}", id))?;
-
+        let mut results = Vec::new();
+        for (id, handle) in self.living_explorers() {
+            let msg = make_msg();
+            let msg_string = format!("{:?}", msg);
+            let result = handle
+                .try_send_to_explorer(msg)
+                .map_err(|_| format!("Cannot send message to explorer {}", id));
+            if result.is_ok() {
                 //LOG
                 log_message!(
                     ActorType::Orchestrator, 0u32,
-                    ActorType::Explorer, *id,
+                    ActorType::Explorer, id,
                     EventType::MessageOrchestratorToExplorer,
-                    "StopExplorerAI";
-                    "explorer_id"=>id
+                    "broadcast";
+                    "msg"=>msg_string
                 );
                 //LOG
             }
+            results.push((id, result));
         }
+        results.sort_by_key(|(id, _)| *id);
+        results
+    }
 
-        Ok(())
+    /// Stop the AI of every explorer.
+    ///
+    /// Broadcasts the `StopExplorerAI` message, see
+    /// [`broadcast_to_explorers`](`Self::broadcast_to_explorers`).
+    ///
+    /// Returns Err if any of the communication channels are inaccessible; the
+    /// message is sent to the other explorers anyway.
+    pub(crate) fn stop_all_explorer_ais(&mut self) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "stop_all_explorer_ais()");
+        //LOG
+
+        let errors: Vec<String> = self
+            .broadcast_to_explorers(|| OrchestratorToExplorer::StopExplorerAI)
+            .into_iter()
+            .filter_map(|(_, result)| result.err())
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
     }
 
     /// Global start function, starts all the planets'
@@ -479,6 +751,163 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Resets the game, killing every actor and clearing the galaxy.
+    ///
+    /// Kills every explorer with [`kill_explorer`](Self::kill_explorer), then sends
+    /// `KillPlanet` to every planet still alive and waits (up to 2 seconds) for the
+    /// planets' `KillPlanetResult`.
+    /// Afterwards every map, channel and topology is cleared so that the galaxy can
+    /// be initialized again. The forge is kept, since only one can exist.
+    ///
+    /// If there are no planets the wait for the planets is skipped, the explorers
+    /// are killed anyway.
+    ///
+    /// Returns Err if the kill messages cannot be delivered.
+    pub fn reset(&mut self) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "reset()");
+        //LOG
+
+        // stop the explorers first so they do not keep talking to dying planets
+        if let Err(err) = self.pause_all_explorers(RESET_PAUSE_TIMEOUT) {
+            //LOG
+            LogEvent::self_directed(
+                Participant::new(ActorType::Orchestrator, 0u32),
+                EventType::InternalOrchestratorAction,
+                Channel::Warning,
+                warning_payload!("some explorers did not pause", err, "reset()"),
+            )
+            .emit();
+            //LOG
+        }
+
+        let explorer_ids: Vec<u32> = self.living_explorers().map(|(id, _)| id).collect();
+        for id in explorer_ids {
+            // an explorer that cannot be killed is removed anyway by kill_explorer
+            if let Err(err) = self.kill_explorer(id, RESET_KILL_TIMEOUT) {
+                //LOG
+                LogEvent::self_directed(
+                    Participant::new(ActorType::Orchestrator, 0u32),
+                    EventType::InternalOrchestratorAction,
+                    Channel::Warning,
+                    warning_payload!(
+                        "explorer did not acknowledge the kill",
+                        err,
+                        "reset()";
+                        "explorer_id"=>id
+                    ),
+                )
+                .emit();
+                //LOG
+            }
+        }
+
+        if !self.planet_channels.is_empty() {
+            let mut pending_planets: HashSet<u32> = HashSet::new();
+            for (id, handle) in self.living_planets() {
+                handle
+                    .send_to_planet(OrchestratorToPlanet::KillPlanet)
+                    .map_err(|_| format!("Cannot send message to {id}"))?;
+                pending_planets.insert(id);
+
+                //LOG
+                log_message!(
+                    ActorType::Orchestrator, 0u32,
+                    ActorType::Planet, id,
+                    EventType::MessageOrchestratorToPlanet,
+                    "KillPlanet";
+                    "planet_id"=>id
+                );
+                //LOG
+            }
+
+            let timeout = crossbeam_channel::after(Duration::from_millis(2000));
+            while !pending_planets.is_empty() {
+                crossbeam_channel::select! {
+                    recv(self.receiver_orch_planet) -> msg => {
+                        match msg {
+                            Ok(PlanetToOrchestrator::KillPlanetResult { planet_id }) => {
+                                pending_planets.remove(&planet_id);
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                // no acknowledgement can arrive anymore
+                                //LOG
+                                LogEvent::self_directed(
+                                    Participant::new(ActorType::Orchestrator, 0u32),
+                                    EventType::InternalOrchestratorAction,
+                                    Channel::Error,
+                                    warning_payload!(
+                                        "Cannot receive message from planets, channel closed",
+                                        e,
+                                        "reset()"
+                                    ),
+                                )
+                                .emit();
+                                //LOG
+                                break;
+                            }
+                        }
+                    }
+                    recv(timeout) -> _ => {
+                        //LOG
+                        LogEvent::self_directed(
+                            Participant::new(ActorType::Orchestrator, 0u32),
+                            EventType::InternalOrchestratorAction,
+                            Channel::Warning,
+                            warning_payload!(
+                                "some planets did not acknowledge the kill",
+                                format!("{:?}", pending_planets),
+                                "reset()"
+                            ),
+                        )
+                        .emit();
+                        //LOG
+                        break;
+                    }
+                }
+            }
+        }
+
+        // reinitialization
+        self.galaxy_topology = Self::new_gtop();
+        self.galaxy_lookup.clear();
+        self.galaxy_reverse_lookup.clear();
+        self.neighbor_cache.clear();
+        self.planets_info = PlanetInfoMap::new();
+        self.explorers_info = ExplorerInfoMap::new();
+        self.planet_channels.clear();
+        self.explorer_channels.clear();
+        self.explorer_kill_senders.clear();
+        self.explorer_topology_senders.clear();
+        self.pending_topology_delta = TopologyDelta::new();
+        self.manual_commands.clear();
+        self.pending_explorer_removals.clear();
+        self.tick = 0;
+        self.idle_watch = IdleWatch::new();
+        self.sunrays_sent = 0;
+        self.asteroids_sent = 0;
+        self.game_clock = GameClock::new(self.game_clock.cadence());
+        // the explorers are killed and their channels dropped, so the threads are ending
+        #[cfg(feature = "expose-handles")]
+        for (_, handle) in self.explorer_handles.drain() {
+            let _ = handle.join();
+        }
+        self.gui_messages.clear();
+        // discard whatever is left from the old actors
+        self.receiver_orch_planet.try_recv_all();
+        self.receiver_orch_explorer.try_recv_all();
+
+        //LOG
+        log_internal_op!(
+            self,
+            "action"=>"orchestrator reset",
+            "status"=>"success"
+        );
+        //LOG
+        Ok(())
+    }
+
     /// Choose whether to create a celestial body (and which one).
     ///
     /// The function chooses randomly whether to do anything at all in a given
@@ -507,13 +936,13 @@ impl Orchestrator {
                 None => return Ok(()),
             };
 
-            let mut params: Option<(u32, Sender<OrchestratorToPlanet>)> = None;
+            let mut params: Option<(u32, TracedSender<OrchestratorToPlanet>)> = None;
 
             // find the set of channels that correspond to the chosen ID
             for (&id, channels) in self.planet_channels.iter() {
                 if id == rand_id {
                     // to_owned is not a deep copy
-                    params = Some((id, channels.0.to_owned()));
+                    params = Some((id, channels.to_planet().to_owned()));
                 }
             }
 
@@ -533,4 +962,216 @@ impl Orchestrator {
 
         Ok(())
     }
+
+    /// Send an asteroid to a living planet chosen at random with the given weights.
+    ///
+    /// Planets missing from `weights` get the default weight, see
+    /// [`WeightedPlanetPicker`] for the details on how the planet is chosen.
+    /// The asteroid is sent through [`send_asteroid`](`Self::send_asteroid`).
+    ///
+    /// Returns the id of the chosen planet, or Err if no planet is alive or its
+    /// channel is inaccessible.
+    pub fn send_asteroid_weighted(&mut self, weights: &HashMap<u32, f64>) -> Result<u32, String> {
+        //LOG
+        log_fn_call!(self, "send_asteroid_weighted()"; "weights"=>format!("{:?}", weights));
+        //LOG
+        let living_things = self.planets_info.get_list_id_alive();
+        let picker = WeightedPlanetPicker::new(weights.clone());
+        let planet_id = picker
+            .pick(&living_things, &mut rand::rng())
+            .ok_or_else(|| "No more planets alive".to_string())?;
+
+        let sender = self
+            .planet_channels
+            .get(&planet_id)
+            .map(|handle| handle.to_planet().clone())
+            .ok_or_else(|| {
+                format!(
+                    "No channels found in the orchestrator for planet:{}",
+                    planet_id
+                )
+            })?;
+        self.send_asteroid(planet_id, &sender)?;
+        Ok(planet_id)
+    }
+
+    /// Runs one step of the game, for embedders driving it from their own loop.
+    ///
+    /// The pending messages are handled first, then the optional action is applied:
+    /// a sunray or an asteroid is sent to a random living planet, a pause sleeps for
+    /// its duration. Finally `tick` is advanced.
+    ///
+    /// Returns Err if the messages cannot be handled, if a channel towards the
+    /// orchestrator is closed or if the action cannot be sent; `tick` is not
+    /// advanced in that case.
+    pub fn tick_once(&mut self, action: Option<OrchestratorAction>) -> Result<(), String> {
+        //LOG
+        log_fn_call!(self, "tick_once()"; "action"=>format!("{:?}", action));
+        //LOG
+        if self.handle_game_messages()? == MessageLoopStatus::ChannelClosed {
+            return Err(
+                "A channel towards the orchestrator is closed, the game cannot go on".to_string(),
+            );
+        }
+        self.nudge_idle_explorers();
+
+        match action {
+            Some(OrchestratorAction::Sunray) => self.launch_to_random_planet(Launch::Sunray)?,
+            Some(OrchestratorAction::Asteroid) => self.launch_to_random_planet(Launch::Asteroid)?,
+            Some(OrchestratorAction::Pause(duration)) => thread::sleep(duration),
+            None => {}
+        }
+
+        self.tick += 1;
+        Ok(())
+    }
+
+    /// Nudges the running explorers that made no progress for too long, as told by
+    /// `idle_policy`.
+    ///
+    /// Called by [`tick_once`](Self::tick_once) at every tick. An explorer makes
+    /// progress when its planet or its bag, as last reported to the orchestrator,
+    /// changes; see [`IdleWatch`]. An explorer that looks idle is first asked for its
+    /// planet and bag, and nudged only if the answer shows no progress either. A probe
+    /// or a nudge that cannot be sent is logged and does not stop the others.
+    ///
+    /// Returns the ids of the explorers nudged.
+    pub fn nudge_idle_explorers(&mut self) -> Vec<u32> {
+        let Some(policy) = self.idle_policy else {
+            return Vec::new();
+        };
+        let mut to_probe = Vec::new();
+        let mut idle = Vec::new();
+        for (id, info) in self.explorers_info.iter() {
+            if info.status != Status::Running {
+                self.idle_watch.forget(*id);
+                continue;
+            }
+            match self.idle_watch.observe(
+                *id,
+                self.tick,
+                info.current_planet_id,
+                &info.bag,
+                policy.idle_ticks,
+            ) {
+                Observation::Active => {}
+                Observation::Probe => to_probe.push(*id),
+                Observation::Idle => idle.push((*id, info.current_planet_id)),
+            }
+        }
+
+        for explorer_id in to_probe {
+            // the bag is asked last: its answer comes after the planet one
+            let result = self
+                .send_current_planet_request(explorer_id)
+                .and_then(|_| self.send_bag_content_request(explorer_id));
+            //LOG
+            log_internal_op!(
+                self,
+                "action"=>"idle explorer probed",
+                "explorer_id"=>explorer_id,
+                "result"=>format!("{:?}", result),
+            );
+            //LOG
+        }
+
+        for &(explorer_id, planet_id) in &idle {
+            let result = match policy.nudge {
+                Nudge::ResetAi => self.send_reset_explorer_ai(explorer_id),
+                Nudge::PushKnowledge => self.push_planet_links(explorer_id, planet_id),
+                Nudge::Relocate => self.relocate_explorer(explorer_id, planet_id),
+            };
+            //LOG
+            log_internal_op!(
+                self,
+                "action"=>"idle explorer nudged",
+                "explorer_id"=>explorer_id,
+                "nudge"=>format!("{:?}", policy.nudge),
+                "result"=>format!("{:?}", result),
+            );
+            //LOG
+        }
+        idle.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Sends the links of a planet to an explorer through its topology channel.
+    fn push_planet_links(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), String> {
+        let mut delta = TopologyDelta::new();
+        for neighbor in self.get_neighbors_of(planet_id)? {
+            delta.add_link(planet_id, neighbor);
+        }
+        self.explorer_topology_senders
+            .get(&explorer_id)
+            .ok_or_else(|| format!("Explorer {} has no topology channel", explorer_id))?
+            .send(delta)
+            .map_err(|_| format!("Failed to send the topology to explorer {}", explorer_id))
+    }
+
+    /// Moves an explorer to a random living neighbour of its planet.
+    fn relocate_explorer(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), String> {
+        let destinations: Vec<u32> = self
+            .get_neighbors_of(planet_id)?
+            .into_iter()
+            .filter(|id| !self.planets_info.is_dead(id))
+            .collect();
+        let destination = *destinations
+            .choose(&mut rand::rng())
+            .ok_or_else(|| format!("Planet {} has no living neighbour", planet_id))?;
+        if let Some(info) = self.explorers_info.get_mut(&explorer_id) {
+            info.move_to_planet_id = destination as i32;
+        }
+        self.send_incoming_explorer_request(destination, explorer_id)?;
+        self.emit_explorer_move_started(explorer_id, destination);
+        Ok(())
+    }
+
+    /// Runs one iteration of a game loop that moves the simulated time by `elapsed`.
+    ///
+    /// The pending messages are handled at every call, then `game_clock` is advanced
+    /// and every sunray and asteroid whose deadline has passed is sent to a random
+    /// living planet. The launches follow the [`Cadence`](crate::utils::Cadence) of
+    /// the clock, so calling this more often with a shorter `elapsed` sends the same
+    /// number of them over the same simulated time. Once every planet is dead the
+    /// game is over: nothing is launched and the clock stops.
+    ///
+    /// Returns the launches sent, or Err if the messages cannot be handled, if a
+    /// channel towards the orchestrator is closed or if a launch cannot be sent.
+    pub fn advance_game(&mut self, elapsed: Duration) -> Result<Vec<Launch>, String> {
+        //LOG
+        log_fn_call!(self, "advance_game()"; "elapsed"=>format!("{:?}", elapsed));
+        //LOG
+        if self.handle_game_messages()? == MessageLoopStatus::ChannelClosed {
+            return Err(
+                "A channel towards the orchestrator is closed, the game cannot go on".to_string(),
+            );
+        }
+
+        if self.planets_info.get_list_id_alive().is_empty() {
+            return Ok(Vec::new());
+        }
+        let due = self.game_clock.advance(elapsed);
+        for launch in &due {
+            self.launch_to_random_planet(*launch)?;
+        }
+        Ok(due)
+    }
+
+    /// Sends a sunray or an asteroid to a random living planet.
+    fn launch_to_random_planet(&mut self, launch: Launch) -> Result<(), String> {
+        let planet_id = self.get_random_planet_id()?;
+        let sender = self
+            .planet_channels
+            .get(&planet_id)
+            .map(|handle| handle.to_planet().clone())
+            .ok_or_else(|| {
+                format!(
+                    "No channels found in the orchestrator for planet:{}",
+                    planet_id
+                )
+            })?;
+        match launch {
+            Launch::Sunray => self.send_sunray(planet_id, &sender),
+            Launch::Asteroid => self.send_asteroid(planet_id, &sender),
+        }
+    }
 }
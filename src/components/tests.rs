@@ -1,9 +1,59 @@
 #[cfg(test)]
 use crate::components::orchestrator::Orchestrator;
 #[cfg(test)]
+use crate::components::orchestrator::channels::{ExplorerHandle, PlanetHandle};
+#[cfg(test)]
+use crate::utils::ExplorerInfo;
+#[cfg(test)]
 use crate::utils::Status;
 #[cfg(test)]
 use crate::utils::registry::PlanetType;
+#[cfg(test)]
+use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
+#[cfg(test)]
+use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
+#[cfg(test)]
+use crossbeam_channel::Receiver;
+
+/// Replaces the orchestrator channel of the planet `id` with a stub one and returns
+/// the planet end of it.
+///
+/// The explorers keep talking to the real planet, if there is one.
+#[cfg(test)]
+fn stub_planet_channels(orch: &mut Orchestrator, id: u32) -> Receiver<OrchestratorToPlanet> {
+    let (to_planet, inbox) = crossbeam_channel::unbounded();
+    let explorer_gateway = match orch.planet_channels.get(&id) {
+        Some(handle) => handle.explorer_sender_for_arrival(),
+        None => crossbeam_channel::unbounded().0,
+    };
+    orch.planet_channels
+        .insert(id, PlanetHandle::new(to_planet, explorer_gateway));
+    inbox
+}
+
+/// Gives the explorer `id` stub channels, without any info, and returns the explorer
+/// end of its orchestrator channel.
+#[cfg(test)]
+fn stub_explorer_channels(orch: &mut Orchestrator, id: u32) -> Receiver<OrchestratorToExplorer> {
+    let (to_explorer, inbox) = crossbeam_channel::unbounded();
+    let (planet_gateway, _) = crossbeam_channel::unbounded();
+    orch.explorer_channels
+        .insert(id, ExplorerHandle::new(to_explorer, planet_gateway));
+    inbox
+}
+
+/// Registers a stub explorer `id` with `status`, on planet 0 and with an empty bag,
+/// see [`stub_explorer_channels`].
+#[cfg(test)]
+fn stub_explorer(
+    orch: &mut Orchestrator,
+    id: u32,
+    status: Status,
+) -> Receiver<OrchestratorToExplorer> {
+    orch.explorers_info
+        .insert(id, ExplorerInfo::from(id, status, Vec::new(), 0));
+    stub_explorer_channels(orch, id)
+}
 
 #[cfg(test)]
 mod tests_core_lifecycle {
@@ -17,6 +67,60 @@ mod tests_core_lifecycle {
             assert!(orch.galaxy_lookup.is_empty());
         }
     }
+
+    #[test]
+    fn test_lifecycle_reset_without_planets_returns_quickly() {
+        let mut orch = Orchestrator::new().unwrap();
+
+        let start = std::time::Instant::now();
+        assert!(orch.reset().is_ok());
+        assert!(start.elapsed() < std::time::Duration::from_millis(500));
+        assert!(orch.planets_info.is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_reset_without_planets_kills_the_explorers() {
+        use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+
+        let mut orch = Orchestrator::new().unwrap();
+        let expl_from_orch = stub_explorer(&mut orch, 4, Status::Running);
+        let to_orch = orch.sender_explorer_orch.clone();
+        let stub = std::thread::spawn(move || {
+            let mut killed = false;
+            for msg in expl_from_orch {
+                let answer = match msg {
+                    OrchestratorToExplorer::StopExplorerAI => {
+                        ExplorerToOrchestrator::StopExplorerAIResult { explorer_id: 4 }
+                    }
+                    OrchestratorToExplorer::KillExplorer => {
+                        killed = true;
+                        ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 }
+                    }
+                    _ => continue,
+                };
+                let _ = to_orch.send(answer);
+            }
+            killed
+        });
+
+        assert!(orch.reset().is_ok());
+        assert!(orch.explorer_channels.is_empty());
+        // the reset drops the channel, so the stub stops after the kill
+        assert!(stub.join().unwrap());
+    }
+
+    #[test]
+    fn test_lifecycle_reset_clears_galaxy() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        assert!(orch.reset().is_ok());
+        assert!(orch.planets_info.is_empty());
+        assert!(orch.planet_channels.is_empty());
+        assert!(orch.galaxy_lookup.is_empty());
+        assert!(orch.galaxy_topology.is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +164,65 @@ mod tests_actor_management {
     }
 }
 
+#[cfg(test)]
+mod tests_single_planet_ai {
+    use super::*;
+    use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+    use std::time::Duration;
+
+    #[test]
+    fn test_start_and_stop_single_planet_ai() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!(
+            "0,{},1\n1,{},0",
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::OneMillionCrabs as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        orch.start_planet_ai(0, Duration::from_secs(1)).unwrap();
+        assert!(orch.planets_info.is_running(&0));
+        assert!(orch.planets_info.is_paused(&1));
+
+        orch.stop_planet_ai(0, Duration::from_secs(1)).unwrap();
+        assert!(orch.planets_info.is_paused(&0));
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_query_planet_state_returns_state() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_planet_ai(0, Duration::from_secs(1)).unwrap();
+        // planet 99 is unknown, so the handler of this message fails
+        orch.sender_planet_orch
+            .send(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 99 })
+            .unwrap();
+
+        let state = orch.query_planet_state(0, Duration::from_secs(1)).unwrap();
+        assert_eq!(
+            state.charged_cells_count,
+            orch.planets_info.get_info(0).unwrap().charged_cells_count
+        );
+        assert!(orch.query_planet_state(7, Duration::from_millis(50)).is_err());
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_start_planet_ai_unknown_planet_errors() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        assert!(orch.start_planet_ai(7, Duration::from_millis(50)).is_err());
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+}
+
 #[cfg(test)]
 mod tests_topology_logic {
     use super::*;
@@ -104,11 +267,34 @@ mod tests_topology_logic {
         let result = orch.destroy_topology_link(5);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_topology_destroy_link_reports_one_directional_links() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!(
+            "0,{t},1,2\n1,{t},0\n2,{t},0\n3,{t}",
+            t = PlanetType::OneMillionCrabs as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        let idx = |id: u32| orch.galaxy_lookup[&id].0 as usize;
+        let (idx0, idx2) = (idx(0), idx(2));
+        // 2 -> 0 is missing, as after a buggy directed load
+        orch.galaxy_topology[idx2][idx0] = false;
+
+        let mut cleared = orch.destroy_topology_link(0).unwrap();
+        cleared.sort();
+        assert_eq!(cleared, vec![(1, true, true), (2, true, false)]);
+        assert!(orch.galaxy_topology.iter().all(|row| !row[idx0]));
+        assert!(orch.galaxy_topology[idx0].iter().all(|linked| !linked));
+    }
 }
 
 #[cfg(test)]
 mod tests_messaging_protocol {
     use super::*;
+    use common_game::protocols::orchestrator_explorer::{
+        ExplorerToOrchestrator, OrchestratorToExplorer,
+    };
     use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
 
     #[test]
@@ -136,6 +322,142 @@ mod tests_messaging_protocol {
         assert!(!orch.galaxy_topology[1][0]); // not b, we don't want the planet to have a link
     }
 
+    #[test]
+    fn test_messaging_asteroid_ack_with_rocket_marks_planet_as_deflecting() {
+        use common_game::components::energy_cell::EnergyCell;
+        use common_game::components::rocket::Rocket;
+
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        assert_eq!(orch.planet_can_deflect(0), Some(false));
+
+        let mut cell = EnergyCell::new();
+        cell.charge(orch.forge.generate_sunray());
+        let rocket = Rocket::new(&mut cell).unwrap();
+        orch.handle_planet_message(PlanetToOrchestrator::AsteroidAck {
+            planet_id: 0,
+            rocket: Some(rocket),
+        })
+        .unwrap();
+
+        assert!(!orch.planets_info.is_dead(&0));
+        assert_eq!(orch.planet_can_deflect(0), Some(true));
+        assert_eq!(orch.planet_can_deflect(42), None);
+    }
+
+    #[test]
+    fn test_messaging_drain_planet_messages_handles_all_pending() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("1,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        for _ in 0..5 {
+            orch.sender_planet_orch
+                .send(PlanetToOrchestrator::SunrayAck { planet_id: 1 })
+                .unwrap();
+        }
+
+        assert_eq!(orch.drain_planet_messages().unwrap(), 5);
+        assert_eq!(orch.gui_messages.len(), 5);
+        assert_eq!(orch.drain_planet_messages().unwrap(), 0);
+    }
+
+    /// Three planets: 0 -- 1 and the isolated planet 2, all running, with
+    /// explorer 5 registered on planet 0.
+    fn setup_travel_galaxy() -> (
+        Orchestrator,
+        crossbeam_channel::Receiver<OrchestratorToExplorer>,
+    ) {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        let content = format!("0,{ty},1\n1,{ty},0\n2,{ty}");
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        for id in 0..3 {
+            orch.planets_info.update_status(id, Status::Running).unwrap();
+        }
+
+        let expl_from_orch = stub_explorer(&mut orch, 5, Status::Running);
+        (orch, expl_from_orch)
+    }
+
+    #[test]
+    fn test_messaging_travel_request_to_neighbour_is_accepted() {
+        let (mut orch, _expl_from_orch) = setup_travel_galaxy();
+
+        orch.handle_explorer_travel_request(ExplorerToOrchestrator::TravelToPlanetRequest {
+            explorer_id: 5,
+            current_planet_id: 0,
+            dst_planet_id: 1,
+        })
+        .unwrap();
+
+        assert_eq!(orch.explorers_info.get(&5).unwrap().move_to_planet_id, 1);
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_messaging_travel_request_without_edge_is_rejected() {
+        let (mut orch, expl_from_orch) = setup_travel_galaxy();
+
+        orch.handle_explorer_travel_request(ExplorerToOrchestrator::TravelToPlanetRequest {
+            explorer_id: 5,
+            current_planet_id: 0,
+            dst_planet_id: 2,
+        })
+        .unwrap();
+
+        match expl_from_orch.try_recv() {
+            Ok(OrchestratorToExplorer::MoveToPlanet {
+                sender_to_new_planet,
+                planet_id,
+            }) => {
+                assert!(sender_to_new_planet.is_none());
+                assert_eq!(planet_id, 2);
+            }
+            other => panic!("expected a rejected MoveToPlanet, got {:?}", other),
+        }
+        assert_eq!(orch.explorers_info.get(&5).unwrap().move_to_planet_id, -1);
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_messaging_neighbors_request_is_answered() {
+        let (mut orch, expl_from_orch) = setup_travel_galaxy();
+
+        assert_eq!(orch.get_neighbors_of(0), Ok(vec![1]));
+        assert_eq!(orch.get_neighbors_of(2), Ok(vec![]));
+        assert!(orch.get_neighbors_of(42).is_err());
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::NeighborsRequest {
+            explorer_id: 5,
+            current_planet_id: 0,
+        })
+        .unwrap();
+
+        match expl_from_orch.try_recv() {
+            Ok(OrchestratorToExplorer::NeighborsResponse { neighbors }) => {
+                assert_eq!(neighbors, vec![1]);
+            }
+            other => panic!("expected NeighborsResponse, got {:?}", other),
+        }
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_messaging_travel_request_rejects_other_messages() {
+        let (mut orch, _expl_from_orch) = setup_travel_galaxy();
+
+        let result = orch.handle_explorer_travel_request(
+            ExplorerToOrchestrator::CurrentPlanetResult {
+                explorer_id: 5,
+                planet_id: 0,
+            },
+        );
+        assert!(result.is_err());
+        let _ = orch.send_planet_kill_to_all();
+    }
+
     #[test]
     fn test_messaging_send_sunray_to_all_skips_dead_planets() {
         let mut orch = Orchestrator::new().unwrap();
@@ -153,138 +475,662 @@ mod tests_messaging_protocol {
 }
 
 #[cfg(test)]
-mod tests_file_integration {
+mod tests_kill_helpers {
     use super::*;
-    use std::fs::File;
-    use std::io::Write;
+    use common_game::protocols::orchestrator_explorer::{
+        ExplorerToOrchestrator, OrchestratorToExplorer,
+    };
+    use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+    use std::thread;
+    use std::time::Duration;
 
     #[test]
-    fn test_file_initialize_galaxy_from_valid_csv() {
+    fn test_kill_explorer_reports_dead_after_stub_ack() {
         let mut orch = Orchestrator::new().unwrap();
-        let file_path = "test_galaxy.csv";
+        let expl_from_orch = stub_explorer(&mut orch, 4, Status::Running);
 
-        // Format: ID, Type, Neighbors...
-        let content = "0, 4, 1, 400\n1, 4, 0, 400\n400, 4, 0, 1";
-        let mut file = File::create(file_path).unwrap();
-        file.write_all(content.as_bytes()).unwrap();
+        // stub explorer: acks the kill
+        let to_orch = orch.sender_explorer_orch.clone();
+        let stub = thread::spawn(move || {
+            if let Ok(OrchestratorToExplorer::KillExplorer) = expl_from_orch.recv() {
+                to_orch
+                    .send(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 })
+                    .unwrap();
+            }
+        });
 
-        let result = orch.initialize_galaxy_by_file(file_path);
+        orch.kill_explorer(4, Duration::from_millis(500)).unwrap();
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+        stub.join().unwrap();
 
-        // Clean up
-        let _ = std::fs::remove_file(file_path);
+        // a dead explorer cannot be killed again
+        assert!(orch.kill_explorer(4, Duration::from_millis(50)).is_err());
+    }
 
-        assert!(result.is_ok());
-        assert!(orch.galaxy_lookup.contains_key(&0));
-        assert!(orch.galaxy_lookup.contains_key(&1));
+    #[test]
+    fn test_kill_explorer_times_out_without_ack() {
+        let mut orch = Orchestrator::new().unwrap();
+        let _expl_from_orch = stub_explorer(&mut orch, 4, Status::Running);
+
+        let result = orch.kill_explorer(4, Duration::from_millis(50));
+        assert!(result.unwrap_err().contains("Timeout"));
+        // the wedged explorer is forcibly removed
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+        assert!(!orch.explorer_channels.contains_key(&4));
     }
-}
-#[cfg(test)]
-mod test_one_million_crabs_planet {
-    use crate::utils::registry::*;
-    use crate::*;
-    use common_game::components::resource::BasicResourceType;
-    use crossbeam_channel::{select, tick};
-    use std::thread::sleep;
-    use std::time::Duration;
 
     #[test]
-    fn planet_energy_cells_management() {
-        let mut orchestrator = Orchestrator::new().unwrap();
-        let planet_id = 1;
-        let explorer_id = 2;
-        let content = "1,7";
-        orchestrator.initialize_galaxy_by_content(content).unwrap();
-        orchestrator
-            .start_all(&[(explorer_id, planet_id)], &[])
-            .unwrap();
-        let planet_channel = orchestrator
-            .planet_channels
-            .get(&planet_id)
-            .unwrap()
-            .0
-            .clone();
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        println!("SENDED 6 SUNRAY");
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        sleep(Duration::from_millis(100));
-        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
-        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
-        sleep(Duration::from_secs(1));
-        let _ = orchestrator.send_bag_content_request(explorer_id);
-        let _ = orchestrator.send_internal_state_request(
-            &orchestrator.planet_channels.get(&planet_id).unwrap().0,
-            planet_id,
-        );
-        let timeout = tick(Duration::from_millis(1000));
-        loop {
-            select! {
-                recv(orchestrator.receiver_orch_planet) -> planet_msg => {
-                    match planet_msg {
-                        Ok(msg) => {
-                            let _=orchestrator.handle_planet_message(msg);
-                        }
-                        Err(_) => {}
-                    }
-                }
-                recv(orchestrator.receiver_orch_explorer)-> explorer_msg=> {
-                    match explorer_msg {
-                        Ok(msg) => {
-                            let _=orchestrator.handle_explorer_message(msg);
-                        }
-                        Err(_) => {}
-                    }
-                }
-                recv(timeout) -> _ => {
-                    break;
-                }
-            }
-        }
-        assert_eq!(
-            orchestrator
-                .planets_info
-                .get_info(planet_id)
-                .unwrap()
-                .energy_cells
-                .iter()
-                .filter(|&&x| x)
-                .count(),
-            0
-        );
-        println!(
-            "explorer bag: {:?}",
-            orchestrator.explorers_info.get(&explorer_id).unwrap().bag
-        );
+    fn test_kill_explorer_cleans_up_disconnected_explorer() {
+        let mut orch = Orchestrator::new().unwrap();
+        let expl_from_orch = stub_explorer(&mut orch, 4, Status::Running);
+        let (kill_sender, _) = crossbeam_channel::bounded(1);
+        orch.explorer_kill_senders.insert(4, kill_sender);
+        // the explorer thread is gone: its control channel is disconnected
+        drop(expl_from_orch);
+
+        assert!(orch.kill_explorer(4, Duration::from_millis(50)).is_err());
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+        assert!(!orch.explorer_channels.contains_key(&4));
+        assert!(!orch.explorer_kill_senders.contains_key(&4));
     }
+
+    #[cfg(feature = "expose-handles")]
     #[test]
-    fn stress_planet_energy_cells_management() {
-        let mut orchestrator = Orchestrator::new().unwrap();
-        let planet_id = 1;
-        let explorer_id = 2;
+    fn test_tommy_explorer_thread_exits_cleanly_after_kill() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[(4, 0)]).unwrap();
+        assert!(orch.explorer_handles.contains_key(&4));
 
-        let content = format!("{},{}", planet_id, PlanetType::OneMillionCrabs as u32);
-        orchestrator.initialize_galaxy_by_content(&content).unwrap();
-        orchestrator
-            .start_all(&[(explorer_id, planet_id)], &[])
-            .unwrap();
-        let planet_channel = orchestrator
-            .planet_channels
-            .get(&planet_id)
-            .unwrap()
-            .0
-            .clone();
-        let _=orchestrator.send_sunray(planet_id, &planet_channel);
-        let _=orchestrator.send_sunray(planet_id, &planet_channel);
+        orch.kill_explorer(4, Duration::from_millis(1000)).unwrap();
+        assert_eq!(orch.join_explorer(4).unwrap(), Ok(()));
+        // the handle has been consumed
+        assert!(orch.join_explorer(4).is_err());
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[cfg(feature = "expose-handles")]
+    #[test]
+    fn test_removed_explorer_drops_its_thread_handle() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[(4, 0)]).unwrap();
+
+        orch.kill_explorer(4, Duration::from_millis(1000)).unwrap();
+        orch.remove_explorer(4).unwrap();
+        assert!(!orch.explorer_handles.contains_key(&4));
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_kill_planet_reports_dead_after_stub_ack() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        // stop the real planet and drop its ack, so only the stub can kill planet 0
+        let _ = orch.send_planet_kill_to_all();
+        let _ = orch
+            .receiver_orch_planet
+            .recv_timeout(Duration::from_millis(500));
+        assert_eq!(orch.planets_info.get_status(&0), Status::Paused);
+
+        // replace the real planet with a stub that acks the kill
+        let planet_from_orch = stub_planet_channels(&mut orch, 0);
+        let to_orch = orch.sender_planet_orch.clone();
+        let stub = thread::spawn(move || {
+            while let Ok(msg) = planet_from_orch.recv() {
+                if let OrchestratorToPlanet::KillPlanet = msg {
+                    to_orch
+                        .send(PlanetToOrchestrator::KillPlanetResult { planet_id: 0 })
+                        .unwrap();
+                    break;
+                }
+            }
+        });
+
+        // planet 99 is unknown, so the handler of this message fails
+        orch.sender_planet_orch
+            .send(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 99 })
+            .unwrap();
+
+        orch.kill_planet(0, Duration::from_millis(500)).unwrap();
+        assert_eq!(orch.planets_info.get_status(&0), Status::Dead);
+        stub.join().unwrap();
+
+        assert!(orch.kill_planet(0, Duration::from_millis(50)).is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_reinitialization {
+    use super::*;
+
+    #[test]
+    fn test_reinit_replaces_previous_galaxy() {
+        let mut orch = Orchestrator::new().unwrap();
+        let map_a = format!(
+            "0,{},1\n1,{},0",
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::OneMillionCrabs as u32
+        );
+        let map_b = format!(
+            "10,{},11\n11,{},10",
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::OneMillionCrabs as u32
+        );
+
+        orch.initialize_galaxy_by_content(&map_a).unwrap();
+        assert!(orch.is_initialized());
+        orch.start_all(&[], &[(4, 0)]).unwrap();
+        #[cfg(feature = "expose-handles")]
+        let to_old_explorer = orch.explorer_channels[&4].to_explorer().clone();
+        orch.initialize_galaxy_by_content(&map_b).unwrap();
+
+        assert!(orch.explorer_channels.is_empty());
+        assert!(orch.explorers_info.is_empty());
+        // the reset joins the old explorer thread, so nothing receives its messages
+        #[cfg(feature = "expose-handles")]
+        {
+            assert!(orch.explorer_handles.is_empty());
+            assert!(
+                to_old_explorer
+                    .send(OrchestratorToExplorer::CurrentPlanetRequest)
+                    .is_err()
+            );
+        }
+
+        assert_eq!(orch.planets_info.len(), 2);
+        assert!(orch.planets_info.contains(&10));
+        assert!(orch.planets_info.contains(&11));
+        assert!(!orch.planet_channels.contains_key(&0));
+        assert!(!orch.planet_channels.contains_key(&1));
+        assert!(!orch.galaxy_lookup.contains_key(&0));
+        assert_eq!(orch.galaxy_topology.len(), 2);
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+}
+
+#[cfg(test)]
+mod tests_ascii_map {
+    use super::*;
+    use crate::utils::ExplorerInfo;
+    use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+
+    #[test]
+    fn test_ascii_map_before_and_after_planet_death() {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        let map = format!("0,{ty},1\n1,{ty},0,2\n2,{ty},1");
+        orch.initialize_galaxy_by_content(&map).unwrap();
+        orch.explorers_info
+            .insert(7, ExplorerInfo::from(7, Status::Paused, Vec::new(), 0));
+
+        assert_eq!(
+            orch.render_ascii_map(),
+            "galaxy: 3 planets, 2 edges\n\
+             [P] 0 OneMillionCrabs ^7 -> [1]\n\
+             [P] 1 OneMillionCrabs -> [0, 2]\n\
+             [P] 2 OneMillionCrabs -> [1]\n"
+        );
+
+        orch.handle_planet_message(PlanetToOrchestrator::KillPlanetResult { planet_id: 1 })
+            .unwrap();
+
+        assert_eq!(
+            orch.render_ascii_map(),
+            "galaxy: 3 planets, 0 edges\n\
+             [P] 0 OneMillionCrabs ^7 -> []\n\
+             [D] 1 OneMillionCrabs -> []\n\
+             [P] 2 OneMillionCrabs -> []\n"
+        );
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+}
+
+#[cfg(test)]
+mod tests_debug_console {
+    use super::*;
+    use crate::components::orchestrator::debug::{DEBUG_HELP, exec_debug_command};
+    use crate::utils::ExplorerInfo;
+
+    fn setup_debug_galaxy() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        let content = format!("0,{ty},1\n1,{ty},0");
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.explorers_info
+            .insert(3, ExplorerInfo::from(3, Status::Running, Vec::new(), 1));
+        orch
+    }
+
+    #[test]
+    fn test_debug_status_and_stats() {
+        let mut orch = setup_debug_galaxy();
+
+        assert_eq!(
+            exec_debug_command(&mut orch, "status").unwrap(),
+            "planets: 2 (running 0, paused 2, dead 0)\nexplorers: 1 (running 1, paused 0, dead 0)"
+        );
+        let stats = exec_debug_command(&mut orch, "  :stats ").unwrap();
+        assert!(stats.contains("alive planets: 2/2"), "{}", stats);
+        assert!(stats.contains("edges: 1"), "{}", stats);
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_debug_planet_and_explorer_details() {
+        let mut orch = setup_debug_galaxy();
+
+        let planet = exec_debug_command(&mut orch, "planet 1").unwrap();
+        assert!(planet.starts_with("planet 1 (OneMillionCrabs)"), "{}", planet);
+        assert!(planet.contains("explorers: [3]"), "{}", planet);
+
+        let explorer = exec_debug_command(&mut orch, "explorer 3").unwrap();
+        assert!(explorer.contains("status: Running"), "{}", explorer);
+        assert!(explorer.contains("planet: 1"), "{}", explorer);
+
+        assert!(exec_debug_command(&mut orch, "planet 9").is_err());
+        assert!(exec_debug_command(&mut orch, "explorer 9").is_err());
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_debug_topology_matches_ascii_map() {
+        let mut orch = setup_debug_galaxy();
+        assert_eq!(
+            exec_debug_command(&mut orch, "topology").unwrap(),
+            orch.render_ascii_map()
+        );
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_debug_unknown_or_malformed_commands_return_help() {
+        let mut orch = Orchestrator::new().unwrap();
+
+        for line in ["dance", "", "planet", "planet abc", "kill explorer 1"] {
+            let err = exec_debug_command(&mut orch, line).unwrap_err();
+            assert!(err.ends_with(DEBUG_HELP), "{:?} -> {}", line, err);
+        }
+        assert_eq!(exec_debug_command(&mut orch, "help").unwrap(), DEBUG_HELP);
+    }
+
+    #[test]
+    fn test_debug_celestial_commands_refuse_dead_planets() {
+        let mut orch = setup_debug_galaxy();
+        orch.planets_info.update_status(1, Status::Dead).unwrap();
+
+        assert!(exec_debug_command(&mut orch, "sunray 1").is_err());
+        assert!(exec_debug_command(&mut orch, "asteroid 1").is_err());
+        assert!(exec_debug_command(&mut orch, "kill planet 1").is_err());
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_picker {
+    use super::*;
+    use crate::utils::WeightedPlanetPicker;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_picker_favours_heavy_planet_with_fixed_seed() {
+        let picker = WeightedPlanetPicker::new(HashMap::from([(0, 20.0), (2, 0.0)]));
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut hits: HashMap<u32, u32> = HashMap::new();
+
+        for _ in 0..10_000 {
+            let id = picker.pick(&[0, 1, 2], &mut rng).unwrap();
+            *hits.entry(id).or_default() += 1;
+        }
+
+        // expected shares: planet 0 20/21, planet 1 (default weight) 1/21, planet 2 none
+        let heavy = hits.get(&0).copied().unwrap_or(0);
+        let light = hits.get(&1).copied().unwrap_or(0);
+        assert!(heavy > 10 * light, "hits: {:?}", hits);
+        assert!(light > 0, "hits: {:?}", hits);
+        assert_eq!(hits.get(&2), None);
+    }
+
+    #[test]
+    fn test_picker_falls_back_to_uniform() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let zero_weights = WeightedPlanetPicker::new(HashMap::from([(0, 0.0), (1, -3.0)]));
+        assert!(zero_weights.pick(&[0, 1], &mut rng).is_some());
+        assert_eq!(WeightedPlanetPicker::default().pick(&[], &mut rng), None);
+    }
+
+    #[test]
+    fn test_send_asteroid_weighted_targets_living_planets() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!(
+            "0,{},1\n1,{},0",
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::Ciuc as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.planets_info.update_status(0, Status::Dead).unwrap();
+
+        let weights = HashMap::from([(0, 100.0)]);
+        assert_eq!(orch.send_asteroid_weighted(&weights), Ok(1));
+
+        orch.planets_info.update_status(1, Status::Dead).unwrap();
+        assert!(orch.send_asteroid_weighted(&weights).is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_filter {
+    use super::*;
+    use crate::components::orchestrator::sequence::PlanetFilter;
+
+    #[test]
+    fn test_planet_filter_by_type_skips_dead_planets() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!(
+            "0,{},1\n1,{},2\n2,{},0\n3,{}",
+            PlanetType::Ciuc as u32,
+            PlanetType::Ciuc as u32,
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::Ciuc as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.planets_info.update_status(1, Status::Dead).unwrap();
+
+        assert_eq!(
+            orch.planets_matching(&PlanetFilter::PlanetType(PlanetType::Ciuc)),
+            vec![0, 3]
+        );
+        assert_eq!(
+            orch.planets_matching(&PlanetFilter::PlanetType(PlanetType::OneMillionCrabs)),
+            vec![2]
+        );
+        assert!(
+            orch.planets_matching(&PlanetFilter::PlanetType(PlanetType::Rustrelli))
+                .is_empty()
+        );
+        assert_eq!(
+            orch.planets_matching(&PlanetFilter::AllPlanets),
+            vec![0, 2, 3]
+        );
+        assert_eq!(
+            orch.planets_matching(&PlanetFilter::PlanetIds(vec![1, 2, 7])),
+            vec![2]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_registry {
+    use crate::utils::registry::{PLANET_REGISTRY, PlanetType, validate_planet_registry};
+
+    #[test]
+    fn test_registry_has_an_entry_for_every_variant() {
+        assert_eq!(PlanetType::all_variants().len(), PLANET_REGISTRY.len());
+        assert!(validate_planet_registry().is_ok());
+    }
+
+    #[test]
+    fn test_all_variants_position_is_the_type_id() {
+        for (id, planet_type) in PlanetType::all_variants().iter().enumerate() {
+            assert_eq!(*planet_type as usize, id);
+        }
+        assert!(PlanetType::valid_types_list().starts_with("0=BlackAdidasShoe, 1=Ciuc"));
+    }
+}
+
+#[cfg(test)]
+mod tests_resource_names {
+    use crate::utils::resource_names::{BASIC_RESOURCES, COMPLEX_RESOURCES};
+    use crate::utils::{ResourceTypeName, parse_resource_type};
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+
+    fn all_resource_types() -> Vec<ResourceType> {
+        BASIC_RESOURCES
+            .into_iter()
+            .map(ResourceType::Basic)
+            .chain(COMPLEX_RESOURCES.into_iter().map(ResourceType::Complex))
+            .collect()
+    }
+
+    #[test]
+    fn test_resource_names_round_trip_every_variant() {
+        for resource in all_resource_types() {
+            let name = ResourceTypeName(resource).to_string();
+            assert_eq!(parse_resource_type(&name), Ok(resource), "{}", name);
+            assert_eq!(
+                parse_resource_type(&name.to_uppercase()),
+                Ok(resource),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_resource_names_parse_bare_and_case_insensitive() {
+        assert_eq!(
+            parse_resource_type("Water"),
+            Ok(ResourceType::Complex(ComplexResourceType::Water))
+        );
+        assert_eq!(
+            parse_resource_type(" carbon "),
+            Ok(ResourceType::Basic(BasicResourceType::Carbon))
+        );
+        assert_eq!(
+            parse_resource_type("basic(Silicon)"),
+            Ok(ResourceType::Basic(BasicResourceType::Silicon))
+        );
+        assert_eq!(
+            parse_resource_type("COMPLEX(aipartner)"),
+            Ok(ResourceType::Complex(ComplexResourceType::AIPartner))
+        );
+    }
+
+    #[test]
+    fn test_resource_names_unknown_string_is_rejected() {
+        let err = parse_resource_type("Unobtainium").unwrap_err();
+        assert!(err.contains("Unobtainium"), "{}", err);
+
+        // a complex name is not a valid basic resource
+        let err = parse_resource_type("Basic(Water)").unwrap_err();
+        assert!(err.contains("Water"), "{}", err);
+
+        assert!(parse_resource_type("Basic(Carbon").is_err());
+        assert!(parse_resource_type("Gas(Oxygen)").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_file_integration {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn test_initialize_galaxy_from_description() {
+        use crate::components::orchestrator::init::parse_galaxy_description;
+
+        let mut orch = Orchestrator::new().unwrap();
+
+        // Format: ID, Type, Neighbors...
+        let content = "0, 4, 1, 400\n1, 4, 0, 400\n400, 4, 0, 1";
+        let parsed = parse_galaxy_description(content).unwrap();
+        assert_eq!(parsed.planet_types().count(), 3);
+        let result = orch.initialize_galaxy(parsed);
+
+        assert!(result.is_ok());
+        assert!(orch.galaxy_lookup.contains_key(&0));
+        assert!(orch.galaxy_lookup.contains_key(&1));
+    }
+
+    #[test]
+    fn test_file_initialize_galaxy_from_missing_file() {
+        let mut orch = Orchestrator::new().unwrap();
+        assert!(orch.initialize_galaxy_by_file("missing_galaxy.csv").is_err());
+    }
+
+    #[test]
+    fn test_file_sequence_from_valid_file() {
+        use crate::components::orchestrator::sequence::OrchestratorAction;
+        use std::time::Duration;
+
+        let file_path = "test_sequence_valid.txt";
+        let content = "# opening\nsunray\n\nAsteroid\npause 500ms\n  pause 2s  \n";
+        let mut file = File::create(file_path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = Orchestrator::sequence_from_file(file_path);
+
+        // Clean up
+        let _ = std::fs::remove_file(file_path);
+
+        assert_eq!(
+            result.unwrap(),
+            vec![
+                OrchestratorAction::Sunray,
+                OrchestratorAction::Asteroid,
+                OrchestratorAction::Pause(Duration::from_millis(500)),
+                OrchestratorAction::Pause(Duration::from_secs(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_sequence_reports_line_of_invalid_action() {
+        let file_path = "test_sequence_invalid.txt";
+        let content = "sunray\nasteroid\npause soon\nsunray";
+        let mut file = File::create(file_path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = Orchestrator::sequence_from_file(file_path);
+
+        // Clean up
+        let _ = std::fs::remove_file(file_path);
+
+        let err = result.unwrap_err();
+        assert!(err.starts_with("line 3: 'pause soon'"), "{}", err);
+        assert!(Orchestrator::sequence_from_file("missing_sequence.txt").is_err());
+    }
+}
+#[cfg(test)]
+mod test_one_million_crabs_planet {
+    use crate::utils::registry::*;
+    use crate::*;
+    use common_game::components::resource::BasicResourceType;
+    use crossbeam_channel::{select, tick};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn planet_energy_cells_management() {
+        let mut orchestrator = Orchestrator::new().unwrap();
+        let planet_id = 1;
+        let explorer_id = 2;
+        let content = "1,7";
+        orchestrator.initialize_galaxy_by_content(content).unwrap();
+        orchestrator
+            .start_all(&[(explorer_id, planet_id)], &[])
+            .unwrap();
+        let planet_channel = orchestrator
+            .planet_channels
+            .get(&planet_id)
+            .unwrap()
+            .to_planet()
+            .clone();
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        println!("SENDED 6 SUNRAY");
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        sleep(Duration::from_millis(100));
+        let _ = orchestrator.send_sunray(planet_id, &planet_channel);
+        let _ = orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
+        sleep(Duration::from_secs(1));
+        let _ = orchestrator.send_bag_content_request(explorer_id);
+        let _ = orchestrator.send_internal_state_request(
+            orchestrator.planet_channels[&planet_id].to_planet(),
+            planet_id,
+        );
+        let timeout = tick(Duration::from_millis(1000));
+        loop {
+            select! {
+                recv(orchestrator.receiver_orch_planet) -> planet_msg => {
+                    match planet_msg {
+                        Ok(msg) => {
+                            let _=orchestrator.handle_planet_message(msg);
+                        }
+                        Err(_) => {}
+                    }
+                }
+                recv(orchestrator.receiver_orch_explorer)-> explorer_msg=> {
+                    match explorer_msg {
+                        Ok(msg) => {
+                            let _=orchestrator.handle_explorer_message(msg);
+                        }
+                        Err(_) => {}
+                    }
+                }
+                recv(timeout) -> _ => {
+                    break;
+                }
+            }
+        }
+        assert_eq!(
+            orchestrator
+                .planets_info
+                .get_info(planet_id)
+                .unwrap()
+                .energy_cells
+                .iter()
+                .filter(|&&x| x)
+                .count(),
+            0
+        );
+        println!(
+            "explorer bag: {:?}",
+            orchestrator.explorers_info.get(&explorer_id).unwrap().bag
+        );
+    }
+    #[test]
+    fn stress_planet_energy_cells_management() {
+        let mut orchestrator = Orchestrator::new().unwrap();
+        let planet_id = 1;
+        let explorer_id = 2;
+
+        let content = format!("{},{}", planet_id, PlanetType::OneMillionCrabs as u32);
+        orchestrator.initialize_galaxy_by_content(&content).unwrap();
+        orchestrator
+            .start_all(&[(explorer_id, planet_id)], &[])
+            .unwrap();
+        let planet_channel = orchestrator
+            .planet_channels
+            .get(&planet_id)
+            .unwrap()
+            .to_planet()
+            .clone();
+        let _=orchestrator.send_sunray(planet_id, &planet_channel);
+        let _=orchestrator.send_sunray(planet_id, &planet_channel);
         let _=orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
         let _=orchestrator.send_sunray(planet_id, &planet_channel);
         let _=orchestrator.send_generate_resource_request(explorer_id, BasicResourceType::Silicon);
@@ -311,435 +1157,3029 @@ mod test_one_million_crabs_planet {
         sleep(Duration::from_secs(1));
         let _=orchestrator.send_bag_content_request(explorer_id);
         let _=orchestrator.send_internal_state_request(
-            &orchestrator.planet_channels.get(&planet_id).unwrap().0,
+            orchestrator.planet_channels[&planet_id].to_planet(),
             planet_id,
         );
-        let timeout = tick(Duration::from_millis(1000));
-        loop {
-            select! {
-                recv(orchestrator.receiver_orch_planet) -> planet_msg => {
-                    match planet_msg {
-                        Ok(msg) => {
-                            let _=orchestrator.handle_planet_message(msg);
-                        }
-                        Err(_) => {}
-                    }
-                }
-                recv(orchestrator.receiver_orch_explorer)-> explorer_msg=> {
-                    match explorer_msg {
-                        Ok(msg) => {
-                            let _=orchestrator.handle_explorer_message(msg);
-                        }
-                        Err(_) => {}
-                    }
-                }
-                recv(timeout) -> _ => {
-                    break;
+        let timeout = tick(Duration::from_millis(1000));
+        loop {
+            select! {
+                recv(orchestrator.receiver_orch_planet) -> planet_msg => {
+                    match planet_msg {
+                        Ok(msg) => {
+                            let _=orchestrator.handle_planet_message(msg);
+                        }
+                        Err(_) => {}
+                    }
+                }
+                recv(orchestrator.receiver_orch_explorer)-> explorer_msg=> {
+                    match explorer_msg {
+                        Ok(msg) => {
+                            let _=orchestrator.handle_explorer_message(msg);
+                        }
+                        Err(_) => {}
+                    }
+                }
+                recv(timeout) -> _ => {
+                    break;
+                }
+            }
+        }
+        assert!(
+            orchestrator
+                .explorers_info
+                .get(&explorer_id)
+                .unwrap()
+                .bag
+                .iter()
+                .filter(|&&x| x.is_silicon())
+                .count()
+                <= 12
+        );
+        let _ =orchestrator.send_planet_kill_to_all();
+        let _ = orchestrator.send_kill_explorer_ai(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::registry::PlanetType;
+    use std::time::Duration;
+
+    // --- MACRO CATEGORY: MIXED SITUATIONS ---
+    // Testing survival rates when different planet types are combined.
+    mod mixed_scenarios {
+        use super::*;
+
+        #[test]
+        fn test_orchestrator_mixed_survival_logic() {
+            let mut orch = Orchestrator::new().unwrap();
+
+            // Type A (HoustonWeHaveABorrow) - Can build rockets
+            let p_id_a = 1;
+            // Type B (BlackAdidasShoe) - Cannot build rockets
+            let p_id_b = 2;
+
+            // Initialize galaxy with both planets connected
+            let content = format!(
+                "{},{},{}\n{},{},{}",
+                p_id_a,
+                PlanetType::HoustonWeHaveABorrow as u32,
+                p_id_b,
+                p_id_b,
+                PlanetType::BlackAdidasShoe as u32,
+                p_id_a
+            );
+            orch.initialize_galaxy_by_content(&content).unwrap();
+
+            orch.start_all(&[], &[]).unwrap();
+
+            // Phase 1: Provide resources
+            // We give them sunrays. Only Type A should effectively use it.
+            // Cloning is ok: Sender is a handler, not a full structure.
+            let channel_a = orch.planet_channels[&p_id_a].to_planet().clone();
+            let channel_b = orch.planet_channels[&p_id_b].to_planet().clone();
+
+            orch.send_sunray(p_id_a, &channel_a).unwrap();
+            orch.send_sunray(p_id_b, &channel_b).unwrap();
+
+            // Give the planet threads a moment to process the sunray and build
+            std::thread::sleep(Duration::from_millis(500));
+            // We simulate receiving the responses from the channels
+            // (In a real run, handle_game_messages would do this)
+            orch.handle_game_messages().unwrap();
+            orch.handle_game_messages().unwrap();
+
+            println!(
+                "after sunray - planet a status: {:?}",
+                orch.planets_info.get_info(p_id_a)
+            );
+            println!(
+                "after sunray - planet b status: {:?}",
+                orch.planets_info.get_info(p_id_b)
+            );
+
+            // Phase 2: Asteroid Attack
+            orch.send_asteroid(p_id_a, &channel_a).unwrap();
+            orch.send_asteroid(p_id_b, &channel_b).unwrap();
+
+            // Give the planet threads a moment to process the asteroids and build
+            std::thread::sleep(Duration::from_millis(500));
+            // We simulate receiving the responses from the channels
+            // (In a real run, handle_game_messages would do this)
+            orch.handle_game_messages().unwrap();
+            orch.handle_game_messages().unwrap();
+
+            println!(
+                "after sunray - planet a status: {:?}",
+                orch.planets_info.get_info(p_id_a)
+            );
+            println!(
+                "after sunray - planet b status: {:?}",
+                orch.planets_info.get_info(p_id_b)
+            );
+
+            // Verification: A should be Alive/Running, B should be Dead
+            assert!(orch.planets_info.is_running(&p_id_a));
+            assert!(orch.planets_info.is_dead(&p_id_b));
+        }
+    }
+
+    // --- MACRO CATEGORY: PLANET INTEGRATION (ALL TYPES) ---
+    // Testing one of every single planet in the registry simultaneously.
+    mod planet_integration {
+        use super::*;
+        use strum::IntoEnumIterator;
+
+        #[test]
+        fn test_orchestrator_integration_all_planet_types_behavior() {
+            let mut orch = Orchestrator::new().unwrap();
+
+            // Build content string dynamically from all planet types
+            let planet_types: Vec<PlanetType> = PlanetType::iter().collect();
+            let id_counter = planet_types.len() as u32;
+
+            let content: String = planet_types
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("{},{}", i, *p as u32))
+                .collect::<Vec<_>>()
+                .join("\n");
+            orch.initialize_galaxy_by_content(&content).unwrap();
+
+            orch.start_all(&[], &[]).unwrap();
+
+            // Sequence: 3 Sunrays (enough to build defense), then 1 Asteroid
+            for _ in 0..3 {
+                for id in 0..id_counter {
+                    let _ = orch.send_sunray(id, &orch.planet_channels[&id].to_planet().clone());
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+
+            // Fire Asteroids
+            for id in 0..id_counter {
+                let _ = orch.send_asteroid(id, &orch.planet_channels[&id].to_planet().clone());
+            }
+
+            // Wait for processing
+            std::thread::sleep(Duration::from_secs(1));
+            orch.handle_game_messages().unwrap();
+
+            // Validation logic based on your rules:
+            // Type A/C (Ciuc, ImmutableCosmicBorrow) should survive.
+            // Type B/D (Houston, BlackAdidas, OneMillionCrabs) should be Dead.
+            for (id, info) in orch.planets_info.iter() {
+                // This is a high-level check. Depending on specific AI timing,
+                // some might still be Alive if they didn't finish processing the death.
+                println!("Planet {} status: {:?}", id, info.status);
+            }
+        }
+
+        #[test]
+        fn sunray_flood_all_planets() {
+            let mut orch = Orchestrator::new().unwrap();
+
+            // Build content string dynamically from all planet types
+            let planet_types: Vec<PlanetType> = PlanetType::iter().collect();
+            let id_counter = planet_types.len() as u32;
+
+            let content: String = planet_types
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("{},{}", i, *p as u32))
+                .collect::<Vec<_>>()
+                .join("\n");
+            orch.initialize_galaxy_by_content(&content).unwrap();
+
+            orch.start_all(&[], &[]).unwrap();
+
+            //send 10 sunrays to all planets: they should all be full
+            for _ in 0..40 {
+                for id in 0..id_counter {
+                    orch.send_sunray(id, &orch.planet_channels[&id].to_planet().clone())
+                        .expect("failed sending sunray");
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+
+            std::thread::sleep(Duration::from_secs(1));
+            for id in 0..id_counter {
+                orch.send_internal_state_request(orch.planet_channels[&id].to_planet(), id)
+                    .expect("failed sending internal state request");
+            }
+            std::thread::sleep(Duration::from_millis(100));
+            orch.handle_game_messages().unwrap();
+
+            //used to see all the charging statuses, even
+            // if a planet fails early
+            let mut failed_counter = 0;
+
+            //check their status after the flood
+            for id in 0..id_counter {
+                let status = orch
+                    .planets_info
+                    .get_info(id)
+                    .expect("error getting planet info");
+                let max_charged = status.energy_cells.len();
+                let curr_charged = status.charged_cells_count;
+
+                println!(
+                    "checking id {}: max of {} and charged to {}",
+                    id, max_charged, curr_charged
+                );
+                if max_charged != curr_charged {
+                    failed_counter += 1;
+                }
+            }
+
+            assert_eq!(failed_counter, 0);
+        }
+    }
+
+    // --- MACRO CATEGORY: HEAVY & LONG TESTS ---
+    // Stress testing the Orchestrator with many actors and repeated cycles.
+    mod heavy_load {
+        use super::*;
+
+        #[test]
+        fn test_orchestrator_heavy_load_mass_extinction() {
+            let mut orch = Orchestrator::new().unwrap();
+            let n_planets = 50u32;
+
+            // Build content string with random planet types
+            let content: String = (0..n_planets)
+                .map(|i| format!("{},{}", i, PlanetType::random() as u32))
+                .collect::<Vec<_>>()
+                .join("\n");
+            orch.initialize_galaxy_by_content(&content).unwrap();
+
+            orch.start_all(&[], &[]).unwrap();
+
+            // Long test: 10 cycles of sunrays/asteroids
+            for cycle in 0..10 {
+                for i in 0..n_planets {
+                    let _ = orch.send_sunray(i, &orch.planet_channels[&i].to_planet().clone());
+                }
+                std::thread::sleep(Duration::from_millis(50));
+
+                for i in 0..n_planets {
+                    let _ = orch.send_asteroid(i, &orch.planet_channels[&i].to_planet().clone());
+                }
+
+                let _ = orch.handle_game_messages();
+                println!("Cycle {} complete", cycle);
+            }
+
+            // Check how many survived the onslaught
+            let survivors = orch.planets_info.count_survivors();
+
+            println!("Survivors: {}/{}", survivors, n_planets);
+            // In a heavy scenario, we just want to ensure the Orchestrator didn't crash
+            assert_eq!(orch.planets_info.len(), n_planets as usize);
+        }
+
+        #[test]
+        fn test_orchestrator_heavy_channel_congestion() {
+            let mut orch = Orchestrator::new().unwrap();
+
+            let content = format!("0,{}", PlanetType::Ciuc as u32);
+            orch.initialize_galaxy_by_content(&content).unwrap();
+
+            orch.start_all(&[], &[]).unwrap();
+
+            // Spam 1000 sunrays to a single planet to test channel capacity/backpressure
+            for _ in 0..1000 {
+                let _ = orch.send_sunray(0u32, &orch.planet_channels[&0].to_planet().clone());
+            }
+
+            // Ensure the orchestrator remains responsive
+            let result = orch.handle_game_messages();
+            assert!(result.is_ok());
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_bag_content_request {
+        use super::*;
+        use crate::components::orchestrator::Orchestrator;
+        use crate::utils::registry::PlanetType;
+
+        #[test]
+        fn explorer_manual_move() {
+            let mut orchestrator = Orchestrator::new().unwrap();
+            let starter_planet = 1;
+            let destination = 3;
+            let explorer_id = 0;
+            let content = "1,4,2,3\n2,4,3\n3,4";
+            orchestrator.initialize_galaxy_by_content(&content).unwrap();
+            orchestrator
+                .start_all(&[], &[(explorer_id, starter_planet)])
+                .unwrap();
+
+            println!("topology: {:?}", orchestrator.get_topology().0);
+            println!(
+                "attempting move from planet {} to planet {}",
+                starter_planet, destination
+            );
+
+            if let Err(res) = orchestrator.send_stop_explorer_ai(explorer_id) {
+                panic!("could not stop explorer AI. full error: {}", res);
+            }
+
+            println!(
+                "explorer is currently stopped and in planet {}",
+                orchestrator
+                    .explorers_info
+                    .get_planet(&explorer_id)
+                    .unwrap()
+            );
+
+            if let Err(res) = orchestrator.send_move_to_planet(explorer_id, destination) {
+                panic!(
+                    "could not send planet move to explorer. full error: {}",
+                    res
+                );
+            }
+
+            std::thread::sleep(Duration::from_millis(100));
+
+            orchestrator.handle_game_messages().unwrap();
+
+            println!(
+                "after the move message the explorer is in planet {}",
+                orchestrator
+                    .explorers_info
+                    .get_planet(&explorer_id)
+                    .unwrap()
+            );
+
+            assert_eq!(orchestrator
+                           .explorers_info
+                           .get_planet(&explorer_id)
+                           .unwrap(), destination);
+        }
+
+        #[test]
+        fn test_send_bag_content_success() {
+            let mut orch = Orchestrator::new().unwrap();
+            let planet_id = 1;
+            let explorer_id = 100;
+
+            let content = format!("{},{}", planet_id, PlanetType::OneMillionCrabs as u32);
+            orch.initialize_galaxy_by_content(&content).unwrap();
+            orch.start_all(&[], &[(explorer_id, planet_id)]).unwrap();
+
+            let result = orch.send_bag_content_request(explorer_id);
+
+            assert!(
+                result.is_ok(),
+                "L'invio della richiesta bag_content all'explorer {} è fallito: {:?}",
+                explorer_id,
+                result.err()
+            );
+        }
+
+        #[test]
+        fn test_send_bag_content_missing_explorer() {
+            let orch = Orchestrator::new().unwrap();
+            let invalid_explorer_id = 999;
+
+            let result = orch.send_bag_content_request(invalid_explorer_id);
+
+            assert!(
+                result.is_err(),
+                "L'invio doveva fallire per un explorer inesistente"
+            );
+            assert_eq!(
+                result.unwrap_err(),
+                format!("No sender found for explorer {}", invalid_explorer_id)
+            );
+        }
+
+        #[test]
+        fn test_send_bag_content_disconnected_channel() {
+            let mut orch = Orchestrator::new().unwrap();
+            let planet_id = 2;
+            let explorer_id = 200;
+
+            let content = format!("{},{}", planet_id, PlanetType::Ciuc as u32);
+            orch.initialize_galaxy_by_content(&content).unwrap();
+            orch.start_all(&[], &[(explorer_id, planet_id)]).unwrap();
+
+            drop(stub_explorer_channels(&mut orch, explorer_id));
+
+            let result = orch.send_bag_content_request(explorer_id);
+
+            assert!(result.is_err());
+            let err_msg = result.unwrap_err();
+            assert!(
+                err_msg.contains("Failed to send bag content request"),
+                "Il messaggio di errore era imprevisto: {}",
+                err_msg
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_message_tracing {
+    use super::*;
+    use crate::utils::tracing::{MessageRecorder, RecordingSender, TracedActor};
+    use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn test_recording_proxy_keeps_the_order_seen_by_the_receiver() {
+        let recorder = MessageRecorder::new();
+        let (sender, receiver) = unbounded::<OrchestratorToPlanet>();
+        let proxy = RecordingSender::new(
+            sender,
+            recorder.clone(),
+            TracedActor::Orchestrator,
+            TracedActor::Planet(1),
+        )
+        .into_proxy();
+
+        // the mock planet writes down what it receives
+        let mock_planet = std::thread::spawn(move || {
+            receiver
+                .iter()
+                .map(|msg| format!("{:?}", msg))
+                .collect::<Vec<_>>()
+        });
+        proxy.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        proxy.send(OrchestratorToPlanet::InternalStateRequest).unwrap();
+        proxy.send(OrchestratorToPlanet::StopPlanetAI).unwrap();
+        drop(proxy);
+        let received = mock_planet.join().unwrap();
+
+        let transcript = recorder.transcript(TracedActor::Planet(1), TracedActor::Orchestrator);
+        let recorded: Vec<String> = transcript.iter().map(|r| r.message.clone()).collect();
+        assert_eq!(recorded, received);
+        assert!(transcript.windows(2).all(|w| w[0].seq < w[1].seq));
+        assert!(
+            transcript
+                .iter()
+                .all(|r| r.from == TracedActor::Orchestrator && r.to == TracedActor::Planet(1))
+        );
+    }
+
+    #[test]
+    fn test_orchestrator_records_planet_exchange() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.enable_message_tracing();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all_planet_ais().unwrap();
+
+        let recorder = orch.message_recorder.clone().unwrap();
+        let transcript = recorder.transcript(TracedActor::Orchestrator, TracedActor::Planet(0));
+        let request = transcript
+            .iter()
+            .position(|r| r.from == TracedActor::Orchestrator && r.message == "StartPlanetAI")
+            .expect("StartPlanetAI not recorded");
+        let response = transcript
+            .iter()
+            .position(|r| {
+                r.from == TracedActor::Planet(0) && r.message.starts_with("StartPlanetAIResult")
+            })
+            .expect("StartPlanetAIResult not recorded");
+        assert!(request < response);
+        assert!(transcript[request].elapsed <= transcript[response].elapsed);
+
+        let dir = std::env::temp_dir().join(format!("omc_transcripts_{}", std::process::id()));
+        let written = orch.dump_transcripts(dir.to_str().unwrap()).unwrap();
+        assert_eq!(written, recorder.pairs().len());
+        let dumped = std::fs::read_to_string(dir.join("orchestrator-planet_0.log")).unwrap();
+        assert!(dumped.lines().count() >= 2);
+        assert!(dumped.lines().next().unwrap().contains("orchestrator -> planet_0"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_traced_explorer_sender_reports_a_dead_explorer() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.enable_message_tracing();
+        let (sender_orch, receiver_orch) = unbounded::<OrchestratorToExplorer>();
+        let (sender_planet, _receiver_planet) = unbounded();
+        let (sender_orch, sender_planet) =
+            orch.traced_explorer_senders(4, 0, sender_orch, sender_planet);
+        let handle = ExplorerHandle::new(sender_orch, sender_planet);
+
+        // the explorer is gone: the very first message must fail, as without tracing
+        drop(receiver_orch);
+        assert!(
+            handle
+                .send_to_explorer(OrchestratorToExplorer::KillExplorer)
+                .is_err()
+        );
+        assert!(
+            handle
+                .try_send_to_explorer(OrchestratorToExplorer::KillExplorer)
+                .is_err()
+        );
+
+        let recorder = orch.message_recorder.clone().unwrap();
+        let transcript = recorder.transcript(TracedActor::Orchestrator, TracedActor::Explorer(4));
+        assert_eq!(transcript.len(), 2);
+        assert!(transcript.iter().all(|r| r.message == "KillExplorer"));
+    }
+
+    #[test]
+    fn test_recording_proxy_disconnects_after_a_failed_forward() {
+        let (sender, receiver) = unbounded::<OrchestratorToPlanet>();
+        let proxy = RecordingSender::new(
+            sender,
+            MessageRecorder::new(),
+            TracedActor::Orchestrator,
+            TracedActor::Planet(1),
+        )
+        .into_proxy();
+        drop(receiver);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(1);
+        while proxy.send(OrchestratorToPlanet::StartPlanetAI).is_ok() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "the proxy never reported the disconnection"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_dump_transcripts_without_tracing_fails() {
+        let orch = Orchestrator::new().unwrap();
+        assert!(orch.message_recorder.is_none());
+        assert!(orch.dump_transcripts("unused").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_game_report {
+    use super::*;
+    use crate::utils::GameReport;
+    use crate::utils::report::{ExplorerReport, PlanetReport, REPORT_CSV_HEADER};
+
+    #[test]
+    fn test_report_csv_of_empty_galaxy_is_header_only() {
+        let orch = Orchestrator::new().unwrap();
+        let mut csv = Vec::new();
+        orch.game_report().to_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), format!("{}\n", REPORT_CSV_HEADER));
+    }
+
+    #[test]
+    fn test_report_csv_has_a_row_per_planet_and_explorer() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!(
+            "0,{},1\n1,{},0",
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::OneMillionCrabs as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[(5, 0)]).unwrap();
+        let sender = orch.planet_channels[&1].to_planet().clone();
+        orch.send_sunray(1, &sender).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        orch.handle_game_messages().unwrap();
+
+        let mut csv = Vec::new();
+        orch.game_report().to_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], REPORT_CSV_HEADER);
+        assert_eq!(lines.len(), 1 + 2 + 1);
+        assert!(lines[1].starts_with("planet,0,OneMillionCrabs,"));
+        assert!(lines[2].starts_with("planet,1,OneMillionCrabs,"));
+        assert!(lines[3].starts_with("explorer,5,0,"));
+        let columns = REPORT_CSV_HEADER.split(',').count();
+        assert!(lines.iter().all(|l| l.split(',').count() == columns));
+
+        let _ = orch.reset();
+    }
+
+    fn synthetic_report() -> GameReport {
+        GameReport {
+            planets: vec![
+                PlanetReport {
+                    id: 0,
+                    planet_type: PlanetType::OneMillionCrabs,
+                    status: Status::Running,
+                    charged_cells: 1,
+                    energy_cells: 5,
+                    rocket: true,
+                },
+                PlanetReport {
+                    id: 1,
+                    planet_type: PlanetType::OneMillionCrabs,
+                    status: Status::Dead,
+                    charged_cells: 0,
+                    energy_cells: 5,
+                    rocket: false,
+                },
+            ],
+            explorers: vec![ExplorerReport {
+                id: 5,
+                planet_id: 0,
+                status: Status::Paused,
+                bag_resources: 3,
+            }],
+            topology: Vec::new(),
+        }
+        .with_topology([(2, 0), (0, 1)])
+    }
+
+    #[test]
+    fn test_report_markdown_matches_the_fixture() {
+        let expected = "\
+# Game report
+
+## Planets
+
+| id | type | status | charged cells | energy cells | rocket |
+|---:|------|--------|--------------:|-------------:|--------|
+| 0 | OneMillionCrabs | Running | 1 | 5 | true |
+| 1 | OneMillionCrabs | Dead | 0 | 5 | false |
+
+## Explorers
+
+| id | planet | status | bag resources |
+|---:|-------:|--------|--------------:|
+| 5 | 0 | Paused | 3 |
+
+## Topology
+
+| planet | planet |
+|-------:|-------:|
+| 0 | 1 |
+| 0 | 2 |
+";
+        assert_eq!(synthetic_report().to_markdown(), expected);
+    }
+
+    #[test]
+    fn test_report_markdown_of_empty_galaxy() {
+        let orch = Orchestrator::new().unwrap();
+        assert_eq!(
+            orch.game_report().to_markdown(),
+            "# Game report\n\n## Planets\n\n_none_\n\n## Explorers\n\n_none_\n\n## Topology\n\n_none_\n"
+        );
+    }
+
+    #[test]
+    fn test_report_json_matches_the_fixture() {
+        let expected = concat!(
+            r#"{"planets":["#,
+            r#"{"id":0,"planet_type":"OneMillionCrabs","status":"Running","charged_cells":1,"energy_cells":5,"rocket":true},"#,
+            r#"{"id":1,"planet_type":"OneMillionCrabs","status":"Dead","charged_cells":0,"energy_cells":5,"rocket":false}"#,
+            r#"],"explorers":["#,
+            r#"{"id":5,"planet_id":0,"status":"Paused","bag_resources":3}"#,
+            r#"],"topology":[[0,1],[0,2]]}"#
+        );
+        assert_eq!(synthetic_report().to_json(), expected);
+        assert_eq!(
+            GameReport::default().to_json(),
+            r#"{"planets":[],"explorers":[],"topology":[]}"#
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_galaxy_parser {
+    use super::*;
+    use crate::components::orchestrator::init::ParsedGalaxy;
+
+    #[test]
+    fn test_parser_from_str_builds_lookup_and_adj_list() {
+        let galaxy: ParsedGalaxy = "10,0,20\n\n20,1,10".parse().unwrap();
+        assert_eq!(galaxy.lookup.len(), 2);
+        assert_eq!(galaxy.lookup[&10].0, 0);
+        assert_eq!(galaxy.lookup[&20].0, 1);
+        assert_eq!(galaxy.adj_list, vec![vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn test_parser_rejects_duplicate_ids_with_line_numbers() {
+        let err = "1,0,2\n2,0,1\n1,1".parse::<ParsedGalaxy>().unwrap_err();
+        assert!(err.contains("Row 3"), "{}", err);
+        assert!(err.contains("row 1"), "{}", err);
+    }
+
+    #[test]
+    fn test_parser_rejects_empty_galaxy() {
+        assert!("".parse::<ParsedGalaxy>().is_err());
+        assert!("\n   \n".parse::<ParsedGalaxy>().is_err());
+    }
+
+    #[test]
+    fn test_parser_rejects_galaxy_over_the_size_cap() {
+        let input = "0,0\n1,0\n2,0";
+        assert!(ParsedGalaxy::parse_with_limit(input, 3).is_ok());
+        let err = ParsedGalaxy::parse_with_limit(input, 2).unwrap_err();
+        assert!(err.contains("more than 2 planets"), "{}", err);
+    }
+
+    #[test]
+    fn test_invalid_content_leaves_the_galaxy_untouched() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        assert!(orch.initialize_galaxy_by_content("").is_err());
+        orch.max_galaxy_size = 1;
+        assert!(orch.initialize_galaxy_by_content("0,0\n1,0").is_err());
+
+        assert!(orch.galaxy_lookup.contains_key(&0));
+        assert_eq!(orch.get_random_planet_id(), Ok(0));
+        let _ = orch.reset();
+    }
+
+    #[test]
+    fn test_empty_galaxy_has_no_random_planet() {
+        let mut orch = Orchestrator::new().unwrap();
+        assert!(orch.initialize_galaxy_by_content("").is_err());
+        assert!(orch.get_random_planet_id().is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_in_memory_galaxy {
+    use super::*;
+    use rustc_hash::FxHashMap;
+
+    fn three_planets_lookup() -> FxHashMap<u32, (u32, PlanetType)> {
+        let mut lookup = FxHashMap::default();
+        lookup.insert(10, (0, PlanetType::OneMillionCrabs));
+        lookup.insert(20, (1, PlanetType::OneMillionCrabs));
+        lookup.insert(30, (2, PlanetType::OneMillionCrabs));
+        lookup
+    }
+
+    #[test]
+    fn test_with_topology_spawns_the_planets() {
+        // 10 - 20 - 30
+        let adj_list = vec![vec![1], vec![0, 2], vec![1]];
+        let mut orch = Orchestrator::with_topology(adj_list, three_planets_lookup()).unwrap();
+
+        assert_eq!(orch.planets_info.len(), 3);
+        for id in [10, 20, 30] {
+            assert!(orch.planet_channels.contains_key(&id));
+            assert!(orch.planets_info.is_paused(&id));
+        }
+        let (mut edges, planet_num) = orch.get_topology();
+        edges.sort();
+        assert_eq!(planet_num, 3);
+        assert_eq!(edges, vec![(10, 20), (20, 30)]);
+
+        let _ = orch.reset();
+    }
+
+    #[test]
+    fn test_with_topology_rejects_inconsistent_galaxies() {
+        // missing row for planet 30
+        assert!(
+            Orchestrator::with_topology(vec![vec![1], vec![0]], three_planets_lookup()).is_err()
+        );
+        // neighbour index out of range
+        assert!(
+            Orchestrator::with_topology(vec![vec![1], vec![0], vec![5]], three_planets_lookup())
+                .is_err()
+        );
+        // empty galaxy
+        assert!(Orchestrator::with_topology(Vec::new(), FxHashMap::default()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_topology_delta {
+    use super::*;
+    use crate::utils::TopologyDelta;
+
+    #[test]
+    fn test_destroyed_links_are_sent_in_one_delta() {
+        let mut orch = Orchestrator::new().unwrap();
+        // 0 - 1 - 2, and 0 - 2
+        let content = format!(
+            "0,{t},1,2\n1,{t},0,2\n2,{t},0,1",
+            t = PlanetType::OneMillionCrabs as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        orch.explorer_topology_senders.insert(7, sender);
+
+        orch.destroy_topology_link(1).unwrap();
+        assert!(receiver.try_recv().is_err(), "deltas wait for the flush");
+
+        orch.flush_topology_deltas();
+        let delta = receiver.try_recv().unwrap();
+        let mut removed = delta.removed.clone();
+        removed.sort();
+        assert_eq!(removed, vec![(0, 1), (1, 2)]);
+        assert!(delta.added.is_empty());
+        assert_eq!(delta.dead_planets, vec![1]);
+        assert!(orch.pending_topology_delta.is_empty());
+
+        // nothing changed since the last flush
+        orch.flush_topology_deltas();
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_flush_forgets_disconnected_explorers() {
+        let mut orch = Orchestrator::new().unwrap();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        orch.explorer_topology_senders.insert(7, sender);
+        drop(receiver);
+
+        orch.pending_topology_delta.remove_link(0, 1);
+        orch.flush_topology_deltas();
+        assert!(orch.explorer_topology_senders.is_empty());
+    }
+
+    #[test]
+    fn test_delta_cancels_opposite_changes() {
+        let mut delta = TopologyDelta::new();
+        delta.add_link(2, 1);
+        delta.remove_link(1, 2);
+        assert_eq!(delta.removed, vec![(1, 2)]);
+        assert!(delta.added.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_actor_threads {
+    use super::*;
+    use crate::components::orchestrator::OrchestratorEvent;
+    use crate::utils::actor_threads::{
+        explorer_thread_name, parse_thread_name, planet_thread_name, spawn_actor_thread,
+    };
+    use crate::utils::tracing::TracedActor;
+
+    #[test]
+    fn test_thread_names_identify_the_actor() {
+        let planet = planet_thread_name(3, PlanetType::OneMillionCrabs);
+        assert_eq!(planet, "planet-3-OneMillionCrabs");
+        assert_eq!(parse_thread_name(&planet), Some(TracedActor::Planet(3)));
+
+        let explorer = explorer_thread_name(12, "tommy");
+        assert_eq!(explorer, "explorer-12-tommy");
+        assert_eq!(
+            parse_thread_name(&explorer),
+            Some(TracedActor::Explorer(12))
+        );
+
+        assert_eq!(parse_thread_name("main"), None);
+        assert_eq!(parse_thread_name("planet-x-OneMillionCrabs"), None);
+    }
+
+    #[test]
+    fn test_actor_panic_is_reported_to_the_gui() {
+        let mut orch = Orchestrator::new().unwrap();
+        let handle = spawn_actor_thread(
+            "planet-4242-Dummy".to_string(),
+            orch.crash_sender.clone(),
+            || panic!("dummy planet failure"),
+        )
+        .unwrap();
+        assert!(handle.join().is_err());
+
+        let crashes = orch.collect_actor_crashes();
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].actor, Some(TracedActor::Planet(4242)));
+        assert_eq!(crashes[0].thread_name, "planet-4242-Dummy");
+        assert!(crashes[0].message.contains("dummy planet failure"));
+
+        assert!(orch.gui_messages.iter().any(|e| matches!(
+            e,
+            OrchestratorEvent::ActorCrashed { thread_name, .. } if thread_name == "planet-4242-Dummy"
+        )));
+    }
+
+    #[test]
+    fn test_actor_panic_is_reported_only_to_its_orchestrator() {
+        let mut owner = Orchestrator::new().unwrap();
+        let mut other = Orchestrator::new().unwrap();
+        let handle = spawn_actor_thread(
+            "explorer-4243-mattia".to_string(),
+            owner.crash_sender.clone(),
+            || panic!("dummy explorer failure"),
+        )
+        .unwrap();
+        assert!(handle.join().is_err());
+
+        assert_eq!(owner.collect_actor_crashes().len(), 1);
+        assert!(other.collect_actor_crashes().is_empty());
+    }
+
+    #[test]
+    fn test_panic_of_a_non_actor_thread_is_ignored() {
+        let mut orch = Orchestrator::new().unwrap();
+        let unnamed = std::thread::spawn(|| panic!("not an actor")).join();
+        assert!(unnamed.is_err());
+        let not_an_actor =
+            spawn_actor_thread("worker".to_string(), orch.crash_sender.clone(), || {
+                panic!("not an actor either")
+            })
+            .unwrap();
+        assert!(not_an_actor.join().is_err());
+
+        assert!(orch.collect_actor_crashes().is_empty());
+        assert!(
+            !orch
+                .gui_messages
+                .iter()
+                .any(|e| matches!(e, OrchestratorEvent::ActorCrashed { .. }))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_topology_edges {
+    use super::*;
+
+    #[test]
+    fn test_edges_yields_each_link_once() {
+        let mut orch = Orchestrator::new().unwrap();
+        // 10 - 20 - 30 - 40, and 10 - 30
+        let links = [(0, 1), (1, 2), (2, 0), (2, 3)];
+        let mut topology = vec![vec![false; 4]; 4];
+        for (a, b) in links {
+            topology[a][b] = true;
+            topology[b][a] = true;
+        }
+        orch.galaxy_topology = topology;
+        for idx in 0..4u32 {
+            orch.galaxy_reverse_lookup.insert(idx, (idx + 1) * 10);
+        }
+
+        let mut edges: Vec<(u32, u32)> = orch.edges().collect();
+        edges.sort();
+        assert_eq!(edges, vec![(10, 20), (10, 30), (20, 30), (30, 40)]);
+    }
+
+    #[test]
+    fn test_edges_of_an_empty_galaxy() {
+        let orch = Orchestrator::new().unwrap();
+        assert_eq!(orch.edges().count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_batch_pause {
+    use super::*;
+    use common_game::protocols::orchestrator_explorer::{
+        ExplorerToOrchestrator, OrchestratorToExplorer,
+    };
+    use std::thread;
+    use std::time::Duration;
+
+    /// registers a stub explorer that acknowledges every start and stop
+    fn add_stub_explorer(
+        orch: &mut Orchestrator,
+        id: u32,
+        status: Status,
+    ) -> thread::JoinHandle<()> {
+        let expl_from_orch = stub_explorer(orch, id, status);
+        let to_orch = orch.sender_explorer_orch.clone();
+        thread::spawn(move || {
+            for msg in expl_from_orch {
+                let answer = match msg {
+                    OrchestratorToExplorer::StopExplorerAI => {
+                        ExplorerToOrchestrator::StopExplorerAIResult { explorer_id: id }
+                    }
+                    OrchestratorToExplorer::StartExplorerAI => {
+                        ExplorerToOrchestrator::StartExplorerAIResult { explorer_id: id }
+                    }
+                    _ => continue,
+                };
+                let _ = to_orch.send(answer);
+            }
+        })
+    }
+
+    #[test]
+    fn test_pause_and_resume_all_explorers() {
+        let mut orch = Orchestrator::new().unwrap();
+        let stubs: Vec<_> = (1..=3)
+            .map(|id| add_stub_explorer(&mut orch, id, Status::Running))
+            .collect();
+
+        orch.pause_all_explorers(Duration::from_millis(500))
+            .unwrap();
+        for id in 1..=3 {
+            assert!(orch.explorers_info.is_paused(&id));
+        }
+
+        orch.resume_all_explorers(Duration::from_millis(500))
+            .unwrap();
+        for id in 1..=3 {
+            assert!(orch.explorers_info.is_running(&id));
+        }
+
+        orch.explorer_channels.clear();
+        for stub in stubs {
+            stub.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pause_all_explorers_reports_silent_explorers() {
+        let mut orch = Orchestrator::new().unwrap();
+        let stub = add_stub_explorer(&mut orch, 1, Status::Running);
+        // explorer 2 never answers
+        let _expl_from_orch = stub_explorer(&mut orch, 2, Status::Running);
+
+        let err = orch
+            .pause_all_explorers(Duration::from_millis(100))
+            .unwrap_err();
+        assert!(err.contains("Timeout"));
+        assert!(err.contains("[2]"));
+        assert!(orch.explorers_info.is_paused(&1));
+
+        orch.explorer_channels.clear();
+        stub.join().unwrap();
+    }
+
+    #[test]
+    fn test_handler_error_does_not_stop_the_wait() {
+        let mut orch = Orchestrator::new().unwrap();
+        let stub = add_stub_explorer(&mut orch, 1, Status::Running);
+        // planet 0 is unknown, so the handler of this message fails
+        orch.sender_explorer_orch
+            .send(ExplorerToOrchestrator::SupportedResourceResult {
+                explorer_id: 1,
+                supported_resources: Default::default(),
+            })
+            .unwrap();
+
+        orch.pause_all_explorers(Duration::from_millis(500))
+            .unwrap();
+        assert!(orch.explorers_info.is_paused(&1));
+
+        orch.explorer_channels.clear();
+        stub.join().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests_explorer_buffer {
+    use crate::utils::explorer_buffer::buffer_orchestrator_msg;
+    use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_repeated_requests_are_buffered_once() {
+        let mut buffer = VecDeque::new();
+        for _ in 0..5 {
+            buffer_orchestrator_msg(&mut buffer, OrchestratorToExplorer::CurrentPlanetRequest);
+        }
+        assert_eq!(buffer.len(), 1);
+        assert!(matches!(
+            buffer[0],
+            OrchestratorToExplorer::CurrentPlanetRequest
+        ));
+    }
+
+    #[test]
+    fn test_latest_request_keeps_its_position() {
+        let mut buffer = VecDeque::new();
+        buffer_orchestrator_msg(&mut buffer, OrchestratorToExplorer::BagContentRequest);
+        buffer_orchestrator_msg(&mut buffer, OrchestratorToExplorer::StopExplorerAI);
+        buffer_orchestrator_msg(&mut buffer, OrchestratorToExplorer::BagContentRequest);
+        assert_eq!(buffer.len(), 2);
+        assert!(matches!(buffer[0], OrchestratorToExplorer::StopExplorerAI));
+        assert!(matches!(
+            buffer[1],
+            OrchestratorToExplorer::BagContentRequest
+        ));
+    }
+
+    #[test]
+    fn test_mutating_messages_are_never_deduplicated() {
+        let mut buffer = VecDeque::new();
+        for planet_id in 0..3 {
+            let (sender, _) = crossbeam_channel::unbounded();
+            buffer_orchestrator_msg(
+                &mut buffer,
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: Some(sender),
+                    planet_id,
+                },
+            );
+        }
+        assert_eq!(buffer.len(), 3);
+    }
+}
+
+#[cfg(test)]
+mod tests_channel_ext {
+    use crate::utils::CrossbeamChannelExt;
+
+    #[test]
+    fn test_try_recv_all_takes_the_queued_messages() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        assert!(receiver.try_recv_all().is_empty());
+
+        for i in 0..4 {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(CrossbeamChannelExt::len(&receiver), 4);
+        assert_eq!(receiver.try_recv_all(), vec![0, 1, 2, 3]);
+        assert!(CrossbeamChannelExt::is_empty(&receiver));
+
+        // the queued messages are still returned after a disconnection
+        sender.send(4).unwrap();
+        drop(sender);
+        assert_eq!(receiver.try_recv_all(), vec![4]);
+    }
+}
+
+#[cfg(test)]
+mod tests_broadcast {
+    use super::*;
+    use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
+
+    #[test]
+    fn test_broadcast_reaches_every_explorer() {
+        let mut orch = Orchestrator::new().unwrap();
+        let mut receivers: Vec<_> = (1..=3)
+            .map(|id| stub_explorer(&mut orch, id, Status::Running))
+            .collect();
+
+        let results = orch.broadcast_to_explorers(|| OrchestratorToExplorer::StopExplorerAI);
+        let ids: Vec<u32> = results.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        for receiver in &receivers {
+            assert!(matches!(
+                receiver.try_recv(),
+                Ok(OrchestratorToExplorer::StopExplorerAI)
+            ));
+        }
+
+        // explorer 2 is gone: the others still get the message
+        receivers.remove(1);
+        let err = orch.stop_all_explorer_ais().unwrap_err();
+        assert!(err.contains("explorer 2"));
+        for receiver in &receivers {
+            assert!(matches!(
+                receiver.try_recv(),
+                Ok(OrchestratorToExplorer::StopExplorerAI)
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_neighbor_cache {
+    use super::*;
+    use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
+
+    fn sorted(mut ids: Vec<u32>) -> Vec<u32> {
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_neighbor_cache_is_filled_and_invalidated() {
+        let mut orch = Orchestrator::new().unwrap();
+        // 0 - 1 - 2
+        let content = format!(
+            "0,{t},1\n1,{t},0,2\n2,{t},1",
+            t = PlanetType::OneMillionCrabs as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        assert_eq!(sorted(orch.neighbor_cache[&1].clone()), vec![0, 2]);
+        assert_eq!(orch.neighbor_cache[&0], vec![1]);
+
+        let expl_from_orch = stub_explorer_channels(&mut orch, 7);
+
+        // the answer comes from the cache, not from the topology
+        orch.neighbor_cache.insert(2, vec![99]);
+        orch.handle_neighbors_request(7, 2).unwrap();
+        assert!(matches!(
+            expl_from_orch.try_recv(),
+            Ok(OrchestratorToExplorer::NeighborsResponse { neighbors }) if neighbors == vec![99]
+        ));
+
+        // the dead planet and its neighbours are dropped from the cache
+        orch.destroy_topology_link(1).unwrap();
+        for id in 0..3 {
+            assert!(!orch.neighbor_cache.contains_key(&id));
+        }
+
+        // a miss reads the topology and fills the cache again
+        orch.handle_neighbors_request(7, 0).unwrap();
+        assert!(matches!(
+            expl_from_orch.try_recv(),
+            Ok(OrchestratorToExplorer::NeighborsResponse { neighbors }) if neighbors.is_empty()
+        ));
+        assert_eq!(orch.neighbor_cache[&0], Vec::<u32>::new());
+
+        let _ = orch.reset();
+        assert!(orch.neighbor_cache.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_recipes {
+    use crate::utils::recipes::{
+        bag_score, check_request, has_ingredients, ingredients, recipe_depth, request_output,
+        resource_score, used_in,
+    };
+    use crate::utils::resource_names::COMPLEX_RESOURCES;
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+
+    #[test]
+    fn test_ingredients_of_ai_partner() {
+        assert_eq!(
+            ingredients(ComplexResourceType::AIPartner),
+            [
+                ResourceType::Complex(ComplexResourceType::Robot),
+                ResourceType::Complex(ComplexResourceType::Diamond),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_depth_follows_the_recipes() {
+        assert_eq!(recipe_depth(ComplexResourceType::Water), 1);
+        assert_eq!(recipe_depth(ComplexResourceType::Diamond), 1);
+        assert_eq!(recipe_depth(ComplexResourceType::Life), 2);
+        assert_eq!(recipe_depth(ComplexResourceType::Robot), 3);
+        assert_eq!(recipe_depth(ComplexResourceType::Dolphin), 3);
+        assert_eq!(recipe_depth(ComplexResourceType::AIPartner), 4);
+
+        // a complex ingredient is always shallower than the recipe using it
+        for complex in COMPLEX_RESOURCES {
+            for ingredient in ingredients(complex) {
+                if let ResourceType::Complex(inner) = ingredient {
+                    assert!(recipe_depth(inner) < recipe_depth(complex));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_used_in_and_has_ingredients() {
+        assert_eq!(
+            used_in(ResourceType::Complex(ComplexResourceType::Life)),
+            vec![ComplexResourceType::Robot, ComplexResourceType::Dolphin]
+        );
+        assert!(used_in(ResourceType::Complex(ComplexResourceType::AIPartner)).is_empty());
+
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        assert!(!has_ingredients(&[carbon], ComplexResourceType::Diamond));
+        assert!(has_ingredients(
+            &[carbon, carbon],
+            ComplexResourceType::Diamond
+        ));
+    }
+
+    #[test]
+    fn test_ai_partner_outscores_its_raw_basics() {
+        let basic = |b| ResourceType::Basic(b);
+        // every basic resource that ends up in an AIPartner
+        let raw = [
+            basic(BasicResourceType::Silicon),
+            basic(BasicResourceType::Hydrogen),
+            basic(BasicResourceType::Oxygen),
+            basic(BasicResourceType::Carbon),
+            basic(BasicResourceType::Carbon),
+            basic(BasicResourceType::Carbon),
+        ];
+        let ai_partner = [ResourceType::Complex(ComplexResourceType::AIPartner)];
+
+        assert_eq!(bag_score(&raw), 6);
+        assert!(bag_score(&ai_partner) > bag_score(&raw));
+        assert_eq!(bag_score(&[]), 0);
+
+        // a complex resource is worth more than its ingredients
+        for complex in COMPLEX_RESOURCES {
+            let [a, b] = ingredients(complex);
+            assert!(resource_score(ResourceType::Complex(complex)) > bag_score(&[a, b]));
+        }
+    }
+
+    #[test]
+    fn test_check_request_rejects_mismatched_output() {
+        use crate::Orchestrator;
+        use common_game::components::energy_cell::EnergyCell;
+        use common_game::components::resource::{ComplexResourceRequest, Generator};
+
+        let orch = Orchestrator::new().unwrap();
+        let mut generator = Generator::new();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        let carbon = || {
+            let mut cell = EnergyCell::new();
+            cell.charge(orch.forge.generate_sunray());
+            generator.make_carbon(&mut cell).unwrap()
+        };
+        let request = ComplexResourceRequest::Diamond(carbon(), carbon());
+
+        assert_eq!(request_output(&request), ComplexResourceType::Diamond);
+        assert!(check_request(&request, ComplexResourceType::Diamond).is_ok());
+        assert!(check_request(&request, ComplexResourceType::Water).is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_explorer_ranking {
+    use super::*;
+    use crate::utils::ExplorerInfo;
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+    use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+
+    #[test]
+    fn test_ranking_follows_the_bag_content_responses() {
+        let mut orch = Orchestrator::new().unwrap();
+        for id in [1, 2, 3] {
+            orch.explorers_info
+                .insert(id, ExplorerInfo::from(id, Status::Running, Vec::new(), 0));
+        }
+        assert_eq!(orch.explorer_ranking(), vec![(1, 0), (2, 0), (3, 0)]);
+
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 1,
+            bag_content: vec![carbon, carbon, carbon],
+        })
+        .unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 3,
+            bag_content: vec![ResourceType::Complex(ComplexResourceType::AIPartner)],
+        })
+        .unwrap();
+
+        let ranking = orch.explorer_ranking();
+        let ids: Vec<u32> = ranking.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+        assert_eq!(ranking[1], (1, 3));
+        assert_eq!(ranking[2], (2, 0));
+    }
+}
+
+#[cfg(test)]
+mod tests_tick_once {
+    use super::*;
+    use crate::components::orchestrator::OrchestratorEvent;
+    use crate::components::orchestrator::sequence::OrchestratorAction;
+
+    fn sunrays_sent(orch: &Orchestrator) -> usize {
+        orch.gui_messages
+            .iter()
+            .filter(|event| matches!(event, OrchestratorEvent::SunraySent { planet_id: 3, .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_tick_once_sends_one_sunray() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("3,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        orch.tick_once(Some(OrchestratorAction::Sunray)).unwrap();
+        assert_eq!(orch.tick, 1);
+        assert_eq!(sunrays_sent(&orch), 1);
+
+        // a tick without an action only handles the messages
+        orch.tick_once(None).unwrap();
+        assert_eq!(orch.tick, 2);
+        assert_eq!(sunrays_sent(&orch), 1);
+
+        let _ = orch.reset();
+        assert_eq!(orch.tick, 0);
+    }
+
+    #[test]
+    fn test_tick_once_fails_without_planets() {
+        let mut orch = Orchestrator::new().unwrap();
+        assert!(orch.tick_once(Some(OrchestratorAction::Asteroid)).is_err());
+        assert_eq!(orch.tick, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_game_clock {
+    use super::*;
+    use crate::components::orchestrator::OrchestratorEvent;
+    use crate::utils::{Cadence, GameClock, Launch};
+    use std::time::Duration;
+
+    const CADENCE: Cadence = Cadence {
+        sunray_period: Some(Duration::from_millis(100)),
+        asteroid_period: Some(Duration::from_millis(300)),
+    };
+
+    /// Advances a clock by `step` until `total`, returning the sunrays and asteroids due.
+    fn launches_over(step: Duration, total: Duration) -> (usize, usize) {
+        let mut clock = GameClock::new(CADENCE);
+        let mut launches = Vec::new();
+        while clock.now() < total {
+            launches.extend(clock.advance(step));
+        }
+        let sunrays = launches.iter().filter(|l| **l == Launch::Sunray).count();
+        (sunrays, launches.len() - sunrays)
+    }
+
+    #[test]
+    fn test_launches_do_not_depend_on_the_step() {
+        let total = Duration::from_secs(3);
+        let counts = launches_over(Duration::from_millis(10), total);
+        assert_eq!(counts, (30, 10));
+        assert_eq!(launches_over(Duration::from_millis(50), total), counts);
+        // a step longer than both periods catches up on every missed deadline
+        assert_eq!(launches_over(Duration::from_millis(750), total), counts);
+    }
+
+    #[test]
+    fn test_launches_follow_their_deadlines() {
+        let mut clock = GameClock::new(CADENCE);
+        assert!(clock.advance(Duration::from_millis(99)).is_empty());
+        assert_eq!(
+            clock.advance(Duration::from_millis(1)),
+            vec![Launch::Sunray]
+        );
+        // deadlines 200 and 300 for the sunrays, 300 for the asteroid
+        assert_eq!(
+            clock.advance(Duration::from_millis(200)),
+            vec![Launch::Sunray, Launch::Sunray, Launch::Asteroid]
+        );
+
+        let no_launch = Cadence {
+            sunray_period: None,
+            asteroid_period: Some(Duration::ZERO),
+        };
+        clock.set_cadence(no_launch);
+        assert!(clock.advance(Duration::from_secs(10)).is_empty());
+        assert_eq!(clock.now(), Duration::from_millis(10_300));
+    }
+
+    fn sunrays_sent_over(step: Duration, total: Duration) -> usize {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.game_clock = GameClock::new(Cadence {
+            sunray_period: Some(Duration::from_millis(100)),
+            asteroid_period: None,
+        });
+
+        while orch.game_clock.now() < total {
+            orch.advance_game(step).unwrap();
+        }
+        let sunrays = orch
+            .gui_messages
+            .iter()
+            .filter(|event| matches!(event, OrchestratorEvent::SunraySent { planet_id: 0, .. }))
+            .count();
+        let _ = orch.reset();
+        sunrays
+    }
+
+    #[test]
+    fn test_advance_game_sends_the_same_sunrays_at_any_tick_rate() {
+        let total = Duration::from_secs(1);
+        assert_eq!(sunrays_sent_over(Duration::from_millis(10), total), 10);
+        assert_eq!(sunrays_sent_over(Duration::from_millis(40), total), 10);
+    }
+}
+
+#[cfg(test)]
+mod tests_routing {
+    use super::*;
+    use crate::components::orchestrator::OrchestratorEvent;
+    use crate::components::orchestrator::routing::{
+        EXPLORER_MESSAGE_KINDS, PLANET_MESSAGE_KINDS, PlanetMessageKind, RoutedMessage,
+    };
+    use crate::components::orchestrator::{ManualCommand, OrchestratorEvent};
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+    use common_game::protocols::orchestrator_explorer::{
+        ExplorerToOrchestrator, OrchestratorToExplorer,
+    };
+    use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+    use crossbeam_channel::Receiver;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn init_galaxy() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch
+    }
+
+    /// Running planets 0 -- 1 behind stub channels, with explorer 4 running on planet 0.
+    ///
+    /// Returns the inboxes of the two planets and of the explorer.
+    fn stub_galaxy() -> (
+        Orchestrator,
+        [Receiver<OrchestratorToPlanet>; 2],
+        Receiver<OrchestratorToExplorer>,
+    ) {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        orch.initialize_galaxy_by_content(&format!("0,{ty},1\n1,{ty},0"))
+            .unwrap();
+        let planet_inboxes = [0, 1].map(|id| {
+            orch.planets_info
+                .update_status(id, Status::Running)
+                .unwrap();
+            stub_planet_channels(&mut orch, id)
+        });
+        let explorer_inbox = stub_explorer(&mut orch, 4, Status::Running);
+        (orch, planet_inboxes, explorer_inbox)
+    }
+
+    #[test]
+    fn test_every_kind_has_a_default_handler() {
+        let orch = Orchestrator::new().unwrap();
+        for kind in PLANET_MESSAGE_KINDS {
+            assert!(orch.planet_handlers.is_registered(kind), "{}", kind);
+        }
+        for kind in EXPLORER_MESSAGE_KINDS {
+            assert!(orch.explorer_handlers.is_registered(kind), "{}", kind);
+        }
+        assert_eq!(
+            PlanetToOrchestrator::Stopped { planet_id: 0 }.message_kind(),
+            PlanetMessageKind::Stopped
+        );
+    }
+
+    #[test]
+    fn test_default_handlers_keep_the_old_effects() {
+        let mut orch = init_galaxy();
+
+        orch.handle_planet_message(PlanetToOrchestrator::SunrayAck { planet_id: 0 })
+            .unwrap();
+        assert!(matches!(
+            orch.gui_messages.last(),
+            Some(OrchestratorEvent::SunrayReceived { planet_id: 0 })
+        ));
+
+        orch.handle_planet_message(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 0 })
+            .unwrap();
+        assert_eq!(orch.planets_info.get_status(&0), Status::Running);
+
+        orch.handle_planet_message(PlanetToOrchestrator::StopPlanetAIResult { planet_id: 0 })
+            .unwrap();
+        assert_eq!(orch.planets_info.get_status(&0), Status::Paused);
+
+        // Stopped has no effect
+        let events = orch.gui_messages.len();
+        orch.handle_planet_message(PlanetToOrchestrator::Stopped { planet_id: 0 })
+            .unwrap();
+        assert_eq!(orch.gui_messages.len(), events);
+        assert_eq!(orch.planets_info.get_status(&0), Status::Paused);
+
+        orch.handle_planet_message(PlanetToOrchestrator::KillPlanetResult { planet_id: 0 })
+            .unwrap();
+        assert_eq!(orch.planets_info.get_status(&0), Status::Dead);
+        assert!(matches!(
+            orch.gui_messages.last(),
+            Some(OrchestratorEvent::PlanetDestroyed { planet_id: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_registered_handler_replaces_the_default() {
+        let mut orch = init_galaxy();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let previous = orch.planet_handlers_mut().unwrap().register(
+            PlanetMessageKind::SunrayAck,
+            move |_: &mut Orchestrator, _: PlanetToOrchestrator| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            },
+        );
+        assert!(previous.is_some());
+
+        let events = orch.gui_messages.len();
+        orch.handle_planet_message(PlanetToOrchestrator::SunrayAck { planet_id: 0 })
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(orch.gui_messages.len(), events);
+    }
+
+    #[test]
+    fn test_unhandled_kind_is_ignored() {
+        let mut orch = init_galaxy();
+        orch.planet_handlers_mut()
+            .unwrap()
+            .unregister(PlanetMessageKind::KillPlanetResult);
+
+        orch.handle_planet_message(PlanetToOrchestrator::KillPlanetResult { planet_id: 0 })
+            .unwrap();
+        assert_eq!(orch.planets_info.get_status(&0), Status::Paused);
+    }
+
+    #[test]
+    fn test_asteroid_ack_without_rocket_kills_the_planet_and_its_explorers() {
+        let (mut orch, [planet_0, _], explorer) = stub_galaxy();
+
+        orch.handle_planet_message(PlanetToOrchestrator::AsteroidAck {
+            planet_id: 0,
+            rocket: None,
+        })
+        .unwrap();
+
+        assert!(matches!(
+            planet_0.try_recv(),
+            Ok(OrchestratorToPlanet::KillPlanet)
+        ));
+        assert!(orch.planets_info.is_dead(&0));
+        assert!(!orch.galaxy_topology[0][1] && !orch.galaxy_topology[1][0]);
+        assert!(matches!(
+            explorer.try_recv(),
+            Ok(OrchestratorToExplorer::KillExplorer)
+        ));
+    }
+
+    #[test]
+    fn test_internal_state_response_updates_the_planet_info() {
+        let mut orch = init_galaxy();
+        orch.start_all(&[], &[]).unwrap();
+        orch.planet_channels[&0]
+            .send_to_planet(OrchestratorToPlanet::InternalStateRequest)
+            .unwrap();
+        let response = loop {
+            let msg = orch
+                .receiver_orch_planet
+                .recv_timeout(Duration::from_secs(2))
+                .unwrap();
+            if matches!(msg, PlanetToOrchestrator::InternalStateResponse { .. }) {
+                break msg;
+            }
+        };
+        let PlanetToOrchestrator::InternalStateResponse { planet_state, .. } = &response else {
+            panic!("expected InternalStateResponse, got {:?}", response);
+        };
+        let (cells, rocket) = (planet_state.energy_cells.clone(), planet_state.has_rocket);
+        orch.planets_info.update_rocket(0, !rocket);
+
+        orch.handle_planet_message(response).unwrap();
+
+        let info = orch.planets_info.get_info(0).unwrap();
+        assert_eq!(info.energy_cells, cells);
+        assert_eq!(info.rocket, rocket);
+        let _ = orch.reset();
+    }
+
+    #[test]
+    fn test_incoming_explorer_response_asks_the_old_planet_to_let_go() {
+        let (mut orch, [planet_0, planet_1], _) = stub_galaxy();
+        orch.explorers_info.get_mut(&4).unwrap().move_to_planet_id = 1;
+
+        orch.handle_planet_message(PlanetToOrchestrator::IncomingExplorerResponse {
+            planet_id: 1,
+            explorer_id: 4,
+            res: Ok(()),
+        })
+        .unwrap();
+
+        assert!(matches!(
+            planet_0.try_recv(),
+            Ok(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id: 4 })
+        ));
+        assert!(planet_1.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_outgoing_explorer_response_moves_the_explorer() {
+        let (mut orch, _, explorer) = stub_galaxy();
+        orch.explorers_info.get_mut(&4).unwrap().move_to_planet_id = 1;
+
+        orch.handle_planet_message(PlanetToOrchestrator::OutgoingExplorerResponse {
+            planet_id: 0,
+            explorer_id: 4,
+            res: Ok(()),
+        })
+        .unwrap();
+
+        assert!(matches!(
+            explorer.try_recv(),
+            Ok(OrchestratorToExplorer::MoveToPlanet {
+                sender_to_new_planet: Some(_),
+                planet_id: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_explorer_status_results_update_the_explorer_status() {
+        let (mut orch, _, _) = stub_galaxy();
+        let status = |orch: &Orchestrator| orch.explorers_info.get_status(&4);
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::StopExplorerAIResult {
+            explorer_id: 4,
+        })
+        .unwrap();
+        assert_eq!(status(&orch), Some(Status::Paused));
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::StartExplorerAIResult {
+            explorer_id: 4,
+        })
+        .unwrap();
+        assert_eq!(status(&orch), Some(Status::Running));
+
+        // the reset also drops the manual command
+        orch.explorers_info.insert_status(4, Status::Paused);
+        orch.manual_commands
+            .insert(4, ManualCommand::Move { destination: 1 });
+        orch.handle_explorer_message(ExplorerToOrchestrator::ResetExplorerAIResult {
+            explorer_id: 4,
+        })
+        .unwrap();
+        assert_eq!(status(&orch), Some(Status::Running));
+        assert!(orch.manual_commands.is_empty());
+    }
+
+    #[test]
+    fn test_kill_explorer_result_marks_the_explorer_dead() {
+        let (mut orch, [planet_0, _], _) = stub_galaxy();
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 })
+            .unwrap();
+
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+        assert!(matches!(
+            planet_0.try_recv(),
+            Ok(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id: 4 })
+        ));
+        assert_eq!(orch.pending_explorer_removals.len(), 1);
+    }
+
+    #[test]
+    fn test_planet_results_update_the_explorer_planet() {
+        let (mut orch, _, _) = stub_galaxy();
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::CurrentPlanetResult {
+            explorer_id: 4,
+            planet_id: 1,
+        })
+        .unwrap();
+        assert_eq!(orch.explorers_info.get_current_planet(&4), Some(1));
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::MovedToPlanetResult {
+            explorer_id: 4,
+            planet_id: 0,
+        })
+        .unwrap();
+        assert_eq!(orch.explorers_info.get_current_planet(&4), Some(0));
+        assert!(matches!(
+            orch.gui_messages.last(),
+            Some(OrchestratorEvent::ExplorerMoved {
+                explorer_id: 4,
+                destination: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_supported_results_update_the_planet_info() {
+        let (mut orch, _, _) = stub_galaxy();
+        let basics = HashSet::from([BasicResourceType::Carbon]);
+        let complexes = HashSet::from([ComplexResourceType::Diamond]);
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::SupportedResourceResult {
+            explorer_id: 4,
+            supported_resources: basics.clone(),
+        })
+        .unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::SupportedCombinationResult {
+            explorer_id: 4,
+            combination_list: complexes.clone(),
+        })
+        .unwrap();
+
+        let info = orch.planets_info.get_info(0).unwrap();
+        assert_eq!(info.supported_resources, Some(basics));
+        assert_eq!(info.supported_combination, Some(complexes));
+    }
+
+    #[test]
+    fn test_resource_responses_ask_for_the_bag_or_report_the_failure() {
+        let (mut orch, _, explorer) = stub_galaxy();
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::GenerateResourceResponse {
+            explorer_id: 4,
+            generated: Ok(()),
+        })
+        .unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::CombineResourceResponse {
+            explorer_id: 4,
+            generated: Ok(()),
+        })
+        .unwrap();
+        for _ in 0..2 {
+            assert!(matches!(
+                explorer.try_recv(),
+                Ok(OrchestratorToExplorer::BagContentRequest)
+            ));
+        }
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::GenerateResourceResponse {
+            explorer_id: 4,
+            generated: Err("no cell".to_string()),
+        })
+        .unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::CombineResourceResponse {
+            explorer_id: 4,
+            generated: Err("no cell".to_string()),
+        })
+        .unwrap();
+        assert!(explorer.try_recv().is_err());
+        let failures = orch
+            .gui_messages
+            .iter()
+            .filter(|e| matches!(e, OrchestratorEvent::ResourceGenerationFailed { .. }))
+            .count();
+        assert_eq!(failures, 2);
+    }
+
+    #[test]
+    fn test_bag_content_response_updates_the_bag() {
+        let (mut orch, _, _) = stub_galaxy();
+        let bag = vec![ResourceType::Basic(BasicResourceType::Oxygen)];
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 4,
+            bag_content: bag.clone(),
+        })
+        .unwrap();
+
+        assert_eq!(orch.explorers_info.get(&4).unwrap().bag, bag);
+    }
+
+    #[test]
+    fn test_explorer_requests_are_answered() {
+        let (mut orch, [_, planet_1], explorer) = stub_galaxy();
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::NeighborsRequest {
+            explorer_id: 4,
+            current_planet_id: 0,
+        })
+        .unwrap();
+        assert!(matches!(
+            explorer.try_recv(),
+            Ok(OrchestratorToExplorer::NeighborsResponse { neighbors }) if neighbors == vec![1]
+        ));
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::TravelToPlanetRequest {
+            explorer_id: 4,
+            current_planet_id: 0,
+            dst_planet_id: 1,
+        })
+        .unwrap();
+        assert_eq!(orch.explorers_info.get(&4).unwrap().move_to_planet_id, 1);
+        assert!(matches!(
+            planet_1.try_recv(),
+            Ok(OrchestratorToPlanet::IncomingExplorerRequest { explorer_id: 4, .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests_remove_explorer {
+    use super::*;
+    use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+    use std::time::Duration;
+
+    fn orch_with_explorer(status: Status) -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        stub_explorer(&mut orch, 4, status);
+        orch
+    }
+
+    #[test]
+    fn test_remove_explorer_requires_dead_explorer() {
+        let mut orch = orch_with_explorer(Status::Running);
+        assert!(orch.remove_explorer(4).is_err());
+        assert!(orch.explorer_channels.contains_key(&4));
+        assert!(orch.remove_explorer(9).unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_remove_explorer_drops_its_state() {
+        let mut orch = orch_with_explorer(Status::Dead);
+        orch.remove_explorer(4).unwrap();
+        assert_eq!(orch.explorers_info.get_status(&4), None);
+        assert!(!orch.explorer_channels.contains_key(&4));
+        // already removed
+        assert!(orch.remove_explorer(4).is_err());
+    }
+
+    #[test]
+    fn test_killed_explorer_is_removed_after_the_delay() {
+        let mut orch = orch_with_explorer(Status::Running);
+        orch.explorer_removal_delay = Some(Duration::ZERO);
+        orch.handle_explorer_message(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 })
+            .unwrap();
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+
+        orch.handle_game_messages().unwrap();
+        assert_eq!(orch.explorers_info.get_status(&4), None);
+        assert!(orch.pending_explorer_removals.is_empty());
+    }
+
+    #[test]
+    fn test_killed_explorer_is_removed_later_by_default() {
+        let mut orch = orch_with_explorer(Status::Running);
+        assert_eq!(
+            orch.explorer_removal_delay,
+            Some(crate::components::orchestrator::handlers::DEFAULT_EXPLORER_REMOVAL_DELAY)
+        );
+        orch.handle_explorer_message(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 })
+            .unwrap();
+        orch.handle_game_messages().unwrap();
+        // the delay has not elapsed yet
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+        assert!(orch.explorer_channels.contains_key(&4));
+        assert_eq!(orch.pending_explorer_removals.len(), 1);
+    }
+
+    #[test]
+    fn test_killed_explorer_is_kept_without_delay() {
+        let mut orch = orch_with_explorer(Status::Running);
+        orch.explorer_removal_delay = None;
+        orch.handle_explorer_message(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 })
+            .unwrap();
+        orch.handle_game_messages().unwrap();
+        assert_eq!(orch.explorers_info.get_status(&4), Some(Status::Dead));
+    }
+}
+
+#[cfg(test)]
+mod tests_run_sequence {
+    use super::*;
+
+    #[test]
+    fn test_run_sequence_on_prepared_orchestrator() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.max_galaxy_size = 4;
+        let content = format!("0,{}\n", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[]).unwrap();
+
+        orch.run_sequence("sunray\n# comment\npause 1ms\nsunray\n")
+            .unwrap();
+        assert_eq!(orch.tick, 3);
+
+        // an invalid sequence runs nothing
+        assert!(orch.run_sequence("sunray\nfly\n").is_err());
+        assert_eq!(orch.tick, 3);
+        let _ = orch.reset();
+    }
+
+    #[test]
+    fn test_run_fails_without_galaxy_file() {
+        assert!(Orchestrator::run("does/not/exist.txt", "sunray").is_err());
+    }
+
+    #[test]
+    fn test_run_headless_reports_the_final_galaxy() {
+        use crate::utils::Cadence;
+        use std::time::Duration;
+
+        let file_path = std::env::temp_dir().join(format!(
+            "omc_run_headless_galaxy_{}.txt",
+            std::process::id()
+        ));
+        let ty = PlanetType::OneMillionCrabs as u32;
+        std::fs::write(&file_path, format!("0,{ty},1\n1,{ty},0\n")).unwrap();
+        let cadence = Cadence {
+            sunray_period: Some(Duration::from_millis(10)),
+            asteroid_period: None,
+        };
+
+        let result = Orchestrator::run_headless(
+            file_path.to_str().unwrap(),
+            &[],
+            &[],
+            cadence,
+            Duration::from_millis(200),
+        );
+        let _ = std::fs::remove_file(&file_path);
+
+        let report = result.unwrap();
+        let ids: Vec<u32> = report.planets.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![0, 1]);
+        assert!(report.planets.iter().all(|p| p.status != Status::Dead));
+        assert_eq!(report.topology, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_run_headless_stops_when_every_planet_is_dead() {
+        use crate::utils::Cadence;
+        use std::time::{Duration, Instant};
+
+        // cannot build a rocket, so the first asteroid destroys it
+        let file_path = std::env::temp_dir().join(format!(
+            "omc_run_headless_doomed_{}.txt",
+            std::process::id()
+        ));
+        let ty = PlanetType::BlackAdidasShoe as u32;
+        std::fs::write(&file_path, format!("0,{ty}\n")).unwrap();
+        let cadence = Cadence {
+            sunray_period: None,
+            asteroid_period: Some(Duration::from_millis(10)),
+        };
+
+        let start = Instant::now();
+        let result = Orchestrator::run_headless(
+            file_path.to_str().unwrap(),
+            &[],
+            &[],
+            cadence,
+            Duration::from_secs(30),
+        );
+        let _ = std::fs::remove_file(&file_path);
+
+        let report = result.unwrap();
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(report.planets.len(), 1);
+        assert_eq!(report.planets[0].status, Status::Dead);
+    }
+}
+
+#[cfg(test)]
+mod tests_explorer_departure {
+    use super::*;
+    use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+    use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+
+    #[test]
+    fn test_killed_explorer_leaves_its_planet() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        // stub planet, sees what the orchestrator sends
+        let planet_rx = stub_planet_channels(&mut orch, 0);
+        let expl_from_orch = stub_explorer(&mut orch, 4, Status::Running);
+
+        orch.handle_explorer_message(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 4 })
+            .unwrap();
+        assert!(matches!(
+            planet_rx.try_recv(),
+            Ok(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id: 4 })
+        ));
+
+        // the planet's ack completes the departure, the explorer is not moved
+        let ack = || PlanetToOrchestrator::OutgoingExplorerResponse {
+            planet_id: 0,
+            explorer_id: 4,
+            res: Ok(()),
+        };
+        orch.handle_planet_message(ack()).unwrap();
+        assert!(expl_from_orch.try_recv().is_err());
+        assert!(planet_rx.try_recv().is_err());
+
+        // a late ack for a removed explorer is ignored as well
+        orch.remove_explorer(4).unwrap();
+        orch.handle_planet_message(ack()).unwrap();
+        assert!(planet_rx.try_recv().is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_type_of {
+    use super::*;
+
+    #[test]
+    fn test_planet_type_of_matches_the_galaxy_file() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!(
+            "0,{},1\n1,{},0\n",
+            PlanetType::OneMillionCrabs as u32,
+            PlanetType::RustyCrab as u32
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+
+        assert_eq!(orch.planet_type_of(0), Some(PlanetType::OneMillionCrabs));
+        assert_eq!(orch.planet_type_of(1), Some(PlanetType::RustyCrab));
+        assert_eq!(orch.planet_type_of(7), None);
+        let _ = orch.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests_prelude {
+    use crate::prelude::*;
+
+    /// Only checks that the prelude keeps exporting what an embedder needs.
+    #[test]
+    fn test_prelude_exports_the_embedding_api() {
+        let _: fn() -> Result<Orchestrator, String> = Orchestrator::new;
+        let _: fn(&str) -> Result<Vec<OrchestratorAction>, String> = parse_sequence;
+        let _: fn(&str) -> Result<ParsedGalaxy, String> = parse_galaxy_description;
+        let _ = ManualCommand::Move { destination: 0 };
+        let _ = (Status::Running, PlanetType::RustyCrab);
+        let _: Option<OrchestratorEvent> = None;
+        let _: Option<(GalaxySnapshot, GameReport, BagType)> = None;
+        let _: Option<(OrchestratorToPlanet, PlanetToOrchestrator)> = None;
+        let _: Option<(OrchestratorToExplorer, ExplorerToOrchestrator<BagType>)> = None;
+        let _: Option<(ExplorerToPlanet, PlanetToExplorer)> = None;
+    }
+}
+
+#[cfg(test)]
+mod tests_galaxy_snapshot {
+    use super::*;
+    use crate::utils::snapshot::{ExplorerView, GalaxyStats};
+    use crate::utils::{ExplorerInfo, GalaxyState, GalaxyUpdate};
+    use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_status(rng: &mut StdRng) -> Status {
+        [Status::Running, Status::Paused, Status::Dead][rng.random_range(0..3)]
+    }
+
+    fn random_state(rng: &mut StdRng, version: u64) -> GalaxyState {
+        let mut state = GalaxyState {
+            version,
+            ..GalaxyState::default()
+        };
+        for id in 0..8 {
+            if rng.random_bool(0.6) {
+                state.planets.insert(id, random_status(rng));
+            }
+            if rng.random_bool(0.4) {
+                let view = ExplorerView {
+                    planet_id: rng.random_range(0..8),
+                    status: random_status(rng),
+                };
+                state.explorers.insert(id, view);
+            }
+        }
+        let edges: Vec<(u32, u32)> = (0..rng.random_range(0..12))
+            .map(|_| (rng.random_range(0..8), rng.random_range(0..8)))
+            .collect();
+        state.stats = GalaxyStats {
+            alive_planets: rng.random_range(0..3),
+            alive_explorers: rng.random_range(0..3),
+        };
+        state.with_edges(edges)
+    }
+
+    #[test]
+    fn test_applying_the_diff_reproduces_the_new_state() {
+        let mut rng = StdRng::seed_from_u64(639);
+        for version in 0..500 {
+            let old = random_state(&mut rng, version);
+            let new = random_state(&mut rng, version + 1);
+
+            let delta = GalaxyState::diff(&old, &new);
+            let mut patched = old.clone();
+            patched.apply(&delta).unwrap();
+            assert_eq!(patched, new, "old: {:?}\ndelta: {:?}", old, delta);
+
+            // the delta is empty only if nothing but the version changed
+            assert_eq!(delta.is_empty(), old.same_content(&new));
+        }
+    }
+
+    #[test]
+    fn test_apply_rejects_a_delta_from_another_version() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let old = random_state(&mut rng, 3);
+        let new = random_state(&mut rng, 4);
+        let delta = GalaxyState::diff(&old, &new);
+
+        let mut stale = GalaxyState {
+            version: 2,
+            ..old.clone()
+        };
+        assert!(stale.apply(&delta).is_err());
+        assert_eq!(stale.version, 2);
+        assert!(stale.same_content(&old));
+    }
+
+    #[test]
+    fn test_galaxy_update_sends_deltas_to_up_to_date_subscribers() {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        orch.initialize_galaxy_by_content(&format!("0,{ty},1\n1,{ty},0,2\n2,{ty},1"))
+            .unwrap();
+        orch.explorers_info
+            .insert(7, ExplorerInfo::from(7, Status::Running, Vec::new(), 0));
+
+        let GalaxyUpdate::Full(mut gui_state) = orch.galaxy_update(None) else {
+            panic!("the first update must be a full snapshot");
+        };
+        assert_eq!(gui_state.edges, vec![(0, 1), (1, 2)]);
+        assert_eq!(gui_state.stats.alive_explorers, 1);
+
+        // nothing changed, the version stays the same
+        let version = gui_state.version;
+        assert_eq!(orch.galaxy_state().version, version);
+        match orch.galaxy_update(Some(version)) {
+            GalaxyUpdate::Delta(delta) => assert!(delta.is_empty()),
+            other => panic!("expected an empty delta, got {:?}", other),
+        }
+
+        orch.handle_planet_message(PlanetToOrchestrator::KillPlanetResult { planet_id: 1 })
+            .unwrap();
+        orch.explorers_info.update_current_planet(7, 2);
+
+        let GalaxyUpdate::Delta(delta) = orch.galaxy_update(Some(version)) else {
+            panic!("the previous version must get a delta");
+        };
+        assert_eq!(delta.to_version, version + 1);
+        assert_eq!(delta.planets_changed, vec![(1, Status::Dead)]);
+        assert_eq!(delta.edges_removed, vec![(0, 1), (1, 2)]);
+        assert_eq!(delta.explorers_changed.len(), 1);
+        gui_state.apply(&delta).unwrap();
+        assert_eq!(gui_state, orch.galaxy_state());
+
+        // an unknown version falls back to the full snapshot
+        assert!(matches!(
+            orch.galaxy_update(Some(version - 1)),
+            GalaxyUpdate::Full(_)
+        ));
+
+        let _ = orch.send_planet_kill_to_all();
+    }
+
+    #[test]
+    fn test_snapshots_never_reference_missing_planets() {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        let ring =
+            format!("0,{ty},1,5\n1,{ty},0,2\n2,{ty},1,3\n3,{ty},2,4\n4,{ty},3,5\n5,{ty},4,0");
+        orch.initialize_galaxy_by_content(&ring).unwrap();
+        for id in 0..4 {
+            orch.explorers_info
+                .insert(id, ExplorerInfo::from(id, Status::Running, Vec::new(), id));
+        }
+
+        let mut rng = StdRng::seed_from_u64(643);
+        let mut gui_state = orch.galaxy_state();
+        for _ in 0..300 {
+            // mutate the galaxy as the handlers do, between two snapshots
+            if rng.random_bool(0.1) {
+                let planet_id = rng.random_range(0..6);
+                if !orch.planets_info.is_dead(&planet_id) {
+                    let killed = PlanetToOrchestrator::KillPlanetResult { planet_id };
+                    orch.handle_planet_message(killed).unwrap();
                 }
+            } else {
+                let explorer_id = rng.random_range(0..4);
+                orch.explorers_info
+                    .update_current_planet(explorer_id, rng.random_range(0..6));
+            }
+
+            let state = orch.galaxy_state();
+            for (explorer_id, view) in &state.explorers {
+                assert!(
+                    state.planets.contains_key(&view.planet_id),
+                    "explorer {} on missing planet {} in {:?}",
+                    explorer_id,
+                    view.planet_id,
+                    state
+                );
+            }
+            for (a, b) in &state.edges {
+                assert_ne!(state.planets.get(a), Some(&Status::Dead));
+                assert_ne!(state.planets.get(b), Some(&Status::Dead));
             }
+
+            // a GUI following the deltas ends up with the same snapshot
+            let delta = GalaxyState::diff(&gui_state, &state);
+            gui_state.apply(&delta).unwrap();
+            assert_eq!(gui_state, state);
         }
-        assert!(
-            orchestrator
-                .explorers_info
-                .get(&explorer_id)
-                .unwrap()
-                .bag
-                .iter()
-                .filter(|&&x| x.is_silicon())
-                .count()
-                <= 12
-        );
-        let _ =orchestrator.send_planet_kill_to_all();
-        let _ = orchestrator.send_kill_explorer_ai(2);
+
+        let _ = orch.send_planet_kill_to_all();
     }
 }
 
 #[cfg(test)]
-mod tests {
+mod tests_channel_closed {
     use super::*;
-    use crate::utils::registry::PlanetType;
-    use std::time::Duration;
+    use crate::components::orchestrator::handlers::MessageLoopStatus;
+    use crossbeam_channel::unbounded;
 
-    // --- MACRO CATEGORY: MIXED SITUATIONS ---
-    // Testing survival rates when different planet types are combined.
-    mod mixed_scenarios {
-        use super::*;
+    #[test]
+    fn test_dropping_the_planet_senders_stops_the_game_loop() {
+        let mut orch = Orchestrator::new().unwrap();
+        assert_eq!(orch.handle_game_messages(), Ok(MessageLoopStatus::Running));
 
-        #[test]
-        fn test_orchestrator_mixed_survival_logic() {
-            let mut orch = Orchestrator::new().unwrap();
+        // no planet holds a sender, so this drops the last one
+        let (replacement, _receiver) = unbounded();
+        orch.sender_planet_orch = replacement;
 
-            // Type A (HoustonWeHaveABorrow) - Can build rockets
-            let p_id_a = 1;
-            // Type B (BlackAdidasShoe) - Cannot build rockets
-            let p_id_b = 2;
+        assert_eq!(
+            orch.handle_game_messages(),
+            Ok(MessageLoopStatus::ChannelClosed)
+        );
+        assert!(orch.tick_once(None).is_err());
+        assert!(orch.run_sequence("pause 1ms\n").is_err());
+        assert_eq!(orch.tick, 0);
+    }
 
-            // Initialize galaxy with both planets connected
-            let content = format!(
-                "{},{},{}\n{},{},{}",
-                p_id_a,
-                PlanetType::HoustonWeHaveABorrow as u32,
-                p_id_b,
-                p_id_b,
-                PlanetType::BlackAdidasShoe as u32,
-                p_id_a
-            );
-            orch.initialize_galaxy_by_content(&content).unwrap();
+    #[test]
+    fn test_dropping_the_explorer_senders_stops_the_game_loop() {
+        let mut orch = Orchestrator::new().unwrap();
+        let (replacement, _receiver) = unbounded();
+        orch.sender_explorer_orch = replacement;
 
-            orch.start_all(&[], &[]).unwrap();
+        assert_eq!(
+            orch.handle_game_messages(),
+            Ok(MessageLoopStatus::ChannelClosed)
+        );
+        assert!(orch.reset().is_ok());
+    }
+}
 
-            // Phase 1: Provide resources
-            // We give them sunrays. Only Type A should effectively use it.
-            // Cloning is ok: Sender is a handler, not a full structure.
-            let channel_a = orch.planet_channels.get(&p_id_a).unwrap().0.clone();
-            let channel_b = orch.planet_channels.get(&p_id_b).unwrap().0.clone();
+#[cfg(test)]
+mod tests_send_to_all {
+    use super::*;
 
-            orch.send_sunray(p_id_a, &channel_a).unwrap();
-            orch.send_sunray(p_id_b, &channel_b).unwrap();
+    #[test]
+    fn test_send_to_all_skips_planets_without_status() {
+        let mut orch = Orchestrator::new().unwrap();
+        // a planet being added: its channels exist, its status not yet
+        let planet_receiver = stub_planet_channels(&mut orch, 9);
+        assert!(orch.planets_info.get_info(9).is_none());
 
-            // Give the planet threads a moment to process the sunray and build
-            std::thread::sleep(Duration::from_millis(500));
-            // We simulate receiving the responses from the channels
-            // (In a real run, handle_game_messages would do this)
-            orch.handle_game_messages().unwrap();
-            orch.handle_game_messages().unwrap();
+        assert!(orch.send_sunray_to_all().is_ok());
+        assert!(orch.send_planet_kill_to_all().is_ok());
+        assert!(planet_receiver.try_recv().is_err());
+    }
+}
 
-            println!(
-                "after sunray - planet a status: {:?}",
-                orch.planets_info.get_info(p_id_a)
-            );
-            println!(
-                "after sunray - planet b status: {:?}",
-                orch.planets_info.get_info(p_id_b)
-            );
+#[cfg(test)]
+mod tests_topology_copy {
+    use super::*;
 
-            // Phase 2: Asteroid Attack
-            orch.send_asteroid(p_id_a, &channel_a).unwrap();
-            orch.send_asteroid(p_id_b, &channel_b).unwrap();
+    #[test]
+    fn test_get_galaxy_topology_returns_a_copy() {
+        let mut orch = Orchestrator::new().unwrap();
+        let crabs = PlanetType::OneMillionCrabs as u32;
+        let content = format!("0,{crabs},1\n1,{crabs},0\n");
+        orch.initialize_galaxy_by_content(&content).unwrap();
 
-            // Give the planet threads a moment to process the asteroids and build
-            std::thread::sleep(Duration::from_millis(500));
-            // We simulate receiving the responses from the channels
-            // (In a real run, handle_game_messages would do this)
-            orch.handle_game_messages().unwrap();
-            orch.handle_game_messages().unwrap();
+        let mut matrix = orch.get_galaxy_topology();
+        assert_eq!(matrix, orch.galaxy_topology);
+        assert!(matrix[0][1] && matrix[1][0]);
 
-            println!(
-                "after sunray - planet a status: {:?}",
-                orch.planets_info.get_info(p_id_a)
-            );
-            println!(
-                "after sunray - planet b status: {:?}",
-                orch.planets_info.get_info(p_id_b)
-            );
+        matrix[0][1] = false;
+        matrix[1][0] = false;
+        assert!(orch.galaxy_topology[0][1]);
+        assert!(orch.galaxy_topology[1][0]);
+    }
+}
 
-            // Verification: A should be Alive/Running, B should be Dead
-            assert!(orch.planets_info.is_running(&p_id_a));
-            assert!(orch.planets_info.is_dead(&p_id_b));
+#[cfg(test)]
+mod tests_status_table {
+    use super::*;
+    use crate::utils::ExplorerInfo;
+    use crate::utils::status_table::{ExplorerRow, PlanetRow};
+    use common_game::components::resource::{BasicResourceType, ResourceType};
+
+    #[test]
+    fn test_status_table_lists_planets_and_explorers_by_id() {
+        let mut orch = Orchestrator::new().unwrap();
+        let crabs = PlanetType::OneMillionCrabs;
+        let shoe = PlanetType::BlackAdidasShoe;
+        let content = format!("3,{},1\n1,{},3\n", crabs as u32, shoe as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        let bag = vec![ResourceType::Basic(BasicResourceType::Carbon); 2];
+        orch.explorers_info
+            .insert(9, ExplorerInfo::from(9, Status::Running, bag, 3));
+        orch.explorers_info
+            .insert(2, ExplorerInfo::from(2, Status::Paused, Vec::new(), 1));
+        orch.planets_info.update_status(3, Status::Dead).unwrap();
+
+        let table = orch.status_table();
+        assert_eq!(
+            table.planets,
+            vec![
+                PlanetRow {
+                    id: 1,
+                    planet_type: shoe,
+                    status: Status::Paused
+                },
+                PlanetRow {
+                    id: 3,
+                    planet_type: crabs,
+                    status: Status::Dead
+                },
+            ]
+        );
+        assert_eq!(
+            table.explorers,
+            vec![
+                ExplorerRow {
+                    id: 2,
+                    planet_id: 1,
+                    status: Status::Paused,
+                    bag_size: 0
+                },
+                ExplorerRow {
+                    id: 9,
+                    planet_id: 3,
+                    status: Status::Running,
+                    bag_size: 2
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_cell_telemetry {
+    use super::*;
+    use crate::utils::cell_telemetry::{CellCause, CellRecord, cell_changes};
+
+    fn record(cell_index: usize, charged: bool, cause: CellCause) -> CellRecord {
+        CellRecord {
+            planet_id: 4,
+            cell_index,
+            charged,
+            cause,
         }
     }
 
-    // --- MACRO CATEGORY: PLANET INTEGRATION (ALL TYPES) ---
-    // Testing one of every single planet in the registry simultaneously.
-    mod planet_integration {
-        use super::*;
-        use strum::IntoEnumIterator;
+    #[test]
+    fn test_cell_changes_reports_only_the_changed_cells() {
+        let old = [true, false, true, false];
+        let new = [true, true, false, false];
+        assert_eq!(
+            cell_changes(4, (&old, false), (&new, false)),
+            vec![
+                record(1, true, CellCause::Sunray),
+                record(2, false, CellCause::Resource),
+            ]
+        );
+        assert!(cell_changes(4, (&new, true), (&new, true)).is_empty());
+    }
 
-        #[test]
-        fn test_orchestrator_integration_all_planet_types_behavior() {
-            let mut orch = Orchestrator::new().unwrap();
+    #[test]
+    fn test_cell_changes_puts_one_discharge_down_to_a_new_rocket() {
+        let old = [true, true];
+        let new = [false, false];
+        assert_eq!(
+            cell_changes(4, (&old, false), (&new, true)),
+            vec![
+                record(0, false, CellCause::Rocket),
+                record(1, false, CellCause::Resource),
+            ]
+        );
+        // cells not known before count as discharged
+        assert_eq!(
+            cell_changes(4, (&[], false), (&[false, true], false)),
+            vec![record(1, true, CellCause::Sunray)]
+        );
+    }
 
-            // Build content string dynamically from all planet types
-            let planet_types: Vec<PlanetType> = PlanetType::iter().collect();
-            let id_counter = planet_types.len() as u32;
+    #[test]
+    fn test_cell_telemetry_is_off_by_default_and_stops_without_receiver() {
+        let mut orch = Orchestrator::new().unwrap();
+        assert!(orch.cell_telemetry.is_none());
+        let content = format!("4,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[]).unwrap();
 
-            let content: String = planet_types
-                .iter()
-                .enumerate()
-                .map(|(i, p)| format!("{},{}", i, *p as u32))
-                .collect::<Vec<_>>()
-                .join("\n");
-            orch.initialize_galaxy_by_content(&content).unwrap();
+        drop(orch.enable_cell_telemetry_with_capacity(4));
+        let channel = orch.planet_channels[&4].to_planet().clone();
+        orch.send_sunray(4, &channel).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        orch.query_planet_state(4, std::time::Duration::from_millis(500))
+            .unwrap();
+        assert!(orch.cell_telemetry.is_none());
 
-            orch.start_all(&[], &[]).unwrap();
+        let _ = orch.reset();
+    }
+}
 
-            // Sequence: 3 Sunrays (enough to build defense), then 1 Asteroid
-            for _ in 0..3 {
-                for id in 0..id_counter {
-                    let _ = orch.send_sunray(id, &orch.planet_channels.get(&id).unwrap().0.clone());
-                }
-                std::thread::sleep(Duration::from_millis(100));
+#[cfg(test)]
+mod tests_status_merge {
+    use super::*;
+    use crate::utils::{ExplorerStatusNotLock, LastUpdate, StatusMapExt};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_merge_resolves_the_conflicts() {
+        let mut statuses = ExplorerStatusNotLock::from([(1, Status::Running), (2, Status::Dead)]);
+        let other = ExplorerStatusNotLock::from([(2, Status::Running), (3, Status::Paused)]);
+
+        // a dead explorer stays dead
+        statuses.merge(&other, |existing, incoming| {
+            if existing == Status::Dead {
+                existing
+            } else {
+                incoming
             }
+        });
+        assert_eq!(
+            statuses,
+            ExplorerStatusNotLock::from([
+                (1, Status::Running),
+                (2, Status::Dead),
+                (3, Status::Paused)
+            ])
+        );
+    }
 
-            // Fire Asteroids
-            for id in 0..id_counter {
-                let _ = orch.send_asteroid(id, &orch.planet_channels.get(&id).unwrap().0.clone());
+    #[test]
+    fn test_merge_newest_keeps_the_latest_update() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(1);
+        let mut statuses = ExplorerStatusNotLock::from([
+            (1, Status::Running),
+            (2, Status::Running),
+            (3, Status::Running),
+        ]);
+        let mut last_update = LastUpdate::from([(1, start), (2, later)]);
+        let other = ExplorerStatusNotLock::from([
+            (1, Status::Dead),
+            (2, Status::Paused),
+            (3, Status::Paused),
+            (4, Status::Paused),
+        ]);
+        let other_last_update = LastUpdate::from([(1, later), (2, start)]);
+
+        statuses.merge_newest(&other, &mut last_update, &other_last_update);
+        assert_eq!(
+            statuses,
+            ExplorerStatusNotLock::from([
+                (1, Status::Dead),
+                (2, Status::Running),
+                (3, Status::Running),
+                (4, Status::Paused),
+            ])
+        );
+        assert_eq!(last_update, LastUpdate::from([(1, later), (2, later)]));
+    }
+}
+
+#[cfg(test)]
+mod tests_spawn_config {
+    use super::*;
+    use crate::components::orchestrator::init::{ExplorerKind, ExplorerSpawnConfig};
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+    use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+    use std::time::Duration;
+
+    const CARBON: ResourceType = ResourceType::Basic(BasicResourceType::Carbon);
+
+    fn first_bag_content(orch: &Orchestrator, explorer_id: u32) -> Vec<ResourceType> {
+        orch.send_bag_content_request(explorer_id).unwrap();
+        loop {
+            let msg = orch
+                .receiver_orch_explorer
+                .recv_timeout(Duration::from_secs(1))
+                .expect("no bag content response");
+            if let ExplorerToOrchestrator::BagContentResponse { bag_content, .. } = msg {
+                return bag_content;
             }
+        }
+    }
 
-            // Wait for processing
-            std::thread::sleep(Duration::from_secs(1));
-            orch.handle_game_messages().unwrap();
+    fn running_galaxy() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("1,{}", PlanetType::TheCompilerStrikesBack as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[]).unwrap();
+        orch
+    }
+
+    #[test]
+    fn test_spawned_explorers_start_with_their_bag() {
+        for (explorer_id, kind) in [(1, ExplorerKind::Tommy), (2, ExplorerKind::Mattia)] {
+            let mut orch = running_galaxy();
+
+            let mut config = ExplorerSpawnConfig::new(kind, explorer_id, 1);
+            config.starting_bag = vec![CARBON];
+            orch.spawn_explorer(config).unwrap();
+
+            assert_eq!(
+                orch.explorers_info.get_bag(&explorer_id),
+                Some(&vec![CARBON])
+            );
+            assert_eq!(first_bag_content(&orch, explorer_id), vec![CARBON]);
+            let _ = orch.reset();
+        }
+    }
+
+    #[test]
+    fn test_spawned_explorer_starts_with_a_complex_resource() {
+        let water = ResourceType::Complex(ComplexResourceType::Water);
+        let ai_partner = ResourceType::Complex(ComplexResourceType::AIPartner);
+        let mut orch = running_galaxy();
+
+        let mut config = ExplorerSpawnConfig::new(ExplorerKind::Tommy, 1, 1);
+        config.starting_bag = vec![water, ai_partner];
+        orch.spawn_explorer(config).unwrap();
+
+        assert_eq!(first_bag_content(&orch, 1), vec![water, ai_partner]);
+        let _ = orch.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests_launch_info {
+    use super::*;
+    use crate::components::orchestrator::OrchestratorEvent;
+    use crate::utils::{Launch, LaunchInfo, PlanetInfo};
+
+    fn orchestrator_with_planet(energy_cells: Vec<bool>, rocket: bool) -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        let charged = energy_cells.iter().filter(|charged| **charged).count();
+        orch.planets_info.insert(
+            0,
+            PlanetInfo::from(
+                PlanetType::OneMillionCrabs,
+                Status::Running,
+                energy_cells,
+                charged,
+                rocket,
+                None,
+                None,
+            ),
+        );
+        orch
+    }
+
+    #[test]
+    fn test_asteroid_log_payload_describes_the_asteroid() {
+        let mut orch = orchestrator_with_planet(vec![true, false], true);
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+
+        orch.send_asteroid(0, &sender).unwrap();
+        orch.send_asteroid(0, &sender).unwrap();
+
+        let Some(OrchestratorEvent::AsteroidSent { planet_id, info }) = orch.gui_messages.last()
+        else {
+            panic!("no AsteroidSent event");
+        };
+        assert_eq!(*planet_id, 0);
+        assert_eq!(info.kind, Launch::Asteroid);
+        assert_eq!(info.sequence, 2);
+        assert!(info.deflectable);
+
+        let payload = info.payload(0);
+        assert_eq!(payload.get("message").map(String::as_str), Some("Asteroid"));
+        assert_eq!(payload.get("planet_id").map(String::as_str), Some("0"));
+        assert_eq!(payload.get("sequence").map(String::as_str), Some("2"));
+        assert_eq!(payload.get("deflectable").map(String::as_str), Some("true"));
+        assert!(!payload.contains_key("free_cells"));
+    }
+
+    #[test]
+    fn test_sunray_log_payload_counts_the_free_cells() {
+        let mut orch = orchestrator_with_planet(vec![true, false, false], false);
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+
+        orch.send_sunray(0, &sender).unwrap();
+
+        let Some(OrchestratorEvent::SunraySent { info, .. }) = orch.gui_messages.last() else {
+            panic!("no SunraySent event");
+        };
+        assert_eq!(info.sequence, 1);
+        assert_eq!(info.free_cells, 2);
+        let payload = info.payload(0);
+        assert_eq!(payload.get("message").map(String::as_str), Some("Sunray"));
+        assert_eq!(payload.get("free_cells").map(String::as_str), Some("2"));
+        assert!(!payload.contains_key("deflectable"));
+    }
+
+    #[test]
+    fn test_launch_to_an_unknown_planet() {
+        let info = LaunchInfo::new(Launch::Asteroid, 1, None);
+        assert_eq!(info.free_cells, 0);
+        assert!(!info.deflectable);
+    }
+}
+
+#[cfg(test)]
+mod tests_drop {
+    use super::*;
+    use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
+    use common_game::protocols::planet_explorer::PlanetToExplorer;
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
 
-            // Validation logic based on your rules:
-            // Type A/C (Ciuc, ImmutableCosmicBorrow) should survive.
-            // Type B/D (Houston, BlackAdidas, OneMillionCrabs) should be Dead.
-            for (id, info) in orch.planets_info.iter() {
-                // This is a high-level check. Depending on specific AI timing,
-                // some might still be Alive if they didn't finish processing the death.
-                println!("Planet {} status: {:?}", id, info.status);
+    /// Polls `send` until it fails, i.e. until the thread holding the receiver is gone.
+    fn disconnected_within(timeout: Duration, send: impl Fn() -> bool) -> bool {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if !send() {
+                return true;
             }
+            sleep(Duration::from_millis(10));
         }
+        false
+    }
 
-        #[test]
-        fn sunray_flood_all_planets() {
-            let mut orch = Orchestrator::new().unwrap();
-
-            // Build content string dynamically from all planet types
-            let planet_types: Vec<PlanetType> = PlanetType::iter().collect();
-            let id_counter = planet_types.len() as u32;
+    #[test]
+    fn test_drop_ends_the_actor_threads() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::OneMillionCrabs as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all(&[], &[(1, 0)]).unwrap();
 
-            let content: String = planet_types
-                .iter()
-                .enumerate()
-                .map(|(i, p)| format!("{},{}", i, *p as u32))
-                .collect::<Vec<_>>()
-                .join("\n");
-            orch.initialize_galaxy_by_content(&content).unwrap();
+        let to_planet = orch.planet_channels[&0].to_planet().clone();
+        let to_explorer = orch.explorer_channels[&1].planet_sender_for_arrival();
+        drop(orch);
 
-            orch.start_all(&[], &[]).unwrap();
+        assert!(disconnected_within(Duration::from_secs(2), || {
+            to_planet
+                .send(OrchestratorToPlanet::InternalStateRequest)
+                .is_ok()
+        }));
+        assert!(disconnected_within(Duration::from_secs(2), || {
+            to_explorer
+                .send(PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 0 })
+                .is_ok()
+        }));
+    }
 
-            //send 10 sunrays to all planets: they should all be full
-            for _ in 0..40 {
-                for id in 0..id_counter {
-                    orch.send_sunray(id, &orch.planet_channels.get(&id).unwrap().0.clone())
-                        .expect("failed sending sunray");
-                }
-                std::thread::sleep(Duration::from_millis(100));
-            }
+    #[test]
+    fn test_drop_does_not_kill_the_dead_actors_again() {
+        let mut orch = Orchestrator::new().unwrap();
+        let planet_inbox = stub_planet_channels(&mut orch, 0);
+        orch.planets_info
+            .insert_status(0, PlanetType::OneMillionCrabs, Status::Dead, None, None);
+        let explorer_inbox = stub_explorer_channels(&mut orch, 1);
 
-            std::thread::sleep(Duration::from_secs(1));
-            for id in 0..id_counter {
-                orch.send_internal_state_request(
-                    &orch.planet_channels.get(&id).unwrap().0.clone(),
-                    id,
-                )
-                .expect("failed sending internal state request");
-            }
-            std::thread::sleep(Duration::from_millis(100));
-            orch.handle_game_messages().unwrap();
+        drop(orch);
 
-            //used to see all the charging statuses, even
-            // if a planet fails early
-            let mut failed_counter = 0;
+        // the explorer has no info, so it counts as dead too
+        assert!(planet_inbox.try_recv().is_err());
+        assert!(explorer_inbox.try_recv().is_err());
+    }
+}
 
-            //check their status after the flood
-            for id in 0..id_counter {
-                let status = orch
-                    .planets_info
-                    .get_info(id)
-                    .expect("error getting planet info");
-                let max_charged = status.energy_cells.len();
-                let curr_charged = status.charged_cells_count;
+#[cfg(test)]
+mod tests_idle_nudge {
+    use super::*;
+    use crate::utils::{ExplorerInfo, IdlePolicy, Nudge, TopologyDelta};
+    use common_game::components::resource::{BasicResourceType, ResourceType};
+    use common_game::protocols::orchestrator_explorer::{
+        ExplorerToOrchestrator, OrchestratorToExplorer,
+    };
+    use crossbeam_channel::Receiver;
 
-                println!(
-                    "checking id {}: max of {} and charged to {}",
-                    id, max_charged, curr_charged
-                );
-                if max_charged != curr_charged {
-                    failed_counter += 1;
-                }
-            }
+    /// Orchestrator with a stub explorer 1, running on planet 0, and its inbox.
+    fn orch_with_stub_explorer(nudge: Nudge) -> (Orchestrator, Receiver<OrchestratorToExplorer>) {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.idle_policy = Some(IdlePolicy {
+            idle_ticks: 3,
+            nudge,
+        });
+        let inbox = stub_explorer(&mut orch, 1, Status::Running);
+        (orch, inbox)
+    }
 
-            assert_eq!(failed_counter, 0);
-        }
+    fn report_bag(orch: &Orchestrator, bag_content: Vec<ResourceType>) {
+        orch.sender_explorer_orch
+            .send(ExplorerToOrchestrator::BagContentResponse {
+                explorer_id: 1,
+                bag_content,
+            })
+            .unwrap();
     }
 
-    // --- MACRO CATEGORY: HEAVY & LONG TESTS ---
-    // Stress testing the Orchestrator with many actors and repeated cycles.
-    mod heavy_load {
-        use super::*;
+    #[test]
+    fn test_identical_reports_trigger_one_nudge() {
+        let (mut orch, inbox) = orch_with_stub_explorer(Nudge::ResetAi);
 
-        #[test]
-        fn test_orchestrator_heavy_load_mass_extinction() {
-            let mut orch = Orchestrator::new().unwrap();
-            let n_planets = 50u32;
+        // the probe is sent at tick 3, the same bag comes back and the nudge follows
+        // at tick 4; the next probe would be at tick 7
+        for _ in 0..6 {
+            report_bag(&orch, Vec::new());
+            orch.tick_once(None).unwrap();
+        }
 
-            // Build content string with random planet types
-            let content: String = (0..n_planets)
-                .map(|i| format!("{},{}", i, PlanetType::random() as u32))
-                .collect::<Vec<_>>()
-                .join("\n");
-            orch.initialize_galaxy_by_content(&content).unwrap();
+        let sent: Vec<_> = inbox.try_iter().collect();
+        let probes = sent
+            .iter()
+            .filter(|msg| matches!(msg, OrchestratorToExplorer::BagContentRequest))
+            .count();
+        let resets = sent
+            .iter()
+            .filter(|msg| matches!(msg, OrchestratorToExplorer::ResetExplorerAI))
+            .count();
+        assert_eq!(probes, 1);
+        assert_eq!(resets, 1);
+        assert_eq!(orch.idle_watch.nudges(1), 1);
+    }
 
-            orch.start_all(&[], &[]).unwrap();
+    #[test]
+    fn test_probe_showing_progress_prevents_the_nudge() {
+        let (mut orch, inbox) = orch_with_stub_explorer(Nudge::ResetAi);
 
-            // Long test: 10 cycles of sunrays/asteroids
-            for cycle in 0..10 {
-                for i in 0..n_planets {
-                    let _ = orch.send_sunray(i, &orch.planet_channels.get(&i).unwrap().0.clone());
-                }
-                std::thread::sleep(Duration::from_millis(50));
+        // nothing is reported on its own, so the explorer is probed at tick 3
+        for _ in 0..4 {
+            orch.tick_once(None).unwrap();
+        }
+        assert!(matches!(
+            inbox.try_recv(),
+            Ok(OrchestratorToExplorer::CurrentPlanetRequest)
+        ));
+        assert!(matches!(
+            inbox.try_recv(),
+            Ok(OrchestratorToExplorer::BagContentRequest)
+        ));
 
-                for i in 0..n_planets {
-                    let _ = orch.send_asteroid(i, &orch.planet_channels.get(&i).unwrap().0.clone());
-                }
+        // the explorer was crafting: its bag changed
+        report_bag(&orch, vec![ResourceType::Basic(BasicResourceType::Carbon)]);
+        orch.tick_once(None).unwrap();
+        orch.tick_once(None).unwrap();
 
-                let _ = orch.handle_game_messages();
-                println!("Cycle {} complete", cycle);
-            }
+        assert!(inbox.try_recv().is_err());
+        assert_eq!(orch.idle_watch.nudges(1), 0);
+    }
 
-            // Check how many survived the onslaught
-            let survivors = orch.planets_info.count_survivors();
+    #[test]
+    fn test_progress_postpones_the_nudge() {
+        let (mut orch, inbox) = orch_with_stub_explorer(Nudge::ResetAi);
 
-            println!("Survivors: {}/{}", survivors, n_planets);
-            // In a heavy scenario, we just want to ensure the Orchestrator didn't crash
-            assert_eq!(orch.planets_info.len(), n_planets as usize);
+        let mut bag = Vec::new();
+        for _ in 0..6 {
+            bag.push(ResourceType::Basic(BasicResourceType::Carbon));
+            report_bag(&orch, bag.clone());
+            orch.tick_once(None).unwrap();
         }
 
-        #[test]
-        fn test_orchestrator_heavy_channel_congestion() {
-            let mut orch = Orchestrator::new().unwrap();
-
-            let content = format!("0,{}", PlanetType::Ciuc as u32);
-            orch.initialize_galaxy_by_content(&content).unwrap();
-
-            orch.start_all(&[], &[]).unwrap();
+        assert!(inbox.try_recv().is_err());
+        assert_eq!(orch.idle_watch.nudges(1), 0);
+    }
 
-            // Spam 1000 sunrays to a single planet to test channel capacity/backpressure
-            for _ in 0..1000 {
-                let _ = orch.send_sunray(0u32, &orch.planet_channels.get(&0).unwrap().0.clone());
-            }
+    #[test]
+    fn test_paused_explorers_are_not_nudged() {
+        let (mut orch, inbox) = orch_with_stub_explorer(Nudge::ResetAi);
+        orch.explorers_info.insert_status(1, Status::Paused);
 
-            // Ensure the orchestrator remains responsive
-            let result = orch.handle_game_messages();
-            assert!(result.is_ok());
+        for _ in 0..6 {
+            orch.tick_once(None).unwrap();
         }
+        assert!(inbox.try_recv().is_err());
     }
 
-    #[cfg(test)]
-    mod tests_bag_content_request {
-        use super::*;
-        use crate::components::orchestrator::Orchestrator;
-        use crate::utils::registry::PlanetType;
+    #[test]
+    fn test_knowledge_push_sends_the_planet_links() {
+        let (mut orch, _inbox) = orch_with_stub_explorer(Nudge::PushKnowledge);
+        let ty = PlanetType::OneMillionCrabs as u32;
+        orch.initialize_galaxy_by_content(&format!("0,{ty},1\n1,{ty},0"))
+            .unwrap();
+        orch.explorers_info
+            .insert(1, ExplorerInfo::from(1, Status::Running, Vec::new(), 0));
+        let (topology_sender, topology_receiver) = crossbeam_channel::unbounded();
+        orch.explorer_topology_senders.insert(1, topology_sender);
 
-        #[test]
-        fn explorer_manual_move() {
-            let mut orchestrator = Orchestrator::new().unwrap();
-            let starter_planet = 1;
-            let destination = 3;
-            let explorer_id = 0;
-            let content = "1,4,2,3\n2,4,3\n3,4";
-            orchestrator.initialize_galaxy_by_content(&content).unwrap();
-            orchestrator
-                .start_all(&[], &[(explorer_id, starter_planet)])
-                .unwrap();
+        assert!(orch.nudge_idle_explorers().is_empty());
+        orch.tick = 3;
+        // the probe is sent first
+        assert!(orch.nudge_idle_explorers().is_empty());
+        report_bag(&orch, Vec::new());
+        orch.handle_game_messages().unwrap();
+        assert_eq!(orch.nudge_idle_explorers(), vec![1]);
 
-            println!("topology: {:?}", orchestrator.get_topology().0);
-            println!(
-                "attempting move from planet {} to planet {}",
-                starter_planet, destination
-            );
+        let mut expected = TopologyDelta::new();
+        expected.add_link(0, 1);
+        assert_eq!(topology_receiver.try_recv(), Ok(expected));
+    }
 
-            if let Err(res) = orchestrator.send_stop_explorer_ai(explorer_id) {
-                panic!("could not stop explorer AI. full error: {}", res);
-            }
+    #[test]
+    fn test_relocation_picks_a_living_neighbour() {
+        let (mut orch, _inbox) = orch_with_stub_explorer(Nudge::Relocate);
+        let ty = PlanetType::OneMillionCrabs as u32;
+        orch.initialize_galaxy_by_content(&format!("0,{ty},1\n1,{ty},0\n2,{ty}"))
+            .unwrap();
+        orch.explorers_info
+            .insert(1, ExplorerInfo::from(1, Status::Running, Vec::new(), 0));
 
-            println!(
-                "explorer is currently stopped and in planet {}",
-                orchestrator
-                    .explorers_info
-                    .get_planet(&explorer_id)
-                    .unwrap()
-            );
+        assert!(orch.nudge_idle_explorers().is_empty());
+        orch.tick = 3;
+        assert!(orch.nudge_idle_explorers().is_empty());
+        report_bag(&orch, Vec::new());
+        orch.handle_game_messages().unwrap();
+        assert_eq!(orch.nudge_idle_explorers(), vec![1]);
 
-            if let Err(res) = orchestrator.send_move_to_planet(explorer_id, destination) {
-                panic!(
-                    "could not send planet move to explorer. full error: {}",
-                    res
-                );
-            }
+        // planet 2 is alive but not linked to planet 0
+        assert_eq!(orch.explorers_info.get(&1).unwrap().move_to_planet_id, 1);
+        let _ = orch.reset();
+    }
+}
 
-            std::thread::sleep(Duration::from_millis(100));
+#[cfg(test)]
+mod tests_channel_handles {
+    use super::*;
+    use crate::utils::ExplorerInfo;
+    use common_game::protocols::orchestrator_planet::OrchestratorToPlanet;
 
-            orchestrator.handle_game_messages().unwrap();
+    #[test]
+    fn test_send_to_a_gone_planet_fails() {
+        let (to_planet, planet_inbox) = crossbeam_channel::unbounded();
+        let (explorer_gateway, _) = crossbeam_channel::unbounded();
+        let handle = PlanetHandle::new(to_planet, explorer_gateway);
 
-            println!(
-                "after the move message the explorer is in planet {}",
-                orchestrator
-                    .explorers_info
-                    .get_planet(&explorer_id)
-                    .unwrap()
-            );
+        let sent = handle.send_to_planet(OrchestratorToPlanet::StartPlanetAI);
+        assert!(sent.is_ok());
+        assert!(matches!(
+            planet_inbox.try_recv(),
+            Ok(OrchestratorToPlanet::StartPlanetAI)
+        ));
+        drop(planet_inbox);
+        let sent = handle.send_to_planet(OrchestratorToPlanet::StartPlanetAI);
+        assert!(sent.is_err());
+    }
 
-            assert_eq!(orchestrator
-                           .explorers_info
-                           .get_planet(&explorer_id)
-                           .unwrap(), destination);
+    #[test]
+    fn test_living_planets_skip_the_dead_ones() {
+        let mut orch = Orchestrator::new().unwrap();
+        for (id, status) in [(0, Status::Running), (1, Status::Dead), (2, Status::Paused)] {
+            stub_planet_channels(&mut orch, id);
+            orch.planets_info
+                .insert_status(id, PlanetType::OneMillionCrabs, status, None, None);
         }
 
-        #[test]
-        fn test_send_bag_content_success() {
-            let mut orch = Orchestrator::new().unwrap();
-            let planet_id = 1;
-            let explorer_id = 100;
+        let mut living: Vec<u32> = orch.living_planets().map(|(id, _)| id).collect();
+        living.sort();
+        assert_eq!(living, vec![0, 2]);
+    }
 
-            let content = format!("{},{}", planet_id, PlanetType::OneMillionCrabs as u32);
-            orch.initialize_galaxy_by_content(&content).unwrap();
-            orch.start_all(&[], &[(explorer_id, planet_id)]).unwrap();
+    #[test]
+    fn test_explorers_without_info_are_not_living() {
+        let mut orch = Orchestrator::new().unwrap();
+        stub_explorer_channels(&mut orch, 3);
 
-            let result = orch.send_bag_content_request(explorer_id);
+        assert_eq!(orch.living_explorers().count(), 0);
+        orch.explorers_info
+            .insert(3, ExplorerInfo::from(3, Status::Paused, Vec::new(), 0));
+        assert_eq!(orch.living_explorers().count(), 1);
+    }
+}
 
-            assert!(
-                result.is_ok(),
-                "L'invio della richiesta bag_content all'explorer {} è fallito: {:?}",
-                explorer_id,
-                result.err()
-            );
-        }
+#[cfg(test)]
+mod tests_galaxy_partition {
+    use super::*;
+    use crate::components::orchestrator::OrchestratorEvent;
 
-        #[test]
-        fn test_send_bag_content_missing_explorer() {
-            let orch = Orchestrator::new().unwrap();
-            let invalid_explorer_id = 999;
+    /// Two triangles, 0-1-2 and 3-4-5, joined by planet 6 between 2 and 3.
+    fn barbell() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        let ty = PlanetType::OneMillionCrabs as u32;
+        let content = format!(
+            "0,{ty},1,2\n1,{ty},0,2\n2,{ty},0,1,6\n6,{ty},2,3\n3,{ty},6,4,5\n4,{ty},3,5\n5,{ty},3,4"
+        );
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch
+    }
 
-            let result = orch.send_bag_content_request(invalid_explorer_id);
+    fn partitions(orch: &Orchestrator) -> Vec<&Vec<Vec<u32>>> {
+        orch.gui_messages
+            .iter()
+            .filter_map(|event| match event {
+                OrchestratorEvent::GalaxyPartitioned { components } => Some(components),
+                _ => None,
+            })
+            .collect()
+    }
 
-            assert!(
-                result.is_err(),
-                "L'invio doveva fallire per un explorer inesistente"
-            );
-            assert_eq!(
-                result.unwrap_err(),
-                format!("No sender found for explorer {}", invalid_explorer_id)
-            );
-        }
+    #[test]
+    fn test_cutting_the_bridge_splits_the_galaxy() {
+        let mut orch = barbell();
+        assert_eq!(orch.connected_components(), vec![vec![0, 1, 2, 3, 4, 5, 6]]);
 
-        #[test]
-        fn test_send_bag_content_disconnected_channel() {
-            let mut orch = Orchestrator::new().unwrap();
-            let planet_id = 2;
-            let explorer_id = 200;
+        orch.destroy_topology_link(6).unwrap();
+        orch.planets_info.update_status(6, Status::Dead).unwrap();
 
-            let content = format!("{},{}", planet_id, PlanetType::Ciuc as u32);
-            orch.initialize_galaxy_by_content(&content).unwrap();
-            orch.start_all(&[], &[(explorer_id, planet_id)]).unwrap();
+        let expected = vec![vec![0, 1, 2], vec![3, 4, 5]];
+        assert_eq!(partitions(&orch), vec![&expected]);
+        assert_eq!(orch.connected_components(), expected);
+    }
 
-            let (dead_sender, dead_receiver) = crossbeam_channel::unbounded();
-            drop(dead_receiver);
+    #[test]
+    fn test_losing_a_planet_without_splitting_is_not_reported() {
+        let mut orch = barbell();
 
-            let (_, planet_sender) = orch.explorer_channels.get(&explorer_id).unwrap().clone();
-            orch.explorer_channels
-                .insert(explorer_id, (dead_sender, planet_sender));
+        orch.destroy_topology_link(0).unwrap();
+        orch.planets_info.update_status(0, Status::Dead).unwrap();
 
-            let result = orch.send_bag_content_request(explorer_id);
+        assert!(partitions(&orch).is_empty());
+        assert_eq!(orch.connected_components(), vec![vec![1, 2, 3, 4, 5, 6]]);
+    }
 
-            assert!(result.is_err());
-            let err_msg = result.unwrap_err();
-            assert!(
-                err_msg.contains("Failed to send bag content request"),
-                "Il messaggio di errore era imprevisto: {}",
-                err_msg
-            );
-        }
+    #[test]
+    fn test_partitions_are_rejoined_when_asked() {
+        let mut orch = barbell();
+        orch.rejoin_partitions = true;
+
+        orch.destroy_topology_link(6).unwrap();
+        orch.planets_info.update_status(6, Status::Dead).unwrap();
+
+        assert_eq!(partitions(&orch).len(), 1);
+        assert_eq!(orch.connected_components(), vec![vec![0, 1, 2, 3, 4, 5]]);
+        assert_eq!(orch.pending_topology_delta.added.len(), 1);
     }
 }
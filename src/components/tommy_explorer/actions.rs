@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::fmt;
 
 /// These are the actions that the explorer can perform.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +12,20 @@ pub enum ExplorerAction {
     Move,
 }
 
+impl fmt::Display for ExplorerAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ExplorerAction::AskNeighbours => "AskNeighbours",
+            ExplorerAction::AskSupportedResources => "AskSupportedResources",
+            ExplorerAction::AskSupportedCombinations => "AskSupportedCombinations",
+            ExplorerAction::AskFreeCells => "AskFreeCells",
+            ExplorerAction::GenerateOrCombine => "GenerateOrCombine",
+            ExplorerAction::Move => "Move",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// This function sets the action flow by putting in the correct order the explorer actions.
 pub fn initialize_action_flow() -> VecDeque<ExplorerAction> {
     let mut res = VecDeque::new();
@@ -23,6 +38,28 @@ pub fn initialize_action_flow() -> VecDeque<ExplorerAction> {
     res
 }
 
+/// This function sets the action flow used when the new planet is already known:
+/// the survey actions are moved to the end of the flow.
+pub fn initialize_known_planet_flow() -> VecDeque<ExplorerAction> {
+    let mut res = VecDeque::new();
+    res.push_back(ExplorerAction::AskFreeCells);
+    res.push_back(ExplorerAction::GenerateOrCombine);
+    res.push_back(ExplorerAction::Move);
+    res.push_back(ExplorerAction::AskNeighbours);
+    res.push_back(ExplorerAction::AskSupportedResources);
+    res.push_back(ExplorerAction::AskSupportedCombinations);
+    res
+}
+
+/// How the explorer surveys the planet it has just arrived on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrivalSurvey {
+    /// AskNeighbours and the full survey are the first actions.
+    First,
+    /// The survey actions are moved to the end of the flow.
+    Last,
+}
+
 /// Struct that manages the action queue for the explorer.
 pub struct ActionQueue {
     queue: VecDeque<ExplorerAction>,
@@ -62,6 +99,15 @@ impl ActionQueue {
         self.queue = initialize_action_flow();
     }
 
+    /// Resets the queue after arriving on a new planet,
+    /// `survey` tells where the survey actions of the planet go.
+    pub fn reset_for_arrival(&mut self, survey: ArrivalSurvey) {
+        self.queue = match survey {
+            ArrivalSurvey::First => initialize_action_flow(),
+            ArrivalSurvey::Last => initialize_known_planet_flow(),
+        };
+    }
+
     /// Returns the number of actions in the queue.
     #[cfg(test)]
     pub fn len(&self) -> usize {
@@ -119,6 +165,14 @@ impl MoveQueue {
         self.move_queue.is_empty()
     }
 
+    /// Checks if the path starting from `start` crosses the link between `a` and `b`.
+    pub fn uses_link(&self, start: u32, a: u32, b: u32) -> bool {
+        std::iter::once(&start)
+            .chain(self.move_queue.iter())
+            .zip(self.move_queue.iter())
+            .any(|(&from, &to)| (from, to) == (a, b) || (from, to) == (b, a))
+    }
+
     /// Clears the queue.
     pub fn clear(&mut self) {
         self.move_queue.clear();
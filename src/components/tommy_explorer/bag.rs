@@ -3,12 +3,14 @@ use common_game::components::resource::{
     GenericResource, ResourceType,
 };
 
+use crate::utils::recipes;
+
 /// The type that is returned to the orchestrator when he asks for the explorer's bag,
 /// it's a vector of ResourceType.
 pub type BagType = Vec<ResourceType>;
 
 /// Struct of the bag for explorer's internal use.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Bag {
     resources: Vec<GenericResource>,
 }
@@ -16,9 +18,7 @@ pub struct Bag {
 impl Bag {
     /// Creates an empty bag.
     pub fn new() -> Self {
-        Self {
-            resources: Vec::new(),
-        }
+        Self::default()
     }
 
     /// Inserts a resource in the bag.
@@ -26,6 +26,11 @@ impl Bag {
         self.resources.push(res);
     }
 
+    /// Removes every resource from the bag.
+    pub fn clear(&mut self) {
+        self.resources.clear();
+    }
+
     /// Takes a resource from the bag if it exists.
     pub fn take_resource(&mut self, ty: ResourceType) -> Option<GenericResource> {
         let idx = self.resources.iter().position(|r| r.get_type() == ty)?;
@@ -37,6 +42,16 @@ impl Bag {
         self.resources.iter().any(|r| r.get_type() == ty)
     }
 
+    /// Checks if the bag holds every ingredient of a complex resource.
+    pub fn has_ingredients(&self, complex: ComplexResourceType) -> bool {
+        recipes::has_ingredients(&self.to_resource_types(), complex)
+    }
+
+    /// Returns the value of the bag, see [`recipes::resource_score`].
+    pub fn score(&self) -> u64 {
+        recipes::bag_score(&self.to_resource_types())
+    }
+
     /// Returns a BagType containing all the ResourceType in the bag.
     // this is needed because the bag cannot give its ownership to the orchestrator
     // and cannot be passed as a reference
@@ -49,26 +64,23 @@ impl Bag {
         &mut self,
         resource_type: ComplexResourceType,
     ) -> Result<ComplexResourceRequest, String> {
-        match resource_type {
+        let request = match resource_type {
             ComplexResourceType::Diamond => self.make_diamond_request(),
             ComplexResourceType::Water => self.make_water_request(),
             ComplexResourceType::Life => self.make_life_request(),
             ComplexResourceType::Robot => self.make_robot_request(),
             ComplexResourceType::Dolphin => self.make_dolphin_request(),
             ComplexResourceType::AIPartner => self.make_ai_partner_request(),
-        }
+        }?;
+        // never send a request that would make something else than what was asked
+        recipes::check_request(&request, resource_type)?;
+        Ok(request)
     }
 
     // The following methods are the ones to combine resources.
     pub(crate) fn make_diamond_request(&mut self) -> Result<ComplexResourceRequest, String> {
         // checks that the explorer has 2 carbons before taking any
-        let carbon_count = self
-            .resources
-            .iter()
-            .filter(|r| r.get_type() == ResourceType::Basic(BasicResourceType::Carbon))
-            .count();
-
-        if carbon_count < 2 {
+        if !self.has_ingredients(ComplexResourceType::Diamond) {
             return Err("Missing resource".to_string());
         }
 
@@ -89,7 +101,7 @@ impl Bag {
         let hydrogen_type = ResourceType::Basic(BasicResourceType::Hydrogen);
         let oxygen_type = ResourceType::Basic(BasicResourceType::Oxygen);
 
-        if !(self.contains(hydrogen_type) && self.contains(oxygen_type)) {
+        if !self.has_ingredients(ComplexResourceType::Water) {
             return Err("Missing resource".to_string());
         }
 
@@ -110,7 +122,7 @@ impl Bag {
         let water_type = ResourceType::Complex(ComplexResourceType::Water);
         let carbon_type = ResourceType::Basic(BasicResourceType::Carbon);
 
-        if !(self.contains(water_type) && self.contains(carbon_type)) {
+        if !self.has_ingredients(ComplexResourceType::Life) {
             return Err("Missing resource".to_string());
         }
 
@@ -131,7 +143,7 @@ impl Bag {
         let silicon_type = ResourceType::Basic(BasicResourceType::Silicon);
         let life_type = ResourceType::Complex(ComplexResourceType::Life);
 
-        if !(self.contains(silicon_type) && self.contains(life_type)) {
+        if !self.has_ingredients(ComplexResourceType::Robot) {
             return Err("Missing resource".to_string());
         }
 
@@ -152,7 +164,7 @@ impl Bag {
         let water_type = ResourceType::Complex(ComplexResourceType::Water);
         let life_type = ResourceType::Complex(ComplexResourceType::Life);
 
-        if !(self.contains(water_type) && self.contains(life_type)) {
+        if !self.has_ingredients(ComplexResourceType::Dolphin) {
             return Err("Missing resource".to_string());
         }
 
@@ -173,7 +185,7 @@ impl Bag {
         let robot_type = ResourceType::Complex(ComplexResourceType::Robot);
         let diamond_type = ResourceType::Complex(ComplexResourceType::Diamond);
 
-        if !(self.contains(robot_type) && self.contains(diamond_type)) {
+        if !self.has_ingredients(ComplexResourceType::AIPartner) {
             return Err("Missing resource".to_string());
         }
 
@@ -191,12 +203,6 @@ impl Bag {
     }
 }
 
-impl Default for Bag {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 /// Trait for the conversion of resources into 'GenericResource'
 pub trait IntoGenericResource {
     /// Converts specialized resources (Basic or Complex) into a unified 'GenericResource' for inventory storage
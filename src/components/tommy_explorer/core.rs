@@ -1,11 +1,15 @@
-use super::actions::{ActionQueue, ExplorerAction, MoveQueue};
+use super::actions::{ActionQueue, ArrivalSurvey, ExplorerAction, MoveQueue};
 use super::bag::{Bag, BagType};
+use super::explorer_ai::{GOAL, Strategy};
 use super::handlers::{orchestrator, planet};
 use super::state::ExplorerState;
 use super::topology::{PlanetInfo, TopologyManager};
 use crate::components::tommy_explorer::handlers::orchestrator::{
     combine_resource_request, generate_resource_request,
 };
+use crate::utils::TopologyDelta;
+use crate::utils::explorer_buffer::buffer_orchestrator_msg;
+use crate::utils::request_ledger::{PlanetRequestKind, PlanetRequestLedger};
 use common_game::components::resource::{
     BasicResourceType, ComplexResourceRequest, ComplexResourceType, GenericResource, ResourceType,
 };
@@ -15,10 +19,19 @@ use common_game::protocols::orchestrator_explorer::{
 };
 use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
 use common_game::utils::ID;
-use crossbeam_channel::{Receiver, Sender, select};
-use logging_utils::{get_receiver_id, get_sender_id, log_fn_call, log_message, warning_payload};
-use std::collections::{VecDeque};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, at, never, select};
+use logging_utils::{
+    get_receiver_id, get_sender_id, log_fn_call, log_internal_op, log_message, warning_payload,
+};
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::time::{Duration, Instant};
+
+/// how long the explorer waits between two iterations of the main loop
+const LOOP_WAIT: Duration = Duration::from_millis(20);
+
+/// how long the explorer waits for the answer of a planet before giving up
+const PLANET_RESPONSE_TIMEOUT: Duration = Duration::from_millis(500);
 
 /// struct of the explorer
 pub struct Explorer {
@@ -39,6 +52,18 @@ pub struct Explorer {
     pub move_queue: MoveQueue,
     manual_mode: bool,
     accept_death: bool,
+    auto_survey_on_arrival: bool, // if false the survey actions keep their usual place on a known planet
+    pub(crate) planet_requests: PlanetRequestLedger, // planet requests waiting for a response, with the planet they were sent to
+    kill_receiver: Receiver<()>, // kill signal sent by the orchestrator along with KillExplorer
+    kill_signaled: bool, // kill signal received while waiting for a planet, handled by the main loop
+    topology_receiver: Receiver<TopologyDelta>, // links of the galaxy created or destroyed, sent by the orchestrator
+    recharge_wait: u32, // AI cycles the explorer waits on a planet without charged cells before moving on
+    recharge_wait_left: u32, // AI cycles still to wait on the current planet
+    energy_reservations: HashMap<ID, u32>, // energy cells used by the generate/combine requests not reconciled yet, per planet
+    no_progress_reason: Option<String>, // why the AI cannot make progress, set only once
+    pub(crate) strategy: Strategy, // how the AI chooses where to move
+    idle_shutdown_after: Option<u64>, // AI cycles with nothing to do before the explorer terminates, None for never
+    idle_ticks: u64, // AI cycles spent in a row with nothing to do
 }
 
 impl Explorer {
@@ -79,6 +104,18 @@ impl Explorer {
             move_queue: MoveQueue::new(),
             manual_mode: true,
             accept_death: false,
+            auto_survey_on_arrival: true,
+            planet_requests: PlanetRequestLedger::new(),
+            kill_receiver: never(),
+            kill_signaled: false,
+            topology_receiver: never(),
+            recharge_wait: 0,
+            recharge_wait_left: 0,
+            energy_reservations: HashMap::new(),
+            no_progress_reason: None,
+            strategy: Strategy::default(),
+            idle_shutdown_after: None,
+            idle_ticks: 0,
         }
     }
 
@@ -100,6 +137,12 @@ impl Explorer {
         &self.state
     }
 
+    /// checks if the AI gave up, and lets it try again
+    #[cfg(test)]
+    pub fn take_accept_death(&mut self) -> bool {
+        std::mem::take(&mut self.accept_death)
+    }
+
     /// gets information about a planet
     pub fn get_planet_info(&self, planet_id: ID) -> Option<&PlanetInfo> {
         self.topology.get(planet_id)
@@ -110,11 +153,42 @@ impl Explorer {
         self.topology.get_mut(planet_id)
     }
 
+    /// gets mutable information about a planet, adding the planet to the topology if unknown
+    pub fn get_or_create_planet_info_mut(&mut self, planet_id: ID) -> &mut PlanetInfo {
+        self.topology.get_or_create(planet_id)
+    }
+
     /// Gets the bag content as resource types.
     pub fn get_bag_content(&self) -> BagType {
         self.bag.to_resource_types()
     }
 
+    /// Gets the reason why the AI cannot make progress, if it gave up.
+    pub fn no_progress_reason(&self) -> Option<&str> {
+        self.no_progress_reason.as_deref()
+    }
+
+    /// Gets the strategy used by the AI to choose where to move.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Gets how the explorer has to survey the given planet when it arrives on it.
+    ///
+    /// With the automatic survey disabled the default action flow is used, so the
+    /// survey actions keep their usual place.
+    pub fn arrival_survey(&self, planet_id: ID) -> ArrivalSurvey {
+        if !self.auto_survey_on_arrival
+            || self
+                .get_planet_info(planet_id)
+                .is_none_or(|info| !info.is_complete())
+        {
+            ArrivalSurvey::First
+        } else {
+            ArrivalSurvey::Last
+        }
+    }
+
     // ==================== Setter Methods ====================
 
     /// Sets the explorer state.
@@ -133,8 +207,84 @@ impl Explorer {
     }
 
     /// Sets the energy cells.
+    ///
+    /// The new value comes from the planet, so it already accounts for the cells reserved
+    /// on the current planet, which are released. A planet with charged cells restarts
+    /// the recharge wait.
     pub fn set_energy_cells(&mut self, cells: u32) {
         self.energy_cells = cells;
+        self.energy_reservations.remove(&self.planet_id);
+        if cells > 0 {
+            self.recharge_wait_left = self.recharge_wait;
+        }
+    }
+
+    /// Sets how many AI cycles the explorer waits for the current planet to recharge
+    /// its energy cells before moving on (0 means it moves on immediately).
+    pub fn set_recharge_wait(&mut self, cycles: u32) {
+        self.recharge_wait = cycles;
+        self.recharge_wait_left = cycles;
+    }
+
+    /// Sets the strategy used by the AI to choose where to move, the path already
+    /// planned is dropped so that the next move follows the new strategy.
+    pub fn set_strategy(&mut self, strategy: Strategy) {
+        self.strategy = strategy;
+        self.move_queue.clear();
+    }
+
+    /// Sets after how many AI cycles with nothing to do the explorer terminates on its
+    /// own, as if it was killed (None, the default, means it never does).
+    pub fn set_idle_shutdown_after(&mut self, ticks: Option<u64>) {
+        self.idle_shutdown_after = ticks;
+        self.idle_ticks = 0;
+    }
+
+    // ==================== Energy Methods ====================
+
+    /// Gets the energy cells of the current planet that are not reserved by a
+    /// generate/combine request.
+    pub fn affordable_energy_cells(&self) -> u32 {
+        self.energy_cells
+            .saturating_sub(self.reserved_energy_cells(self.planet_id))
+    }
+
+    /// Gets the energy cells reserved on a planet.
+    pub fn reserved_energy_cells(&self, planet_id: ID) -> u32 {
+        self.energy_reservations
+            .get(&planet_id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Reserves an energy cell of the current planet for a generate/combine request.
+    ///
+    /// The reservation lasts until the answer tells whether the cell was used, or until
+    /// the planet sends its energy cells again (see [`set_energy_cells`](Self::set_energy_cells)).
+    pub(crate) fn reserve_energy_cell(&mut self) {
+        *self.energy_reservations.entry(self.planet_id).or_insert(0) += 1;
+    }
+
+    /// Releases the cell reserved by a generate/combine request that did not use it.
+    pub(crate) fn release_energy_cell(&mut self) {
+        if let Some(reserved) = self.energy_reservations.get_mut(&self.planet_id) {
+            *reserved -= 1;
+            if *reserved == 0 {
+                self.energy_reservations.remove(&self.planet_id);
+            }
+        }
+    }
+
+    /// Releases the cell reserved by a successful generate/combine request, the cell has
+    /// been used so it is removed from the energy cells of the current planet.
+    pub(crate) fn consume_energy_cell(&mut self) {
+        self.release_energy_cell();
+        self.energy_cells = self.energy_cells.saturating_sub(1);
+    }
+
+    /// Restarts the recharge wait, e.g. when the explorer arrives on a new planet.
+    pub fn reset_recharge_wait(&mut self) {
+        self.recharge_wait_left = self.recharge_wait;
     }
 
     /// Sets the manual mode to on.
@@ -147,6 +297,21 @@ impl Explorer {
         self.manual_mode = false;
     }
 
+    /// Enables or disables the automatic survey of a new planet on arrival.
+    pub fn set_auto_survey_on_arrival(&mut self, enabled: bool) {
+        self.auto_survey_on_arrival = enabled;
+    }
+
+    /// Sets the receiver of the orchestrator kill signal.
+    pub fn set_kill_receiver(&mut self, receiver: Receiver<()>) {
+        self.kill_receiver = receiver;
+    }
+
+    /// Sets the receiver of the orchestrator topology notifications.
+    pub fn set_topology_receiver(&mut self, receiver: Receiver<TopologyDelta>) {
+        self.topology_receiver = receiver;
+    }
+
     // ==================== Communication Methods ====================
 
     /// sends a message to the orchestrator
@@ -170,6 +335,32 @@ impl Explorer {
         self.planet_channels.0.recv()
     }
 
+    /// receives a message from the planet, waiting at most `PLANET_RESPONSE_TIMEOUT`
+    ///
+    /// returns None if nothing arrives in time or if the kill signal arrives first, the
+    /// signal is then handled by the main loop
+    pub fn receive_from_planet_timeout(
+        &mut self,
+    ) -> Option<Result<PlanetToExplorer, crossbeam_channel::RecvError>> {
+        let deadline = at(Instant::now() + PLANET_RESPONSE_TIMEOUT);
+        while !self.kill_signaled {
+            let mut kill_channel_closed = false;
+            select! {
+                recv(self.planet_channels.0) -> msg => return Some(msg),
+                recv(self.kill_receiver) -> signal => match signal {
+                    Ok(()) => self.kill_signaled = true,
+                    Err(_) => kill_channel_closed = true,
+                },
+                recv(deadline) -> _ => return None,
+            }
+            if kill_channel_closed {
+                // see wait_kill_signal
+                self.kill_receiver = never();
+            }
+        }
+        None
+    }
+
     // ==================== Bag Methods ====================
 
     /// inserts a resource in the bag
@@ -200,140 +391,257 @@ impl Explorer {
     // ==================== Main Loop ====================
 
     /// the explorer main loop
+    ///
+    /// every iteration the explorer receives messages from both planet and orchestrator channels,
+    /// then it behaves based on the message received, if the message received and the explorer state
+    /// do not match together the message is pushed into the corresponding buffer, and it will be read
+    /// when the explorer will be in an "Idle" state
     pub fn run(&mut self) -> Result<(), String> {
-
-        // every iteration the explorer receives messages from both planet and orchestrator channels,
-        // then it behaves based on the message received, if the message received and the explorer state
-        // do not match together the message is pushed into the corresponding buffer, and it will be read
-        // when the explorer will be in an "Idle" state
         loop {
-            if self.state.should_terminate() {
-                return Ok(());
+            if let Some(exit) = self.run_iteration() {
+                return exit;
             }
+        }
+    }
 
-            select! {
-                // receive the orchestrator messages
-                recv(self.orchestrator_channels.0) -> msg_orchestrator => {
-                    match msg_orchestrator {
-                        Ok(msg) => {
-                            // LOG
-                            log_message!(
-                                ActorType::Orchestrator,
-                                0u32,
-                                ActorType::Explorer,
-                                self.explorer_id,
-                                EventType::MessageOrchestratorToExplorer,
-                                "message received";
-                                "msg"=>format!("{:?}", msg),
-                                "explorer data"=>format!("{:?}", self)
-                            );
-                            // LOG
-
-                            // the explorer handles the message only if he is in the correct state to do so
-                            if self.state.matches_orchestrator_msg(&msg) {
-                                // handle_message return Ok(true) if the explorer thread should terminate
-                                let should_terminate = orchestrator::handle_message(self, msg)?;
-                                if should_terminate {
-                                    return Ok(());
-                                }
-                            } else {
-                                // if the explorer is not in the correct state to handle the message,
-                                // the message is buffered
-                                self.buffer_orchestrator_msg.push_back(msg);
+    /// runs the main loop until `deadline`, then returns the state of the explorer
+    ///
+    /// it returns earlier if the explorer is killed, or with an error if the orchestrator
+    /// channel disconnects; used to drive the real loop without a thread
+    pub fn run_until(&mut self, deadline: Instant) -> Result<&ExplorerState, String> {
+        while Instant::now() < deadline {
+            if let Some(exit) = self.run_iteration() {
+                return exit.map(|_| &self.state);
+            }
+        }
+        Ok(&self.state)
+    }
+
+    /// runs `ticks` iterations of the main loop, then returns the state of the explorer
+    ///
+    /// see [`run_until`](Self::run_until)
+    pub fn run_ticks(&mut self, ticks: u64) -> Result<&ExplorerState, String> {
+        for _ in 0..ticks {
+            if let Some(exit) = self.run_iteration() {
+                return exit.map(|_| &self.state);
+            }
+        }
+        Ok(&self.state)
+    }
+
+    /// a single iteration of the main loop
+    ///
+    /// returns Some when the loop has to exit, with the result of the loop
+    fn run_iteration(&mut self) -> Option<Result<(), String>> {
+        if self.state.should_terminate() {
+            return Some(Ok(()));
+        }
+        // the kill signal has priority over every other message
+        if self.wait_kill_signal(Duration::ZERO) {
+            return Some(self.handle_kill_signal());
+        }
+        self.apply_topology_deltas();
+
+        select! {
+            // receive the orchestrator messages
+            recv(self.orchestrator_channels.0) -> msg_orchestrator => {
+                match msg_orchestrator {
+                    Ok(msg) => {
+                        // LOG
+                        log_message!(
+                            ActorType::Orchestrator,
+                            0u32,
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            EventType::MessageOrchestratorToExplorer,
+                            "message received";
+                            "msg"=>format!("{:?}", msg),
+                            "explorer data"=>format!("{:?}", self)
+                        );
+                        // LOG
+
+                        // the explorer handles the message only if he is in the correct state to do so
+                        if self.state.matches_orchestrator_msg(&msg) {
+                            // handle_message return Ok(true) if the explorer thread should terminate
+                            match orchestrator::handle_message(self, msg) {
+                                Ok(true) => return Some(Ok(())),
+                                Ok(false) => {}
+                                Err(err) => return Some(Err(err)),
                             }
-                        }
-                        Err(err) => {
-                            // LOG
-                            LogEvent::new(
-                                Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
-                                EventType::MessageOrchestratorToExplorer,
-                                Channel::Error,
-                                warning_payload!(
-                                    "receiving channel from orchestrator disconnected",
-                                    err,
-                                    "tommy_explorer::run()"
-                                )
-                            ).emit();
-                            // LOG
-                            return Err(err.to_string());
+                        } else {
+                            // if the explorer is not in the correct state to handle the message,
+                            // the message is buffered
+                            buffer_orchestrator_msg(&mut self.buffer_orchestrator_msg, msg);
                         }
                     }
-                },
-                // receive the planet messages
-                recv(self.planet_channels.0) -> msg_planet => {
-                    match msg_planet {
-                        Ok(msg) => {
-                            // LOG
-                            log_message!(
-                                ActorType::Planet,
-                                self.planet_id,
-                                ActorType::Explorer,
-                                self.explorer_id,
-                                EventType::MessagePlanetToExplorer,
-                                "message received";
-                                "msg"=>format!("{:?}", msg),
-                                "explorer data"=>format!("{:?}", self)
-                            );
-                            // LOG
-
-                            // the explorer handles the message only if he is in the correct state to do so
-                            if self.state.matches_planet_msg(&msg) {
-                                planet::handle_message(self, msg)?;
-                            } else {
-                                // if the explorer is not in the correct state to handle the message,
-                                // the message is buffered
-                                self.buffer_planet_msg.push_back(msg);
+                    Err(err) => {
+                        // LOG
+                        LogEvent::new(
+                            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+                            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                            EventType::MessageOrchestratorToExplorer,
+                            Channel::Error,
+                            warning_payload!(
+                                "receiving channel from orchestrator disconnected",
+                                err,
+                                "tommy_explorer::run()"
+                            )
+                        ).emit();
+                        // LOG
+                        return Some(Err(err.to_string()));
+                    }
+                }
+            },
+            // receive the planet messages
+            recv(self.planet_channels.0) -> msg_planet => {
+                match msg_planet {
+                    Ok(msg) => {
+                        // LOG
+                        log_message!(
+                            ActorType::Planet,
+                            self.planet_id,
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            EventType::MessagePlanetToExplorer,
+                            "message received";
+                            "msg"=>format!("{:?}", msg),
+                            "explorer data"=>format!("{:?}", self)
+                        );
+                        // LOG
+
+                        // the explorer handles the message only if he is in the correct state to do so
+                        if self.state.matches_planet_msg(&msg) {
+                            if let Err(err) = planet::handle_message(self, msg) {
+                                return Some(Err(err));
                             }
+                        } else {
+                            // if the explorer is not in the correct state to handle the message,
+                            // the message is buffered
+                            self.buffer_planet_msg.push_back(msg);
                         }
-                        Err(err) => {
-                            // LOG
-                            LogEvent::new(
-                                Some(Participant::new(ActorType::Planet, self.planet_id)),
-                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
-                                EventType::MessagePlanetToExplorer,
-                                Channel::Error,
-                                warning_payload!(
-                                    "receiving channel from planet disconnected",
-                                    err,
-                                    "tommy_explorer::run()"
-                                )
-                            ).emit();
-                            // LOG
-                        }
+                    }
+                    Err(err) => {
+                        // LOG
+                        LogEvent::new(
+                            Some(Participant::new(ActorType::Planet, self.planet_id)),
+                            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                            EventType::MessagePlanetToExplorer,
+                            Channel::Error,
+                            warning_payload!(
+                                "receiving channel from planet disconnected",
+                                err,
+                                "tommy_explorer::run()"
+                            )
+                        ).emit();
+                        // LOG
                     }
                 }
-                // default branch, here the explorer performs choices and actions
-                // other than managing the buffered messages
-                default => {
-                    // priority to the buffered messages
-                    if self.manual_mode {
-                        std::thread::sleep(std::time::Duration::from_millis(20));
-                        continue;
+            }
+            // default branch, here the explorer performs choices and actions
+            // other than managing the buffered messages
+            default => {
+                // priority to the buffered messages
+                if self.manual_mode {
+                    if self.wait_kill_signal(LOOP_WAIT) {
+                        return Some(self.handle_kill_signal());
                     }
+                    return None;
+                }
 
-                    match self.state {
-                        ExplorerState::Idle => {
-                            self.process_buffered_messages()?;
-                            if self.state.should_terminate() {
-                                return Ok(());
-                            }
+                match self.state {
+                    ExplorerState::Idle => {
+                        if let Err(err) = self.process_buffered_messages() {
+                            return Some(Err(err));
+                        }
+                        if self.state.should_terminate() {
+                            return Some(Ok(()));
                         }
-                        // if we are not in idle state we need to manage some other message
-                        _ => {
-                            std::thread::sleep(std::time::Duration::from_millis(20));
-                            continue;
-                        },
                     }
+                    // if we are not in idle state we need to manage some other message
+                    _ => {
+                        if self.wait_kill_signal(LOOP_WAIT) {
+                            return Some(self.handle_kill_signal());
+                        }
+                        return None;
+                    },
+                }
 
-                    // if the state is still idle after processing buffers, execute AI actions
-                    if matches!(self.state, ExplorerState::Idle) {
-                        self.execute_ai_action();
+                // if the state is still idle after processing buffers, execute AI actions
+                if matches!(self.state, ExplorerState::Idle) {
+                    self.execute_ai_action();
+                    if self.idle_shutdown_due() {
+                        // LOG
+                        log_internal_op!(dir
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            "action" => "idle shutdown",
+                            "idle_ticks" => self.idle_ticks.to_string()
+                        );
+                        // LOG
+                        return Some(self.handle_kill_signal());
                     }
                 }
             }
-            std::thread::sleep(std::time::Duration::from_millis(20));
         }
+        if self.wait_kill_signal(LOOP_WAIT) {
+            return Some(self.handle_kill_signal());
+        }
+        None
+    }
+
+    /// Applies the topology notifications received from the orchestrator.
+    ///
+    /// The planned path is dropped if it crosses a removed link, the next Move action
+    /// plans a new one on the updated topology; the dead planets are forgotten.
+    pub(crate) fn apply_topology_deltas(&mut self) {
+        while let Ok(delta) = self.topology_receiver.try_recv() {
+            for &(a, b) in &delta.removed {
+                self.topology.remove_edge(a, b);
+                if self.move_queue.uses_link(self.planet_id, a, b) {
+                    self.move_queue.clear();
+                }
+            }
+            for &(a, b) in &delta.added {
+                self.topology.add_edge(a, b);
+            }
+            for &planet_id in &delta.dead_planets {
+                self.topology.mark_as_dead(planet_id);
+                self.planet_requests.forget_planet(planet_id);
+            }
+            // LOG
+            log_internal_op!(dir
+                ActorType::Explorer,
+                self.explorer_id,
+                "action" => "topology delta applied",
+                "delta" => format!("{:?}", delta)
+            );
+            // LOG
+        }
+    }
+
+    /// Waits up to `timeout` for the kill signal of the orchestrator.
+    ///
+    /// Returns true if the signal was received.
+    fn wait_kill_signal(&mut self, timeout: Duration) -> bool {
+        if std::mem::take(&mut self.kill_signaled) {
+            return true;
+        }
+        match self.kill_receiver.recv_timeout(timeout) {
+            Ok(()) => true,
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => {
+                // the orchestrator dropped the kill channel, from now on only
+                // KillExplorer can stop the explorer
+                self.kill_receiver = never();
+                self.kill_receiver.recv_timeout(timeout).is_ok()
+            }
+        }
+    }
+
+    /// Handles the kill signal as a KillExplorer message, without waiting for the
+    /// message itself to be received.
+    fn handle_kill_signal(&mut self) -> Result<(), String> {
+        orchestrator::handle_message(self, OrchestratorToExplorer::KillExplorer).map(|_| ())
     }
 
     // ==================== Buffer Management ====================
@@ -368,8 +676,67 @@ impl Explorer {
 
     // ==================== AI Logic ====================
 
+    /// Checks if the explorer has to keep waiting for the current planet to recharge
+    /// its energy cells, and counts one more AI cycle of waiting.
+    fn wait_for_recharge(&mut self) -> bool {
+        if self.energy_cells > 0 || self.recharge_wait_left == 0 {
+            return false;
+        }
+        self.recharge_wait_left -= 1;
+        true
+    }
+
+    /// Checks if the AI has nothing left to do: it gave up, or it holds the goal and
+    /// the whole topology is discovered.
+    fn has_nothing_to_do(&self) -> bool {
+        self.accept_death
+            || (self.bag.contains(ResourceType::Complex(GOAL))
+                && self
+                    .topology
+                    .find_path_to_nearest_frontier(self.planet_id)
+                    .is_none())
+    }
+
+    /// Counts one more AI cycle with nothing to do, or restarts the count.
+    ///
+    /// Returns true if the explorer has to terminate, see
+    /// [`set_idle_shutdown_after`](Self::set_idle_shutdown_after).
+    fn idle_shutdown_due(&mut self) -> bool {
+        if self.has_nothing_to_do() {
+            self.idle_ticks += 1;
+        } else {
+            self.idle_ticks = 0;
+        }
+        self.idle_shutdown_after
+            .is_some_and(|after| self.idle_ticks >= after)
+    }
+
+    /// reports, only the first time, that the AI cannot make progress
+    ///
+    /// the protocol has no explorer error message, so the report is an error log
+    /// addressed to the orchestrator
+    pub(crate) fn report_no_progress(&mut self, reason: String) {
+        if self.no_progress_reason.is_some() {
+            return;
+        }
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+            EventType::MessageExplorerToOrchestrator,
+            Channel::Error,
+            warning_payload!(
+                "explorer cannot make progress",
+                reason,
+                "execute_ai_action()";
+                "planet_id" => self.planet_id.to_string()
+            ),
+        )
+        .emit();
+        self.no_progress_reason = Some(reason);
+    }
+
     /// executes the next AI action
-    fn execute_ai_action(&mut self) {
+    pub(crate) fn execute_ai_action(&mut self) {
         // 1) ask for neighbours (every time, they could change)
         // 2) ask for resources and combining rules (only if not memorized yet)
         // 3) generate/combine resources in order to achieve your explorer goal
@@ -381,359 +748,398 @@ impl Explorer {
         if let Some(action) = self.action_queue.next_action()
             && !self.accept_death
         {
-            match action {
-                ExplorerAction::AskNeighbours => {
-                    self.action_queue.push_back(action);
-                    match self.send_to_orchestrator(ExplorerToOrchestrator::NeighborsRequest {
-                        explorer_id: self.explorer_id,
-                        current_planet_id: self.planet_id,
-                    }) {
-                        Ok(_) => {
-                            // if the sending is successful change the state to WaitingForNeighbours
-                            self.set_state(ExplorerState::WaitingForNeighbours);
-
-                            log_message!(
-                                ActorType::Explorer,
-                                self.explorer_id,
-                                ActorType::Orchestrator,
-                                0u32,
-                                EventType::MessageExplorerToOrchestrator,
-                                "neighbors request sent";
-                                "planet_id" => self.planet_id.to_string()
-                            );
-                        }
-                        Err(err) => {
-                            LogEvent::new(
-                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
-                                Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                                EventType::MessageExplorerToOrchestrator,
-                                Channel::Error,
-                                warning_payload!(
-                                    "NeighborsRequest not sent",
-                                    err.to_string(),
-                                    "execute_ai_action()";
-                                    "explorer data" => format!("{:?}", self)
-                                ),
-                            )
-                            .emit();
-                        }
+            //LOG
+            log_internal_op!(dir
+                ActorType::Explorer,
+                self.explorer_id,
+                "ai_action" => action.to_string()
+            );
+            //LOG
+            self.run_ai_action(action);
+            //LOG
+            log_internal_op!(dir
+                ActorType::Explorer,
+                self.explorer_id,
+                "ai_action_result" => format!("{:?}", self.state)
+            );
+            //LOG
+        }
+    }
+
+    /// runs a single AI action, the action is pushed back to the queue
+    fn run_ai_action(&mut self, action: ExplorerAction) {
+        match action {
+            ExplorerAction::AskNeighbours => {
+                self.action_queue.push_back(action);
+                match self.send_to_orchestrator(ExplorerToOrchestrator::NeighborsRequest {
+                    explorer_id: self.explorer_id,
+                    current_planet_id: self.planet_id,
+                }) {
+                    Ok(_) => {
+                        // if the sending is successful change the state to WaitingForNeighbours
+                        self.set_state(ExplorerState::WaitingForNeighbours);
+                        self.planet_requests
+                            .sent(PlanetRequestKind::Neighbours, self.planet_id);
+
+                        log_message!(
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            ActorType::Orchestrator,
+                            0u32,
+                            EventType::MessageExplorerToOrchestrator,
+                            "neighbors request sent";
+                            "planet_id" => self.planet_id.to_string()
+                        );
+                    }
+                    Err(err) => {
+                        LogEvent::new(
+                            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                            Some(Participant::new(ActorType::Orchestrator, 0u32)),
+                            EventType::MessageExplorerToOrchestrator,
+                            Channel::Error,
+                            warning_payload!(
+                                "NeighborsRequest not sent",
+                                err.to_string(),
+                                "execute_ai_action()";
+                                "explorer data" => format!("{:?}", self)
+                            ),
+                        )
+                        .emit();
                     }
                 }
-                ExplorerAction::AskSupportedResources => {
-                    // push back the action
-                    self.action_queue.push_back(action);
-
-                    // skip the action if the supported resources are already known
-                    if let Some(info) = self.get_planet_info(self.planet_id) {
-                        if info.get_basic_resources().is_some() {
-                            return;
-                        }
+            }
+            ExplorerAction::AskSupportedResources => {
+                // push back the action
+                self.action_queue.push_back(action);
+
+                // skip the action if the supported resources are already known
+                if let Some(info) = self.get_planet_info(self.planet_id) {
+                    if info.get_basic_resources().is_some() {
+                        return;
                     }
+                }
 
-                    match self.send_to_planet(ExplorerToPlanet::SupportedResourceRequest {
-                        explorer_id: self.explorer_id,
-                    }) {
-                        Ok(_) => {
-                            // if the sending was successful change the state to WaitingForSupportedResources
-                            self.set_state(ExplorerState::WaitingForSupportedResources);
-
-                            log_message!(
-                                ActorType::Explorer,
-                                self.explorer_id,
-                                ActorType::Planet,
-                                self.planet_id,
-                                EventType::MessageExplorerToPlanet,
-                                "supported resource request sent"
-                            );
-                        }
-                        Err(err) => {
-                            LogEvent::new(
-                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
-                                Some(Participant::new(ActorType::Planet, self.planet_id)),
-                                EventType::MessageExplorerToPlanet,
-                                Channel::Error,
-                                warning_payload!(
-                                    "SupportedResourceRequest not sent",
-                                    err.to_string(),
-                                    "execute_ai_action()";
-                                    "explorer data" => format!("{:?}", self)
-                                ),
-                            )
-                            .emit();
-                        }
+                match self.send_to_planet(ExplorerToPlanet::SupportedResourceRequest {
+                    explorer_id: self.explorer_id,
+                }) {
+                    Ok(_) => {
+                        // if the sending was successful change the state to WaitingForSupportedResources
+                        self.set_state(ExplorerState::WaitingForSupportedResources);
+                        self.planet_requests
+                            .sent(PlanetRequestKind::SupportedResources, self.planet_id);
+
+                        log_message!(
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            ActorType::Planet,
+                            self.planet_id,
+                            EventType::MessageExplorerToPlanet,
+                            "supported resource request sent"
+                        );
+                    }
+                    Err(err) => {
+                        LogEvent::new(
+                            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                            Some(Participant::new(ActorType::Planet, self.planet_id)),
+                            EventType::MessageExplorerToPlanet,
+                            Channel::Error,
+                            warning_payload!(
+                                "SupportedResourceRequest not sent",
+                                err.to_string(),
+                                "execute_ai_action()";
+                                "explorer data" => format!("{:?}", self)
+                            ),
+                        )
+                        .emit();
                     }
                 }
-                ExplorerAction::AskSupportedCombinations => {
-                    // push back the action
-                    self.action_queue.push_back(action);
-
-                    // skip the action if the complex resources are already known
-                    if let Some(info) = self.get_planet_info(self.planet_id) {
-                        if info.get_complex_resources().is_some() {
-                            return;
-                        }
+            }
+            ExplorerAction::AskSupportedCombinations => {
+                // push back the action
+                self.action_queue.push_back(action);
+
+                // skip the action if the complex resources are already known
+                if let Some(info) = self.get_planet_info(self.planet_id) {
+                    if info.get_complex_resources().is_some() {
+                        return;
                     }
+                }
 
-                    match self.send_to_planet(ExplorerToPlanet::SupportedCombinationRequest {
-                        explorer_id: self.explorer_id,
-                    }) {
-                        Ok(_) => {
-                            // if the sending was successful change the state to WaitingForSupportedCombinations
-                            self.set_state(ExplorerState::WaitingForSupportedCombinations);
-
-                            log_message!(
-                                ActorType::Explorer,
-                                self.explorer_id,
-                                ActorType::Planet,
-                                self.planet_id,
-                                EventType::MessageExplorerToPlanet,
-                                "supported combination request sent"
-                            );
-                        }
-                        Err(err) => {
-                            LogEvent::new(
-                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
-                                Some(Participant::new(ActorType::Planet, self.planet_id)),
-                                EventType::MessageExplorerToPlanet,
-                                Channel::Error,
-                                warning_payload!(
-                                    "SupportedCombinationRequest not sent",
-                                    err.to_string(),
-                                    "execute_ai_action()";
-                                    "explorer data" => format!("{:?}", self)
-                                ),
-                            )
-                            .emit();
-                        }
+                match self.send_to_planet(ExplorerToPlanet::SupportedCombinationRequest {
+                    explorer_id: self.explorer_id,
+                }) {
+                    Ok(_) => {
+                        // if the sending was successful change the state to WaitingForSupportedCombinations
+                        self.set_state(ExplorerState::WaitingForSupportedCombinations);
+                        self.planet_requests
+                            .sent(PlanetRequestKind::SupportedCombinations, self.planet_id);
+
+                        log_message!(
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            ActorType::Planet,
+                            self.planet_id,
+                            EventType::MessageExplorerToPlanet,
+                            "supported combination request sent"
+                        );
+                    }
+                    Err(err) => {
+                        LogEvent::new(
+                            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                            Some(Participant::new(ActorType::Planet, self.planet_id)),
+                            EventType::MessageExplorerToPlanet,
+                            Channel::Error,
+                            warning_payload!(
+                                "SupportedCombinationRequest not sent",
+                                err.to_string(),
+                                "execute_ai_action()";
+                                "explorer data" => format!("{:?}", self)
+                            ),
+                        )
+                        .emit();
                     }
                 }
-                ExplorerAction::AskFreeCells => {
-                    self.action_queue.push_back(action);
-                    match self.send_to_planet(ExplorerToPlanet::AvailableEnergyCellRequest {
-                        explorer_id: self.explorer_id,
-                    }) {
-                        Ok(_) => {
-                            self.set_state(ExplorerState::WaitingForAvailableEnergyCells);
-
-                            log_message!(
-                                ActorType::Explorer,
-                                self.explorer_id,
-                                ActorType::Planet,
-                                self.planet_id,
-                                EventType::MessageExplorerToPlanet,
-                                "available energy cell request sent"
-                            );
-                        }
-                        Err(err) => {
-                            LogEvent::new(
-                                Some(Participant::new(ActorType::Explorer, self.explorer_id)),
-                                Some(Participant::new(ActorType::Planet, self.planet_id)),
-                                EventType::MessageExplorerToPlanet,
-                                Channel::Error,
-                                warning_payload!(
-                                    "AvailableEnergyCellRequest not sent",
-                                    err.to_string(),
-                                    "execute_ai_action()";
-                                    "explorer data" => format!("{:?}", self)
-                                ),
-                            )
-                            .emit();
-                        }
+            }
+            ExplorerAction::AskFreeCells => {
+                self.action_queue.push_back(action);
+                match self.send_to_planet(ExplorerToPlanet::AvailableEnergyCellRequest {
+                    explorer_id: self.explorer_id,
+                }) {
+                    Ok(_) => {
+                        self.set_state(ExplorerState::WaitingForAvailableEnergyCells);
+                        self.planet_requests
+                            .sent(PlanetRequestKind::AvailableEnergyCells, self.planet_id);
+
+                        log_message!(
+                            ActorType::Explorer,
+                            self.explorer_id,
+                            ActorType::Planet,
+                            self.planet_id,
+                            EventType::MessageExplorerToPlanet,
+                            "available energy cell request sent"
+                        );
+                    }
+                    Err(err) => {
+                        LogEvent::new(
+                            Some(Participant::new(ActorType::Explorer, self.explorer_id)),
+                            Some(Participant::new(ActorType::Planet, self.planet_id)),
+                            EventType::MessageExplorerToPlanet,
+                            Channel::Error,
+                            warning_payload!(
+                                "AvailableEnergyCellRequest not sent",
+                                err.to_string(),
+                                "execute_ai_action()";
+                                "explorer data" => format!("{:?}", self)
+                            ),
+                        )
+                        .emit();
                     }
                 }
-                ExplorerAction::GenerateOrCombine => {
-                    // IMPORTANT continue to generate/combine till the explorer can
-
-                    // if the topology isn't fully discovered simply generate/combine the useful resources
-                    // otherwise:
-                    // checks what resources the explorer has
-                    // checks how many resources are needed to complete the goal -> see the dependency graph of the resources
-                    // maybe check what resources can be obtained from other planets in a possible path
-                    // choose the resource based on the things written above
-                    // generate/combine it
-
-                    self.action_queue.push_back(action);
-
-                    if self.energy_cells > 0 {
-                        if let Some(resource) = self.decide_resource_action() {
-                            match resource {
-                                ResourceType::Basic(basic_resource) => match basic_resource {
-                                    BasicResourceType::Oxygen => {
-                                        generate_resource_request(
-                                            self,
-                                            BasicResourceType::Oxygen,
-                                            false,
-                                        );
-                                    }
-                                    BasicResourceType::Hydrogen => {
-                                        generate_resource_request(
-                                            self,
-                                            BasicResourceType::Hydrogen,
-                                            false,
-                                        );
-                                    }
-                                    BasicResourceType::Carbon => {
-                                        generate_resource_request(
-                                            self,
-                                            BasicResourceType::Carbon,
-                                            false,
-                                        );
-                                    }
-                                    BasicResourceType::Silicon => {
-                                        generate_resource_request(
-                                            self,
-                                            BasicResourceType::Silicon,
-                                            false,
-                                        );
-                                    }
-                                },
-                                ResourceType::Complex(complex_resource) => match complex_resource {
-                                    ComplexResourceType::Diamond => {
-                                        combine_resource_request(
-                                            self,
-                                            ComplexResourceType::Diamond,
-                                            false,
-                                        );
-                                    }
-                                    ComplexResourceType::Water => {
-                                        combine_resource_request(
-                                            self,
-                                            ComplexResourceType::Water,
-                                            false,
-                                        );
-                                    }
-                                    ComplexResourceType::Life => {
-                                        combine_resource_request(
-                                            self,
-                                            ComplexResourceType::Life,
-                                            false,
-                                        );
-                                    }
-                                    ComplexResourceType::Robot => {
-                                        combine_resource_request(
-                                            self,
-                                            ComplexResourceType::Robot,
-                                            false,
-                                        );
-                                    }
-                                    ComplexResourceType::Dolphin => {
-                                        combine_resource_request(
-                                            self,
-                                            ComplexResourceType::Dolphin,
-                                            false,
-                                        );
-                                    }
-                                    ComplexResourceType::AIPartner => {
-                                        combine_resource_request(
-                                            self,
-                                            ComplexResourceType::AIPartner,
-                                            false,
-                                        );
-                                    }
-                                },
-                            }
+            }
+            ExplorerAction::GenerateOrCombine => {
+                // IMPORTANT continue to generate/combine till the explorer can
+
+                // if the topology isn't fully discovered simply generate/combine the useful resources
+                // otherwise:
+                // checks what resources the explorer has
+                // checks how many resources are needed to complete the goal -> see the dependency graph of the resources
+                // maybe check what resources can be obtained from other planets in a possible path
+                // choose the resource based on the things written above
+                // generate/combine it
+
+                self.action_queue.push_back(action);
+
+                // the cells reserved by unreconciled requests cannot be used
+                if self.affordable_energy_cells() > 0 {
+                    if let Some(resource) = self.decide_resource_action() {
+                        match resource {
+                            ResourceType::Basic(basic_resource) => match basic_resource {
+                                BasicResourceType::Oxygen => {
+                                    generate_resource_request(
+                                        self,
+                                        BasicResourceType::Oxygen,
+                                        false,
+                                    );
+                                }
+                                BasicResourceType::Hydrogen => {
+                                    generate_resource_request(
+                                        self,
+                                        BasicResourceType::Hydrogen,
+                                        false,
+                                    );
+                                }
+                                BasicResourceType::Carbon => {
+                                    generate_resource_request(
+                                        self,
+                                        BasicResourceType::Carbon,
+                                        false,
+                                    );
+                                }
+                                BasicResourceType::Silicon => {
+                                    generate_resource_request(
+                                        self,
+                                        BasicResourceType::Silicon,
+                                        false,
+                                    );
+                                }
+                            },
+                            ResourceType::Complex(complex_resource) => match complex_resource {
+                                ComplexResourceType::Diamond => {
+                                    combine_resource_request(
+                                        self,
+                                        ComplexResourceType::Diamond,
+                                        false,
+                                    );
+                                }
+                                ComplexResourceType::Water => {
+                                    combine_resource_request(
+                                        self,
+                                        ComplexResourceType::Water,
+                                        false,
+                                    );
+                                }
+                                ComplexResourceType::Life => {
+                                    combine_resource_request(
+                                        self,
+                                        ComplexResourceType::Life,
+                                        false,
+                                    );
+                                }
+                                ComplexResourceType::Robot => {
+                                    combine_resource_request(
+                                        self,
+                                        ComplexResourceType::Robot,
+                                        false,
+                                    );
+                                }
+                                ComplexResourceType::Dolphin => {
+                                    combine_resource_request(
+                                        self,
+                                        ComplexResourceType::Dolphin,
+                                        false,
+                                    );
+                                }
+                                ComplexResourceType::AIPartner => {
+                                    combine_resource_request(
+                                        self,
+                                        ComplexResourceType::AIPartner,
+                                        false,
+                                    );
+                                }
+                            },
                         }
                     }
                 }
-                ExplorerAction::Move => {
-                    // 1st case -> the topology isn't fully discovered yet
-                    // check the planets that still need to be visited
-                    // choose the best path to visit those planets in the shortest way possible
-
-                    // 2nd case -> the topology is fully discovered
-                    // check what and how many resources the explorer has
-                    // maybe check what resources can be obtained from other planets in a possible path
-                    // choose the best path to achieve the goal
-
-                    self.action_queue.push_back(action);
-
-                    // obtain the needed resource
-                    let resource = self.get_production_priority();
-                    if let Some(path) = self.topology.find_path_to_nearest_frontier(self.planet_id)
-                    {
-                        // if the topology isn't fully discovered yet, continue exploring
-                        self.move_queue.push_path(path)
-                    } else if let Some(path) = self
-                        .topology
-                        .find_path_to_resource(self.planet_id, resource)
-                    {
-                        // else find the best path to reach the resource goal
-                        self.move_queue.push_path(path)
-                    } else {
-                        self.accept_death = true;
-                    }
-
-                    let mut next_planet = self.move_queue.next_move();
-
-                    if next_planet == Some(self.planet_id) {
-                        next_planet = self.move_queue.next_move();
-                    }
+            }
+            ExplorerAction::Move => {
+                // 1st case -> the topology isn't fully discovered yet
+                // check the planets that still need to be visited
+                // choose the best path to visit those planets in the shortest way possible
+
+                // 2nd case -> the topology is fully discovered
+                // check what and how many resources the explorer has
+                // maybe check what resources can be obtained from other planets in a possible path
+                // choose the best path to achieve the goal
+
+                self.action_queue.push_back(action);
+
+                // obtain the needed resource
+                let resource = self.get_production_priority();
+                if let Some(path) = self.next_move_path(resource) {
+                    // explore or reach the resource goal, as the strategy says
+                    self.move_queue.push_path(path)
+                } else if !self.goal_reachable() {
+                    // nothing left to discover can provide the goal
+                    self.accept_death = true;
+                    self.report_no_progress(format!(
+                        "topology fully explored and goal {:?} unreachable, needs {:?}",
+                        GOAL, resource
+                    ));
+                } else {
+                    self.accept_death = true;
+                    self.report_no_progress(format!(
+                        "topology fully explored and no known planet provides {:?}",
+                        resource
+                    ));
+                }
 
-                    // Wander instinct UNIVERSAL
-                    if next_planet.is_none() {
-                        let can_craft_here = self.decide_resource_action().is_some();
+                let mut next_planet = self.move_queue.next_move();
 
-                        let stuck_no_energy = can_craft_here && self.energy_cells == 0;
+                if next_planet == Some(self.planet_id) {
+                    next_planet = self.move_queue.next_move();
+                }
 
-                        let stuck_no_path = !can_craft_here;
+                // Wander instinct UNIVERSAL
+                if next_planet.is_none() {
+                    let craft_here = self.decide_resource_action();
+                    let can_craft_here = craft_here.is_some();
+
+                    // without charged cells, wait for a sunray before leaving the planet,
+                    // and keep waiting if no planet nearby provides the resource
+                    let stuck_no_energy = craft_here.is_some_and(|resource| {
+                        self.energy_cells == 0
+                            && !self.wait_for_recharge()
+                            && self.provided_nearby(resource)
+                    });
+
+                    let stuck_no_path = !can_craft_here;
+
+                    if stuck_no_energy || stuck_no_path {
+                        next_planet = self
+                            .topology
+                            .neighbour_ids_sorted(self.planet_id)
+                            .first()
+                            .copied();
+                    }
+                }
 
-                        if stuck_no_energy || stuck_no_path {
-                            if let Some(info) = self.topology.get(self.planet_id) {
-                                if let Some(neighbours) = info.get_neighbours() {
-                                    next_planet = neighbours.iter().next().copied();
-                                }
+                // if the explorer has to move somewhere send a TravelToPlanetRequest
+                if let Some(target_planet) = next_planet {
+                    if self.topology.contains(target_planet) {
+                        match self.send_to_orchestrator(
+                            ExplorerToOrchestrator::TravelToPlanetRequest {
+                                explorer_id: self.explorer_id,
+                                current_planet_id: self.planet_id,
+                                dst_planet_id: target_planet,
+                            },
+                        ) {
+                            Ok(_) => {
+                                self.set_state(ExplorerState::Traveling);
+
+                                log_message!(
+                                    ActorType::Explorer,
+                                    self.explorer_id,
+                                    ActorType::Orchestrator,
+                                    0u32,
+                                    EventType::MessageExplorerToOrchestrator,
+                                    "travel to planet request sent";
+                                    "target_planet" => target_planet.to_string()
+                                );
                             }
-                        }
-                    }
+                            Err(err) => {
+                                self.move_queue.clear();
 
-                    // if the explorer has to move somewhere send a TravelToPlanetRequest
-                    if let Some(target_planet) = next_planet {
-                        if self.topology.contains(target_planet) {
-                            match self.send_to_orchestrator(
-                                ExplorerToOrchestrator::TravelToPlanetRequest {
-                                    explorer_id: self.explorer_id,
-                                    current_planet_id: self.planet_id,
-                                    dst_planet_id: target_planet,
-                                },
-                            ) {
-                                Ok(_) => {
-                                    self.set_state(ExplorerState::Traveling);
-
-                                    log_message!(
+                                LogEvent::new(
+                                    Some(Participant::new(
                                         ActorType::Explorer,
                                         self.explorer_id,
-                                        ActorType::Orchestrator,
-                                        0u32,
-                                        EventType::MessageExplorerToOrchestrator,
-                                        "travel to planet request sent";
-                                        "target_planet" => target_planet.to_string()
-                                    );
-                                }
-                                Err(err) => {
-                                    self.move_queue.clear();
-
-                                    LogEvent::new(
-                                        Some(Participant::new(
-                                            ActorType::Explorer,
-                                            self.explorer_id,
-                                        )),
-                                        Some(Participant::new(ActorType::Orchestrator, 0u32)),
-                                        EventType::MessageExplorerToOrchestrator,
-                                        Channel::Error,
-                                        warning_payload!(
-                                            "TravelToPlanetRequest not sent",
-                                            err.to_string(),
-                                            "execute_ai_action()";
-                                            "target_planet" => target_planet.to_string(),
-                                            "explorer data" => format!("{:?}", self)
-                                        ),
-                                    )
-                                    .emit();
-                                }
+                                    )),
+                                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
+                                    EventType::MessageExplorerToOrchestrator,
+                                    Channel::Error,
+                                    warning_payload!(
+                                        "TravelToPlanetRequest not sent",
+                                        err.to_string(),
+                                        "execute_ai_action()";
+                                        "target_planet" => target_planet.to_string(),
+                                        "explorer data" => format!("{:?}", self)
+                                    ),
+                                )
+                                .emit();
                             }
-                        } else {
-                            self.move_queue.clear();
                         }
+                    } else {
+                        self.move_queue.clear();
                     }
                 }
             }
@@ -766,6 +1172,13 @@ impl fmt::Debug for Explorer {
             .field("state", &self.state)
             .field("bag", &self.bag)
             .field("manual_mode", &self.manual_mode)
+            .field("auto_survey_on_arrival", &self.auto_survey_on_arrival)
+            .field("recharge_wait", &self.recharge_wait)
+            .field("recharge_wait_left", &self.recharge_wait_left)
+            .field("energy_reservations", &self.energy_reservations)
+            .field("no_progress_reason", &self.no_progress_reason)
+            .field("idle_shutdown_after", &self.idle_shutdown_after)
+            .field("idle_ticks", &self.idle_ticks)
             .field(
                 "buffer_orchestrator_len",
                 &self.buffer_orchestrator_msg.len(),
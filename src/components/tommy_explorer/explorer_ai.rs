@@ -1,9 +1,45 @@
 use crate::components::tommy_explorer::Explorer;
-use crate::components::tommy_explorer::topology::TopologyManager;
+use crate::components::tommy_explorer::topology::{PlanetKnowledge, TopologyManager};
+use crate::utils::recipes;
+use crate::utils::resource_names::{BASIC_RESOURCES, COMPLEX_RESOURCES};
 use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+use common_game::utils::ID;
 use std::collections::{HashMap, HashSet, VecDeque};
 
-impl TopologyManager {
+/// The resource the explorer AI works towards.
+pub const GOAL: ComplexResourceType = ComplexResourceType::AIPartner;
+
+/// Maximum distance, in hops, of the planet the explorer moves to instead of waiting
+/// for the current one to recharge.
+pub const RECHARGE_DETOUR_HOPS: u32 = 2;
+
+/// Resources obtainable around a planet, each with the planets providing it and their
+/// distance in hops, see [`TopologyManager::reachable_resources`].
+pub type ReachableResources = Vec<(ResourceType, Vec<(ID, u32)>)>;
+
+/// How the explorer AI chooses where to move, see [`Explorer::set_strategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Surveys every reachable planet before going after the resources of the goal.
+    #[default]
+    FullExplore,
+    /// Goes after the resources as soon as the goal can be obtained from the known
+    /// planets, and explores only until then.
+    GoalDirected,
+    /// Goes to the nearest planet that is either unexplored or provides the needed
+    /// resource, preferring the latter at the same distance.
+    Greedy,
+}
+
+impl<P: PlanetKnowledge> TopologyManager<P> {
+    /// Checks if a planet is still worth a visit to survey it.
+    ///
+    /// A planet is a frontier if it is not in the topology yet or if any of its
+    /// resources or neighbours are still unknown.
+    pub fn is_frontier(&self, planet_id: u32) -> bool {
+        self.get(planet_id).is_none_or(|info| !info.is_complete())
+    }
+
     /// Finds the shortest path to the nearest unexplored or partially explored planet.
     ///
     /// This method leverages a lazy Breadth-First Search (BFS) iterator to scan the
@@ -15,15 +51,10 @@ impl TopologyManager {
         // Initialize the custom BFS iterator starting from the current node
         let mut bfs = self.bfs_iter(start_node);
 
-        // Lazily evaluate each node to find the first one that matches the frontier criteria
-        let target = bfs.find(|&node| {
-            match self.get(node) {
-                // Node is not in the topology yet (it's a newly discovered neighbor)
-                None => true,
-                // Node is known, but we haven't queried all its resources or neighbors yet
-                Some(info) => !info.is_complete(),
-            }
-        })?; // Early return None if the iterator is exhausted without finding a match
+        // Lazily evaluate each node to find the first one that matches the frontier criteria:
+        // the BFS only goes past complete planets, so branches made only of complete
+        // planets are exhausted without ever being chosen as a target
+        let target = bfs.find(|&node| self.is_frontier(node))?; // Early return None if the iterator is exhausted without finding a match
 
         // If the target is found, ask the iterator to reconstruct the route via the parent map
         Some(bfs.reconstruct_path(target))
@@ -43,28 +74,61 @@ impl TopologyManager {
         // Initialize the BFS iterator to explore the topology outward from the current position
         let mut bfs = self.bfs_iter(start_node);
 
-        // Find the first node that contains the specific resource we need
-        let target = bfs.find(|&node| {
-            if let Some(info) = self.get(node) {
-                match target_res {
-                    // Check if the planet can provide the required basic resource
-                    ResourceType::Basic(b) => {
-                        info.get_basic_resources().map_or(false, |s| s.contains(&b))
-                    }
-                    // Check if the planet's laboratories support the required complex combination
-                    ResourceType::Complex(c) => info
-                        .get_complex_resources()
-                        .map_or(false, |s| s.contains(&c)),
-                }
-            } else {
-                // If we have no info on the node, we safely skip it
-                false
-            }
-        })?;
+        // Find the first node that contains the specific resource we need,
+        // the nodes we have no info on are safely skipped
+        let target = bfs.find(|&node| self.provides(node, target_res))?;
 
         // Reconstruct and return the shortest path to the successful node
         Some(bfs.reconstruct_path(target))
     }
+
+    /// Checks if a known planet can generate or combine the given resource.
+    pub fn provides(&self, planet_id: u32, resource: ResourceType) -> bool {
+        self.get(planet_id).is_some_and(|info| match resource {
+            ResourceType::Basic(b) => info.get_basic_resources().is_some_and(|s| s.contains(&b)),
+            ResourceType::Complex(c) => {
+                info.get_complex_resources().is_some_and(|s| s.contains(&c))
+            }
+        })
+    }
+
+    /// Lists the resources that the planets within `max_hops` of `from` can provide.
+    ///
+    /// Each resource comes with the planets providing it and their distance, sorted by
+    /// distance then id; `from` itself is at distance 0. The resources are in
+    /// declaration order, basic ones first, and the ones nobody provides are left out.
+    /// Only the running planets are listed, see [`is_running`](Self::is_running); the
+    /// stopped ones are still crossed to reach the others.
+    pub fn reachable_resources(&self, from: u32, max_hops: u32) -> ReachableResources {
+        // the BFS returns the planets by increasing distance
+        let mut bfs = self.bfs_iter(from);
+        let mut within = Vec::new();
+        while let Some(planet_id) = bfs.next() {
+            let hops = bfs.reconstruct_path(planet_id).len() as u32;
+            if hops > max_hops {
+                break;
+            }
+            within.push((planet_id, hops));
+        }
+        within.sort_by_key(|(planet_id, hops)| (*hops, *planet_id));
+
+        let resources = BASIC_RESOURCES
+            .into_iter()
+            .map(ResourceType::Basic)
+            .chain(COMPLEX_RESOURCES.into_iter().map(ResourceType::Complex));
+        resources
+            .filter_map(|resource| {
+                let providers: Vec<(ID, u32)> = within
+                    .iter()
+                    .filter(|(planet_id, _)| {
+                        self.is_running(*planet_id) && self.provides(*planet_id, resource)
+                    })
+                    .copied()
+                    .collect();
+                (!providers.is_empty()).then_some((resource, providers))
+            })
+            .collect()
+    }
 }
 
 /// Trait to define crafting dependencies
@@ -78,27 +142,11 @@ pub trait RecipeExt {
 
 impl RecipeExt for ComplexResourceType {
     fn ingredients(&self) -> Vec<(ResourceType, usize)> {
-        match self {
-            ComplexResourceType::Water => vec![
-                (ResourceType::Basic(BasicResourceType::Hydrogen), 1),
-                (ResourceType::Basic(BasicResourceType::Oxygen), 1),
-            ],
-            ComplexResourceType::Life => vec![
-                (ResourceType::Complex(ComplexResourceType::Water), 1),
-                (ResourceType::Basic(BasicResourceType::Carbon), 1),
-            ],
-            ComplexResourceType::Diamond => {
-                vec![(ResourceType::Basic(BasicResourceType::Carbon), 2)]
-            }
-            ComplexResourceType::Robot => vec![
-                (ResourceType::Basic(BasicResourceType::Silicon), 1),
-                (ResourceType::Complex(ComplexResourceType::Life), 1),
-            ],
-            ComplexResourceType::AIPartner => vec![
-                (ResourceType::Complex(ComplexResourceType::Robot), 1),
-                (ResourceType::Complex(ComplexResourceType::Diamond), 1),
-            ],
-            _ => vec![],
+        let [a, b] = recipes::ingredients(*self);
+        if a == b {
+            vec![(a, 2)]
+        } else {
+            vec![(a, 1), (b, 1)]
         }
     }
 
@@ -115,6 +163,66 @@ impl RecipeExt for ComplexResourceType {
 }
 
 impl Explorer {
+    /// Checks if the goal can still be obtained from the bag and the planets reachable
+    /// from the current one.
+    ///
+    /// Planets whose resources are unknown count as providing nothing, so the answer
+    /// is final only once the reachable topology is fully discovered.
+    pub fn goal_reachable(&self) -> bool {
+        let mut basic = HashSet::new();
+        let mut complex = HashSet::new();
+        for planet_id in self.topology.bfs_iter(self.planet_id) {
+            if let Some(info) = self.topology.get(planet_id) {
+                basic.extend(info.get_basic_resources().into_iter().flatten().copied());
+                complex.extend(info.get_complex_resources().into_iter().flatten().copied());
+            }
+        }
+        let bag = self.bag.to_resource_types();
+        can_obtain(ResourceType::Complex(GOAL), 1, &bag, &basic, &complex)
+    }
+
+    /// Returns the path of the next move of the AI towards the planets to survey or the
+    /// ones providing `resource`, as chosen by the strategy of the explorer.
+    ///
+    /// Returns None if the strategy finds no planet worth a visit.
+    pub fn next_move_path(&self, resource: ResourceType) -> Option<VecDeque<u32>> {
+        let frontier = || self.topology.find_path_to_nearest_frontier(self.planet_id);
+        let to_resource = || {
+            self.topology
+                .find_path_to_resource(self.planet_id, resource)
+        };
+        match self.strategy {
+            Strategy::FullExplore => {
+                frontier().or_else(|| self.goal_reachable().then(to_resource).flatten())
+            }
+            Strategy::GoalDirected => {
+                if self.goal_reachable() {
+                    to_resource().or_else(frontier)
+                } else {
+                    frontier()
+                }
+            }
+            Strategy::Greedy => match (to_resource(), frontier()) {
+                (Some(resource_path), Some(frontier_path))
+                    if frontier_path.len() < resource_path.len() =>
+                {
+                    Some(frontier_path)
+                }
+                (resource_path, frontier_path) => resource_path.or(frontier_path),
+            },
+        }
+    }
+
+    /// Checks if a planet other than the current one, within [`RECHARGE_DETOUR_HOPS`],
+    /// provides the given resource, making it worth leaving instead of waiting.
+    pub fn provided_nearby(&self, resource: ResourceType) -> bool {
+        self.topology
+            .reachable_resources(self.planet_id, RECHARGE_DETOUR_HOPS)
+            .into_iter()
+            .find(|(provided, _)| *provided == resource)
+            .is_some_and(|(_, providers)| providers.iter().any(|(id, _)| *id != self.planet_id))
+    }
+
     /// Returns the absolute priority resource to craft
     pub fn get_production_priority(&self) -> ResourceType {
         let bag = self.bag.to_resource_types();
@@ -271,14 +379,59 @@ impl Explorer {
         res
     }
 
+    /// Returns the charged cells needed to obtain the goal from the current bag.
+    pub fn plan_energy_cost(&self) -> u32 {
+        let mut bag = self.bag.to_resource_types();
+        energy_cost(ResourceType::Complex(GOAL), &mut bag)
+    }
+
+    /// Checks if the charged cells of the current planet are enough to obtain the goal.
+    pub fn plan_fits_energy(&self) -> bool {
+        self.plan_energy_cost() <= self.energy_cells
+    }
+
+    /// Returns the cheapest resource on the way to the goal that the current planet can
+    /// make with its charged cells, ties broken by declaration order.
+    ///
+    /// Resources already in the bag are skipped, and so is the goal itself.
+    fn affordable_intermediate(&self) -> Option<ComplexResourceType> {
+        let info = self.topology.get(self.planet_id)?;
+        let basic = info.get_basic_resources().cloned().unwrap_or_default();
+        let complex = info.get_complex_resources().cloned().unwrap_or_default();
+        let bag = self.bag.to_resource_types();
+        let on_the_way = goal_intermediates();
+        COMPLEX_RESOURCES
+            .into_iter()
+            .filter(|c| on_the_way.contains(c))
+            .map(ResourceType::Complex)
+            .filter(|res| !bag.contains(res))
+            .filter(|res| can_obtain(*res, 1, &bag, &basic, &complex))
+            .map(|res| (res, energy_cost(res, &mut bag.clone())))
+            .filter(|(_, cost)| *cost <= self.energy_cells)
+            .min_by_key(|(_, cost)| *cost)
+            .and_then(|(res, _)| match res {
+                ResourceType::Complex(c) => Some(c),
+                ResourceType::Basic(_) => None,
+            })
+    }
+
     /// Returns the resource to generate/combine based on the needs and the availability of the planet,
     /// or None if no resource can be crafted.
+    ///
+    /// When the charged cells are not enough for the whole goal, the explorer works towards
+    /// the cheapest intermediate it can afford instead, see [`Explorer::plan_fits_energy`].
     pub fn decide_resource_action(&self) -> Option<ResourceType> {
         let current_planet_info = self.topology.get(self.planet_id)?;
         let needed = self.resources_needed();
 
         let bag_items = self.bag.to_resource_types();
 
+        if !self.plan_fits_energy()
+            && let Some(target) = self.affordable_intermediate()
+        {
+            return Some(next_step(ResourceType::Complex(target), &bag_items));
+        }
+
         let craft_order = [
             ComplexResourceType::AIPartner,
             ComplexResourceType::Robot,
@@ -316,3 +469,76 @@ impl Explorer {
             })
     }
 }
+
+/// Returns the charged cells needed to obtain `resource`: every generation and every
+/// combination uses one. The resources found in `bag` cost nothing and are taken out of it.
+fn energy_cost(resource: ResourceType, bag: &mut Vec<ResourceType>) -> u32 {
+    if let Some(pos) = bag.iter().position(|r| *r == resource) {
+        bag.swap_remove(pos);
+        return 0;
+    }
+    match resource {
+        ResourceType::Basic(_) => 1,
+        ResourceType::Complex(c) => {
+            let ingredients: u32 = c
+                .ingredients()
+                .into_iter()
+                .map(|(ingredient, qty)| {
+                    (0..qty).map(|_| energy_cost(ingredient, bag)).sum::<u32>()
+                })
+                .sum();
+            1 + ingredients
+        }
+    }
+}
+
+/// Returns the complex resources needed, directly or not, to combine the goal.
+fn goal_intermediates() -> HashSet<ComplexResourceType> {
+    let mut found = HashSet::new();
+    let mut to_visit = vec![GOAL];
+    while let Some(complex) = to_visit.pop() {
+        for (ingredient, _) in complex.ingredients() {
+            if let ResourceType::Complex(c) = ingredient
+                && found.insert(c)
+            {
+                to_visit.push(c);
+            }
+        }
+    }
+    found
+}
+
+/// Returns the first resource to generate or combine to obtain `resource` from `bag`.
+fn next_step(resource: ResourceType, bag: &[ResourceType]) -> ResourceType {
+    let ResourceType::Complex(c) = resource else {
+        return resource;
+    };
+    c.ingredients()
+        .into_iter()
+        .find(|(ingredient, qty)| bag.iter().filter(|r| *r == ingredient).count() < *qty)
+        .map_or(resource, |(ingredient, _)| next_step(ingredient, bag))
+}
+
+/// Checks if `amount` units of a resource are in the bag or can be made with the
+/// given generation and combination rules.
+fn can_obtain(
+    resource: ResourceType,
+    amount: usize,
+    bag: &[ResourceType],
+    basic: &HashSet<BasicResourceType>,
+    complex: &HashSet<ComplexResourceType>,
+) -> bool {
+    if bag.iter().filter(|r| **r == resource).count() >= amount {
+        return true;
+    }
+    match resource {
+        ResourceType::Basic(b) => basic.contains(&b),
+        // the recipes have no cycles, so the recursion ends on the basic resources
+        ResourceType::Complex(c) => {
+            complex.contains(&c)
+                && c.ingredients()
+                    .into_iter()
+                    .all(|(ingredient, qty)| can_obtain(ingredient, qty, bag, basic, complex))
+        }
+    }
+}
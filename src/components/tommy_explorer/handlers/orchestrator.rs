@@ -3,6 +3,7 @@ use std::collections::HashSet;
 
 use super::planet;
 use crate::components::tommy_explorer::{Explorer, ExplorerState};
+use crate::utils::request_ledger::PlanetRequestKind;
 use common_game::components::resource::{BasicResourceType, ComplexResourceType};
 use common_game::logging::{ActorType, Channel, EventType, LogEvent, Participant};
 use common_game::protocols::orchestrator_explorer::{
@@ -146,7 +147,7 @@ fn start_explorer_ai(explorer: &mut Explorer) -> Result<(), String> {
     Ok(())
 }
 
-/// Resets the topology known by the explorer.
+/// Resets the topology known by the explorer and empties its bag.
 fn reset_explorer_ai(explorer: &mut Explorer) {
     match explorer.send_to_orchestrator(ExplorerToOrchestrator::ResetExplorerAIResult {
         explorer_id: explorer.id(),
@@ -154,6 +155,7 @@ fn reset_explorer_ai(explorer: &mut Explorer) {
         Ok(_) => {
             explorer.manual_mode_off();
             explorer.clear_topology();
+            explorer.bag.clear();
             explorer.set_state(ExplorerState::Idle);
             log_message!(
                 ActorType::Orchestrator,
@@ -262,12 +264,16 @@ fn move_to_planet(
     explorer.set_state(ExplorerState::Idle);
     match sender_to_new_planet {
         Some(sender) => {
+            // the first actions on a fresh planet are AskNeighbours and the full survey
+            let survey = explorer.arrival_survey(planet_id);
             explorer.action_queue.clear();
-            explorer.action_queue.reset();
+            explorer.action_queue.reset_for_arrival(survey);
             explorer.move_queue.clear();
 
             explorer.set_planet_sender(sender);
             explorer.set_planet_id(planet_id);
+            explorer.reset_recharge_wait();
+            explorer.get_or_create_planet_info_mut(planet_id);
 
             let _ = explorer.send_to_orchestrator(ExplorerToOrchestrator::MovedToPlanetResult {
                 explorer_id: explorer.id(),
@@ -306,6 +312,7 @@ fn move_to_planet(
             explorer.move_queue.clear();
 
             explorer.topology.mark_as_dead(planet_id);
+            explorer.planet_requests.forget_planet(planet_id);
         }
     }
 }
@@ -358,15 +365,20 @@ fn supported_resource_request(explorer: &mut Explorer) {
         match explorer.send_to_planet(ExplorerToPlanet::SupportedResourceRequest {
             explorer_id: explorer.id(),
         }) {
-            Ok(_) => log_message!(
-                ActorType::Explorer,
-                explorer.explorer_id,
-                ActorType::Planet,
-                explorer.planet_id,
-                EventType::MessageExplorerToPlanet,
-                "supported resource request";
-                "planet_id"=>explorer.planet_id.to_string()
-            ),
+            Ok(_) => {
+                explorer
+                    .planet_requests
+                    .sent(PlanetRequestKind::SupportedResources, explorer.planet_id());
+                log_message!(
+                    ActorType::Explorer,
+                    explorer.explorer_id,
+                    ActorType::Planet,
+                    explorer.planet_id,
+                    EventType::MessageExplorerToPlanet,
+                    "supported resource request";
+                    "planet_id"=>explorer.planet_id.to_string()
+                )
+            }
             Err(err) => {
                 LogEvent::new(
                     Some(Participant::new(ActorType::Explorer, explorer.explorer_id)),
@@ -385,12 +397,55 @@ fn supported_resource_request(explorer: &mut Explorer) {
             }
         }
 
-        // waits for the response
-        match explorer.receive_from_planet() {
+        // waits for the response, the late answers of the planets the explorer left are
+        // applied to their planet
+        let mut late_answers = Vec::new();
+        let received = loop {
+            match explorer.receive_from_planet_timeout() {
+                Some(Ok(PlanetToExplorer::SupportedResourceResponse { resource_list })) => {
+                    let origin =
+                        planet::response_origin(explorer, PlanetRequestKind::SupportedResources);
+                    if origin == explorer.planet_id() {
+                        break Ok(PlanetToExplorer::SupportedResourceResponse { resource_list });
+                    }
+                    late_answers.push((origin, resource_list));
+                }
+                Some(other) => break other.map_err(|err| err.to_string()),
+                None => {
+                    // the planet the last answer was matched to never answered, so the
+                    // answer was the one of the current planet
+                    match late_answers.pop() {
+                        Some((_, resource_list)) => {
+                            explorer.planet_requests.withdraw(
+                                PlanetRequestKind::SupportedResources,
+                                explorer.planet_id(),
+                            );
+                            break Ok(PlanetToExplorer::SupportedResourceResponse {
+                                resource_list,
+                            });
+                        }
+                        None => {
+                            break Err(no_response(
+                                explorer,
+                                PlanetRequestKind::SupportedResources,
+                            ));
+                        }
+                    }
+                }
+            }
+        };
+        for (origin, resource_list) in late_answers {
+            planet::update_basic_resources(explorer, origin, resource_list);
+        }
+        match received {
             Ok(PlanetToExplorer::SupportedResourceResponse { resource_list }) => {
                 supported_resources = resource_list;
             }
-            Ok(_) => {
+            Ok(msg) => {
+                if let PlanetToExplorer::Stopped = msg {
+                    // a stopped planet rejects every request
+                    explorer.planet_requests.forget_planet(explorer.planet_id());
+                }
                 supported_resources.clear();
                 log_message!(
                     ActorType::Planet,
@@ -459,15 +514,21 @@ fn supported_combination_request(explorer: &mut Explorer) {
         match explorer.send_to_planet(ExplorerToPlanet::SupportedCombinationRequest {
             explorer_id: explorer.id(),
         }) {
-            Ok(_) => log_message!(
-                ActorType::Explorer,
-                explorer.explorer_id,
-                ActorType::Planet,
-                explorer.planet_id,
-                EventType::MessageExplorerToPlanet,
-                "supported combination request";
-                "planet_id"=>explorer.planet_id.to_string()
-            ),
+            Ok(_) => {
+                explorer.planet_requests.sent(
+                    PlanetRequestKind::SupportedCombinations,
+                    explorer.planet_id(),
+                );
+                log_message!(
+                    ActorType::Explorer,
+                    explorer.explorer_id,
+                    ActorType::Planet,
+                    explorer.planet_id,
+                    EventType::MessageExplorerToPlanet,
+                    "supported combination request";
+                    "planet_id"=>explorer.planet_id.to_string()
+                )
+            }
             Err(err) => {
                 LogEvent::new(
                     Some(Participant::new(ActorType::Explorer, explorer.explorer_id)),
@@ -486,12 +547,57 @@ fn supported_combination_request(explorer: &mut Explorer) {
             }
         }
 
-        // waits for the response
-        match explorer.receive_from_planet() {
+        // waits for the response, the late answers of the planets the explorer left are
+        // applied to their planet
+        let mut late_answers = Vec::new();
+        let received = loop {
+            match explorer.receive_from_planet_timeout() {
+                Some(Ok(PlanetToExplorer::SupportedCombinationResponse { combination_list })) => {
+                    let origin =
+                        planet::response_origin(explorer, PlanetRequestKind::SupportedCombinations);
+                    if origin == explorer.planet_id() {
+                        break Ok(PlanetToExplorer::SupportedCombinationResponse {
+                            combination_list,
+                        });
+                    }
+                    late_answers.push((origin, combination_list));
+                }
+                Some(other) => break other.map_err(|err| err.to_string()),
+                None => {
+                    // the planet the last answer was matched to never answered, so the
+                    // answer was the one of the current planet
+                    match late_answers.pop() {
+                        Some((_, combination_list)) => {
+                            explorer.planet_requests.withdraw(
+                                PlanetRequestKind::SupportedCombinations,
+                                explorer.planet_id(),
+                            );
+                            break Ok(PlanetToExplorer::SupportedCombinationResponse {
+                                combination_list,
+                            });
+                        }
+                        None => {
+                            break Err(no_response(
+                                explorer,
+                                PlanetRequestKind::SupportedCombinations,
+                            ));
+                        }
+                    }
+                }
+            }
+        };
+        for (origin, combination_list) in late_answers {
+            planet::update_complex_resources(explorer, origin, combination_list);
+        }
+        match received {
             Ok(PlanetToExplorer::SupportedCombinationResponse { combination_list }) => {
                 supported_combinations = combination_list;
             }
-            Ok(_) => {
+            Ok(msg) => {
+                if let PlanetToExplorer::Stopped = msg {
+                    // a stopped planet rejects every request
+                    explorer.planet_requests.forget_planet(explorer.planet_id());
+                }
                 supported_combinations.clear();
                 log_message!(
                     ActorType::Planet,
@@ -536,6 +642,15 @@ fn supported_combination_request(explorer: &mut Explorer) {
     );
 }
 
+/// Gives up waiting for the answer of the current planet, its request is forgotten so
+/// that it does not take the answers of the other planets.
+fn no_response(explorer: &mut Explorer, kind: PlanetRequestKind) -> String {
+    explorer
+        .planet_requests
+        .withdraw(kind, explorer.planet_id());
+    format!("planet {} did not answer in time", explorer.planet_id())
+}
+
 /// Sends the GenerateResourceRequest, waits for the planet response, and if successful puts the resource in the bag.
 pub fn generate_resource_request(explorer: &mut Explorer, to_generate: BasicResourceType, is_from_orchestrator: bool) {
     log_message!(
@@ -555,6 +670,7 @@ pub fn generate_resource_request(explorer: &mut Explorer, to_generate: BasicReso
     }) {
         Ok(_) => {
             explorer.set_state(ExplorerState::GeneratingResource);
+            explorer.reserve_energy_cell();
 
             log_message!(
                 ActorType::Explorer,
@@ -614,7 +730,12 @@ pub fn generate_resource_request(explorer: &mut Explorer, to_generate: BasicReso
                 "planet_id"=>explorer.planet_id.to_string()
             );
         }
+        Ok(PlanetToExplorer::Stopped) => {
+            // a stopped planet rejects the request without using the cell
+            explorer.release_energy_cell();
+        }
         Ok(_) => {
+            // the cell stays reserved until the planet sends its energy cells again
             debug_println!("Explorer received an unexpected message from planet")
         }
         Err(err) => {
@@ -690,6 +811,7 @@ pub fn combine_resource_request(explorer: &mut Explorer, to_generate: ComplexRes
             }) {
                 Ok(_) => {
                     explorer.set_state(ExplorerState::CombiningResources);
+                    explorer.reserve_energy_cell();
 
                     log_message!(
                         ActorType::Explorer,
@@ -735,13 +857,20 @@ pub fn combine_resource_request(explorer: &mut Explorer, to_generate: ComplexRes
                 }
             }
 
-            match explorer.receive_from_planet() {
+            let combined = match explorer.receive_from_planet() {
                 Ok(PlanetToExplorer::CombineResourceResponse { complex_response }) => {
-                    planet::put_complex_resource_in_bag(explorer, complex_response);
+                    planet::put_complex_resource_in_bag(explorer, complex_response)
+                }
+                Ok(PlanetToExplorer::Stopped) => {
+                    // a stopped planet rejects the request without using the cell
+                    explorer.release_energy_cell();
+                    Ok(())
                 }
                 Ok(_) => {
-                    // should not happen
+                    // should not happen, the cell stays reserved until the planet sends
+                    // its energy cells again
                     debug_println!("Explorer received an unexpected message from planet");
+                    Ok(())
                 }
                 Err(err) => {
                     LogEvent::new(
@@ -760,14 +889,15 @@ pub fn combine_resource_request(explorer: &mut Explorer, to_generate: ComplexRes
                         .emit();
                     return;
                 }
-            }
+            };
 
             if is_from_orchestrator {
+                // the orchestrator gets the planet's reason if the combination failed
                 send_to_orchestrator_and_log!(
                     explorer,
                     ExplorerToOrchestrator::CombineResourceResponse {
                         explorer_id: explorer.explorer_id,
-                        generated: Ok(()),
+                        generated: combined,
                     },
                     Some(ExplorerState::Idle),
                     "CombineResourceResponse was not sent",
@@ -822,12 +952,20 @@ fn bag_content_request(explorer: &mut Explorer) {
 
 /// Updates the neighbours of the current planet.
 fn neighbors_response(explorer: &mut Explorer, neighbors: Vec<u32>) {
-    explorer.set_state(ExplorerState::Idle);
-    explorer.update_neighbors(explorer.planet_id(), neighbors.clone());
+    // the response describes the planet the request was about, which is not the
+    // current one if the explorer moved before receiving it
+    let planet_id = explorer
+        .planet_requests
+        .answered(PlanetRequestKind::Neighbours)
+        .unwrap_or(explorer.planet_id());
+    if planet_id == explorer.planet_id() {
+        explorer.set_state(ExplorerState::Idle);
+    }
+    explorer.update_neighbors(planet_id, neighbors.clone());
 
     log_message!(
         ActorType::Planet,
-        explorer.planet_id,
+        planet_id,
         ActorType::Explorer,
         explorer.explorer_id,
         EventType::MessagePlanetToExplorer,
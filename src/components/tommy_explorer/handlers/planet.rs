@@ -1,22 +1,36 @@
 use crate::components::tommy_explorer::{Explorer, ExplorerState};
 
 use crate::components::tommy_explorer::bag::IntoGenericResource;
+use crate::utils::request_ledger::PlanetRequestKind;
 use common_game::components::resource::{BasicResource, ComplexResource, GenericResource};
 use common_game::logging::{ActorType, Channel, EventType, LogEvent, Participant};
 use common_game::protocols::planet_explorer::PlanetToExplorer;
-use logging_utils::{log_message, warning_payload};
+use common_game::utils::ID;
+use logging_utils::{log_internal_op, log_message, warning_payload};
 
 /// Handles all messages from the planet.
 pub fn handle_message(explorer: &mut Explorer, msg: PlanetToExplorer) -> Result<(), String> {
+    // a stopped planet answers Stopped to every request, any other answer means it runs
+    if matches!(msg, PlanetToExplorer::Stopped) {
+        explorer.topology.mark_as_stopped(explorer.planet_id());
+    } else {
+        explorer.topology.mark_as_running(explorer.planet_id());
+    }
     match msg {
         PlanetToExplorer::SupportedResourceResponse { resource_list } => {
-            update_basic_resources(explorer, resource_list);
-            explorer.set_state(ExplorerState::Idle);
+            let origin = response_origin(explorer, PlanetRequestKind::SupportedResources);
+            update_basic_resources(explorer, origin, resource_list);
+            if origin == explorer.planet_id() {
+                explorer.set_state(ExplorerState::Idle);
+            }
             Ok(())
         }
         PlanetToExplorer::SupportedCombinationResponse { combination_list } => {
-            update_complex_resources(explorer, combination_list);
-            explorer.set_state(ExplorerState::Idle);
+            let origin = response_origin(explorer, PlanetRequestKind::SupportedCombinations);
+            update_complex_resources(explorer, origin, combination_list);
+            if origin == explorer.planet_id() {
+                explorer.set_state(ExplorerState::Idle);
+            }
             Ok(())
         }
         PlanetToExplorer::GenerateResourceResponse { resource } => {
@@ -25,32 +39,80 @@ pub fn handle_message(explorer: &mut Explorer, msg: PlanetToExplorer) -> Result<
             Ok(())
         }
         PlanetToExplorer::CombineResourceResponse { complex_response } => {
-            put_complex_resource_in_bag(explorer, complex_response);
+            // a failed combination is already logged, and its ingredients are back in the bag
+            let _ = put_complex_resource_in_bag(explorer, complex_response);
             explorer.set_state(ExplorerState::Idle);
             Ok(())
         }
         PlanetToExplorer::AvailableEnergyCellResponse { available_cells } => {
-            explorer.set_energy_cells(available_cells);
-            explorer.set_state(ExplorerState::Idle);
+            let origin = response_origin(explorer, PlanetRequestKind::AvailableEnergyCells);
+            // the energy cells of a planet the explorer already left are useless
+            if origin == explorer.planet_id() {
+                explorer.set_energy_cells(available_cells);
+                explorer.set_state(ExplorerState::Idle);
+            } else {
+                log_stale_response(explorer, origin, "available energy cell response discarded");
+            }
             Ok(())
         }
         PlanetToExplorer::Stopped => {
+            // a stopped planet rejects every request, the pending ones are never answered
+            explorer.planet_requests.forget_planet(explorer.planet_id());
             explorer.set_state(ExplorerState::Idle);
             Ok(())
         }
     }
 }
 
-/// Updates the basic resources information in the topology.
-fn update_basic_resources(
+/// Returns the planet that the response of the given kind comes from.
+///
+/// Responses to requests sent before a move can arrive after the planet sender has been
+/// swapped, so the planet is taken from the in-flight ledger (the current planet is used
+/// if the request was not recorded).
+pub(super) fn response_origin(explorer: &mut Explorer, kind: PlanetRequestKind) -> ID {
+    explorer
+        .planet_requests
+        .answered(kind)
+        .unwrap_or(explorer.planet_id())
+}
+
+/// Logs a response that comes from a planet other than the current one.
+fn log_stale_response(explorer: &Explorer, origin: ID, action: &str) {
+    log_internal_op!(dir
+        ActorType::Explorer,
+        explorer.explorer_id,
+        "action"=>action,
+        "origin_planet_id"=>origin,
+        "current_planet_id"=>explorer.planet_id
+    );
+}
+
+/// Updates the basic resources information of the planet that sent the response.
+pub(super) fn update_basic_resources(
     explorer: &mut Explorer,
+    origin: ID,
     resource_list: std::collections::HashSet<common_game::components::resource::BasicResourceType>,
 ) {
-    if let Some(planet_info) = explorer.get_planet_info_mut(explorer.planet_id()) {
+    if origin != explorer.planet_id() {
+        if explorer.get_planet_info(origin).is_some() {
+            log_stale_response(
+                explorer,
+                origin,
+                "late supported resource response applied to its planet",
+            );
+        } else {
+            log_stale_response(
+                explorer,
+                origin,
+                "late supported resource response discarded",
+            );
+        }
+    }
+    if let Some(planet_info) = explorer.get_planet_info_mut(origin) {
         planet_info.set_basic_resources(resource_list);
         log_message!(
             ActorType::Planet,
-            explorer.planet_id,
+            origin,
             ActorType::Explorer,
             explorer.explorer_id,
             EventType::MessagePlanetToExplorer,
@@ -59,18 +121,34 @@ fn update_basic_resources(
     }
 }
 
-/// Updates the complex resources information in the topology.
-fn update_complex_resources(
+/// Updates the complex resources information of the planet that sent the response.
+pub(super) fn update_complex_resources(
     explorer: &mut Explorer,
+    origin: ID,
     combination_list: std::collections::HashSet<
         common_game::components::resource::ComplexResourceType,
     >,
 ) {
-    if let Some(planet_info) = explorer.get_planet_info_mut(explorer.planet_id()) {
+    if origin != explorer.planet_id() {
+        if explorer.get_planet_info(origin).is_some() {
+            log_stale_response(
+                explorer,
+                origin,
+                "late supported combination response applied to its planet",
+            );
+        } else {
+            log_stale_response(
+                explorer,
+                origin,
+                "late supported combination response discarded",
+            );
+        }
+    }
+    if let Some(planet_info) = explorer.get_planet_info_mut(origin) {
         planet_info.set_complex_resources(combination_list);
         log_message!(
             ActorType::Planet,
-            explorer.planet_id,
+            origin,
             ActorType::Explorer,
             explorer.explorer_id,
             EventType::MessagePlanetToExplorer,
@@ -84,6 +162,7 @@ pub fn put_basic_resource_in_bag(explorer: &mut Explorer, resource: Option<Basic
     if let Some(resource) = resource {
         let new_resource = resource.into_generic_resource();
         explorer.insert_in_bag(new_resource);
+        explorer.consume_energy_cell();
         log_message!(
             ActorType::Planet,
             explorer.planet_id,
@@ -99,14 +178,18 @@ pub fn put_basic_resource_in_bag(explorer: &mut Explorer, resource: Option<Basic
 }
 
 /// Puts a complex resource in the explorer's bag.
+///
+/// If the combination failed, the two ingredients returned by the planet are put back
+/// in the bag and the planet's reason is returned as Err.
 pub fn put_complex_resource_in_bag(
     explorer: &mut Explorer,
     complex_response: Result<ComplexResource, (String, GenericResource, GenericResource)>,
-) {
+) -> Result<(), String> {
     match complex_response {
         Ok(complex_resource) => {
             let new_resource = complex_resource.into_generic_resource();
             explorer.insert_in_bag(new_resource);
+            explorer.consume_energy_cell();
             log_message!(
                 ActorType::Planet,
                 explorer.planet_id,
@@ -116,6 +199,7 @@ pub fn put_complex_resource_in_bag(
                 "combine resource response";
                 "explorer data"=>format!("{:?}", explorer)
             );
+            Ok(())
         }
         Err((err_msg, res1, res2)) => {
             LogEvent::new(
@@ -125,7 +209,7 @@ pub fn put_complex_resource_in_bag(
                 Channel::Error,
                 warning_payload!(
                     "CombineResourceResponse failed",
-                    err_msg,
+                    err_msg.clone(),
                     "put_complex_resource_in_bag()";
                     "explorer data"=>format!("{:?}", explorer)
                 ),
@@ -137,6 +221,7 @@ pub fn put_complex_resource_in_bag(
             explorer.insert_in_bag(res2);
 
             explorer.set_energy_cells(0);
+            Err(err_msg)
         }
     }
 }
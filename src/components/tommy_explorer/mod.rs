@@ -9,4 +9,5 @@ pub mod topology;
 
 // re-export commonly used types
 pub use core::Explorer;
+pub use explorer_ai::Strategy;
 pub use state::ExplorerState;
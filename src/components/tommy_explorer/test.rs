@@ -59,6 +59,17 @@ mod tests {
             assert_eq!(bag.to_resource_types().len(), 0);
         }
 
+        #[test]
+        fn test_bag_default_is_empty() {
+            let bag = Bag::default();
+            assert!(bag.to_resource_types().is_empty());
+        }
+
+        #[test]
+        fn test_bag_score_empty() {
+            assert_eq!(Bag::new().score(), 0);
+        }
+
         #[test]
         fn test_bag_contains_empty() {
             let bag = Bag::new();
@@ -126,6 +137,27 @@ mod tests {
             assert!(neighbours.contains(&300));
         }
 
+        #[test]
+        fn test_topology_neighbours_of_known_planet() {
+            let mut topology = TopologyManager::new(100);
+            topology.update_neighbours(100, vec![400, 200, 300]);
+
+            assert_eq!(
+                topology.neighbours_of(100),
+                Some(&HashSet::from([200, 300, 400]))
+            );
+            assert_eq!(topology.neighbour_ids_sorted(100), vec![200, 300, 400]);
+            // planet 200 is known but its neighbours are not
+            assert_eq!(topology.neighbours_of(200), None);
+        }
+
+        #[test]
+        fn test_topology_neighbours_of_unknown_planet() {
+            let topology = TopologyManager::new(100);
+            assert_eq!(topology.neighbours_of(999), None);
+            assert!(topology.neighbour_ids_sorted(999).is_empty());
+        }
+
         #[test]
         fn test_topology_is_fully_discovered_empty() {
             let topology = TopologyManager::new(100);
@@ -152,6 +184,35 @@ mod tests {
             topology.clear();
             assert_eq!(topology.known_planets().len(), 0);
         }
+
+        #[test]
+        fn test_topology_add_and_remove_edge() {
+            let mut topology = TopologyManager::new(100);
+            topology.update_neighbours(100, vec![200]);
+            topology.update_neighbours(200, vec![100]);
+
+            topology.remove_edge(200, 100);
+            assert!(topology.neighbour_ids_sorted(100).is_empty());
+            assert!(topology.neighbour_ids_sorted(200).is_empty());
+
+            // 300 has no known neighbours, only 100 gets the edge
+            topology.add_edge(100, 300);
+            assert_eq!(topology.neighbour_ids_sorted(100), vec![300]);
+            assert_eq!(topology.neighbours_of(300), None);
+        }
+
+        #[test]
+        fn test_topology_mark_as_dead_removes_its_edges() {
+            let mut topology = TopologyManager::new(100);
+            topology.update_neighbours(100, vec![200, 300]);
+            topology.update_neighbours(200, vec![100, 300]);
+            topology.update_neighbours(300, vec![100, 200]);
+
+            topology.mark_as_dead(200);
+            assert!(!topology.contains(200));
+            assert_eq!(topology.neighbour_ids_sorted(100), vec![300]);
+            assert_eq!(topology.neighbour_ids_sorted(300), vec![100]);
+        }
     }
 
     // ==================== PlanetInfo Tests ====================
@@ -298,6 +359,16 @@ mod tests {
             assert!(!queue.is_empty());
             assert_eq!(queue.len(), 6);
         }
+
+        #[test]
+        fn test_explorer_action_display() {
+            assert_eq!(ExplorerAction::AskNeighbours.to_string(), "AskNeighbours");
+            assert_eq!(ExplorerAction::Move.to_string(), "Move");
+            assert_eq!(
+                ExplorerAction::GenerateOrCombine.to_string(),
+                "GenerateOrCombine"
+            );
+        }
     }
 
     // ==================== MoveQueue Tests ====================
@@ -412,6 +483,32 @@ mod tests {
             assert!(neighbours.contains(&300));
         }
 
+        #[test]
+        fn test_explorer_update_neighbors_of_unknown_planet() {
+            let (mut explorer, _, _, _, _) = create_test_explorer();
+            assert!(explorer.get_planet_info(500).is_none());
+
+            explorer.update_neighbors(500, vec![100]);
+            let info = explorer.get_planet_info(500).unwrap();
+            assert_eq!(info.get_neighbours(), Some(&HashSet::from([100])));
+        }
+
+        #[test]
+        fn test_explorer_get_or_create_planet_info_mut() {
+            let (mut explorer, _, _, _, _) = create_test_explorer();
+            explorer
+                .get_or_create_planet_info_mut(700)
+                .set_neighbours(HashSet::from([100]));
+            // an existing entry is returned, not replaced
+            assert!(
+                explorer
+                    .get_or_create_planet_info_mut(700)
+                    .get_neighbours()
+                    .is_some()
+            );
+            assert!(explorer.topology.contains(700));
+        }
+
         #[test]
         fn test_explorer_send_to_orchestrator() {
             let (explorer, receiver, _, _, _) = create_test_explorer();
@@ -449,6 +546,408 @@ mod tests {
             explorer.set_energy_cells(10);
             assert_eq!(explorer.energy_cells, 10);
         }
+
+        #[test]
+        fn test_explorer_arrival_on_fresh_planet_queues_survey() {
+            let (mut explorer, receiver, _, _, _) = create_test_explorer();
+            let (new_planet_send, _new_planet_recv) = unbounded::<ExplorerToPlanet>();
+
+            let should_terminate = handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: Some(new_planet_send),
+                    planet_id: 200,
+                },
+            )
+            .unwrap();
+            assert!(!should_terminate);
+            assert_eq!(explorer.planet_id(), 200);
+            assert!(explorer.topology.contains(200));
+
+            let received = receiver.recv_timeout(std::time::Duration::from_millis(100));
+            assert!(matches!(
+                received,
+                Ok(ExplorerToOrchestrator::MovedToPlanetResult {
+                    explorer_id: 1,
+                    planet_id: 200
+                })
+            ));
+
+            // the next AI ticks ask for neighbours and survey the new planet
+            assert_eq!(
+                explorer.action_queue.next_action(),
+                Some(ExplorerAction::AskNeighbours)
+            );
+            assert_eq!(
+                explorer.action_queue.next_action(),
+                Some(ExplorerAction::AskSupportedResources)
+            );
+            assert_eq!(
+                explorer.action_queue.next_action(),
+                Some(ExplorerAction::AskSupportedCombinations)
+            );
+        }
+
+        #[test]
+        fn test_explorer_late_neighbors_response_updates_requested_planet() {
+            use crate::utils::request_ledger::PlanetRequestKind;
+
+            let (mut explorer, _, _, _, _) = create_test_explorer();
+            let (new_planet_send, _new_planet_recv) = unbounded::<ExplorerToPlanet>();
+
+            // the neighbours of planet 100 were requested before moving
+            explorer
+                .planet_requests
+                .sent(PlanetRequestKind::Neighbours, 100);
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: Some(new_planet_send),
+                    planet_id: 200,
+                },
+            )
+            .unwrap();
+
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::NeighborsResponse {
+                    neighbors: vec![300, 400],
+                },
+            )
+            .unwrap();
+
+            let old_planet = explorer.get_planet_info(100).unwrap();
+            assert_eq!(
+                old_planet.get_neighbours(),
+                Some(&HashSet::from([300, 400]))
+            );
+            let new_planet = explorer.get_planet_info(200).unwrap();
+            assert!(new_planet.get_neighbours().is_none());
+        }
+
+        #[test]
+        fn test_explorer_exits_on_kill_signal() {
+            let (mut explorer, explorer_recv, _orch_send, _planet_recv, _planet_send) =
+                create_test_explorer();
+            let (kill_send, kill_recv) = crossbeam_channel::bounded(1);
+            explorer.set_kill_receiver(kill_recv);
+
+            let handle = std::thread::spawn(move || explorer.run());
+            // only the kill signal is sent, no KillExplorer message
+            kill_send.send(()).unwrap();
+
+            match explorer_recv.recv_timeout(std::time::Duration::from_millis(500)) {
+                Ok(ExplorerToOrchestrator::KillExplorerResult { explorer_id }) => {
+                    assert_eq!(explorer_id, 1)
+                }
+                other => panic!("expected KillExplorerResult, got {:?}", other),
+            }
+            assert!(handle.join().unwrap().is_ok());
+        }
+
+        #[test]
+        fn test_explorer_shuts_down_when_idle_for_too_long() {
+            let (mut explorer, explorer_recv, _orch_send, _planet_recv, _planet_send) =
+                create_test_explorer();
+            // fully discovered topology where nothing provides the goal
+            explorer.update_neighbors(100, vec![]);
+            let info = explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            explorer.action_queue.clear();
+            explorer.action_queue.push_back(ExplorerAction::Move);
+            explorer.manual_mode_off();
+            explorer.set_idle_shutdown_after(Some(3));
+
+            let handle = std::thread::spawn(move || explorer.run());
+
+            // no KillExplorer message nor kill signal is sent
+            match explorer_recv.recv_timeout(std::time::Duration::from_millis(1000)) {
+                Ok(ExplorerToOrchestrator::KillExplorerResult { explorer_id }) => {
+                    assert_eq!(explorer_id, 1)
+                }
+                other => panic!("expected KillExplorerResult, got {:?}", other),
+            }
+            assert!(handle.join().unwrap().is_ok());
+        }
+
+        #[test]
+        fn test_explorer_late_planet_response_after_move_is_not_misattributed() {
+            use crate::utils::request_ledger::PlanetRequestKind;
+
+            let (mut explorer, _, _, _, _) = create_test_explorer();
+            let (new_planet_send, _new_planet_recv) = unbounded::<ExplorerToPlanet>();
+
+            // a supported resource request was sent to planet 100 before moving
+            explorer
+                .planet_requests
+                .sent(PlanetRequestKind::SupportedResources, 100);
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: Some(new_planet_send),
+                    planet_id: 200,
+                },
+            )
+            .unwrap();
+
+            // the response of planet 100 arrives after the move
+            handlers::planet::handle_message(
+                &mut explorer,
+                PlanetToExplorer::SupportedResourceResponse {
+                    resource_list: HashSet::from([BasicResourceType::Carbon]),
+                },
+            )
+            .unwrap();
+
+            let new_planet = explorer.get_planet_info(200).unwrap();
+            assert!(new_planet.get_basic_resources().is_none());
+            let old_planet = explorer.get_planet_info(100).unwrap();
+            assert_eq!(
+                old_planet.get_basic_resources(),
+                Some(&HashSet::from([BasicResourceType::Carbon]))
+            );
+        }
+
+        #[test]
+        fn test_explorer_supported_resource_request_records_its_request() {
+            use crate::utils::request_ledger::PlanetRequestKind;
+
+            let (mut explorer, explorer_recv, _, planet_recv, planet_send) = create_test_explorer();
+            explorer.get_or_create_planet_info_mut(50);
+
+            // a request sent to planet 50 before moving is still waiting for its answer
+            explorer
+                .planet_requests
+                .sent(PlanetRequestKind::SupportedResources, 50);
+            planet_send
+                .send(PlanetToExplorer::SupportedResourceResponse {
+                    resource_list: HashSet::from([BasicResourceType::Carbon]),
+                })
+                .unwrap();
+            planet_send
+                .send(PlanetToExplorer::SupportedResourceResponse {
+                    resource_list: HashSet::from([BasicResourceType::Oxygen]),
+                })
+                .unwrap();
+
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::SupportedResourceRequest,
+            )
+            .unwrap();
+
+            assert!(matches!(
+                planet_recv.try_recv(),
+                Ok(ExplorerToPlanet::SupportedResourceRequest { .. })
+            ));
+            match explorer_recv.try_recv() {
+                Ok(ExplorerToOrchestrator::SupportedResourceResult {
+                    supported_resources,
+                    ..
+                }) => assert_eq!(
+                    supported_resources,
+                    HashSet::from([BasicResourceType::Oxygen])
+                ),
+                other => panic!("expected SupportedResourceResult, got {:?}", other),
+            }
+            assert_eq!(
+                explorer.get_planet_info(50).unwrap().get_basic_resources(),
+                Some(&HashSet::from([BasicResourceType::Carbon]))
+            );
+            assert!(
+                !explorer
+                    .planet_requests
+                    .is_pending(PlanetRequestKind::SupportedResources, 100)
+            );
+        }
+
+        #[test]
+        fn test_explorer_gets_the_answer_when_the_left_planet_never_answers() {
+            use crate::utils::request_ledger::PlanetRequestKind;
+
+            let (mut explorer, explorer_recv, _, planet_recv, planet_send) = create_test_explorer();
+            explorer.get_or_create_planet_info_mut(50);
+
+            // planet 50 was left with a request that it will never answer
+            explorer
+                .planet_requests
+                .sent(PlanetRequestKind::SupportedResources, 50);
+            planet_send
+                .send(PlanetToExplorer::SupportedResourceResponse {
+                    resource_list: HashSet::from([BasicResourceType::Oxygen]),
+                })
+                .unwrap();
+
+            let start = std::time::Instant::now();
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::SupportedResourceRequest,
+            )
+            .unwrap();
+            assert!(start.elapsed() < std::time::Duration::from_secs(2));
+
+            assert!(matches!(
+                planet_recv.try_recv(),
+                Ok(ExplorerToPlanet::SupportedResourceRequest { .. })
+            ));
+            match explorer_recv.try_recv() {
+                Ok(ExplorerToOrchestrator::SupportedResourceResult {
+                    supported_resources,
+                    ..
+                }) => assert_eq!(
+                    supported_resources,
+                    HashSet::from([BasicResourceType::Oxygen])
+                ),
+                other => panic!("expected SupportedResourceResult, got {:?}", other),
+            }
+            assert!(
+                explorer
+                    .get_planet_info(50)
+                    .unwrap()
+                    .get_basic_resources()
+                    .is_none()
+            );
+            for planet_id in [50, 100] {
+                assert!(
+                    !explorer
+                        .planet_requests
+                        .is_pending(PlanetRequestKind::SupportedResources, planet_id)
+                );
+            }
+        }
+
+        #[test]
+        fn test_explorer_stopped_planet_requests_are_forgotten() {
+            use crate::utils::request_ledger::PlanetRequestKind;
+
+            let (mut explorer, _, _, _, _) = create_test_explorer();
+            explorer
+                .planet_requests
+                .sent(PlanetRequestKind::AvailableEnergyCells, 100);
+
+            handlers::planet::handle_message(&mut explorer, PlanetToExplorer::Stopped).unwrap();
+
+            assert!(
+                !explorer
+                    .planet_requests
+                    .is_pending(PlanetRequestKind::AvailableEnergyCells, 100)
+            );
+        }
+
+        #[test]
+        fn test_explorer_arrival_without_auto_survey() {
+            let (mut explorer, explorer_recv, _, _, _) = create_test_explorer();
+            let (new_planet_send, _new_planet_recv) = unbounded::<ExplorerToPlanet>();
+            explorer.set_auto_survey_on_arrival(false);
+            // a known planet, that the arrival survey would survey last
+            explorer.update_neighbors(100, vec![200]);
+            let info = explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            info.set_neighbours(HashSet::from([100]));
+            assert_eq!(explorer.arrival_survey(200), ArrivalSurvey::First);
+
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: Some(new_planet_send),
+                    planet_id: 200,
+                },
+            )
+            .unwrap();
+            assert!(matches!(
+                explorer_recv.try_recv(),
+                Ok(ExplorerToOrchestrator::MovedToPlanetResult { .. })
+            ));
+
+            // the default flow: the first AI tick asks for the neighbours
+            explorer.execute_ai_action();
+            assert!(matches!(
+                explorer_recv.try_recv(),
+                Ok(ExplorerToOrchestrator::NeighborsRequest {
+                    explorer_id: 1,
+                    current_planet_id: 200
+                })
+            ));
+            assert_eq!(explorer.action_queue.len(), 6);
+        }
+
+        #[test]
+        fn test_explorer_arrival_on_complete_planet_skips_survey() {
+            let (mut explorer, _, _, _, _) = create_test_explorer();
+            let (new_planet_send, _new_planet_recv) = unbounded::<ExplorerToPlanet>();
+            explorer.update_neighbors(100, vec![200]);
+            let info = explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            info.set_neighbours(HashSet::from([100]));
+            assert_eq!(explorer.arrival_survey(200), ArrivalSurvey::Last);
+
+            handlers::orchestrator::handle_message(
+                &mut explorer,
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet: Some(new_planet_send),
+                    planet_id: 200,
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                explorer.action_queue.next_action(),
+                Some(ExplorerAction::AskFreeCells)
+            );
+        }
+
+        /// run_ticks: a request of the orchestrator is answered by the real loop
+        #[test]
+        fn test_current_planet_request_is_answered_by_the_loop() {
+            let (mut explorer, from_explorer, to_explorer, _planet, _) = create_test_explorer();
+            // no AI action, only the message handling
+            explorer.manual_mode_on();
+
+            to_explorer
+                .send(OrchestratorToExplorer::CurrentPlanetRequest)
+                .unwrap();
+            assert_eq!(explorer.run_ticks(3), Ok(&ExplorerState::Idle));
+            assert!(matches!(
+                from_explorer.try_recv(),
+                Ok(ExplorerToOrchestrator::CurrentPlanetResult {
+                    explorer_id: 1,
+                    planet_id: 100
+                })
+            ));
+            assert!(from_explorer.try_recv().is_err());
+        }
+
+        /// run_until: the loop returns as soon as the explorer is killed
+        #[test]
+        fn test_kill_ends_the_bounded_loop_early() {
+            use std::time::{Duration, Instant};
+
+            let (mut explorer, from_explorer, to_explorer, _planet, _) = create_test_explorer();
+
+            to_explorer
+                .send(OrchestratorToExplorer::KillExplorer)
+                .unwrap();
+            let start = Instant::now();
+            let state = explorer.run_until(start + Duration::from_secs(5));
+            assert_eq!(state, Ok(&ExplorerState::Killed));
+            assert!(start.elapsed() < Duration::from_secs(5));
+            assert!(matches!(
+                from_explorer.try_recv(),
+                Ok(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 1 })
+            ));
+        }
+
+        /// run_ticks: the loop stops with an error when the orchestrator is gone
+        #[test]
+        fn test_disconnected_orchestrator_stops_the_bounded_loop() {
+            let (mut explorer, _from_explorer, to_explorer, _planet, _) = create_test_explorer();
+
+            drop(to_explorer);
+            assert!(explorer.run_ticks(3).is_err());
+        }
     }
 
     // ==================== Pathfinding Tests ====================
@@ -492,6 +991,31 @@ mod tests {
             assert!(path.is_none());
         }
 
+        #[test]
+        fn test_find_path_to_nearest_frontier_skips_complete_branch() {
+            let mut topology = TopologyManager::new(100);
+
+            // Setup: 100 -> 200 -> 400 is a dead end made of complete planets,
+            // 100 -> 300 -> 500 leads to the incomplete planet 500
+            let complete = [
+                (100, vec![200, 300]),
+                (200, vec![100, 400]),
+                (400, vec![200]),
+                (300, vec![100, 500]),
+            ];
+            for (planet_id, neighbours) in complete {
+                topology.update_neighbours(planet_id, neighbours);
+                let info = topology.get_or_create(planet_id);
+                info.set_basic_resources(HashSet::new());
+                info.set_complex_resources(HashSet::new());
+            }
+
+            assert!(!topology.is_frontier(400));
+            assert!(topology.is_frontier(500));
+            let path = topology.find_path_to_nearest_frontier(100).unwrap();
+            assert_eq!(path, VecDeque::from([300, 500]));
+        }
+
         #[test]
         fn test_find_path_to_resource_simple() {
             let mut topology = TopologyManager::new(100);
@@ -566,6 +1090,85 @@ mod tests {
             assert_eq!(path[0], 200);
             assert_eq!(path[1], 300);
         }
+
+        /// 100 - 200 - 300 - 400 and 100 - 500, whose resources are unknown
+        fn mixed_topology() -> TopologyManager {
+            use BasicResourceType::{Carbon, Hydrogen, Oxygen, Silicon};
+            use ComplexResourceType::Water;
+
+            let mut topology = TopologyManager::new(100);
+            let planets = [
+                (100, vec![200, 500], vec![Carbon], vec![]),
+                (200, vec![100, 300], vec![Carbon, Hydrogen], vec![Water]),
+                (300, vec![200, 400], vec![Silicon], vec![]),
+                (400, vec![300], vec![Oxygen], vec![]),
+            ];
+            for (planet_id, neighbours, basic, complex) in planets {
+                topology.update_neighbours(planet_id, neighbours);
+                let info = topology.get_or_create(planet_id);
+                info.set_basic_resources(basic.into_iter().collect());
+                info.set_complex_resources(complex.into_iter().collect());
+            }
+            topology
+        }
+
+        #[test]
+        fn test_reachable_resources_within_hops() {
+            let topology = mixed_topology();
+            let basic = ResourceType::Basic;
+            let carbon = basic(BasicResourceType::Carbon);
+            let water = ResourceType::Complex(ComplexResourceType::Water);
+
+            assert_eq!(
+                topology.reachable_resources(100, 2),
+                vec![
+                    (basic(BasicResourceType::Hydrogen), vec![(200, 1)]),
+                    (carbon, vec![(100, 0), (200, 1)]),
+                    (basic(BasicResourceType::Silicon), vec![(300, 2)]),
+                    (water, vec![(200, 1)]),
+                ]
+            );
+            // Oxygen is three hops away
+            let scan = topology.reachable_resources(100, 3);
+            let oxygen = basic(BasicResourceType::Oxygen);
+            assert_eq!(scan[0], (oxygen, vec![(400, 3)]));
+            assert_eq!(
+                topology.reachable_resources(100, 0),
+                vec![(carbon, vec![(100, 0)])]
+            );
+        }
+
+        #[test]
+        fn test_reachable_resources_skip_dead_planets() {
+            let mut topology = mixed_topology();
+            topology.mark_as_dead(200);
+
+            // 300 and 400 are cut off along with 200
+            let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+            assert_eq!(
+                topology.reachable_resources(100, 5),
+                vec![(carbon, vec![(100, 0)])]
+            );
+            assert!(topology.reachable_resources(999, 5).is_empty());
+        }
+
+        #[test]
+        fn test_reachable_resources_skip_stopped_planets() {
+            let mut topology = mixed_topology();
+            let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+            let silicon = ResourceType::Basic(BasicResourceType::Silicon);
+            topology.mark_as_stopped(200);
+
+            // 200 is still crossed to reach 300
+            assert!(!topology.is_running(200));
+            assert_eq!(
+                topology.reachable_resources(100, 2),
+                vec![(carbon, vec![(100, 0)]), (silicon, vec![(300, 2)])]
+            );
+
+            topology.mark_as_running(200);
+            assert_eq!(topology.reachable_resources(100, 2).len(), 4);
+        }
     }
 
     // ==================== Integration Tests ====================
@@ -955,24 +1558,27 @@ mod explorer_full_tests {
         }
 
         /// OrchestratorToExplorer::ResetExplorerAI
-        /// -> Explorer should clear topology, set Idle, send ResetExplorerAIResult
+        /// -> Explorer should clear topology and bag, set Idle, send ResetExplorerAIResult
         #[test]
         fn test_reset_explorer_ai() {
+            use crate::utils::resource_mint::mint_resource;
+            use common_game::components::forge::Forge;
+
             let mut h = TestStruct::new();
 
-            // Pre-populate topology
+            // Pre-populate topology and bag
             h.explorer.update_neighbors(100, vec![200, 300]);
             assert!(h.explorer.topology.known_planets().len() > 1);
-
-            // Simulate reset_explorer_ai handler
+            let oxygen = ResourceType::Basic(BasicResourceType::Oxygen);
             h.explorer
-                .send_to_orchestrator(ExplorerToOrchestrator::ResetExplorerAIResult {
-                    explorer_id: h.explorer.id(),
-                })
-                .unwrap();
-            h.explorer.manual_mode_off();
-            h.explorer.clear_topology();
-            h.explorer.set_state(ExplorerState::Idle);
+                .insert_in_bag(mint_resource(oxygen, &Forge::new().unwrap()).unwrap());
+            assert!(!h.explorer.get_bag_content().is_empty());
+
+            handlers::orchestrator::handle_message(
+                &mut h.explorer,
+                OrchestratorToExplorer::ResetExplorerAI,
+            )
+            .unwrap();
 
             let msg = h.recv_from_explorer_to_orch();
             assert!(matches!(
@@ -980,6 +1586,7 @@ mod explorer_full_tests {
                 ExplorerToOrchestrator::ResetExplorerAIResult { explorer_id: 1 }
             ));
             assert_eq!(h.explorer.topology.known_planets().len(), 0);
+            assert!(h.explorer.get_bag_content().is_empty());
             assert_eq!(*h.explorer.state(), ExplorerState::Idle);
         }
 
@@ -2162,6 +2769,37 @@ mod explorer_full_tests {
             );
         }
 
+        /// plan_fits_energy: the whole AIPartner plan from an empty bag takes 11 cells
+        #[test]
+        fn test_plan_fits_energy_counts_the_whole_plan() {
+            let mut h = TestStruct::new_with_params(1, 100, 10);
+            assert_eq!(h.explorer.plan_energy_cost(), 11);
+            assert!(!h.explorer.plan_fits_energy());
+
+            h.explorer.set_energy_cells(11);
+            assert!(h.explorer.plan_fits_energy());
+        }
+
+        /// decide_resource_action: with 2 cells and a hydrogen in the bag the goal is out
+        /// of reach, so the explorer works on water (2 cells) and not on diamond (3 cells)
+        #[test]
+        fn test_decide_resource_action_targets_an_affordable_intermediate() {
+            use crate::utils::resource_mint::mint_resource;
+            use common_game::components::forge::Forge;
+
+            let mut h = TestStruct::new_with_params(1, 100, 2);
+            setup_planet_with_all_resources(&mut h);
+            let hydrogen = ResourceType::Basic(BasicResourceType::Hydrogen);
+            h.explorer
+                .insert_in_bag(mint_resource(hydrogen, &Forge::new().unwrap()).unwrap());
+
+            assert!(!h.explorer.plan_fits_energy());
+            assert_eq!(
+                h.explorer.decide_resource_action(),
+                Some(ResourceType::Basic(BasicResourceType::Oxygen))
+            );
+        }
+
         /// resources_needed: consistent with decision priority
         #[test]
         fn test_resources_needed_nonempty_coverage() {
@@ -2239,6 +2877,10 @@ mod explorer_full_tests {
 
     mod edge_case_tests {
         use super::*;
+        use crate::components::tommy_explorer::actions::ExplorerAction;
+        use crate::components::tommy_explorer::handlers::planet;
+        use crate::utils::TopologyDelta;
+        use crate::utils::request_ledger::PlanetRequestKind;
 
         /// Sending on a disconnected channel should return Err
         #[test]
@@ -2268,6 +2910,24 @@ mod explorer_full_tests {
             assert!(result.is_err(), "Send to dropped receiver should fail");
         }
 
+        /// A Stopped answer marks the current planet as not running, until it answers
+        /// something else
+        #[test]
+        fn test_stopped_answer_marks_the_planet_as_not_running() {
+            let mut h = TestStruct::new();
+            assert!(h.explorer.topology.is_running(100));
+
+            planet::handle_message(&mut h.explorer, PlanetToExplorer::Stopped).unwrap();
+            assert!(!h.explorer.topology.is_running(100));
+
+            planet::handle_message(
+                &mut h.explorer,
+                PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 1 },
+            )
+            .unwrap();
+            assert!(h.explorer.topology.is_running(100));
+        }
+
         /// Multiple explorers on the same channels (different IDs)
         #[test]
         fn test_multiple_explorer_ids_distinct() {
@@ -2319,6 +2979,514 @@ mod explorer_full_tests {
             // Verified by the `if self.energy_cells > 0` guard in execute_ai_action
             assert_eq!(h.explorer.energy_cells, 0); // no messages sent to planet
         }
+
+        /// Cached energy cells = 0: the AI defers generation and waits for the
+        /// configured cycles before moving on to a neighbour
+        #[test]
+        fn test_recharge_wait_defers_generation_then_moves_on() {
+            let mut h = TestStruct::new();
+            h.explorer.manual_mode_off();
+            h.explorer.set_recharge_wait(2);
+
+            // fully known topology: 100 can generate everything, 200 is its only neighbour
+            h.explorer.update_neighbors(100, vec![200]);
+            h.explorer.update_neighbors(200, vec![100]);
+            for planet_id in [100, 200] {
+                let info = h.explorer.get_planet_info_mut(planet_id).unwrap();
+                info.set_basic_resources(HashSet::from([
+                    BasicResourceType::Oxygen,
+                    BasicResourceType::Hydrogen,
+                    BasicResourceType::Carbon,
+                    BasicResourceType::Silicon,
+                ]));
+                info.set_complex_resources(HashSet::from([
+                    ComplexResourceType::Water,
+                    ComplexResourceType::Life,
+                    ComplexResourceType::Diamond,
+                    ComplexResourceType::Robot,
+                    ComplexResourceType::AIPartner,
+                ]));
+            }
+
+            // the planet answered that no cell is charged
+            h.send_to_explorer_from_planet(PlanetToExplorer::AvailableEnergyCellResponse {
+                available_cells: 0,
+            });
+            h.explorer
+                .planet_requests
+                .sent(PlanetRequestKind::AvailableEnergyCells, 100);
+            let msg = h.explorer.planet_channels.0.recv().unwrap();
+            planet::handle_message(&mut h.explorer, msg).unwrap();
+            assert_eq!(h.explorer.energy_cells, 0);
+
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::GenerateOrCombine);
+            h.explorer.execute_ai_action();
+            assert!(
+                h.planet_receiver.try_recv().is_err(),
+                "no generation request with zero cached energy cells"
+            );
+
+            // the first two Move actions wait on the planet
+            for _ in 0..2 {
+                h.explorer.action_queue.clear();
+                h.explorer.action_queue.push_back(ExplorerAction::Move);
+                h.explorer.execute_ai_action();
+                assert!(h.orch_receiver.try_recv().is_err());
+                assert_ne!(*h.explorer.state(), ExplorerState::Traveling);
+            }
+
+            // then the explorer gives up and moves on
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.recv_from_explorer_to_orch(),
+                ExplorerToOrchestrator::TravelToPlanetRequest {
+                    dst_planet_id: 200,
+                    ..
+                }
+            ));
+        }
+
+        /// Without charged cells and with no other planet nearby providing the resource,
+        /// the explorer keeps waiting instead of wandering off
+        #[test]
+        fn test_no_energy_waits_when_nothing_nearby_provides() {
+            let mut h = TestStruct::new();
+            h.explorer.manual_mode_off();
+            h.explorer.set_energy_cells(0);
+
+            // 100 provides everything, its only neighbour 200 provides nothing
+            h.explorer.update_neighbors(100, vec![200]);
+            h.explorer.update_neighbors(200, vec![100]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::from([
+                BasicResourceType::Oxygen,
+                BasicResourceType::Hydrogen,
+                BasicResourceType::Carbon,
+                BasicResourceType::Silicon,
+            ]));
+            info.set_complex_resources(HashSet::from([
+                ComplexResourceType::Water,
+                ComplexResourceType::Life,
+                ComplexResourceType::Diamond,
+                ComplexResourceType::Robot,
+                ComplexResourceType::AIPartner,
+            ]));
+            let info = h.explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+
+            let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+            assert!(!h.explorer.provided_nearby(carbon));
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            assert!(h.orch_receiver.try_recv().is_err());
+
+            // once 200 provides the basic resources too, the explorer leaves
+            let info = h.explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::from([
+                BasicResourceType::Oxygen,
+                BasicResourceType::Hydrogen,
+                BasicResourceType::Carbon,
+                BasicResourceType::Silicon,
+            ]));
+            assert!(h.explorer.provided_nearby(carbon));
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.recv_from_explorer_to_orch(),
+                ExplorerToOrchestrator::TravelToPlanetRequest {
+                    dst_planet_id: 200,
+                    ..
+                }
+            ));
+        }
+
+        /// Nothing left to explore and no planet provides the needed resource:
+        /// the AI gives up and reports it only once
+        #[test]
+        fn test_no_progress_is_reported_once() {
+            let mut h = TestStruct::new();
+            h.explorer.manual_mode_off();
+            h.explorer.update_neighbors(100, vec![]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            assert!(h.explorer.take_accept_death());
+            let reason = h.explorer.no_progress_reason().unwrap().to_string();
+            assert!(reason.contains("Carbon"));
+
+            // the AI gives up again on every retry, the first report is kept
+            for _ in 0..2 {
+                h.explorer.action_queue.clear();
+                h.explorer.action_queue.push_back(ExplorerAction::Move);
+                h.explorer.execute_ai_action();
+                assert!(h.explorer.take_accept_death());
+                assert_eq!(h.explorer.no_progress_reason(), Some(reason.as_str()));
+            }
+            assert!(h.orch_receiver.try_recv().is_err());
+
+            // a different dead end does not replace the first report
+            h.explorer.report_no_progress("another reason".to_string());
+            assert_eq!(h.explorer.no_progress_reason(), Some(reason.as_str()));
+        }
+
+        /// Fully discovered topology without Silicon on the reachable planets: the goal
+        /// is unreachable and the AI gives up
+        #[test]
+        fn test_goal_unreachable_without_ingredients() {
+            let mut h = TestStruct::new();
+            h.explorer.manual_mode_off();
+
+            // 100 - 200, and 300 which cannot be reached
+            h.explorer.update_neighbors(100, vec![200]);
+            h.explorer.update_neighbors(200, vec![100]);
+            h.explorer.update_neighbors(300, vec![]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::from([
+                BasicResourceType::Oxygen,
+                BasicResourceType::Hydrogen,
+                BasicResourceType::Carbon,
+            ]));
+            info.set_complex_resources(HashSet::from([
+                ComplexResourceType::Water,
+                ComplexResourceType::Life,
+                ComplexResourceType::Diamond,
+            ]));
+            let info = h.explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::from([
+                ComplexResourceType::Robot,
+                ComplexResourceType::AIPartner,
+            ]));
+            let info = h.explorer.get_planet_info_mut(300).unwrap();
+            info.set_basic_resources(HashSet::from([BasicResourceType::Silicon]));
+            info.set_complex_resources(HashSet::new());
+
+            let frontier = h.explorer.topology.find_path_to_nearest_frontier(100);
+            assert!(frontier.is_none());
+            assert!(!h.explorer.goal_reachable());
+
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            let reason = h.explorer.no_progress_reason().unwrap();
+            assert!(reason.contains("AIPartner"));
+            assert!(h.orch_receiver.try_recv().is_err());
+
+            // a reachable planet generating Silicon makes the goal reachable again
+            let info = h.explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::from([BasicResourceType::Silicon]));
+            assert!(h.explorer.goal_reachable());
+        }
+
+        /// The only link of the planned path is destroyed: the path is dropped and the
+        /// next travel request takes another route
+        #[test]
+        fn test_destroyed_link_replans_the_path() {
+            let mut h = TestStruct::new();
+            h.explorer.manual_mode_off();
+            let (topology_sender, topology_receiver) = unbounded();
+            h.explorer.set_topology_receiver(topology_receiver);
+
+            // 100 - 200 - 300 and 100 - 400 - 300, the current planet is fully known
+            h.explorer.update_neighbors(100, vec![200, 400]);
+            h.explorer.update_neighbors(200, vec![100, 300]);
+            h.explorer.update_neighbors(400, vec![100, 300]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            h.explorer.move_queue.push_path(VecDeque::from([200, 300]));
+
+            // planet 200 died
+            topology_sender
+                .send(TopologyDelta {
+                    removed: vec![(100, 200), (200, 300)],
+                    added: Vec::new(),
+                    dead_planets: vec![200],
+                })
+                .unwrap();
+            h.explorer.apply_topology_deltas();
+            assert!(h.explorer.move_queue.is_empty());
+            assert_eq!(h.explorer.topology.neighbour_ids_sorted(100), vec![400]);
+            assert!(!h.explorer.topology.contains(200));
+
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.recv_from_explorer_to_orch(),
+                ExplorerToOrchestrator::TravelToPlanetRequest {
+                    dst_planet_id: 400,
+                    ..
+                }
+            ));
+        }
+
+        /// One known cell: a generation the planet does not fulfil leaves the cell for
+        /// the next request, an empty response means no cell is charged
+        #[test]
+        fn test_unfulfilled_generation_keeps_the_energy_cell() {
+            let mut h = TestStruct::new_with_params(1, 100, 1);
+            h.explorer.manual_mode_off();
+            h.explorer.update_neighbors(100, vec![]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::from([BasicResourceType::Carbon]));
+            info.set_complex_resources(HashSet::new());
+
+            // the planet is stopped, no cell is used
+            h.send_to_explorer_from_planet(PlanetToExplorer::Stopped);
+            h.explorer.action_queue.clear();
+            h.explorer
+                .action_queue
+                .push_back(ExplorerAction::GenerateOrCombine);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.planet_receiver.try_recv(),
+                Ok(ExplorerToPlanet::GenerateResourceRequest { .. })
+            ));
+            assert_eq!(h.explorer.energy_cells, 1);
+            assert_eq!(h.explorer.reserved_energy_cells(100), 0);
+
+            // the cell is still there, the planet now has nothing to give
+            h.send_to_explorer_from_planet(PlanetToExplorer::GenerateResourceResponse {
+                resource: None,
+            });
+            h.explorer.action_queue.clear();
+            h.explorer
+                .action_queue
+                .push_back(ExplorerAction::GenerateOrCombine);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.planet_receiver.try_recv(),
+                Ok(ExplorerToPlanet::GenerateResourceRequest { .. })
+            ));
+            assert_eq!(h.explorer.energy_cells, 0);
+
+            h.explorer.action_queue.clear();
+            h.explorer
+                .action_queue
+                .push_back(ExplorerAction::GenerateOrCombine);
+            h.explorer.execute_ai_action();
+            assert!(h.planet_receiver.try_recv().is_err(), "no cell is charged");
+        }
+
+        /// One known cell and two planned generations: the first one uses the cell, so
+        /// only one request goes out until the energy cells are queried again
+        #[test]
+        fn test_one_known_cell_allows_one_generation() {
+            use crate::utils::resource_mint::mint_resource;
+            use common_game::components::forge::Forge;
+            use common_game::components::resource::BasicResource;
+
+            let mut h = TestStruct::new_with_params(1, 100, 1);
+            h.explorer.manual_mode_off();
+            h.explorer.update_neighbors(100, vec![]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::from([BasicResourceType::Carbon]));
+            info.set_complex_resources(HashSet::new());
+
+            let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+            let carbon = mint_resource(carbon, &Forge::new().unwrap())
+                .unwrap()
+                .to_carbon()
+                .unwrap();
+            h.send_to_explorer_from_planet(PlanetToExplorer::GenerateResourceResponse {
+                resource: Some(BasicResource::Carbon(carbon)),
+            });
+            h.explorer.action_queue.clear();
+            for _ in 0..2 {
+                h.explorer
+                    .action_queue
+                    .push_back(ExplorerAction::GenerateOrCombine);
+            }
+
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.planet_receiver.try_recv(),
+                Ok(ExplorerToPlanet::GenerateResourceRequest { .. })
+            ));
+            assert_eq!(h.explorer.energy_cells, 0);
+            assert_eq!(h.explorer.reserved_energy_cells(100), 0);
+
+            h.explorer.execute_ai_action();
+            assert!(
+                h.planet_receiver.try_recv().is_err(),
+                "the only cell has been used"
+            );
+
+            // the planet has recharged its cell
+            planet::handle_message(
+                &mut h.explorer,
+                PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 1 },
+            )
+            .unwrap();
+            h.send_to_explorer_from_planet(PlanetToExplorer::Stopped);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.planet_receiver.try_recv(),
+                Ok(ExplorerToPlanet::GenerateResourceRequest { .. })
+            ));
+        }
+
+        /// One known cell and a generation without a proper answer: the cell stays
+        /// reserved, so the second generation waits for fresh energy cell data
+        #[test]
+        fn test_energy_reservation_blocks_unaffordable_generation() {
+            let mut h = TestStruct::new_with_params(1, 100, 1);
+            h.explorer.manual_mode_off();
+            h.explorer.update_neighbors(100, vec![]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::from([BasicResourceType::Carbon]));
+            info.set_complex_resources(HashSet::new());
+
+            // an unexpected answer does not tell whether the cell was used
+            h.send_to_explorer_from_planet(PlanetToExplorer::SupportedResourceResponse {
+                resource_list: HashSet::new(),
+            });
+            h.explorer.action_queue.clear();
+            for _ in 0..2 {
+                h.explorer
+                    .action_queue
+                    .push_back(ExplorerAction::GenerateOrCombine);
+            }
+
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.planet_receiver.try_recv(),
+                Ok(ExplorerToPlanet::GenerateResourceRequest { .. })
+            ));
+            assert_eq!(h.explorer.reserved_energy_cells(100), 1);
+            assert_eq!(h.explorer.affordable_energy_cells(), 0);
+
+            h.explorer.execute_ai_action();
+            assert!(
+                h.planet_receiver.try_recv().is_err(),
+                "the only cell is already reserved"
+            );
+
+            // fresh energy cell data releases the reservation
+            planet::handle_message(
+                &mut h.explorer,
+                PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 1 },
+            )
+            .unwrap();
+            assert_eq!(h.explorer.reserved_energy_cells(100), 0);
+            h.send_to_explorer_from_planet(PlanetToExplorer::Stopped);
+            h.explorer.execute_ai_action();
+            assert!(matches!(
+                h.planet_receiver.try_recv(),
+                Ok(ExplorerToPlanet::GenerateResourceRequest { .. })
+            ));
+        }
+    }
+
+    mod strategy_tests {
+        use super::*;
+        use crate::components::tommy_explorer::actions::ExplorerAction;
+
+        /// 100 is fully known and provides nothing, its neighbour 200 provides every
+        /// resource and its neighbour 300 is still unknown
+        fn setup_goal_next_to_frontier(strategy: Strategy) -> TestStruct {
+            let mut h = TestStruct::new();
+            h.explorer.manual_mode_off();
+            h.explorer.set_strategy(strategy);
+            h.explorer.update_neighbors(100, vec![200, 300]);
+            h.explorer.update_neighbors(200, vec![100]);
+            let info = h.explorer.get_planet_info_mut(100).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            let info = h.explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::from([
+                BasicResourceType::Oxygen,
+                BasicResourceType::Hydrogen,
+                BasicResourceType::Carbon,
+                BasicResourceType::Silicon,
+            ]));
+            info.set_complex_resources(HashSet::from([
+                ComplexResourceType::Water,
+                ComplexResourceType::Life,
+                ComplexResourceType::Diamond,
+                ComplexResourceType::Robot,
+                ComplexResourceType::AIPartner,
+            ]));
+            h
+        }
+
+        /// Runs a Move action and returns the planet the explorer asked to travel to.
+        fn travel_destination(h: &mut TestStruct) -> u32 {
+            h.explorer.move_queue.clear();
+            h.explorer.action_queue.clear();
+            h.explorer.action_queue.push_back(ExplorerAction::Move);
+            h.explorer.execute_ai_action();
+            match h.recv_from_explorer_to_orch() {
+                ExplorerToOrchestrator::TravelToPlanetRequest { dst_planet_id, .. } => {
+                    dst_planet_id
+                }
+                other => panic!("expected a TravelToPlanetRequest, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_default_strategy_is_full_explore() {
+            let h = TestStruct::new();
+            assert_eq!(h.explorer.strategy(), Strategy::FullExplore);
+        }
+
+        /// FullExplore surveys 300 first and goes after the resources only once the
+        /// topology is fully discovered
+        #[test]
+        fn test_full_explore_surveys_until_fully_discovered() {
+            let mut h = setup_goal_next_to_frontier(Strategy::FullExplore);
+            assert!(h.explorer.goal_reachable());
+            assert!(!h.explorer.topology.is_fully_discovered());
+            assert_eq!(travel_destination(&mut h), 300);
+
+            let info = h.explorer.get_planet_info_mut(300).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            info.set_neighbours(HashSet::from([100]));
+            assert!(h.explorer.topology.is_fully_discovered());
+            assert_eq!(travel_destination(&mut h), 200);
+        }
+
+        /// GoalDirected stops surveying as soon as the goal can be obtained
+        #[test]
+        fn test_goal_directed_stops_exploring_once_goal_is_attainable() {
+            let mut h = setup_goal_next_to_frontier(Strategy::GoalDirected);
+            assert!(!h.explorer.topology.is_fully_discovered());
+            assert_eq!(travel_destination(&mut h), 200);
+
+            // without Silicon the goal cannot be obtained yet, so it keeps exploring
+            let info = h.explorer.get_planet_info_mut(200).unwrap();
+            info.set_basic_resources(HashSet::from([BasicResourceType::Carbon]));
+            assert!(!h.explorer.goal_reachable());
+            assert_eq!(travel_destination(&mut h), 300);
+        }
+
+        /// Greedy goes to the nearest useful planet, the provider winning a tie
+        #[test]
+        fn test_greedy_goes_to_the_nearest_useful_planet() {
+            let mut h = setup_goal_next_to_frontier(Strategy::Greedy);
+            assert_eq!(travel_destination(&mut h), 200);
+
+            // the only provider is now two jumps away, behind 300
+            h.explorer.update_neighbors(100, vec![300, 400]);
+            h.explorer.update_neighbors(400, vec![100, 200]);
+            h.explorer.update_neighbors(200, vec![400]);
+            let info = h.explorer.get_planet_info_mut(400).unwrap();
+            info.set_basic_resources(HashSet::new());
+            info.set_complex_resources(HashSet::new());
+            assert_eq!(travel_destination(&mut h), 300);
+        }
     }
 
     #[test]
@@ -2330,8 +3498,7 @@ mod explorer_full_tests {
 
                 // start planet AI
                 orch.planet_channels[&0]
-                    .0
-                    .send(OrchestratorToPlanet::StartPlanetAI)
+                    .send_to_planet(OrchestratorToPlanet::StartPlanetAI)
                     .unwrap();
 
                 // wait for the planet to be running
@@ -2356,8 +3523,7 @@ mod explorer_full_tests {
 
                 // start explorer ai
                 orch.explorer_channels[&0]
-                    .0
-                    .send(OrchestratorToExplorer::StartExplorerAI)
+                    .send_to_explorer(OrchestratorToExplorer::StartExplorerAI)
                     .unwrap();
 
                 // wait for the explorer to be running
@@ -2385,47 +3551,14 @@ mod explorer_full_tests {
                 println!("[TEST] simulation complete, send kill explorer");
 
                 // kill explorer
-                orch.explorer_channels[&0]
-                    .0
-                    .send(OrchestratorToExplorer::KillExplorer)
-                    .unwrap();
-
-                // wait for the kill explorer response
-                let deadline = std::time::Instant::now() + Duration::from_millis(50);
-                loop {
-                    orch.handle_game_messages().unwrap();
-                    if orch.explorers_info.get_status(&0).unwrap() == Status::Dead {
-                        break;
-                    }
-                    if std::time::Instant::now() > deadline {
-                        println!("[TEST WARNING] Orchestrator didn't set Explorer to Dead. Moving on...");
-                        break;
-                    }
-                    thread::sleep(Duration::from_millis(10));
+                if let Err(err) = orch.kill_explorer(0, Duration::from_millis(50)) {
+                    println!("[TEST WARNING] {}. Moving on...", err);
                 }
                 println!("[TEST] explorer 0 dead");
 
                 // kill planet
-                orch.planet_channels[&0]
-                    .0
-                    .send(OrchestratorToPlanet::KillPlanet)
-                    .unwrap();
-
-                // wait for the kill planet response
-                let deadline = std::time::Instant::now() + Duration::from_millis(50);
-                loop {
-                    match orch.handle_game_messages() {
-                        Ok(_) => {}
-                        Err(_) => break,
-                    }
-                    if orch.planets_info.get_status(&0) == Status::Dead {
-                        break;
-                    }
-                    if std::time::Instant::now() > deadline {
-                        println!("[TEST WARNING] Orchestrator didn't set Planet to Dead. Test finished.");
-                        break;
-                    }
-                    thread::sleep(Duration::from_millis(10));
+                if let Err(err) = orch.kill_planet(0, Duration::from_millis(50)) {
+                    println!("[TEST WARNING] {}. Test finished.", err);
                 }
                 println!("[TEST] planet 0 dead. Full simulation passed!");
             }
@@ -2434,4 +3567,59 @@ mod explorer_full_tests {
             }
         }
     }
+
+    /// Handles the orchestrator messages for the given time.
+    fn run_orchestrator(orch: &mut Orchestrator, duration: Duration) {
+        let deadline = std::time::Instant::now() + duration;
+        while std::time::Instant::now() < deadline {
+            orch.handle_game_messages().unwrap();
+        }
+    }
+
+    /// The planet cannot combine the Diamond because its charged cells were used to
+    /// generate the carbons: both carbons go back in the bag and the orchestrator gets
+    /// the failure
+    #[test]
+    fn test_failed_combine_restores_ingredients() {
+        let mut orch = Orchestrator::new().unwrap();
+        let content = format!("0,{}", PlanetType::BlackAdidasShoe as u32);
+        orch.initialize_galaxy_by_content(&content).unwrap();
+        orch.start_all_planet_ais().unwrap();
+        orch.add_tommy_explorer(0, 0).unwrap();
+        run_orchestrator(&mut orch, Duration::from_millis(100));
+
+        let planet_channel = orch.planet_channels[&0].to_planet().clone();
+        for _ in 0..2 {
+            orch.send_sunray(0, &planet_channel).unwrap();
+        }
+        run_orchestrator(&mut orch, Duration::from_millis(100));
+        for _ in 0..2 {
+            orch.send_generate_resource_request(0, BasicResourceType::Carbon)
+                .unwrap();
+        }
+        run_orchestrator(&mut orch, Duration::from_millis(200));
+
+        orch.send_combine_resource_request(0, ComplexResourceType::Diamond)
+            .unwrap();
+        let msg = orch
+            .receiver_orch_explorer
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            msg,
+            ExplorerToOrchestrator::CombineResourceResponse {
+                explorer_id: 0,
+                generated: Err(_)
+            }
+        ));
+
+        orch.send_bag_content_request(0).unwrap();
+        run_orchestrator(&mut orch, Duration::from_millis(100));
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        let bag = &orch.explorers_info.get(&0).unwrap().bag;
+        assert_eq!(bag.iter().filter(|r| **r == carbon).count(), 2);
+
+        let _ = orch.kill_explorer(0, Duration::from_millis(50));
+        let _ = orch.kill_planet(0, Duration::from_millis(50));
+    }
 }
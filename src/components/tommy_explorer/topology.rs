@@ -2,6 +2,33 @@ use common_game::components::resource::{BasicResourceType, ComplexResourceType};
 use common_game::utils::ID;
 use std::collections::{HashMap, HashSet};
 
+/// Information that an explorer gathers about a planet, as stored by [`TopologyManager`].
+///
+/// A planet met for the first time gets the `Default` value.
+pub trait PlanetKnowledge: Default {
+    /// Gets the basic resources, None if they are not known yet.
+    fn get_basic_resources(&self) -> Option<&HashSet<BasicResourceType>>;
+
+    /// Gets the complex resources, None if they are not known yet.
+    fn get_complex_resources(&self) -> Option<&HashSet<ComplexResourceType>>;
+
+    /// Gets the neighbours, None if they are not known yet.
+    fn get_neighbours(&self) -> Option<&HashSet<ID>>;
+
+    /// Gets the neighbours to update them, None if they are not known yet.
+    fn neighbours_mut(&mut self) -> Option<&mut HashSet<ID>>;
+
+    /// Updates the neighbours information.
+    fn set_neighbours(&mut self, neighbours: HashSet<ID>);
+
+    /// Checks if the resources and the neighbours of the planet are all known.
+    fn is_complete(&self) -> bool {
+        self.get_basic_resources().is_some()
+            && self.get_complex_resources().is_some()
+            && self.get_neighbours().is_some()
+    }
+}
+
 /// Struct that contains information about a planet.
 #[derive(Debug, Clone)]
 pub struct PlanetInfo {
@@ -80,45 +107,108 @@ impl Default for PlanetInfo {
     }
 }
 
+impl PlanetKnowledge for PlanetInfo {
+    fn get_basic_resources(&self) -> Option<&HashSet<BasicResourceType>> {
+        self.basic_resources.as_ref()
+    }
+
+    fn get_complex_resources(&self) -> Option<&HashSet<ComplexResourceType>> {
+        self.complex_resources.as_ref()
+    }
+
+    fn get_neighbours(&self) -> Option<&HashSet<ID>> {
+        self.neighbours.as_ref()
+    }
+
+    fn neighbours_mut(&mut self) -> Option<&mut HashSet<ID>> {
+        self.neighbours.as_mut()
+    }
+
+    fn set_neighbours(&mut self, neighbours: HashSet<ID>) {
+        self.neighbours = Some(neighbours);
+    }
+}
+
 /// Struct that manages the topology information for all known planets.
+///
+/// The information kept for each planet is a [`PlanetInfo`] unless another
+/// [`PlanetKnowledge`] is given, so explorers that track more about a planet
+/// can still share the pathfinding.
 // ex TopologyInfo
 #[derive(Debug)]
-pub struct TopologyManager {
-    planets: HashMap<ID, PlanetInfo>,
+pub struct TopologyManager<P = PlanetInfo> {
+    planets: HashMap<ID, P>,
+    /// Planets that answered Stopped, until they answer anything else.
+    stopped: HashSet<ID>,
 }
 
 impl TopologyManager {
     /// Creates a new TopologyManager with a starting planet.
     pub fn new(starting_planet_id: ID) -> Self {
+        Self::with_starting_planet(starting_planet_id)
+    }
+}
+
+impl<P: PlanetKnowledge> TopologyManager<P> {
+    /// Creates a new TopologyManager with a starting planet, for any kind of planet information.
+    pub fn with_starting_planet(starting_planet_id: ID) -> Self {
         let mut planets = HashMap::new();
-        planets.insert(starting_planet_id, PlanetInfo::new());
-        Self { planets }
+        planets.insert(starting_planet_id, P::default());
+        Self {
+            planets,
+            stopped: HashSet::new(),
+        }
     }
 
     /// Gets information about a planet, creating an entry if it doesn't exist.
-    #[cfg(test)]
-    pub fn get_or_create(&mut self, planet_id: ID) -> &mut PlanetInfo {
-        self.planets
-            .entry(planet_id)
-            .or_insert_with(PlanetInfo::new)
+    pub fn get_or_create(&mut self, planet_id: ID) -> &mut P {
+        self.planets.entry(planet_id).or_default()
+    }
+
+    /// Gets information about a planet, creating it with `create` if it doesn't exist.
+    pub fn get_or_insert_with(&mut self, planet_id: ID, create: impl FnOnce() -> P) -> &mut P {
+        self.planets.entry(planet_id).or_insert_with(create)
+    }
+
+    /// Sets the information about a planet, replacing the previous one.
+    pub fn insert(&mut self, planet_id: ID, info: P) {
+        self.planets.insert(planet_id, info);
     }
 
     /// Gets information about a planet (read-only).
-    pub fn get(&self, planet_id: ID) -> Option<&PlanetInfo> {
+    pub fn get(&self, planet_id: ID) -> Option<&P> {
         self.planets.get(&planet_id)
     }
 
     /// Gets mutable information about a planet.
-    pub fn get_mut(&mut self, planet_id: ID) -> Option<&mut PlanetInfo> {
+    pub fn get_mut(&mut self, planet_id: ID) -> Option<&mut P> {
         self.planets.get_mut(&planet_id)
     }
 
+    /// Gets the known neighbours of a planet.
+    ///
+    /// Returns None if the planet is unknown or its neighbours have not been received yet.
+    pub fn neighbours_of(&self, planet_id: ID) -> Option<&HashSet<ID>> {
+        self.planets.get(&planet_id)?.get_neighbours()
+    }
+
+    /// Gets the known neighbours of a planet sorted by id, so that iterating them
+    /// is deterministic.
+    ///
+    /// Returns an empty vector if the neighbours are not known.
+    pub fn neighbour_ids_sorted(&self, planet_id: ID) -> Vec<ID> {
+        let mut ids: Vec<ID> = self
+            .neighbours_of(planet_id)
+            .map(|neighbours| neighbours.iter().copied().collect())
+            .unwrap_or_default();
+        ids.sort_unstable();
+        ids
+    }
+
     /// Adds multiple planets to the topology.
     pub fn add_planets(&mut self, planet_ids: &[ID]) {
         for &planet_id in planet_ids {
-            self.planets
-                .entry(planet_id)
-                .or_insert_with(PlanetInfo::new);
+            self.planets.entry(planet_id).or_default();
         }
     }
 
@@ -128,18 +218,17 @@ impl TopologyManager {
         self.add_planets(&neighbours);
 
         // update the planet's neighbour information
-        if let Some(info) = self.planets.get_mut(&planet_id) {
-            info.set_neighbours(neighbours.into_iter().collect());
-        }
+        self.get_or_create(planet_id)
+            .set_neighbours(neighbours.into_iter().collect());
     }
 
     /// Clears all topology information.
     pub fn clear(&mut self) {
         self.planets.clear();
+        self.stopped.clear();
     }
 
     /// Returns all known planet IDs.
-    #[cfg(test)]
     pub fn known_planets(&self) -> Vec<ID> {
         self.planets.keys().copied().collect()
     }
@@ -156,7 +245,7 @@ impl TopologyManager {
 
         for (id, info) in &self.planets {
             all_known_ids.insert(*id);
-            if let Some(neighbours) = &info.neighbours {
+            if let Some(neighbours) = info.get_neighbours() {
                 for &neighbor in neighbours {
                     all_known_ids.insert(neighbor);
                 }
@@ -185,29 +274,85 @@ impl TopologyManager {
     //     })
     // }
 
-    /// Remove the planet from the explorer memory
-    pub fn mark_as_dead(&mut self, planet_id: ID) {
-        self.planets.remove(&planet_id);
-        for info in self.planets.values_mut() {
-            if let Some(neighbours) = &mut info.neighbours {
-                neighbours.remove(&planet_id);
+    /// Removes the edge between two planets from their known neighbours.
+    pub fn remove_edge(&mut self, from: ID, to: ID) {
+        for (planet_id, other) in [(from, to), (to, from)] {
+            if let Some(neighbours) = self
+                .planets
+                .get_mut(&planet_id)
+                .and_then(|info| info.neighbours_mut())
+            {
+                neighbours.remove(&other);
             }
         }
     }
+
+    /// Adds the edge between two planets to their known neighbours.
+    ///
+    /// The planets whose neighbours are not known yet are left untouched, they will
+    /// get the edge with their first neighbours response.
+    pub fn add_edge(&mut self, from: ID, to: ID) {
+        for (planet_id, other) in [(from, to), (to, from)] {
+            let linked = self
+                .planets
+                .get_mut(&planet_id)
+                .and_then(|info| info.neighbours_mut())
+                .is_some_and(|neighbours| neighbours.insert(other));
+            if linked {
+                self.add_planets(&[other]);
+            }
+        }
+    }
+
+    /// Remove the planet from the explorer memory, along with all its edges
+    pub fn mark_as_dead(&mut self, planet_id: ID) {
+        // the dead planet's own neighbours are dropped with it
+        let linked: Vec<ID> = self
+            .planets
+            .iter()
+            .filter(|(_, info)| {
+                info.get_neighbours()
+                    .is_some_and(|neighbours| neighbours.contains(&planet_id))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for other in linked {
+            self.remove_edge(planet_id, other);
+        }
+        self.planets.remove(&planet_id);
+        self.stopped.remove(&planet_id);
+    }
+
+    /// Remembers that a planet answered Stopped, see [`is_running`](Self::is_running).
+    pub fn mark_as_stopped(&mut self, planet_id: ID) {
+        self.stopped.insert(planet_id);
+    }
+
+    /// Forgets that a planet was stopped, as it answered a request again.
+    pub fn mark_as_running(&mut self, planet_id: ID) {
+        self.stopped.remove(&planet_id);
+    }
+
+    /// Checks if a known planet is running, i.e. it did not answer Stopped last.
+    ///
+    /// The dead planets are removed from the topology, so they are never running.
+    pub fn is_running(&self, planet_id: ID) -> bool {
+        self.contains(planet_id) && !self.stopped.contains(&planet_id)
+    }
 }
 
 use std::collections::VecDeque;
 
 // Definiamo il nostro iteratore con la parent_map inclusa
-pub struct BFSPathIterator<'a> {
-    topology: &'a TopologyManager,
+pub struct BFSPathIterator<'a, P = PlanetInfo> {
+    topology: &'a TopologyManager<P>,
     queue: VecDeque<u32>,
     visited: HashSet<u32>,
     parent_map: HashMap<u32, u32>,
 }
 
-impl<'a> BFSPathIterator<'a> {
-    pub fn new(topology: &'a TopologyManager, start_node: u32) -> Self {
+impl<'a, P: PlanetKnowledge> BFSPathIterator<'a, P> {
+    pub fn new(topology: &'a TopologyManager<P>, start_node: u32) -> Self {
         let mut queue = VecDeque::new();
         let mut visited = HashSet::new();
         queue.push_back(start_node);
@@ -232,20 +377,17 @@ impl<'a> BFSPathIterator<'a> {
     }
 }
 
-impl<'a> Iterator for BFSPathIterator<'a> {
+impl<'a, P: PlanetKnowledge> Iterator for BFSPathIterator<'a, P> {
     type Item = u32;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(current) = self.queue.pop_front() {
-            if let Some(info) = self.topology.get(current) {
-                if let Some(neighbours) = &info.neighbours {
-                    for &neighbor in neighbours {
-                        if !self.visited.contains(&neighbor) {
-                            self.visited.insert(neighbor);
-                            self.parent_map.insert(neighbor, current);
-                            self.queue.push_back(neighbor);
-                        }
-                    }
+            // sorted neighbours make the paths found with the iterator deterministic
+            for neighbor in self.topology.neighbour_ids_sorted(current) {
+                if !self.visited.contains(&neighbor) {
+                    self.visited.insert(neighbor);
+                    self.parent_map.insert(neighbor, current);
+                    self.queue.push_back(neighbor);
                 }
             }
             return Some(current);
@@ -254,9 +396,9 @@ impl<'a> Iterator for BFSPathIterator<'a> {
     }
 }
 
-impl TopologyManager {
+impl<P: PlanetKnowledge> TopologyManager<P> {
     /// Crea un nuovo iteratore BFS a partire da un nodo
-    pub fn bfs_iter(&self, start: u32) -> BFSPathIterator<'_> {
+    pub fn bfs_iter(&self, start: u32) -> BFSPathIterator<'_, P> {
         BFSPathIterator::new(self, start)
     }
 }
@@ -1,4 +1,29 @@
+//! Galaxy simulation: an orchestrator driving planets and explorers.
+//!
+//! A minimal headless game using only the [`prelude`]:
+//!
+//! ```no_run
+//! use omc_galaxy::prelude::*;
+//!
+//! fn main() -> Result<(), String> {
+//!     let mut orch = Orchestrator::new()?;
+//!     orch.initialize_galaxy_by_file("galaxy.txt")?;
+//!     // a tommy explorer with id 1 on planet 0
+//!     orch.start_all(&[], &[(1, 0)])?;
+//!     orch.run_sequence("sunray\npause 100ms\nasteroid")?;
+//!
+//!     for event in orch.gui_messages.drain(..) {
+//!         if let OrchestratorEvent::PlanetDestroyed { planet_id } = event {
+//!             println!("planet {} destroyed", planet_id);
+//!         }
+//!     }
+//!     println!("{}", orch.game_report().to_markdown());
+//!     Ok(())
+//! }
+//! ```
+
 mod components;
+pub mod prelude;
 pub mod utils;
 
 //Orchestrator example
@@ -6,9 +31,13 @@ pub use utils::{GalaxyTopology, PlanetInfoMap};
 
 //Both GUIs
 pub use components::orchestrator::Orchestrator;
+pub use components::orchestrator::init::{
+    ExplorerKind, ExplorerSpawnConfig, ParsedGalaxy, parse_galaxy_description,
+};
+pub use components::orchestrator::sequence::OrchestratorAction;
 
 //Bevy-GUI
 pub use components::orchestrator::OrchestratorEvent;
 pub use utils::GalaxySnapshot;
 //Ratatui-GUI
-pub use utils::{ExplorerStatusNotLock, PlanetStatusNotLock, Status};
+pub use utils::{ExplorerStatusNotLock, PlanetStatusNotLock, Status, StatusMapExt, StatusTable};
@@ -0,0 +1,25 @@
+//! Types needed to embed the galaxy in another program.
+//!
+//! `use omc_galaxy::prelude::*;` brings in the orchestrator, the types it hands
+//! to a GUI and the protocol enums, so a custom `main` does not need to depend on
+//! the module layout of the crate or on `common_game` directly.
+
+pub use crate::components::orchestrator::channels::{ExplorerHandle, PlanetHandle};
+pub use crate::components::orchestrator::handlers::MessageLoopStatus;
+pub use crate::components::orchestrator::init::{
+    ExplorerKind, ExplorerSpawnConfig, ParsedGalaxy, parse_galaxy_description,
+};
+pub use crate::components::orchestrator::sequence::{OrchestratorAction, parse_sequence};
+pub use crate::components::orchestrator::{ManualCommand, Orchestrator, OrchestratorEvent};
+pub use crate::components::tommy_explorer::bag::BagType;
+pub use crate::utils::registry::PlanetType;
+pub use crate::utils::{
+    Cadence, GalaxySnapshot, GalaxyState, GalaxyUpdate, GameClock, GameReport, Launch, LaunchInfo,
+    SnapshotDelta, Status, StatusTable,
+};
+
+pub use common_game::protocols::orchestrator_explorer::{
+    ExplorerToOrchestrator, OrchestratorToExplorer,
+};
+pub use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+pub use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
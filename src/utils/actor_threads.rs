@@ -0,0 +1,146 @@
+use common_game::logging::{ActorType, Channel, EventType, LogEvent, Participant};
+use common_game::utils::ID;
+use crossbeam_channel::Sender;
+use logging_utils::warning_payload;
+use std::cell::RefCell;
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+use std::thread;
+
+use crate::utils::registry::PlanetType;
+use crate::utils::tracing::TracedActor;
+
+static PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Where the panics of the current thread are reported, set only on the
+    /// threads started by [`spawn_actor_thread`].
+    static CRASH_OWNER: RefCell<Option<Sender<ActorCrash>>> = const { RefCell::new(None) };
+}
+
+/// A panic of an actor thread, reported by the panic hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActorCrash {
+    pub thread_name: String,
+    /// The actor running on the thread, None if the thread name is not an actor name.
+    pub actor: Option<TracedActor>,
+    pub message: String,
+}
+
+/// Returns the name of the thread of a planet, e.g. `planet-3-OneMillionCrabs`.
+pub fn planet_thread_name(planet_id: ID, planet_type: PlanetType) -> String {
+    format!("planet-{}-{:?}", planet_id, planet_type)
+}
+
+/// Returns the name of the thread of an explorer, e.g. `explorer-1-tommy`.
+pub fn explorer_thread_name(explorer_id: ID, kind: &str) -> String {
+    format!("explorer-{}-{}", explorer_id, kind)
+}
+
+/// Returns the actor of a thread named by [`planet_thread_name`] or
+/// [`explorer_thread_name`].
+pub fn parse_thread_name(name: &str) -> Option<TracedActor> {
+    let mut parts = name.splitn(3, '-');
+    let kind = parts.next()?;
+    let id = parts.next()?.parse().ok()?;
+    match kind {
+        "planet" => Some(TracedActor::Planet(id)),
+        "explorer" => Some(TracedActor::Explorer(id)),
+        _ => None,
+    }
+}
+
+/// Spawns a named actor thread.
+///
+/// A panic of the thread is reported only to `owner`, which is the crash
+/// sender of the orchestrator starting the actor.
+///
+/// Returns Err if the thread cannot be created.
+pub fn spawn_actor_thread<F, T>(
+    name: String,
+    owner: Sender<ActorCrash>,
+    f: F,
+) -> Result<thread::JoinHandle<T>, String>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::Builder::new()
+        .name(name.clone())
+        .spawn(move || {
+            CRASH_OWNER.with(|o| *o.borrow_mut() = Some(owner));
+            f()
+        })
+        .map_err(|e| format!("Unable to spawn thread {}: {}", name, e))
+}
+
+/// Installs the panic hook that reports the panics of the actor threads.
+///
+/// The hook is installed only once, the previous hook is still called after it so
+/// the panic message and the backtrace are printed as usual.
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            report_crash(info);
+            previous(info);
+        }));
+    });
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string());
+    match info.location() {
+        Some(location) => format!("{} at {}", payload, location),
+        None => payload,
+    }
+}
+
+fn report_crash(info: &PanicHookInfo) {
+    // the panics of the threads not spawned by an orchestrator (e.g. the test
+    // threads) are not actor crashes
+    let Some(owner) = CRASH_OWNER.try_with(|o| o.borrow().clone()).ok().flatten() else {
+        return;
+    };
+    let thread_name = thread::current().name().unwrap_or("<unnamed>").to_string();
+    let Some(actor) = parse_thread_name(&thread_name) else {
+        return;
+    };
+    let message = panic_message(info);
+
+    let (actor_type, actor_id, event_type) = match actor {
+        TracedActor::Planet(id) => (ActorType::Planet, id, EventType::InternalPlanetAction),
+        TracedActor::Explorer(id) => (ActorType::Explorer, id, EventType::InternalExplorerAction),
+        TracedActor::Orchestrator => (
+            ActorType::Orchestrator,
+            0u32,
+            EventType::InternalOrchestratorAction,
+        ),
+    };
+    //LOG
+    LogEvent::self_directed(
+        Participant::new(actor_type, actor_id),
+        event_type,
+        Channel::Error,
+        warning_payload!(
+            "actor thread panicked",
+            message,
+            "panic hook";
+            "thread" => thread_name
+        ),
+    )
+    .emit();
+    //LOG
+
+    // the orchestrator may already be gone
+    let _ = owner.send(ActorCrash {
+        thread_name,
+        actor: Some(actor),
+        message,
+    });
+}
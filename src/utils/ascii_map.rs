@@ -0,0 +1,77 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::utils::{ExplorerInfoMap, GalaxySnapshot, PlanetInfoMap, Status};
+
+/// Returns the glyph used for a planet status in the ascii map.
+///
+/// `U` (unknown) is used for planets that appear in the topology but have no
+/// entry in the [`PlanetInfoMap`].
+fn status_glyph(status: Option<Status>) -> char {
+    match status {
+        Some(Status::Running) => 'R',
+        Some(Status::Paused) => 'P',
+        Some(Status::Dead) => 'D',
+        None => 'U',
+    }
+}
+
+/// Renders the galaxy as plain text, for headless runs and test failure messages.
+///
+/// Every planet is printed on its own line as `[<status>] <planet_id> <type>`,
+/// followed by `^<explorer ids>` for the alive explorers on it and by the
+/// sorted list of its neighbours. Planets and explorers are iterated in id order,
+/// so the output is deterministic and can be compared in tests.
+///
+/// * `edges` - the edge list returned by `Orchestrator::get_topology()`
+pub fn render_ascii_map(
+    edges: &GalaxySnapshot,
+    planets_info: &PlanetInfoMap,
+    explorers_info: &ExplorerInfoMap,
+) -> String {
+    let mut adjacency: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+    for (id, _) in planets_info.iter() {
+        adjacency.entry(*id).or_default();
+    }
+    for &(a, b) in edges {
+        adjacency.entry(a).or_default().insert(b);
+        adjacency.entry(b).or_default().insert(a);
+    }
+
+    let mut explorers_on: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for (id, info) in explorers_info.iter() {
+        if info.status != Status::Dead {
+            explorers_on
+                .entry(info.current_planet_id)
+                .or_default()
+                .push(*id);
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "galaxy: {} planets, {} edges",
+        adjacency.len(),
+        edges.len()
+    );
+    for (planet_id, neighbours) in &adjacency {
+        let info = planets_info.get_info(*planet_id);
+        let _ = write!(
+            out,
+            "[{}] {}",
+            status_glyph(info.map(|i| i.status)),
+            planet_id
+        );
+        if let Some(info) = info {
+            let _ = write!(out, " {:?}", info.name);
+        }
+        if let Some(explorers) = explorers_on.get(planet_id) {
+            let ids: Vec<String> = explorers.iter().map(|id| id.to_string()).collect();
+            let _ = write!(out, " ^{}", ids.join(","));
+        }
+        let ids: Vec<String> = neighbours.iter().map(|id| id.to_string()).collect();
+        let _ = writeln!(out, " -> [{}]", ids.join(", "));
+    }
+    out
+}
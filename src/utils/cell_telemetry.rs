@@ -0,0 +1,61 @@
+use common_game::utils::ID;
+
+/// Default number of records the telemetry channel holds before dropping new ones.
+pub const DEFAULT_TELEMETRY_CAPACITY: usize = 1024;
+
+/// What changed the charge of an energy cell, as far as the orchestrator can tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellCause {
+    /// Only sunrays charge the cells.
+    Sunray,
+    /// The charge was used to build a rocket.
+    Rocket,
+    /// The charge was spent to generate or combine a resource for an explorer.
+    Resource,
+}
+
+/// Change of the charge of one energy cell of a planet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRecord {
+    pub planet_id: ID,
+    /// Position of the cell among the energy cells of the planet.
+    pub cell_index: usize,
+    /// The new level of the cell.
+    pub charged: bool,
+    pub cause: CellCause,
+}
+
+/// Returns the cells whose charge differs between two states of a planet.
+///
+/// Cells missing from `old_cells` count as discharged. If the planet got a rocket
+/// between the two states, the first discharged cell is put down to the rocket and
+/// the others to the resources.
+pub fn cell_changes(
+    planet_id: ID,
+    (old_cells, old_rocket): (&[bool], bool),
+    (new_cells, new_rocket): (&[bool], bool),
+) -> Vec<CellRecord> {
+    let mut rocket_built = new_rocket && !old_rocket;
+    let mut changes = Vec::new();
+    for (cell_index, charged) in new_cells.iter().copied().enumerate() {
+        let was_charged = old_cells.get(cell_index).copied().unwrap_or(false);
+        if charged == was_charged {
+            continue;
+        }
+        let cause = if charged {
+            CellCause::Sunray
+        } else if rocket_built {
+            rocket_built = false;
+            CellCause::Rocket
+        } else {
+            CellCause::Resource
+        };
+        changes.push(CellRecord {
+            planet_id,
+            cell_index,
+            charged,
+            cause,
+        });
+    }
+    changes
+}
@@ -0,0 +1,28 @@
+use crossbeam_channel::Receiver;
+
+/// Non-blocking helpers for crossbeam receivers.
+pub trait CrossbeamChannelExt<T> {
+    /// Takes every message currently queued, without waiting for new ones.
+    ///
+    /// Stops at the first empty or disconnected state, so the messages sent while
+    /// draining may or may not be part of the result.
+    fn try_recv_all(&self) -> Vec<T>;
+
+    /// Number of messages currently queued, for queue depth monitoring.
+    fn len(&self) -> usize;
+
+    /// Checks if no message is queued.
+    fn is_empty(&self) -> bool {
+        CrossbeamChannelExt::len(self) == 0
+    }
+}
+
+impl<T> CrossbeamChannelExt<T> for Receiver<T> {
+    fn try_recv_all(&self) -> Vec<T> {
+        self.try_iter().collect()
+    }
+
+    fn len(&self) -> usize {
+        Receiver::len(self)
+    }
+}
@@ -0,0 +1,32 @@
+use common_game::protocols::orchestrator_explorer::OrchestratorToExplorer;
+use std::collections::VecDeque;
+use std::mem;
+
+/// Checks if answering the message twice gives nothing more than answering it once.
+///
+/// Only the read-only requests qualify; lifecycle commands and the messages that
+/// change the explorer or its bag (e.g. `MoveToPlanet`) do not.
+pub fn is_idempotent_request(msg: &OrchestratorToExplorer) -> bool {
+    matches!(
+        msg,
+        OrchestratorToExplorer::CurrentPlanetRequest
+            | OrchestratorToExplorer::SupportedResourceRequest
+            | OrchestratorToExplorer::SupportedCombinationRequest
+            | OrchestratorToExplorer::BagContentRequest
+    )
+}
+
+/// Appends an orchestrator message the explorer cannot handle yet to its buffer.
+///
+/// An idempotent request replaces the buffered requests of the same kind, so an
+/// orchestrator that keeps asking while the explorer is busy gets a single answer.
+pub fn buffer_orchestrator_msg(
+    buffer: &mut VecDeque<OrchestratorToExplorer>,
+    msg: OrchestratorToExplorer,
+) {
+    if is_idempotent_request(&msg) {
+        let kind = mem::discriminant(&msg);
+        buffer.retain(|buffered| mem::discriminant(buffered) != kind);
+    }
+    buffer.push_back(msg);
+}
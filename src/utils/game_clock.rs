@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+/// Simulated time between two sunrays in the default [`Cadence`].
+pub const DEFAULT_SUNRAY_PERIOD: Duration = Duration::from_secs(1);
+
+/// How often the celestial bodies are launched, in simulated time.
+///
+/// The cadence does not depend on how often the game loop runs: a faster loop
+/// only splits the same simulated time in more steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cadence {
+    /// Simulated time between two sunrays, None to send no sunray.
+    pub sunray_period: Option<Duration>,
+    /// Simulated time between two asteroids, None to send no asteroid.
+    pub asteroid_period: Option<Duration>,
+}
+
+impl Default for Cadence {
+    fn default() -> Self {
+        Cadence {
+            sunray_period: Some(DEFAULT_SUNRAY_PERIOD),
+            asteroid_period: None,
+        }
+    }
+}
+
+/// A celestial body whose launch is due, see [`GameClock::advance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Launch {
+    Sunray,
+    Asteroid,
+}
+
+/// Simulated time of the game and deadlines of the next launches.
+///
+/// Each deadline is computed from the previous one and the period of the
+/// [`Cadence`], so the number of launches over a given simulated time is the
+/// same whatever the steps the clock is advanced by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameClock {
+    cadence: Cadence,
+    now: Duration,
+    next_sunray: Option<Duration>,
+    next_asteroid: Option<Duration>,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        GameClock::new(Cadence::default())
+    }
+}
+
+/// Returns the launches of one kind due up to `now`, with their deadlines,
+/// and moves `next` to the first deadline after `now`.
+///
+/// A zero period counts as no period, otherwise it would never stop.
+fn take_due(
+    next: &mut Option<Duration>,
+    period: Option<Duration>,
+    now: Duration,
+    launch: Launch,
+) -> Vec<(Duration, Launch)> {
+    let mut due = Vec::new();
+    let Some(period) = period.filter(|p| !p.is_zero()) else {
+        *next = None;
+        return due;
+    };
+    while let Some(deadline) = *next {
+        if deadline > now {
+            break;
+        }
+        due.push((deadline, launch));
+        *next = Some(deadline + period);
+    }
+    due
+}
+
+impl GameClock {
+    /// Creates a clock at simulated time zero.
+    ///
+    /// The first launch of each kind is due one period after the start.
+    pub fn new(cadence: Cadence) -> Self {
+        let mut clock = GameClock {
+            cadence,
+            now: Duration::ZERO,
+            next_sunray: None,
+            next_asteroid: None,
+        };
+        clock.set_cadence(cadence);
+        clock
+    }
+
+    /// Returns the simulated time since the start.
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    pub fn cadence(&self) -> Cadence {
+        self.cadence
+    }
+
+    /// Changes the cadence; the next launches are due one new period from now.
+    pub fn set_cadence(&mut self, cadence: Cadence) {
+        let first_deadline = |period: Option<Duration>| {
+            period
+                .filter(|p| !p.is_zero())
+                .map(|period| self.now + period)
+        };
+        self.next_sunray = first_deadline(cadence.sunray_period);
+        self.next_asteroid = first_deadline(cadence.asteroid_period);
+        self.cadence = cadence;
+    }
+
+    /// Moves the simulated time forward by `elapsed`.
+    ///
+    /// Returns the launches whose deadline has passed, in the order of their
+    /// deadlines; at the same deadline the sunray comes first.
+    pub fn advance(&mut self, elapsed: Duration) -> Vec<Launch> {
+        self.now += elapsed;
+        let mut due = take_due(
+            &mut self.next_sunray,
+            self.cadence.sunray_period,
+            self.now,
+            Launch::Sunray,
+        );
+        due.extend(take_due(
+            &mut self.next_asteroid,
+            self.cadence.asteroid_period,
+            self.now,
+            Launch::Asteroid,
+        ));
+        // stable sort, so the sunrays stay before the asteroids due at the same time
+        due.sort_by_key(|(deadline, _)| *deadline);
+        due.into_iter().map(|(_, launch)| launch).collect()
+    }
+}
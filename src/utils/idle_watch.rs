@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use common_game::components::resource::ResourceType;
+use common_game::utils::ID;
+
+/// What the orchestrator does to an explorer that made no progress for too long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nudge {
+    /// Sends ResetExplorerAI, the explorer starts over with an empty bag.
+    ResetAi,
+    /// Sends the links of the explorer's planet again through its topology channel.
+    PushKnowledge,
+    /// Moves the explorer to a random living neighbour of its planet, as a regular
+    /// travel would.
+    Relocate,
+}
+
+/// When and how running explorers that made no progress are nudged, see
+/// [`Orchestrator::nudge_idle_explorers`](crate::Orchestrator::nudge_idle_explorers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdlePolicy {
+    /// Ticks without progress after which an explorer is nudged.
+    pub idle_ticks: u64,
+    pub nudge: Nudge,
+}
+
+/// What the orchestrator has to do with an explorer it observed, see [`IdleWatch::observe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Observation {
+    /// The explorer made progress recently, or a probe is waiting for its answer.
+    Active,
+    /// No progress for too long: the explorer must be asked for its planet and bag,
+    /// and [`IdleWatch::probe_answered`] called when the bag arrives.
+    Probe,
+    /// The answer to the probe showed no progress either: the explorer must be nudged.
+    Idle,
+}
+
+/// State of the probe sent to an explorer that looked idle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProbeState {
+    Sent,
+    Answered,
+}
+
+/// Last progress of an explorer: its planet and bag, and the tick they were seen at.
+#[derive(Debug, Clone, PartialEq)]
+struct Progress {
+    tick: u64,
+    planet_id: ID,
+    bag: Vec<ResourceType>,
+    probe: Option<ProbeState>,
+}
+
+/// Progress of the explorers as seen by the orchestrator, and the nudges they got.
+///
+/// An explorer makes progress when its planet or its bag changes; reporting the same
+/// planet and bag again does not count. Explorers do not report their bag on their
+/// own, so an explorer that looks idle is probed first and nudged only if the answer
+/// shows no progress either.
+#[derive(Debug, Clone, Default)]
+pub struct IdleWatch {
+    progress: HashMap<ID, Progress>,
+    nudges: HashMap<ID, u32>,
+}
+
+impl IdleWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the planet and the bag of an explorer at `tick`.
+    ///
+    /// Once the explorer made no progress for `idle_ticks` ticks it is probed; if the
+    /// answer to the probe shows no progress either, the nudge is counted and the
+    /// explorer gets `idle_ticks` more ticks before the next probe.
+    pub fn observe(
+        &mut self,
+        explorer_id: ID,
+        tick: u64,
+        planet_id: ID,
+        bag: &[ResourceType],
+        idle_ticks: u64,
+    ) -> Observation {
+        let progress = self
+            .progress
+            .entry(explorer_id)
+            .or_insert_with(|| Progress {
+                tick,
+                planet_id,
+                bag: bag.to_vec(),
+                probe: None,
+            });
+        if progress.planet_id != planet_id || progress.bag != bag {
+            *progress = Progress {
+                tick,
+                planet_id,
+                bag: bag.to_vec(),
+                probe: None,
+            };
+            return Observation::Active;
+        }
+        if tick.saturating_sub(progress.tick) < idle_ticks {
+            return Observation::Active;
+        }
+        match progress.probe {
+            None => {
+                progress.probe = Some(ProbeState::Sent);
+                Observation::Probe
+            }
+            Some(ProbeState::Sent) => Observation::Active,
+            Some(ProbeState::Answered) => {
+                progress.tick = tick;
+                progress.probe = None;
+                *self.nudges.entry(explorer_id).or_insert(0) += 1;
+                Observation::Idle
+            }
+        }
+    }
+
+    /// Records that an explorer reported its bag, answering the probe sent to it if any.
+    pub fn probe_answered(&mut self, explorer_id: ID) {
+        if let Some(progress) = self.progress.get_mut(&explorer_id)
+            && progress.probe == Some(ProbeState::Sent)
+        {
+            progress.probe = Some(ProbeState::Answered);
+        }
+    }
+
+    /// Returns how many times an explorer has been nudged.
+    pub fn nudges(&self, explorer_id: ID) -> u32 {
+        self.nudges.get(&explorer_id).copied().unwrap_or(0)
+    }
+
+    /// Forgets the progress of an explorer, e.g. when it stops running; its nudges are kept.
+    pub fn forget(&mut self, explorer_id: ID) {
+        self.progress.remove(&explorer_id);
+    }
+}
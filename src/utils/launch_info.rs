@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use logging_utils::payload;
+
+use crate::utils::{Launch, PlanetInfo};
+
+/// Details of a sunray or an asteroid, taken when the orchestrator sends it.
+///
+/// The bodies made by the forge carry no data of their own, so the details are the
+/// ones the orchestrator knows of the target planet, as last reported by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaunchInfo {
+    pub kind: Launch,
+    /// Number of bodies of this kind sent by the orchestrator so far, this one included.
+    pub sequence: u64,
+    /// Discharged cells of the planet; a sunray sent to a planet without any is lost.
+    pub free_cells: usize,
+    /// Whether the planet had a rocket, i.e. whether an asteroid can be deflected.
+    pub deflectable: bool,
+}
+
+impl LaunchInfo {
+    /// Details of the `sequence`-th body of kind `kind`, sent to the planet described
+    /// by `planet`.
+    ///
+    /// An unknown planet counts as one without free cells nor rocket.
+    pub fn new(kind: Launch, sequence: u64, planet: Option<&PlanetInfo>) -> Self {
+        let (free_cells, deflectable) = match planet {
+            Some(info) => (
+                info.energy_cells
+                    .iter()
+                    .filter(|charged| !**charged)
+                    .count(),
+                info.rocket,
+            ),
+            None => (0, false),
+        };
+        LaunchInfo {
+            kind,
+            sequence,
+            free_cells,
+            deflectable,
+        }
+    }
+
+    /// Payload of the log of the launch to `planet_id`.
+    ///
+    /// Only the details that matter for the kind of body are included: the free cells
+    /// for a sunray and whether it can be deflected for an asteroid.
+    pub fn payload(&self, planet_id: u32) -> BTreeMap<String, String> {
+        let mut p = payload!(
+            "message" => format!("{:?}", self.kind),
+            "planet_id" => planet_id,
+            "sequence" => self.sequence,
+        );
+        match self.kind {
+            Launch::Sunray => p.insert("free_cells".to_string(), self.free_cells.to_string()),
+            Launch::Asteroid => p.insert("deflectable".to_string(), self.deflectable.to_string()),
+        };
+        p
+    }
+}
@@ -1,6 +1,38 @@
+pub mod actor_threads;
+pub mod ascii_map;
+pub mod cell_telemetry;
+pub mod channel_ext;
+pub mod explorer_buffer;
+pub mod game_clock;
+pub mod idle_watch;
+pub mod launch_info;
+pub mod planet_picker;
+pub mod recipes;
 pub mod registry;
+pub mod report;
+pub mod request_ledger;
+pub mod resource_mint;
+pub mod resource_names;
+pub mod snapshot;
 pub mod state_enums;
+pub mod status_merge;
+pub mod status_table;
+pub mod topology_delta;
+pub mod tracing;
 pub mod types;
 
+pub use ascii_map::render_ascii_map;
+pub use cell_telemetry::{CellCause, CellRecord};
+pub use channel_ext::CrossbeamChannelExt;
+pub use game_clock::{Cadence, GameClock, Launch};
+pub use idle_watch::{IdlePolicy, IdleWatch, Nudge, Observation};
+pub use launch_info::LaunchInfo;
+pub use planet_picker::WeightedPlanetPicker;
+pub use report::GameReport;
+pub use resource_names::{ResourceTypeName, parse_resource_type};
+pub use snapshot::{GalaxyState, GalaxyUpdate, SnapshotDelta};
 pub use state_enums::*;
+pub use status_merge::{LastUpdate, StatusMapExt};
+pub use status_table::StatusTable;
+pub use topology_delta::TopologyDelta;
 pub use types::*;
@@ -0,0 +1,43 @@
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use std::collections::HashMap;
+
+/// Weight given to the planets that have no entry in the picker.
+pub const DEFAULT_PLANET_WEIGHT: f64 = 1.0;
+
+/// Random planet selection with per-planet weights.
+///
+/// A planet is picked with probability proportional to its weight; planets
+/// without an explicit weight get [`DEFAULT_PLANET_WEIGHT`], so an empty picker
+/// behaves as a uniform choice. Negative or non-finite weights count as zero.
+#[derive(Debug, Clone, Default)]
+pub struct WeightedPlanetPicker {
+    weights: HashMap<u32, f64>,
+}
+
+impl WeightedPlanetPicker {
+    pub fn new(weights: HashMap<u32, f64>) -> Self {
+        WeightedPlanetPicker { weights }
+    }
+
+    /// Returns the weight used for `planet_id`.
+    pub fn weight_of(&self, planet_id: u32) -> f64 {
+        match self.weights.get(&planet_id) {
+            Some(&w) if w.is_finite() && w > 0.0 => w,
+            Some(_) => 0.0,
+            None => DEFAULT_PLANET_WEIGHT,
+        }
+    }
+
+    /// Picks one of the `candidates` (usually the living planets).
+    ///
+    /// If every candidate has weight zero the choice falls back to uniform.
+    ///
+    /// Returns None if `candidates` is empty.
+    pub fn pick<R: Rng + ?Sized>(&self, candidates: &[u32], rng: &mut R) -> Option<u32> {
+        match candidates.choose_weighted(rng, |id| self.weight_of(*id)) {
+            Ok(id) => Some(*id),
+            Err(_) => candidates.choose(rng).copied(),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use common_game::components::resource::{
+    BasicResourceType, ComplexResourceRequest, ComplexResourceType, ResourceType,
+};
+
+use crate::utils::resource_names::COMPLEX_RESOURCES;
+
+/// Returns the two resources combined to make a complex resource.
+///
+/// A recipe that needs two units of the same resource (e.g. Diamond) lists it twice.
+pub fn ingredients(complex: ComplexResourceType) -> [ResourceType; 2] {
+    match complex {
+        ComplexResourceType::Diamond => [
+            ResourceType::Basic(BasicResourceType::Carbon),
+            ResourceType::Basic(BasicResourceType::Carbon),
+        ],
+        ComplexResourceType::Water => [
+            ResourceType::Basic(BasicResourceType::Hydrogen),
+            ResourceType::Basic(BasicResourceType::Oxygen),
+        ],
+        ComplexResourceType::Life => [
+            ResourceType::Complex(ComplexResourceType::Water),
+            ResourceType::Basic(BasicResourceType::Carbon),
+        ],
+        ComplexResourceType::Robot => [
+            ResourceType::Basic(BasicResourceType::Silicon),
+            ResourceType::Complex(ComplexResourceType::Life),
+        ],
+        ComplexResourceType::Dolphin => [
+            ResourceType::Complex(ComplexResourceType::Water),
+            ResourceType::Complex(ComplexResourceType::Life),
+        ],
+        ComplexResourceType::AIPartner => [
+            ResourceType::Complex(ComplexResourceType::Robot),
+            ResourceType::Complex(ComplexResourceType::Diamond),
+        ],
+    }
+}
+
+/// Returns the number of combinations on the longest path from the basic resources
+/// to a complex resource, e.g. 1 for Water and 4 for AIPartner.
+pub fn recipe_depth(complex: ComplexResourceType) -> u32 {
+    let depth = |resource: ResourceType| match resource {
+        ResourceType::Basic(_) => 0,
+        ResourceType::Complex(c) => recipe_depth(c),
+    };
+    let [a, b] = ingredients(complex);
+    1 + depth(a).max(depth(b))
+}
+
+/// Returns the value of a resource, used to compare the bags of the explorers.
+///
+/// A basic resource is worth 1, a complex resource is worth its ingredients plus
+/// its [`recipe_depth`], so combining always adds value and AIPartner is worth most.
+pub fn resource_score(resource: ResourceType) -> u64 {
+    match resource {
+        ResourceType::Basic(_) => 1,
+        ResourceType::Complex(complex) => {
+            let [a, b] = ingredients(complex);
+            resource_score(a) + resource_score(b) + u64::from(recipe_depth(complex))
+        }
+    }
+}
+
+/// Returns the total value of a bag, see [`resource_score`].
+pub fn bag_score(resources: &[ResourceType]) -> u64 {
+    resources.iter().map(|r| resource_score(*r)).sum()
+}
+
+/// Returns the complex resources that have `resource` among their ingredients,
+/// in declaration order.
+pub fn used_in(resource: ResourceType) -> Vec<ComplexResourceType> {
+    COMPLEX_RESOURCES
+        .into_iter()
+        .filter(|c| ingredients(*c).contains(&resource))
+        .collect()
+}
+
+/// Checks if `resources` holds every ingredient of a complex resource,
+/// counting the repeated ones.
+pub fn has_ingredients(resources: &[ResourceType], complex: ComplexResourceType) -> bool {
+    let [a, b] = ingredients(complex);
+    let count = |ty: ResourceType| resources.iter().filter(|r| **r == ty).count();
+    if a == b {
+        count(a) >= 2
+    } else {
+        count(a) >= 1 && count(b) >= 1
+    }
+}
+
+/// Returns the complex resource a combine request makes.
+pub fn request_output(request: &ComplexResourceRequest) -> ComplexResourceType {
+    match request {
+        ComplexResourceRequest::Diamond(..) => ComplexResourceType::Diamond,
+        ComplexResourceRequest::Water(..) => ComplexResourceType::Water,
+        ComplexResourceRequest::Life(..) => ComplexResourceType::Life,
+        ComplexResourceRequest::Robot(..) => ComplexResourceType::Robot,
+        ComplexResourceRequest::Dolphin(..) => ComplexResourceType::Dolphin,
+        ComplexResourceRequest::AIPartner(..) => ComplexResourceType::AIPartner,
+    }
+}
+
+/// Checks that a combine request makes the `expected` complex resource.
+///
+/// Returns Err if the request was built for another resource, so that it is not sent.
+pub fn check_request(
+    request: &ComplexResourceRequest,
+    expected: ComplexResourceType,
+) -> Result<(), String> {
+    let output = request_output(request);
+    if output == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "Combine request makes {:?} instead of {:?}",
+            output, expected
+        ))
+    }
+}
@@ -20,7 +20,35 @@ pub enum PlanetType {
     RustyCrab,
     TheCompilerStrikesBack,
 }
+/// Every planet type, in declaration order (the index is the type id used in the
+/// galaxy files).
+const ALL_PLANET_TYPES: [PlanetType; 8] = [
+    PlanetType::BlackAdidasShoe,
+    PlanetType::Ciuc,
+    PlanetType::HoustonWeHaveABorrow,
+    PlanetType::ImmutableCosmicBorrow,
+    PlanetType::OneMillionCrabs,
+    PlanetType::Rustrelli,
+    PlanetType::RustyCrab,
+    PlanetType::TheCompilerStrikesBack,
+];
+
 impl PlanetType {
+    /// Returns every planet type; the position of a type is its id in the galaxy files.
+    pub fn all_variants() -> &'static [PlanetType] {
+        &ALL_PLANET_TYPES
+    }
+
+    /// Returns the list of the valid planet types with their id, for error messages.
+    pub fn valid_types_list() -> String {
+        Self::all_variants()
+            .iter()
+            .enumerate()
+            .map(|(id, planet_type)| format!("{}={:?}", id, planet_type))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     pub fn random() -> Self {
         let mut rng = rand::rng();
         let mut variants: Vec<PlanetType> = PlanetType::iter().collect();
@@ -114,3 +142,22 @@ pub static PLANET_REGISTRY: Lazy<HashMap<PlanetType, PlanetFactory>> = Lazy::new
 
     map
 });
+
+/// Checks that every planet type has a factory in [`PLANET_REGISTRY`].
+///
+/// Returns Err listing the planet types without a factory.
+pub fn validate_planet_registry() -> Result<(), String> {
+    let missing: Vec<String> = PlanetType::all_variants()
+        .iter()
+        .filter(|planet_type| !PLANET_REGISTRY.contains_key(planet_type))
+        .map(|planet_type| format!("{:?}", planet_type))
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "planet types without a registry entry: {}",
+            missing.join(", ")
+        ))
+    }
+}
@@ -0,0 +1,198 @@
+use std::io::Write;
+
+use crate::utils::registry::PlanetType;
+use crate::utils::{ExplorerInfoMap, PlanetInfoMap, Status};
+
+/// Header of the CSV written by [`GameReport::to_csv`].
+pub const REPORT_CSV_HEADER: &str =
+    "kind,id,type_or_planet,status,charged_cells,energy_cells,rocket,bag_resources";
+
+/// Final state of a planet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetReport {
+    pub id: u32,
+    pub planet_type: PlanetType,
+    pub status: Status,
+    pub charged_cells: usize,
+    pub energy_cells: usize,
+    pub rocket: bool,
+}
+
+/// Final state of an explorer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplorerReport {
+    pub id: u32,
+    /// The planet the explorer is on.
+    pub planet_id: u32,
+    pub status: Status,
+    pub bag_resources: usize,
+}
+
+/// Summary of a game, with planets and explorers sorted by id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameReport {
+    pub planets: Vec<PlanetReport>,
+    pub explorers: Vec<ExplorerReport>,
+    /// Links of the final topology, as sorted pairs of planet ids.
+    pub topology: Vec<(u32, u32)>,
+}
+
+impl GameReport {
+    /// Builds the report from the orchestrator's view of the galaxy.
+    pub fn from_state(planets: &PlanetInfoMap, explorers: &ExplorerInfoMap) -> Self {
+        let mut planets: Vec<PlanetReport> = planets
+            .iter()
+            .map(|(id, info)| PlanetReport {
+                id: *id,
+                planet_type: info.name,
+                status: info.status,
+                charged_cells: info.charged_cells_count,
+                energy_cells: info.energy_cells.len(),
+                rocket: info.rocket,
+            })
+            .collect();
+        planets.sort_by_key(|p| p.id);
+
+        let mut explorers: Vec<ExplorerReport> = explorers
+            .iter()
+            .map(|(id, info)| ExplorerReport {
+                id: *id,
+                planet_id: info.current_planet_id,
+                status: info.status,
+                bag_resources: info.bag.len(),
+            })
+            .collect();
+        explorers.sort_by_key(|e| e.id);
+
+        GameReport {
+            planets,
+            explorers,
+            topology: Vec::new(),
+        }
+    }
+
+    /// Adds the links of the final topology to the report.
+    ///
+    /// Each link is stored with the smaller planet id first, and the links are sorted.
+    pub fn with_topology(mut self, edges: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        self.topology = edges
+            .into_iter()
+            .map(|(a, b)| if a <= b { (a, b) } else { (b, a) })
+            .collect();
+        self.topology.sort();
+        self.topology.dedup();
+        self
+    }
+
+    /// Renders the report as Markdown, with a table per section.
+    ///
+    /// An empty section is rendered as `_none_` instead of a table.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Game report\n\n## Planets\n\n");
+        if self.planets.is_empty() {
+            out.push_str("_none_\n");
+        } else {
+            out.push_str("| id | type | status | charged cells | energy cells | rocket |\n");
+            out.push_str("|---:|------|--------|--------------:|-------------:|--------|\n");
+            for p in &self.planets {
+                out.push_str(&format!(
+                    "| {} | {:?} | {:?} | {} | {} | {} |\n",
+                    p.id, p.planet_type, p.status, p.charged_cells, p.energy_cells, p.rocket
+                ));
+            }
+        }
+
+        out.push_str("\n## Explorers\n\n");
+        if self.explorers.is_empty() {
+            out.push_str("_none_\n");
+        } else {
+            out.push_str("| id | planet | status | bag resources |\n");
+            out.push_str("|---:|-------:|--------|--------------:|\n");
+            for e in &self.explorers {
+                out.push_str(&format!(
+                    "| {} | {} | {:?} | {} |\n",
+                    e.id, e.planet_id, e.status, e.bag_resources
+                ));
+            }
+        }
+
+        out.push_str("\n## Topology\n\n");
+        if self.topology.is_empty() {
+            out.push_str("_none_\n");
+        } else {
+            out.push_str("| planet | planet |\n");
+            out.push_str("|-------:|-------:|\n");
+            for (a, b) in &self.topology {
+                out.push_str(&format!("| {} | {} |\n", a, b));
+            }
+        }
+        out
+    }
+
+    /// Renders the report as compact JSON.
+    ///
+    /// The keys are always written in the same order and the entries are sorted
+    /// by id, so two reports of the same state give the same string.
+    pub fn to_json(&self) -> String {
+        let planets: Vec<String> = self
+            .planets
+            .iter()
+            .map(|p| {
+                format!(
+                    "{{\"id\":{},\"planet_type\":\"{:?}\",\"status\":\"{:?}\",\"charged_cells\":{},\"energy_cells\":{},\"rocket\":{}}}",
+                    p.id, p.planet_type, p.status, p.charged_cells, p.energy_cells, p.rocket
+                )
+            })
+            .collect();
+        let explorers: Vec<String> = self
+            .explorers
+            .iter()
+            .map(|e| {
+                format!(
+                    "{{\"id\":{},\"planet_id\":{},\"status\":\"{:?}\",\"bag_resources\":{}}}",
+                    e.id, e.planet_id, e.status, e.bag_resources
+                )
+            })
+            .collect();
+        let topology: Vec<String> = self
+            .topology
+            .iter()
+            .map(|(a, b)| format!("[{},{}]", a, b))
+            .collect();
+        format!(
+            "{{\"planets\":[{}],\"explorers\":[{}],\"topology\":[{}]}}",
+            planets.join(","),
+            explorers.join(","),
+            topology.join(",")
+        )
+    }
+
+    /// Writes the report as CSV: a header, then one row per planet and one per explorer.
+    ///
+    /// The `type_or_planet` column holds the planet type for planets and the current
+    /// planet for explorers; the columns that do not apply to a row are left empty.
+    /// An empty galaxy produces the header only.
+    ///
+    /// Returns Err if the writer fails.
+    pub fn to_csv<W: Write>(&self, writer: &mut W) -> Result<(), String> {
+        let write_err = |e: std::io::Error| format!("Unable to write the CSV report: {}", e);
+        writeln!(writer, "{}", REPORT_CSV_HEADER).map_err(write_err)?;
+        for p in &self.planets {
+            writeln!(
+                writer,
+                "planet,{},{:?},{:?},{},{},{},",
+                p.id, p.planet_type, p.status, p.charged_cells, p.energy_cells, p.rocket
+            )
+            .map_err(write_err)?;
+        }
+        for e in &self.explorers {
+            writeln!(
+                writer,
+                "explorer,{},{},{:?},,,,{}",
+                e.id, e.planet_id, e.status, e.bag_resources
+            )
+            .map_err(write_err)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use common_game::utils::ID;
+use std::collections::{HashMap, VecDeque};
+
+/// Kinds of explorer requests whose answer describes a planet without naming it.
+///
+/// All of them are sent to the planet they describe, except `Neighbours`, which is
+/// a `NeighborsRequest` sent to the orchestrator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanetRequestKind {
+    SupportedResources,
+    SupportedCombinations,
+    AvailableEnergyCells,
+    Neighbours,
+}
+
+/// In-flight ledger of the planet requests sent by an explorer.
+///
+/// An explorer keeps a single planet receiver while moving, so the answer to a
+/// request sent before a `MoveToPlanet` may arrive after the planet sender has
+/// been swapped. Every request is recorded under the id of the planet it was sent
+/// to; an answer of a kind is matched to the oldest pending request of that kind.
+///
+/// The requests of a planet that will never answer (dead, stopped) must be dropped
+/// with [`forget_planet`](Self::forget_planet), otherwise the answers of the other
+/// planets would be matched to them. A planet can also stop answering silently, so
+/// the explorers wait for an answer only for a while.
+#[derive(Debug, Default)]
+pub struct PlanetRequestLedger {
+    pending: HashMap<ID, VecDeque<(u64, PlanetRequestKind)>>,
+    next_seq: u64,
+}
+
+impl PlanetRequestLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a request of the given kind sent to `planet_id`.
+    pub fn sent(&mut self, kind: PlanetRequestKind, planet_id: ID) {
+        self.pending
+            .entry(planet_id)
+            .or_default()
+            .push_back((self.next_seq, kind));
+        self.next_seq += 1;
+    }
+
+    /// Returns the planet the oldest pending request of the given kind was sent to,
+    /// and removes it from the ledger.
+    ///
+    /// Returns None if no request of that kind is pending.
+    pub fn answered(&mut self, kind: PlanetRequestKind) -> Option<ID> {
+        let (planet_id, seq) = self
+            .pending
+            .iter()
+            .filter_map(|(planet_id, requests)| {
+                requests
+                    .iter()
+                    .find(|(_, k)| *k == kind)
+                    .map(|(seq, _)| (*planet_id, *seq))
+            })
+            .min_by_key(|(_, seq)| *seq)?;
+
+        let requests = self.pending.get_mut(&planet_id)?;
+        requests.retain(|(s, _)| *s != seq);
+        if requests.is_empty() {
+            self.pending.remove(&planet_id);
+        }
+        Some(planet_id)
+    }
+
+    /// Removes the oldest pending request of the given kind sent to `planet_id`, e.g.
+    /// because its answer was matched to another planet that never answered.
+    ///
+    /// Returns false if no request of that kind is pending for the planet.
+    pub fn withdraw(&mut self, kind: PlanetRequestKind, planet_id: ID) -> bool {
+        let Some(requests) = self.pending.get_mut(&planet_id) else {
+            return false;
+        };
+        let Some(pos) = requests.iter().position(|(_, k)| *k == kind) else {
+            return false;
+        };
+        requests.remove(pos);
+        if requests.is_empty() {
+            self.pending.remove(&planet_id);
+        }
+        true
+    }
+
+    /// Returns true if a request of the given kind sent to `planet_id` is waiting
+    /// for its answer.
+    pub fn is_pending(&self, kind: PlanetRequestKind, planet_id: ID) -> bool {
+        self.pending
+            .get(&planet_id)
+            .is_some_and(|requests| requests.iter().any(|(_, k)| *k == kind))
+    }
+
+    /// Forgets every pending request sent to `planet_id` (e.g. because the planet died
+    /// or answered `Stopped`).
+    ///
+    /// Pending `Neighbours` requests are kept, since the orchestrator answers them
+    /// even if the planet is dead.
+    pub fn forget_planet(&mut self, planet_id: ID) {
+        if let Some(requests) = self.pending.get_mut(&planet_id) {
+            requests.retain(|(_, kind)| *kind == PlanetRequestKind::Neighbours);
+            if requests.is_empty() {
+                self.pending.remove(&planet_id);
+            }
+        }
+    }
+}
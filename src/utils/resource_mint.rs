@@ -0,0 +1,89 @@
+use common_game::components::energy_cell::EnergyCell;
+use common_game::components::forge::Forge;
+use common_game::components::resource::{
+    BasicResourceType, Combinator, ComplexResourceType, Generator, GenericResource, ResourceType,
+};
+
+use crate::utils::recipes;
+
+/// Makes a resource out of nothing, for the starting bag of an explorer.
+///
+/// Resources can only be made by a generator or a combinator from a charged energy
+/// cell, so a new cell is charged with a sunray of `forge` for each of them. A complex
+/// resource is made by minting its ingredients (see [`recipes::ingredients`]) and
+/// combining them, so it costs one cell per combination on its recipe.
+pub fn mint_resource(resource: ResourceType, forge: &Forge) -> Result<GenericResource, String> {
+    match resource {
+        ResourceType::Basic(basic) => mint_basic(basic, forge),
+        ResourceType::Complex(complex) => mint_complex(complex, forge),
+    }
+}
+
+/// Mints every resource of `resources`, see [`mint_resource`].
+///
+/// Returns the first error, in which case nothing is minted.
+pub fn mint_resources(
+    resources: &[ResourceType],
+    forge: &Forge,
+) -> Result<Vec<GenericResource>, String> {
+    resources
+        .iter()
+        .map(|resource| mint_resource(*resource, forge))
+        .collect()
+}
+
+fn charged_cell(forge: &Forge) -> EnergyCell {
+    let mut cell = EnergyCell::new();
+    cell.charge(forge.generate_sunray());
+    cell
+}
+
+fn mint_basic(basic: BasicResourceType, forge: &Forge) -> Result<GenericResource, String> {
+    let mut generator = Generator::new();
+    generator.add(basic)?;
+    let mut cell = charged_cell(forge);
+    let minted = match basic {
+        BasicResourceType::Oxygen => generator.make_oxygen(&mut cell)?.to_generic(),
+        BasicResourceType::Hydrogen => generator.make_hydrogen(&mut cell)?.to_generic(),
+        BasicResourceType::Carbon => generator.make_carbon(&mut cell)?.to_generic(),
+        BasicResourceType::Silicon => generator.make_silicon(&mut cell)?.to_generic(),
+    };
+    Ok(minted)
+}
+
+fn mint_complex(complex: ComplexResourceType, forge: &Forge) -> Result<GenericResource, String> {
+    let [first, second] = recipes::ingredients(complex);
+    let first = mint_resource(first, forge)?;
+    let second = mint_resource(second, forge)?;
+    let mut combinator = Combinator::new();
+    combinator.add(complex)?;
+    let mut cell = charged_cell(forge);
+    // the combinator gives the ingredients back on failure, they are dropped here
+    let minted = match complex {
+        ComplexResourceType::Diamond => combinator
+            .make_diamond(first.to_carbon()?, second.to_carbon()?, &mut cell)
+            .map_err(|(err, _, _)| err)?
+            .to_generic(),
+        ComplexResourceType::Water => combinator
+            .make_water(first.to_hydrogen()?, second.to_oxygen()?, &mut cell)
+            .map_err(|(err, _, _)| err)?
+            .to_generic(),
+        ComplexResourceType::Life => combinator
+            .make_life(first.to_water()?, second.to_carbon()?, &mut cell)
+            .map_err(|(err, _, _)| err)?
+            .to_generic(),
+        ComplexResourceType::Robot => combinator
+            .make_robot(first.to_silicon()?, second.to_life()?, &mut cell)
+            .map_err(|(err, _, _)| err)?
+            .to_generic(),
+        ComplexResourceType::Dolphin => combinator
+            .make_dolphin(first.to_water()?, second.to_life()?, &mut cell)
+            .map_err(|(err, _, _)| err)?
+            .to_generic(),
+        ComplexResourceType::AIPartner => combinator
+            .make_aipartner(first.to_robot()?, second.to_diamond()?, &mut cell)
+            .map_err(|(err, _, _)| err)?
+            .to_generic(),
+    };
+    Ok(minted)
+}
@@ -0,0 +1,111 @@
+use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+use std::fmt;
+
+/// Every basic resource variant, in declaration order.
+pub const BASIC_RESOURCES: [BasicResourceType; 4] = [
+    BasicResourceType::Oxygen,
+    BasicResourceType::Hydrogen,
+    BasicResourceType::Carbon,
+    BasicResourceType::Silicon,
+];
+
+/// Every complex resource variant, in declaration order.
+pub const COMPLEX_RESOURCES: [ComplexResourceType; 6] = [
+    ComplexResourceType::Diamond,
+    ComplexResourceType::Water,
+    ComplexResourceType::Life,
+    ComplexResourceType::Robot,
+    ComplexResourceType::Dolphin,
+    ComplexResourceType::AIPartner,
+];
+
+/// Returns the config-file name of a basic resource.
+pub fn basic_resource_name(resource: BasicResourceType) -> &'static str {
+    match resource {
+        BasicResourceType::Oxygen => "Oxygen",
+        BasicResourceType::Hydrogen => "Hydrogen",
+        BasicResourceType::Carbon => "Carbon",
+        BasicResourceType::Silicon => "Silicon",
+    }
+}
+
+/// Returns the config-file name of a complex resource.
+pub fn complex_resource_name(resource: ComplexResourceType) -> &'static str {
+    match resource {
+        ComplexResourceType::Diamond => "Diamond",
+        ComplexResourceType::Water => "Water",
+        ComplexResourceType::Life => "Life",
+        ComplexResourceType::Robot => "Robot",
+        ComplexResourceType::Dolphin => "Dolphin",
+        ComplexResourceType::AIPartner => "AIPartner",
+    }
+}
+
+/// Display wrapper for [`ResourceType`].
+///
+/// Formats the resource as `Basic(Carbon)` or `Complex(Water)`, which is the
+/// format accepted back by [`parse_resource_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceTypeName(pub ResourceType);
+
+impl fmt::Display for ResourceTypeName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            ResourceType::Basic(basic) => write!(f, "Basic({})", basic_resource_name(basic)),
+            ResourceType::Complex(complex) => {
+                write!(f, "Complex({})", complex_resource_name(complex))
+            }
+        }
+    }
+}
+
+/// Parses a basic resource name, ignoring case.
+pub fn parse_basic_resource_type(s: &str) -> Result<BasicResourceType, String> {
+    let name = s.trim();
+    BASIC_RESOURCES
+        .into_iter()
+        .find(|r| basic_resource_name(*r).eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown basic resource: '{}'", name))
+}
+
+/// Parses a complex resource name, ignoring case.
+pub fn parse_complex_resource_type(s: &str) -> Result<ComplexResourceType, String> {
+    let name = s.trim();
+    COMPLEX_RESOURCES
+        .into_iter()
+        .find(|r| complex_resource_name(*r).eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown complex resource: '{}'", name))
+}
+
+/// Parses a resource from a config string, ignoring case.
+///
+/// Accepted formats:
+/// - a bare variant name: `"Water"`, `"carbon"`
+/// - a qualified name: `"Basic(Carbon)"`, `"Complex(Water)"`
+///
+/// Returns Err with a message naming the unknown string otherwise.
+pub fn parse_resource_type(s: &str) -> Result<ResourceType, String> {
+    let input = s.trim();
+
+    if let Some((kind, rest)) = input.split_once('(') {
+        let inner = rest
+            .strip_suffix(')')
+            .ok_or_else(|| format!("Unknown resource: '{}' (missing closing parenthesis)", input))?;
+        return match kind.trim().to_ascii_lowercase().as_str() {
+            "basic" => parse_basic_resource_type(inner).map(ResourceType::Basic),
+            "complex" => parse_complex_resource_type(inner).map(ResourceType::Complex),
+            _ => Err(format!(
+                "Unknown resource: '{}' (expected Basic(..) or Complex(..))",
+                input
+            )),
+        };
+    }
+
+    if let Ok(basic) = parse_basic_resource_type(input) {
+        return Ok(ResourceType::Basic(basic));
+    }
+    if let Ok(complex) = parse_complex_resource_type(input) {
+        return Ok(ResourceType::Complex(complex));
+    }
+    Err(format!("Unknown resource: '{}'", input))
+}
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use crate::utils::{ExplorerInfoMap, PlanetInfoMap, Status};
+
+/// State of an explorer as shown by the GUI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExplorerView {
+    /// The planet the explorer is on.
+    pub planet_id: u32,
+    pub status: Status,
+}
+
+/// Counters of the galaxy shown next to the map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GalaxyStats {
+    pub alive_planets: usize,
+    pub alive_explorers: usize,
+}
+
+/// Versioned view of the galaxy for the GUI.
+///
+/// The orchestrator gives a new version to every state that differs from the
+/// previous one, so a GUI that already holds a state only needs the
+/// [`SnapshotDelta`] to the next one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GalaxyState {
+    pub version: u64,
+    pub planets: BTreeMap<u32, Status>,
+    pub explorers: BTreeMap<u32, ExplorerView>,
+    /// Links of the topology, as sorted pairs of planet ids with the smaller id first.
+    pub edges: Vec<(u32, u32)>,
+    pub stats: GalaxyStats,
+}
+
+/// Changes between two versions of a [`GalaxyState`], see [`GalaxyState::diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDelta {
+    /// Version the delta applies to.
+    pub from_version: u64,
+    /// Version of the state after the delta is applied.
+    pub to_version: u64,
+    pub planets_added: Vec<(u32, Status)>,
+    pub planets_removed: Vec<u32>,
+    /// Planets whose status changed, with the new status.
+    pub planets_changed: Vec<(u32, Status)>,
+    pub explorers_added: Vec<(u32, ExplorerView)>,
+    pub explorers_removed: Vec<u32>,
+    /// Explorers that moved or whose status changed, with their new state.
+    pub explorers_changed: Vec<(u32, ExplorerView)>,
+    pub edges_added: Vec<(u32, u32)>,
+    pub edges_removed: Vec<(u32, u32)>,
+    /// The new counters, None if they did not change.
+    pub stats: Option<GalaxyStats>,
+}
+
+/// What the GUI receives when it asks for the galaxy, see
+/// [`Orchestrator::galaxy_update`](crate::Orchestrator::galaxy_update).
+#[derive(Debug, Clone, PartialEq)]
+pub enum GalaxyUpdate {
+    /// The whole state, sent when the GUI does not hold a usable version.
+    Full(GalaxyState),
+    /// The changes from the version held by the GUI.
+    Delta(SnapshotDelta),
+}
+
+fn diff_maps<V: Copy + PartialEq>(
+    old: &BTreeMap<u32, V>,
+    new: &BTreeMap<u32, V>,
+) -> (Vec<(u32, V)>, Vec<u32>, Vec<(u32, V)>) {
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (id, value) in new {
+        match old.get(id) {
+            None => added.push((*id, *value)),
+            Some(old_value) if old_value != value => changed.push((*id, *value)),
+            Some(_) => {}
+        }
+    }
+    let removed = old
+        .keys()
+        .filter(|id| !new.contains_key(id))
+        .copied()
+        .collect();
+    (added, removed, changed)
+}
+
+impl GalaxyState {
+    /// Builds the state from the orchestrator's view of the galaxy, with no links.
+    pub fn from_state(planets: &PlanetInfoMap, explorers: &ExplorerInfoMap, version: u64) -> Self {
+        let planets: BTreeMap<u32, Status> = planets
+            .iter()
+            .map(|(id, info)| (*id, info.status))
+            .collect();
+        let explorers: BTreeMap<u32, ExplorerView> = explorers
+            .iter()
+            .map(|(id, info)| {
+                let view = ExplorerView {
+                    planet_id: info.current_planet_id,
+                    status: info.status,
+                };
+                (*id, view)
+            })
+            .collect();
+        let stats = GalaxyStats {
+            alive_planets: planets.values().filter(|s| **s != Status::Dead).count(),
+            alive_explorers: explorers
+                .values()
+                .filter(|e| e.status != Status::Dead)
+                .count(),
+        };
+        GalaxyState {
+            version,
+            planets,
+            explorers,
+            edges: Vec::new(),
+            stats,
+        }
+    }
+
+    /// Sets the links of the topology.
+    ///
+    /// Each link is stored with the smaller planet id first, and the links are sorted.
+    pub fn with_edges(mut self, edges: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        self.edges = edges
+            .into_iter()
+            .map(|(a, b)| if a <= b { (a, b) } else { (b, a) })
+            .collect();
+        self.edges.sort();
+        self.edges.dedup();
+        self
+    }
+
+    /// Checks if two states describe the same galaxy, whatever their version.
+    pub fn same_content(&self, other: &GalaxyState) -> bool {
+        self.planets == other.planets
+            && self.explorers == other.explorers
+            && self.edges == other.edges
+            && self.stats == other.stats
+    }
+
+    /// Computes the changes that turn `old` into `new`.
+    pub fn diff(old: &GalaxyState, new: &GalaxyState) -> SnapshotDelta {
+        let (planets_added, planets_removed, planets_changed) =
+            diff_maps(&old.planets, &new.planets);
+        let (explorers_added, explorers_removed, explorers_changed) =
+            diff_maps(&old.explorers, &new.explorers);
+        SnapshotDelta {
+            from_version: old.version,
+            to_version: new.version,
+            planets_added,
+            planets_removed,
+            planets_changed,
+            explorers_added,
+            explorers_removed,
+            explorers_changed,
+            edges_added: new
+                .edges
+                .iter()
+                .filter(|e| old.edges.binary_search(e).is_err())
+                .copied()
+                .collect(),
+            edges_removed: old
+                .edges
+                .iter()
+                .filter(|e| new.edges.binary_search(e).is_err())
+                .copied()
+                .collect(),
+            stats: (old.stats != new.stats).then_some(new.stats),
+        }
+    }
+
+    /// Applies a delta computed by [`diff`](Self::diff) from this version.
+    ///
+    /// Returns Err, leaving the state unchanged, if the delta starts from another version.
+    pub fn apply(&mut self, delta: &SnapshotDelta) -> Result<(), String> {
+        if delta.from_version != self.version {
+            return Err(format!(
+                "Delta from version {} cannot be applied to version {}",
+                delta.from_version, self.version
+            ));
+        }
+        for id in &delta.planets_removed {
+            self.planets.remove(id);
+        }
+        for (id, status) in delta.planets_added.iter().chain(&delta.planets_changed) {
+            self.planets.insert(*id, *status);
+        }
+        for id in &delta.explorers_removed {
+            self.explorers.remove(id);
+        }
+        for (id, view) in delta.explorers_added.iter().chain(&delta.explorers_changed) {
+            self.explorers.insert(*id, *view);
+        }
+        self.edges.retain(|e| !delta.edges_removed.contains(e));
+        self.edges.extend_from_slice(&delta.edges_added);
+        self.edges.sort();
+        self.edges.dedup();
+        if let Some(stats) = delta.stats {
+            self.stats = stats;
+        }
+        self.version = delta.to_version;
+        Ok(())
+    }
+}
+
+impl SnapshotDelta {
+    /// Checks if the delta has no changes, apart from the version.
+    pub fn is_empty(&self) -> bool {
+        self.planets_added.is_empty()
+            && self.planets_removed.is_empty()
+            && self.planets_changed.is_empty()
+            && self.explorers_added.is_empty()
+            && self.explorers_removed.is_empty()
+            && self.explorers_changed.is_empty()
+            && self.edges_added.is_empty()
+            && self.edges_removed.is_empty()
+            && self.stats.is_none()
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::utils::{ExplorerStatusNotLock, Status};
+
+/// Time of the last status update of each actor, see [`StatusMapExt::merge_newest`].
+pub type LastUpdate = HashMap<u32, Instant>;
+
+/// Aggregation of the status snapshots of several orchestrators or runs.
+///
+/// Implemented for [`ExplorerStatusNotLock`], and so for
+/// [`PlanetStatusNotLock`](crate::utils::PlanetStatusNotLock) which is the same type.
+pub trait StatusMapExt {
+    /// Inserts every entry of `other`.
+    ///
+    /// An id present in both maps gets `resolve(existing, incoming)`.
+    fn merge(&mut self, other: &Self, resolve: fn(Status, Status) -> Status);
+
+    /// Inserts every entry of `other` updated after the entry with the same id in `self`.
+    ///
+    /// An entry without update time is older than any other, and on a tie the existing
+    /// entry is kept. The update time of the entries taken from `other` is copied into
+    /// `last_update`.
+    fn merge_newest(
+        &mut self,
+        other: &Self,
+        last_update: &mut LastUpdate,
+        other_last_update: &LastUpdate,
+    );
+}
+
+impl StatusMapExt for ExplorerStatusNotLock {
+    fn merge(&mut self, other: &Self, resolve: fn(Status, Status) -> Status) {
+        for (id, incoming) in other {
+            self.entry(*id)
+                .and_modify(|existing| *existing = resolve(*existing, *incoming))
+                .or_insert(*incoming);
+        }
+    }
+
+    fn merge_newest(
+        &mut self,
+        other: &Self,
+        last_update: &mut LastUpdate,
+        other_last_update: &LastUpdate,
+    ) {
+        for (id, incoming) in other {
+            let incoming_time = other_last_update.get(id);
+            let newer = match (self.contains_key(id), last_update.get(id), incoming_time) {
+                (false, _, _) => true,
+                (true, None, Some(_)) => true,
+                (true, Some(existing), Some(incoming)) => incoming > existing,
+                (true, _, None) => false,
+            };
+            if newer {
+                self.insert(*id, *incoming);
+                match incoming_time {
+                    Some(time) => last_update.insert(*id, *time),
+                    None => last_update.remove(id),
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::utils::registry::PlanetType;
+use crate::utils::{ExplorerInfoMap, PlanetInfoMap, Status};
+
+/// Row of a planet in the [`StatusTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetRow {
+    pub id: u32,
+    pub planet_type: PlanetType,
+    pub status: Status,
+}
+
+/// Row of an explorer in the [`StatusTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplorerRow {
+    pub id: u32,
+    /// The planet the explorer is on.
+    pub planet_id: u32,
+    pub status: Status,
+    /// Number of resources in the last bag received from the explorer.
+    pub bag_size: usize,
+}
+
+/// Status of every planet and explorer, as rendered by the ratatui GUI.
+///
+/// The rows are sorted by id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusTable {
+    pub planets: Vec<PlanetRow>,
+    pub explorers: Vec<ExplorerRow>,
+}
+
+impl StatusTable {
+    /// Builds the table from the orchestrator's view of the galaxy.
+    pub fn from_state(planets: &PlanetInfoMap, explorers: &ExplorerInfoMap) -> Self {
+        let mut planets: Vec<PlanetRow> = planets
+            .iter()
+            .map(|(id, info)| PlanetRow {
+                id: *id,
+                planet_type: info.name,
+                status: info.status,
+            })
+            .collect();
+        planets.sort_by_key(|p| p.id);
+
+        let mut explorers: Vec<ExplorerRow> = explorers
+            .iter()
+            .map(|(id, info)| ExplorerRow {
+                id: *id,
+                planet_id: info.current_planet_id,
+                status: info.status,
+                bag_size: info.bag.len(),
+            })
+            .collect();
+        explorers.sort_by_key(|e| e.id);
+
+        StatusTable { planets, explorers }
+    }
+}
@@ -0,0 +1,60 @@
+use common_game::utils::ID;
+
+/// Links of the galaxy topology that changed since the last notification.
+///
+/// The orchestrator collects the changes of a whole tick and sends them to the
+/// explorers in a single delta; every link is stored once, as a pair of planet ids
+/// with the smaller id first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopologyDelta {
+    /// Links that no longer exist.
+    pub removed: Vec<(ID, ID)>,
+    /// Links that have been created.
+    pub added: Vec<(ID, ID)>,
+    /// Planets that died, their links are in `removed` too.
+    pub dead_planets: Vec<ID>,
+}
+
+fn link(a: ID, b: ID) -> (ID, ID) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+impl TopologyDelta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks if the delta has no changes.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.dead_planets.is_empty()
+    }
+
+    /// Records that the link between `a` and `b` has been removed.
+    ///
+    /// A pending creation of the same link is cancelled.
+    pub fn remove_link(&mut self, a: ID, b: ID) {
+        let link = link(a, b);
+        self.added.retain(|l| *l != link);
+        if !self.removed.contains(&link) {
+            self.removed.push(link);
+        }
+    }
+
+    /// Records that the link between `a` and `b` has been created.
+    ///
+    /// A pending removal of the same link is cancelled.
+    pub fn add_link(&mut self, a: ID, b: ID) {
+        let link = link(a, b);
+        self.removed.retain(|l| *l != link);
+        if !self.added.contains(&link) {
+            self.added.push(link);
+        }
+    }
+
+    /// Records that a planet died.
+    pub fn planet_died(&mut self, planet_id: ID) {
+        if !self.dead_planets.contains(&planet_id) {
+            self.dead_planets.push(planet_id);
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use common_game::protocols::planet_explorer::ExplorerToPlanet;
+use common_game::utils::ID;
+use crossbeam_channel::{SendError, Sender, TrySendError, unbounded};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// An endpoint of a recorded protocol exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TracedActor {
+    Orchestrator,
+    Planet(ID),
+    Explorer(ID),
+}
+
+impl fmt::Display for TracedActor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TracedActor::Orchestrator => write!(f, "orchestrator"),
+            TracedActor::Planet(id) => write!(f, "planet_{}", id),
+            TracedActor::Explorer(id) => write!(f, "explorer_{}", id),
+        }
+    }
+}
+
+/// A protocol message recorded in a transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptRecord {
+    /// Position of the message among all the recorded messages.
+    pub seq: u64,
+    /// Time elapsed between the creation of the recorder and the message.
+    pub elapsed: Duration,
+    pub from: TracedActor,
+    pub to: TracedActor,
+    /// The message, formatted with `Debug`.
+    pub message: String,
+}
+
+impl fmt::Display for TranscriptRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{} +{}us {} -> {}: {}",
+            self.seq,
+            self.elapsed.as_micros(),
+            self.from,
+            self.to,
+            self.message
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    next_seq: u64,
+    /// Transcripts indexed by the (sorted) pair of actors exchanging the messages.
+    transcripts: BTreeMap<(TracedActor, TracedActor), Vec<TranscriptRecord>>,
+    /// Planet each explorer last sent a message to.
+    explorer_locations: HashMap<ID, ID>,
+}
+
+/// Shared store of the protocol transcripts, one per pair of actors.
+///
+/// Cloning the recorder is cheap and every clone writes to the same transcripts.
+#[derive(Debug, Clone)]
+pub struct MessageRecorder {
+    start: Instant,
+    state: Arc<Mutex<RecorderState>>,
+}
+
+impl Default for MessageRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageRecorder {
+    pub fn new() -> Self {
+        MessageRecorder {
+            start: Instant::now(),
+            state: Arc::new(Mutex::new(RecorderState::default())),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RecorderState> {
+        // a panicking sender thread cannot leave the transcripts half-written
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Appends a message to the transcript of the pair `from`-`to`.
+    pub fn record(&self, from: TracedActor, to: TracedActor, message: &impl fmt::Debug) {
+        let elapsed = self.start.elapsed();
+        let mut state = self.lock();
+        if let (TracedActor::Explorer(explorer_id), TracedActor::Planet(planet_id)) = (from, to) {
+            state.explorer_locations.insert(explorer_id, planet_id);
+        }
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state
+            .transcripts
+            .entry(pair_key(from, to))
+            .or_default()
+            .push(TranscriptRecord {
+                seq,
+                elapsed,
+                from,
+                to,
+                message: format!("{:?}", message),
+            });
+    }
+
+    /// Returns the planet the explorer last sent a message to.
+    ///
+    /// Planet responses carry no planet id, so this is the planet they are
+    /// attributed to.
+    pub fn planet_of(&self, explorer_id: ID) -> Option<ID> {
+        self.lock().explorer_locations.get(&explorer_id).copied()
+    }
+
+    /// Returns the messages exchanged between `a` and `b`, in both directions,
+    /// in the order they were sent.
+    pub fn transcript(&self, a: TracedActor, b: TracedActor) -> Vec<TranscriptRecord> {
+        self.lock()
+            .transcripts
+            .get(&pair_key(a, b))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the pairs of actors that exchanged at least one message.
+    pub fn pairs(&self) -> Vec<(TracedActor, TracedActor)> {
+        self.lock().transcripts.keys().copied().collect()
+    }
+
+    /// Writes one file per pair of actors into `dir`, one message per line.
+    ///
+    /// The files are named `<actor>-<actor>.log` (e.g. `planet_3-explorer_1.log`);
+    /// the directory is created if it does not exist.
+    ///
+    /// Returns the number of files written, or Err if a file cannot be written.
+    pub fn dump(&self, dir: &Path) -> Result<usize, String> {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Unable to create directory {}: {}", dir.display(), e))?;
+        let state = self.lock();
+        for ((a, b), records) in state.transcripts.iter() {
+            let path = dir.join(format!("{}-{}.log", a, b));
+            let content: String = records.iter().map(|r| format!("{}\n", r)).collect();
+            fs::write(&path, content)
+                .map_err(|e| format!("Unable to write transcript {}: {}", path.display(), e))?;
+        }
+        Ok(state.transcripts.len())
+    }
+}
+
+fn pair_key(a: TracedActor, b: TracedActor) -> (TracedActor, TracedActor) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+/// Returns the explorer that sent a message to a planet.
+pub fn explorer_to_planet_sender(msg: &ExplorerToPlanet) -> ID {
+    match msg {
+        ExplorerToPlanet::SupportedResourceRequest { explorer_id }
+        | ExplorerToPlanet::SupportedCombinationRequest { explorer_id }
+        | ExplorerToPlanet::GenerateResourceRequest { explorer_id, .. }
+        | ExplorerToPlanet::CombineResourceRequest { explorer_id, .. }
+        | ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id } => *explorer_id,
+    }
+}
+
+type Route<T> = Arc<dyn Fn(&T) -> (TracedActor, TracedActor) + Send + Sync>;
+
+/// A `Sender` that records every message it sends.
+///
+/// The pair of actors of each message is given by a route, so a sender shared by
+/// many actors (e.g. the planet to orchestrator one) can still split its messages
+/// among the right transcripts.
+pub struct RecordingSender<T> {
+    inner: Sender<T>,
+    recorder: MessageRecorder,
+    route: Route<T>,
+}
+
+impl<T> Clone for RecordingSender<T> {
+    fn clone(&self) -> Self {
+        RecordingSender {
+            inner: self.inner.clone(),
+            recorder: self.recorder.clone(),
+            route: Arc::clone(&self.route),
+        }
+    }
+}
+
+impl<T: fmt::Debug> RecordingSender<T> {
+    /// Records every message as sent from `from` to `to`.
+    pub fn new(
+        inner: Sender<T>,
+        recorder: MessageRecorder,
+        from: TracedActor,
+        to: TracedActor,
+    ) -> Self {
+        Self::with_route(inner, recorder, move |_| (from, to))
+    }
+
+    /// Records every message as sent between the actors returned by `route`.
+    pub fn with_route(
+        inner: Sender<T>,
+        recorder: MessageRecorder,
+        route: impl Fn(&T) -> (TracedActor, TracedActor) + Send + Sync + 'static,
+    ) -> Self {
+        RecordingSender {
+            inner,
+            recorder,
+            route: Arc::new(route),
+        }
+    }
+
+    /// Records the message and forwards it, see [`Sender::send`].
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        let (from, to) = (self.route)(&msg);
+        self.recorder.record(from, to, &msg);
+        self.inner.send(msg)
+    }
+
+    /// Records the message and forwards it, see [`Sender::try_send`].
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let (from, to) = (self.route)(&msg);
+        self.recorder.record(from, to, &msg);
+        self.inner.try_send(msg)
+    }
+
+    /// Returns the wrapped sender.
+    pub fn inner(&self) -> &Sender<T> {
+        &self.inner
+    }
+}
+
+impl<T: fmt::Debug + Send + 'static> RecordingSender<T> {
+    /// Turns the recording sender into a plain `Sender`, for the constructors that
+    /// only accept one.
+    ///
+    /// A forwarding thread records the messages in the order they are sent and
+    /// passes them to the wrapped sender; it stops when every clone of the returned
+    /// sender is dropped or when the wrapped channel is disconnected. In the latter
+    /// case the returned channel is disconnected too, but only once a message fails
+    /// to be forwarded: that message is lost and the sends after it fail. Senders
+    /// whose errors must be handled should be wrapped in a [`TracedSender`] instead.
+    pub fn into_proxy(self) -> Sender<T> {
+        let (proxy_sender, proxy_receiver) = unbounded::<T>();
+        thread::spawn(move || {
+            for msg in proxy_receiver {
+                if self.send(msg).is_err() {
+                    break;
+                }
+            }
+        });
+        proxy_sender
+    }
+}
+
+/// A `Sender` that records the messages it sends when message tracing is enabled.
+///
+/// The messages are sent on the wrapped channel by the caller itself, so the send
+/// errors are the ones of the wrapped channel (e.g. a disconnection is reported as
+/// soon as the receiver is gone).
+pub enum TracedSender<T> {
+    Plain(Sender<T>),
+    Recording(RecordingSender<T>),
+}
+
+impl<T> Clone for TracedSender<T> {
+    fn clone(&self) -> Self {
+        match self {
+            TracedSender::Plain(sender) => TracedSender::Plain(sender.clone()),
+            TracedSender::Recording(sender) => TracedSender::Recording(sender.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for TracedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TracedSender::Plain(sender) => f.debug_tuple("Plain").field(sender).finish(),
+            TracedSender::Recording(sender) => {
+                f.debug_tuple("Recording").field(sender.inner()).finish()
+            }
+        }
+    }
+}
+
+impl<T> From<Sender<T>> for TracedSender<T> {
+    fn from(sender: Sender<T>) -> Self {
+        TracedSender::Plain(sender)
+    }
+}
+
+impl<T> From<RecordingSender<T>> for TracedSender<T> {
+    fn from(sender: RecordingSender<T>) -> Self {
+        TracedSender::Recording(sender)
+    }
+}
+
+impl<T: fmt::Debug> TracedSender<T> {
+    /// Sends the message, recording it if tracing is enabled, see [`Sender::send`].
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        match self {
+            TracedSender::Plain(sender) => sender.send(msg),
+            TracedSender::Recording(sender) => sender.send(msg),
+        }
+    }
+
+    /// Sends the message without blocking, recording it if tracing is enabled, see
+    /// [`Sender::try_send`].
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        match self {
+            TracedSender::Plain(sender) => sender.try_send(msg),
+            TracedSender::Recording(sender) => sender.try_send(msg),
+        }
+    }
+}
+
+impl<T> TracedSender<T> {
+    /// Returns the wrapped sender.
+    pub fn inner(&self) -> &Sender<T> {
+        match self {
+            TracedSender::Plain(sender) => sender,
+            TracedSender::Recording(sender) => &sender.inner,
+        }
+    }
+}
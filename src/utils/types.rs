@@ -100,16 +100,22 @@ impl PlanetInfoMap {
         }
     }
 
-    pub fn update_from_planet_state(&mut self, planet_id: u32, planet_state: DummyPlanetState) {
+    pub fn update_from_planet_state(&mut self, planet_id: u32, planet_state: &DummyPlanetState) {
         if let Some(planet_info) = self.map.get_mut(&planet_id) {
             log_internal_op!(dir ActorType::Planet, planet_id, "action"=>format!(
                 "updated planet info from DummyPlanetState, new energy_cells: {:?}, new charged_cells_count: {}, new has_rocket: {}",  planet_state.energy_cells, planet_state.charged_cells_count, planet_state.has_rocket
             ));
-            planet_info.energy_cells = planet_state.energy_cells;
+            planet_info.energy_cells = planet_state.energy_cells.clone();
             planet_info.charged_cells_count = planet_state.charged_cells_count;
             planet_info.rocket = planet_state.has_rocket;
         }
     }
+    pub fn update_rocket(&mut self, planet_id: u32, rocket: bool) {
+        if let Some(planet_info) = self.map.get_mut(&planet_id) {
+            log_internal_op!(dir ActorType::Planet, planet_id, "action"=>format!("planet: {} rocket updated to: {}", planet_id, rocket));
+            planet_info.rocket = rocket;
+        }
+    }
     pub fn len(&self) -> usize {
         self.map.len()
     }
@@ -241,6 +247,10 @@ impl ExplorerInfoMap {
         }
     }
 
+    pub fn remove(&mut self, explorer_id: &u32) -> Option<ExplorerInfo> {
+        self.map.remove(explorer_id)
+    }
+
     pub fn update_bag(&mut self, explorer_id: u32, bag: Vec<ResourceType>) {
         if let Some(explorer_info) = self.map.get_mut(&explorer_id) {
             log_internal_op!(dir ActorType::Explorer, explorer_id, "action"=>format!("explorer: {} bag updated to: {:?}", explorer_id, bag));